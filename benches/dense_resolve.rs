@@ -0,0 +1,107 @@
+//! Occupancy pass of a simultaneous tick on crowded worlds: the sparse
+//! per-ant `gen` stamp against the dense per-colony reset the engine
+//! switches to once alive colonies drop below `DENSE_RATIO` per active ant.
+//!
+//! Both count every ant on its colony over the stationary stock and list
+//! the touched colonies in first-arrival order, as the destruction phase
+//! resolves them. Run with `cargo bench --bench dense_resolve`.
+
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+const ROUNDS: usize = 20;
+const SAMPLES: usize = 5;
+
+/// Per-colony counters the occupancy pass fills
+struct Tally {
+    gen: Vec<u32>,
+    occ: Vec<u32>,
+    first: Vec<u32>,
+    base_occ: Vec<u32>,
+    base_first: Vec<u32>,
+    touched: Vec<usize>,
+}
+
+impl Tally {
+    fn new(len: usize) -> Self {
+        Self {
+            gen: vec![0; len],
+            occ: vec![0; len],
+            first: vec![u32::MAX; len],
+            base_occ: vec![0; len],
+            base_first: vec![u32::MAX; len],
+            touched: Vec::new(),
+        }
+    }
+
+    fn sparse(&mut self, cur_gen: u32, dests: &[usize]) {
+        self.touched.clear();
+        for (id, &nid) in dests.iter().enumerate() {
+            if self.gen[nid] != cur_gen {
+                self.gen[nid] = cur_gen;
+                self.occ[nid] = self.base_occ[nid];
+                self.first[nid] = self.base_first[nid];
+                self.touched.push(nid);
+            }
+            if self.occ[nid] == 0 {
+                self.first[nid] = id as u32;
+            }
+            self.occ[nid] = self.occ[nid].saturating_add(1);
+        }
+    }
+
+    fn dense(&mut self, dests: &[usize]) {
+        self.touched.clear();
+        self.occ.copy_from_slice(&self.base_occ);
+        self.first.copy_from_slice(&self.base_first);
+        for (id, &nid) in dests.iter().enumerate() {
+            if self.occ[nid] == self.base_occ[nid] {
+                self.touched.push(nid);
+            }
+            if self.occ[nid] == 0 {
+                self.first[nid] = id as u32;
+            }
+            self.occ[nid] = self.occ[nid].saturating_add(1);
+        }
+    }
+}
+
+/// Median time of `ROUNDS` occupancy passes over fresh destinations
+fn time(len: usize, ants: usize, dense: bool) -> Duration {
+    let mut runs: Vec<Duration> = (0..SAMPLES)
+        .map(|_| {
+            let mut rng = fastrand::Rng::with_seed(7);
+            let rounds: Vec<Vec<usize>> =
+                (0..ROUNDS).map(|_| (0..ants).map(|_| rng.usize(..len)).collect()).collect();
+            let mut tally = Tally::new(len);
+            let start = Instant::now();
+            for (round, dests) in rounds.iter().enumerate() {
+                if dense {
+                    tally.dense(dests);
+                } else {
+                    tally.sparse(round as u32 + 1, dests);
+                }
+                black_box(&tally.touched);
+            }
+            start.elapsed()
+        })
+        .collect();
+    runs.sort();
+    runs[SAMPLES / 2]
+}
+
+fn main() {
+    println!("{:>10} {:>10} {:>12} {:>12} {:>8}", "colonies", "ants", "sparse_ms", "dense_ms", "speedup");
+    for (len, ants) in [(1 << 22, 1 << 14), (1 << 20, 1 << 18), (1 << 20, 1 << 20), (1 << 22, 1 << 22)] {
+        let sparse = time(len, ants, false);
+        let dense = time(len, ants, true);
+        println!(
+            "{:>10} {:>10} {:>12.2} {:>12.2} {:>7.2}x",
+            len,
+            ants,
+            sparse.as_secs_f64() * 1e3,
+            dense.as_secs_f64() * 1e3,
+            sparse.as_secs_f64() / dense.as_secs_f64()
+        );
+    }
+}
@@ -0,0 +1,126 @@
+//! Movement-phase access pattern over the old array-of-structs node list
+//! (`Vec<Node>`) and the structure-of-arrays [`NodeTable`] on large lattices.
+//!
+//! Each round moves every ant once: read its colony's tunnels, check which
+//! neighbors still stand, step to one of them, then count ants per alive
+//! colony as the occupancy phase does. Run with
+//! `cargo bench --bench node_layout`.
+
+use ants_code_challenge::prelude::NodeId;
+use ants_code_challenge::world::{Node, NodeTable};
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+const ROUNDS: usize = 20;
+const SAMPLES: usize = 5;
+
+/// `side` x `side` lattice with about a fifth of the colonies destroyed
+fn lattice(side: usize, rng: &mut fastrand::Rng) -> Vec<Node> {
+    let at = |x: usize, y: usize| NodeId::from_index(y * side + x);
+    (0..side * side)
+        .map(|i| {
+            let (x, y) = (i % side, i / side);
+            let mut node = Node::new(i as _);
+            if y > 0 {
+                node.set_neighbor(0, at(x, y - 1));
+            }
+            if y + 1 < side {
+                node.set_neighbor(1, at(x, y + 1));
+            }
+            if x + 1 < side {
+                node.set_neighbor(2, at(x + 1, y));
+            }
+            if x > 0 {
+                node.set_neighbor(3, at(x - 1, y));
+            }
+            node.alive = rng.u8(..5) != 0;
+            node
+        })
+        .collect()
+}
+
+/// The two reads the hot phases make, whatever the layout
+trait Layout {
+    fn neighbors(&self, i: usize) -> [Option<NodeId>; 4];
+    fn is_alive(&self, i: usize) -> bool;
+}
+
+impl Layout for Vec<Node> {
+    #[inline]
+    fn neighbors(&self, i: usize) -> [Option<NodeId>; 4] {
+        self[i].neighbors
+    }
+
+    #[inline]
+    fn is_alive(&self, i: usize) -> bool {
+        self[i].is_alive()
+    }
+}
+
+impl Layout for NodeTable {
+    #[inline]
+    fn neighbors(&self, i: usize) -> [Option<NodeId>; 4] {
+        NodeTable::neighbors(self, i)
+    }
+
+    #[inline]
+    fn is_alive(&self, i: usize) -> bool {
+        NodeTable::is_alive(self, i)
+    }
+}
+
+/// Median time of `ROUNDS` move + occupancy rounds of `ants`
+fn time<L: Layout>(nodes: &L, len: usize, ants: &[usize]) -> Duration {
+    let mut runs: Vec<Duration> = (0..SAMPLES)
+        .map(|_| {
+            let mut rng = fastrand::Rng::with_seed(7);
+            let mut pos = ants.to_vec();
+            let mut occupancy = vec![0u32; len];
+            let start = Instant::now();
+            for _ in 0..ROUNDS {
+                for p in pos.iter_mut() {
+                    let mut opts = [*p; 4];
+                    let mut k = 0;
+                    for n in nodes.neighbors(*p).into_iter().flatten() {
+                        opts[k] = n.index();
+                        k += nodes.is_alive(n.index()) as usize;
+                    }
+                    if k > 0 {
+                        *p = opts[rng.usize(..k)];
+                    }
+                }
+                occupancy.fill(0);
+                for &p in &pos {
+                    occupancy[p] += nodes.is_alive(p) as u32;
+                }
+                black_box(&occupancy);
+            }
+            start.elapsed()
+        })
+        .collect();
+    runs.sort();
+    runs[SAMPLES / 2]
+}
+
+fn main() {
+    println!("{:>10} {:>10} {:>12} {:>12} {:>8}", "colonies", "ants", "aos_ms", "soa_ms", "speedup");
+    for side in [256, 1024, 2048] {
+        let mut rng = fastrand::Rng::with_seed(1);
+        let aos = lattice(side, &mut rng);
+        let soa = NodeTable::from(aos.clone());
+        let len = aos.len();
+        let ants: Vec<usize> =
+            std::iter::repeat_with(|| rng.usize(..len)).filter(|&i| aos[i].is_alive()).take(len / 4).collect();
+
+        let aos_time = time(&aos, len, &ants);
+        let soa_time = time(&soa, len, &ants);
+        println!(
+            "{:>10} {:>10} {:>12.2} {:>12.2} {:>7.2}x",
+            len,
+            ants.len(),
+            aos_time.as_secs_f64() * 1e3,
+            soa_time.as_secs_f64() * 1e3,
+            aos_time.as_secs_f64() / soa_time.as_secs_f64()
+        );
+    }
+}
@@ -0,0 +1,28 @@
+//! Stamps the binary with the commit and profile it was built from, for
+//! `--version`
+
+use std::path::Path;
+use std::process::Command;
+
+fn main() {
+    let git_hash = Command::new("git")
+        .args(["rev-parse", "--short=12", "HEAD"])
+        .output()
+        .ok()
+        .filter(|out| out.status.success())
+        .and_then(|out| String::from_utf8(out.stdout).ok())
+        .map(|hash| hash.trim().to_string())
+        .unwrap_or_else(|| "unknown".to_string());
+    println!("cargo:rustc-env=ANTS_GIT_HASH={git_hash}");
+    println!("cargo:rustc-env=ANTS_BUILD_PROFILE={}", std::env::var("PROFILE").unwrap_or_default());
+    println!("cargo:rustc-env=ANTS_BUILD_TARGET={}", std::env::var("TARGET").unwrap_or_default());
+
+    // New commits move HEAD or the branch it points to; outside a checkout
+    // only this script matters
+    println!("cargo:rerun-if-changed=build.rs");
+    for path in [".git/HEAD", ".git/refs/heads", ".git/packed-refs"] {
+        if Path::new(path).exists() {
+            println!("cargo:rerun-if-changed={path}");
+        }
+    }
+}
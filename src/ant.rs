@@ -1,10 +1,213 @@
+use crate::node_id::NodeId;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Ant caste
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+#[repr(u8)]
+pub enum AntKind {
+    /// Regular ant that just wanders
+    #[default]
+    Worker = 0,
+    /// Wanders like a worker and periodically spawns new workers
+    Queen = 1,
+    /// Wanders like a worker and destroys any colony it enters, alone
+    Mega = 2,
+}
+
+/// Why an ant died
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+#[repr(u8)]
+pub enum DeathReason {
+    /// Landed on a colony shared with another ant and fought there at t=0
+    CollisionAtStart,
+    /// Fought another ant during the run
+    Collision,
+    /// Stood on a colony that fell to a blast, or that a reloaded map no longer has
+    VanishedWithColony,
+    /// Headed for a colony that fell to a blast on the same tick
+    SteppedIntoRuins,
+    /// Left with no way out under `--trapped-policy die`
+    Trapped,
+    /// Killed by a fortified colony it reached alone
+    Repelled,
+    /// Ran out of energy under `--energy`
+    Starved,
+}
+
+impl DeathReason {
+    /// Every reason, in the order tallies list them
+    pub const ALL: [DeathReason; 7] = [
+        DeathReason::CollisionAtStart,
+        DeathReason::Collision,
+        DeathReason::VanishedWithColony,
+        DeathReason::SteppedIntoRuins,
+        DeathReason::Trapped,
+        DeathReason::Repelled,
+        DeathReason::Starved,
+    ];
+}
+
+impl fmt::Display for DeathReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            DeathReason::CollisionAtStart => "collision_at_start",
+            DeathReason::Collision => "collision",
+            DeathReason::VanishedWithColony => "vanished_with_colony",
+            DeathReason::SteppedIntoRuins => "stepped_into_ruins",
+            DeathReason::Trapped => "trapped",
+            DeathReason::Repelled => "repelled",
+            DeathReason::Starved => "starved",
+        })
+    }
+}
+
+/// Dead ants by [`DeathReason`], tallied at the end of a run
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DeathCounts {
+    pub collision_at_start: usize,
+    pub collision: usize,
+    pub vanished_with_colony: usize,
+    pub stepped_into_ruins: usize,
+    pub trapped: usize,
+    pub repelled: usize,
+    pub starved: usize,
+}
+
+impl DeathCounts {
+    /// Count the dead ants by reason; ants that left the run alive (through
+    /// a portal) have none and are not counted
+    pub fn of(ants: &[Ant]) -> Self {
+        ants.iter().filter_map(|a| a.death).fold(Self::default(), |mut acc, reason| {
+            *acc.get_mut(reason) += 1;
+            acc
+        })
+    }
+
+    /// Ants that died of `reason`
+    pub fn get(&self, reason: DeathReason) -> usize {
+        match reason {
+            DeathReason::CollisionAtStart => self.collision_at_start,
+            DeathReason::Collision => self.collision,
+            DeathReason::VanishedWithColony => self.vanished_with_colony,
+            DeathReason::SteppedIntoRuins => self.stepped_into_ruins,
+            DeathReason::Trapped => self.trapped,
+            DeathReason::Repelled => self.repelled,
+            DeathReason::Starved => self.starved,
+        }
+    }
+
+    fn get_mut(&mut self, reason: DeathReason) -> &mut usize {
+        match reason {
+            DeathReason::CollisionAtStart => &mut self.collision_at_start,
+            DeathReason::Collision => &mut self.collision,
+            DeathReason::VanishedWithColony => &mut self.vanished_with_colony,
+            DeathReason::SteppedIntoRuins => &mut self.stepped_into_ruins,
+            DeathReason::Trapped => &mut self.trapped,
+            DeathReason::Repelled => &mut self.repelled,
+            DeathReason::Starved => &mut self.starved,
+        }
+    }
+
+    /// Dead ants, whatever the reason
+    pub fn total(&self) -> usize {
+        DeathReason::ALL.iter().map(|&r| self.get(r)).sum()
+    }
+}
+
+/// The non-zero reasons as `reason=count`, e.g. `collision=4 starved=1`
+impl fmt::Display for DeathCounts {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut sep = "";
+        for reason in DeathReason::ALL.into_iter().filter(|&r| self.get(r) > 0) {
+            write!(f, "{sep}{reason}={}", self.get(reason))?;
+            sep = " ";
+        }
+        Ok(())
+    }
+}
+
+/// Population tally for one ant kind
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KindStats {
+    pub total: usize,
+    pub alive: usize,
+}
+
+impl KindStats {
+    /// Count ants of `kind`, and how many of them are still alive
+    pub fn of(ants: &[Ant], kind: AntKind) -> Self {
+        ants.iter()
+            .filter(|a| a.kind == kind)
+            .fold(Self::default(), |acc, a| Self {
+                total: acc.total + 1,
+                alive: acc.alive + a.is_alive() as usize,
+            })
+    }
+}
+
+/// Why each ant stopped, tallied at the end of a run
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AntOutcomes {
+    /// Alive, free and out of moves
+    pub max_moves: usize,
+    /// Killed in a fight, a blast or by a fortified colony (see
+    /// [`DeathCounts`] for the reasons)
+    pub died: usize,
+    /// Ran out of energy under `--energy`
+    #[serde(default)]
+    pub starved: usize,
+    /// Alive but left with no way out
+    pub trapped: usize,
+    /// Alive, free and with moves left (the run ended for another reason)
+    pub moving: usize,
+}
+
+impl AntOutcomes {
+    /// Sort every ant into exactly one outcome: starved or died, else
+    /// trapped, else out of moves, else still moving
+    pub fn of(ants: &[Ant]) -> Self {
+        ants.iter().fold(Self::default(), |mut acc, a| {
+            if a.is_starved() {
+                acc.starved += 1;
+            } else if !a.is_alive() {
+                acc.died += 1;
+            } else if a.is_trapped() {
+                acc.trapped += 1;
+            } else if a.has_max_moves() {
+                acc.max_moves += 1;
+            } else {
+                acc.moving += 1;
+            }
+            acc
+        })
+    }
+}
+
 /// Ant state packed into a byte (alive/trapped) + aligned fields
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Ant {
-    pub pos: u32,
+    pub pos: NodeId,
+    /// Colony the ant started from (its birthplace, or where it arrived
+    /// through a portal), for `--strategy homing:H`
+    pub home: NodeId,
     pub id: u32,
+    /// Moves made so far; stops counting at `u32::MAX`
     pub moves: u32,
+    /// Move limit, assigned by the engine when a run starts (or the ant is born)
+    pub max_moves: u32,
+    /// Moves left before the ant starves under `--energy` (`u32::MAX` = unlimited)
+    pub energy: u32,
     state: u8, // bit 0 = alive, bit 1 = trapped
+    pub kind: AntKind,
+    /// Why the ant died, once it has
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub death: Option<DeathReason>,
+    /// Index into the run's [`AntNames`](crate::ant_names::AntNames) table, if named
+    pub name: Option<u32>,
 }
 
 impl Ant {
@@ -12,15 +215,49 @@ impl Ant {
     const TRAPPED: u8 = 0b10;
 
     /// Create a new ant at the given position
-    pub fn new(id: u32, pos: u32) -> Self {
+    pub fn new(id: u32, pos: NodeId) -> Self {
         Self {
             id,
             pos,
+            home: pos,
             moves: 0,
+            max_moves: u32::MAX,
+            energy: u32::MAX,
             state: Self::ALIVE,
+            kind: AntKind::Worker,
+            death: None,
+            name: None,
+        }
+    }
+
+    /// Create a new queen at the given position
+    pub fn queen(id: u32, pos: NodeId) -> Self {
+        Self {
+            kind: AntKind::Queen,
+            ..Self::new(id, pos)
         }
     }
 
+    /// Create a new mega ant at the given position
+    pub fn mega(id: u32, pos: NodeId) -> Self {
+        Self {
+            kind: AntKind::Mega,
+            ..Self::new(id, pos)
+        }
+    }
+
+    /// Check if ant is a queen
+    #[inline]
+    pub fn is_queen(&self) -> bool {
+        self.kind == AntKind::Queen
+    }
+
+    /// Check if ant is a mega ant
+    #[inline]
+    pub fn is_mega(&self) -> bool {
+        self.kind == AntKind::Mega
+    }
+
     /// Check if ant is alive
     #[inline]
     pub fn is_alive(&self) -> bool {
@@ -33,11 +270,36 @@ impl Ant {
         self.state & Self::TRAPPED != 0
     }
 
-    /// Set alive state
+    /// Check if ant died of hunger (rather than in a fight or blast)
+    #[inline]
+    pub fn is_starved(&self) -> bool {
+        self.death == Some(DeathReason::Starved)
+    }
+
+    /// Kill the ant for `reason`. An ant dies once: killing a dead ant
+    /// keeps its first reason.
+    #[inline]
+    pub fn die(&mut self, reason: DeathReason) {
+        if self.is_alive() {
+            self.death = Some(reason);
+        }
+        self.state &= !Self::ALIVE;
+    }
+
+    /// Kill the ant for running out of energy
+    #[inline]
+    pub fn starve(&mut self) {
+        self.die(DeathReason::Starved);
+        self.state &= !Self::TRAPPED;
+    }
+
+    /// Set alive state, without a death reason (an ant leaving the run);
+    /// bringing an ant back clears its reason
     #[inline]
     pub fn set_alive(&mut self, alive: bool) {
         if alive {
             self.state |= Self::ALIVE;
+            self.death = None;
         } else {
             self.state &= !Self::ALIVE;
         }
@@ -53,15 +315,21 @@ impl Ant {
         }
     }
 
-    /// Move ant to new position and increment move counter
-    pub fn move_to(&mut self, new_pos: u32) {
+    /// Move ant to new position, increment move counter and spend one
+    /// energy (unless unlimited)
+    pub fn move_to(&mut self, new_pos: NodeId) {
         self.pos = new_pos;
-        self.moves += 1;
+        // Saturates: with ticks counted in u64 a run can outlast the counter
+        self.moves = self.moves.saturating_add(1);
+        if self.energy != u32::MAX {
+            self.energy = self.energy.saturating_sub(1);
+        }
     }
 
     /// Check if ant has reached maximum moves
-    pub fn has_max_moves(&self, max_moves: u32) -> bool {
-        self.moves >= max_moves
+    #[inline]
+    pub fn has_max_moves(&self) -> bool {
+        self.moves >= self.max_moves
     }
 }
 
@@ -71,18 +339,76 @@ mod tests {
 
     #[test]
     fn test_ant_creation() {
-        let ant = Ant::new(42, 100);
+        let ant = Ant::new(42, NodeId::new(100));
         
         assert_eq!(ant.id, 42);
-        assert_eq!(ant.pos, 100);
+        assert_eq!(ant.pos, NodeId::new(100));
         assert_eq!(ant.moves, 0);
         assert!(ant.is_alive());
         assert!(!ant.is_trapped());
+        assert_eq!(ant.kind, AntKind::Worker);
+    }
+
+    #[test]
+    fn test_queen_creation() {
+        let queen = Ant::queen(3, NodeId::new(7));
+
+        assert_eq!(queen.id, 3);
+        assert_eq!(queen.pos, NodeId::new(7));
+        assert!(queen.is_queen());
+        assert!(queen.is_alive());
+    }
+
+    #[test]
+    fn test_kind_stats() {
+        let mut ants = vec![Ant::queen(0, NodeId::new(0)), Ant::new(1, NodeId::new(0)), Ant::new(2, NodeId::new(0))];
+        ants[2].set_alive(false);
+
+        assert_eq!(KindStats::of(&ants, AntKind::Queen), KindStats { total: 1, alive: 1 });
+        assert_eq!(KindStats::of(&ants, AntKind::Worker), KindStats { total: 2, alive: 1 });
+    }
+
+    #[test]
+    fn test_ant_outcomes() {
+        let mut ants: Vec<Ant> = (0..5).map(|i| Ant::new(i, NodeId::new(0))).collect();
+        for a in &mut ants {
+            a.max_moves = 2;
+        }
+        ants[0].set_alive(false);
+        ants[1].set_trapped(true);
+        ants[2].moves = 2;
+        // Dead beats trapped and out of moves
+        ants[3].moves = 2;
+        ants[3].set_trapped(true);
+        ants[3].set_alive(false);
+
+        let outcomes = AntOutcomes::of(&ants);
+        assert_eq!(outcomes, AntOutcomes { max_moves: 1, died: 2, starved: 0, trapped: 1, moving: 1 });
+
+        ants[4].starve();
+        assert!(!ants[4].is_alive());
+        assert_eq!(AntOutcomes::of(&ants), AntOutcomes { max_moves: 1, died: 2, starved: 1, trapped: 1, moving: 0 });
+    }
+
+    #[test]
+    fn test_death_reasons() {
+        let mut ants: Vec<Ant> = (0..4).map(|i| Ant::new(i, NodeId::new(0))).collect();
+        ants[0].die(DeathReason::Collision);
+        ants[1].die(DeathReason::Collision);
+        ants[2].starve();
+        // A dead ant keeps its first reason
+        ants[2].die(DeathReason::Collision);
+
+        assert!(!ants[0].is_alive() && ants[2].is_starved());
+        let counts = DeathCounts::of(&ants);
+        assert_eq!(counts, DeathCounts { collision: 2, starved: 1, ..Default::default() });
+        assert_eq!(counts.total(), 2 + 1);
+        assert_eq!(counts.to_string(), "collision=2 starved=1");
     }
 
     #[test]
     fn test_ant_state_management() {
-        let mut ant = Ant::new(1, 10);
+        let mut ant = Ant::new(1, NodeId::new(10));
         
         // Initial state
         assert!(ant.is_alive());
@@ -107,33 +433,49 @@ mod tests {
 
     #[test]
     fn test_ant_movement() {
-        let mut ant = Ant::new(1, 10);
+        let mut ant = Ant::new(1, NodeId::new(10));
         
         assert_eq!(ant.moves, 0);
-        assert_eq!(ant.pos, 10);
+        assert_eq!(ant.pos, NodeId::new(10));
         
-        ant.move_to(20);
-        assert_eq!(ant.pos, 20);
+        ant.move_to(NodeId::new(20));
+        assert_eq!(ant.pos, NodeId::new(20));
         assert_eq!(ant.moves, 1);
         
-        ant.move_to(30);
-        assert_eq!(ant.pos, 30);
+        ant.move_to(NodeId::new(30));
+        assert_eq!(ant.pos, NodeId::new(30));
         assert_eq!(ant.moves, 2);
     }
 
     #[test]
     fn test_ant_max_moves() {
-        let mut ant = Ant::new(1, 10);
+        let mut ant = Ant::new(1, NodeId::new(10));
+        assert_eq!(ant.max_moves, u32::MAX);
+        ant.max_moves = 10;
         
-        assert!(!ant.has_max_moves(10));
+        assert!(!ant.has_max_moves());
         
         // Move ant 5 times
         for _ in 0..5 {
-            ant.move_to(ant.pos + 1);
+            ant.move_to(NodeId::new(ant.pos.get() + 1));
         }
         
-        assert!(!ant.has_max_moves(10));
-        assert!(ant.has_max_moves(5));
-        assert!(ant.has_max_moves(3));
+        assert!(!ant.has_max_moves());
+        ant.max_moves = 5;
+        assert!(ant.has_max_moves());
+        ant.max_moves = 3;
+        assert!(ant.has_max_moves());
+    }
+
+    #[test]
+    fn test_move_counter_saturates() {
+        let mut ant = Ant::new(1, NodeId::new(10));
+        ant.moves = u32::MAX - 1;
+        ant.move_to(NodeId::new(11));
+        ant.move_to(NodeId::new(12));
+
+        assert_eq!(ant.moves, u32::MAX);
+        assert_eq!(ant.pos, NodeId::new(12));
+        assert!(ant.has_max_moves());
     }
 }
@@ -0,0 +1,130 @@
+use crate::ant::Ant;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::io::BufRead;
+
+/// Colors cycled through by generated names
+const COLORS: [&str; 8] = ["red", "blue", "green", "gold", "black", "white", "amber", "violet"];
+
+/// Table of display names for ants, indexed by [`Ant::name`].
+///
+/// Ant `i` takes entry `i`, so events and reports, which only record ant
+/// ids, can look names up by id. A table read from a file may run out;
+/// ants past its end keep `ant N`. A generated table grows as queens lay
+/// workers.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AntNames {
+    names: Vec<String>,
+    generate: bool,
+}
+
+impl AntNames {
+    /// Names read one per line from `path`; blank lines are skipped
+    pub fn read(path: &str) -> std::io::Result<Self> {
+        let file = std::fs::File::open(path)?;
+        let mut names = Vec::new();
+        for line in std::io::BufReader::new(file).lines() {
+            let line = line?;
+            let name = line.trim();
+            if !name.is_empty() {
+                names.push(name.to_string());
+            }
+        }
+        Ok(Self { names, generate: false })
+    }
+
+    /// Generated names like `ant-red-17`, made on demand
+    pub fn generated() -> Self {
+        Self { names: Vec::new(), generate: true }
+    }
+
+    /// Generated name of ant `id`
+    pub fn generate_name(id: u32) -> String {
+        format!("ant-{}-{}", COLORS[id as usize % COLORS.len()], id)
+    }
+
+    /// Name at `index`, if any
+    pub fn get(&self, index: u32) -> Option<&str> {
+        self.names.get(index as usize).map(String::as_str)
+    }
+
+    /// Number of names in the table
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+
+    /// Name index for ant `id`, generating names up to it if needed
+    pub fn name_for(&mut self, id: u32) -> Option<u32> {
+        if self.generate {
+            while self.names.len() <= id as usize {
+                self.names.push(Self::generate_name(self.names.len() as u32));
+            }
+        }
+        ((id as usize) < self.names.len()).then_some(id)
+    }
+
+    /// Give every ant in `ants` its name index
+    pub fn assign(&mut self, ants: &mut [Ant]) {
+        for ant in ants {
+            ant.name = self.name_for(ant.id);
+        }
+    }
+
+    /// Display label of ant `id`: its name, or `ant N` without one
+    pub fn label(names: Option<&Self>, id: u32) -> AntLabel<'_> {
+        AntLabel { name: names.and_then(|n| n.get(id)), id }
+    }
+}
+
+/// An ant as shown in logs: its name when it has one, else `ant N`
+#[derive(Clone, Copy, Debug)]
+pub struct AntLabel<'a> {
+    name: Option<&'a str>,
+    id: u32,
+}
+
+impl fmt::Display for AntLabel<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.name {
+            Some(name) => f.write_str(name),
+            None => write!(f, "ant {}", self.id),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::node_id::NodeId;
+    use std::io::Write;
+
+    #[test]
+    fn test_generated_names_cover_every_ant() {
+        let mut names = AntNames::generated();
+        let mut ants = vec![Ant::new(0, NodeId::new(0)), Ant::new(17, NodeId::new(0))];
+        names.assign(&mut ants);
+
+        assert_eq!(ants[1].name, Some(17));
+        assert_eq!(names.get(17), Some("ant-blue-17"));
+        assert_eq!(AntNames::label(Some(&names), 0).to_string(), "ant-red-0");
+    }
+
+    #[test]
+    fn test_file_names_run_out() {
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        writeln!(file, "Ada\n\n  Grace \n").unwrap();
+        let mut names = AntNames::read(file.path().to_str().unwrap()).unwrap();
+        let mut ants: Vec<Ant> = (0..3).map(|i| Ant::new(i, NodeId::new(0))).collect();
+        names.assign(&mut ants);
+
+        assert_eq!(names.len(), 2);
+        assert_eq!(ants.iter().map(|a| a.name).collect::<Vec<_>>(), [Some(0), Some(1), None]);
+        assert_eq!(AntNames::label(Some(&names), 1).to_string(), "Grace");
+        assert_eq!(AntNames::label(Some(&names), 2).to_string(), "ant 2");
+        assert_eq!(AntNames::label(None, 1).to_string(), "ant 1");
+    }
+}
@@ -0,0 +1,126 @@
+use crate::cli::Args;
+use crate::seeds::SubSeeds;
+use crate::simulation::{SimBuffers, SimulationEngine};
+use crate::world::World;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Latencies of a `--bench` run
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct BenchResult {
+    pub ants: usize,
+    pub colonies: usize,
+    /// Latency of every repetition, in run order
+    pub runs_ms: Vec<f64>,
+    /// Surviving colonies (the same in every repetition)
+    pub survivors: usize,
+}
+
+impl BenchResult {
+    /// Median latency; the mean of the middle two for an even count
+    pub fn median_ms(&self) -> f64 {
+        let mut sorted = self.runs_ms.clone();
+        sorted.sort_by(f64::total_cmp);
+        match sorted.len() {
+            0 => 0.0,
+            n if n % 2 == 1 => sorted[n / 2],
+            n => (sorted[n / 2 - 1] + sorted[n / 2]) / 2.0,
+        }
+    }
+
+    pub fn min_ms(&self) -> f64 {
+        self.runs_ms.iter().copied().fold(f64::INFINITY, f64::min)
+    }
+
+    pub fn max_ms(&self) -> f64 {
+        self.runs_ms.iter().copied().fold(0.0, f64::max)
+    }
+}
+
+/// One `key=value` line, stable for scripts to parse
+impl fmt::Display for BenchResult {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "bench ants={} colonies={} repeat={} median_ms={:.3} min_ms={:.3} max_ms={:.3} survivors={}",
+            self.ants,
+            self.colonies,
+            self.runs_ms.len(),
+            self.median_ms(),
+            self.min_ms(),
+            self.max_ms(),
+            self.survivors
+        )
+    }
+}
+
+/// Run `args` on clones of `world` `repeat` times with the same `seeds`.
+///
+/// Events are always suppressed, and one scratch arena, sized and touched
+/// before the first run, is shared by every repetition, so the timings
+/// cover the tick loop only. Every repetition does the same work.
+pub fn run_bench(world: &World, args: &Args, seeds: SubSeeds, repeat: u32) -> BenchResult {
+    let mut args = args.clone();
+    args.suppress_events = true;
+    let mut buffers = SimBuffers::with_capacity(world.nodes.len(), args.ants);
+    buffers.touch(world.nodes.len(), args.ants);
+
+    let mut runs_ms = Vec::with_capacity(repeat as usize);
+    let mut survivors = world.count_survivors();
+    for _ in 0..repeat {
+        let mut run_world = world.clone();
+        let mut ants = run_world.place_colony(args.ants, args.queen_count(), args.placement, &mut seeds.placement_rng());
+        let mut engine = SimulationEngine::new(&run_world, args.ants)
+            .with_seeds(seeds)
+            .with_buffers(std::mem::take(&mut buffers));
+        let latency = engine.run_simulation(&mut run_world, &mut ants, &args, &mut seeds.movement_rng());
+        runs_ms.push(latency.as_secs_f64() * 1000.0);
+        survivors = run_world.count_survivors();
+        buffers = engine.into_buffers();
+    }
+
+    BenchResult { ants: args.ants, colonies: world.nodes.len(), runs_ms, survivors }
+}
+
+/// Pin the calling thread to CPU `cpu` (`--pin-cpu`)
+#[cfg(feature = "pin")]
+pub fn pin_current_thread(cpu: usize) -> Result<(), String> {
+    if core_affinity::set_for_current(core_affinity::CoreId { id: cpu }) {
+        Ok(())
+    } else {
+        Err(format!("could not pin the thread to CPU {cpu}"))
+    }
+}
+
+/// Pin the calling thread to CPU `cpu` (`--pin-cpu`)
+#[cfg(not(feature = "pin"))]
+pub fn pin_current_thread(_cpu: usize) -> Result<(), String> {
+    Err("thread pinning is not supported (build with --features pin)".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::world::parser::parse_world_from_str;
+
+    #[test]
+    fn test_median_of_odd_and_even_counts() {
+        let mut result = BenchResult { ants: 1, colonies: 1, runs_ms: vec![3.0, 1.0, 2.0], survivors: 0 };
+        assert_eq!((result.median_ms(), result.min_ms(), result.max_ms()), (2.0, 1.0, 3.0));
+        result.runs_ms.push(10.0);
+        assert_eq!(result.median_ms(), 2.5);
+    }
+
+    #[test]
+    fn test_repetitions_do_the_same_work() {
+        let (names, nodes) = parse_world_from_str("A east=B\nB west=A south=C\nC north=B\n");
+        let world = World::new(names, nodes);
+        let args = Args::with_defaults(4, "unused");
+
+        let result = run_bench(&world, &args, SubSeeds::derive(7), 3);
+        assert_eq!(result.runs_ms.len(), 3);
+        assert!(result.to_string().starts_with("bench ants=4 colonies=3 repeat=3 median_ms="));
+        let again = run_bench(&world, &args, SubSeeds::derive(7), 1);
+        assert_eq!(again.survivors, result.survivors);
+    }
+}
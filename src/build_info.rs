@@ -0,0 +1,112 @@
+use crate::cli::Args;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Cargo features this build was compiled with
+pub const FEATURES: &[(&str, bool)] = &[
+    ("cli", cfg!(feature = "cli")),
+    ("gzip", cfg!(feature = "gzip")),
+    ("zstd", cfg!(feature = "zstd")),
+    ("pin", cfg!(feature = "pin")),
+    ("watch", cfg!(feature = "watch")),
+    ("async", cfg!(feature = "async")),
+    ("server", cfg!(feature = "server")),
+    ("sqlite", cfg!(feature = "sqlite")),
+    ("wide-ids", cfg!(feature = "wide-ids")),
+];
+
+/// What a bug report needs to name a reproducible build: the version and
+/// commit, how it was compiled and the engine defaults it runs with
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BuildInfo {
+    pub version: String,
+    /// Short commit hash, `unknown` when built outside a git checkout
+    pub git_hash: String,
+    /// Cargo profile, `debug` or `release`
+    pub profile: String,
+    /// Target triple
+    pub target: String,
+    /// Enabled cargo features
+    pub features: Vec<String>,
+    /// Width of colony indices (`wide-ids` makes them 64-bit)
+    pub node_index_bits: u32,
+    pub defaults: EngineDefaults,
+}
+
+/// Engine parameters a run gets when the command line leaves them out
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EngineDefaults {
+    pub max_moves: u32,
+    pub scheduling: String,
+    pub trapped_policy: String,
+    pub placement: String,
+    pub strategy: String,
+    pub blast_radius: u32,
+    pub spawn_every: u32,
+}
+
+impl BuildInfo {
+    /// This build
+    pub fn current() -> Self {
+        let args = Args::with_defaults(0, "");
+        Self {
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            git_hash: env!("ANTS_GIT_HASH").to_string(),
+            profile: env!("ANTS_BUILD_PROFILE").to_string(),
+            target: env!("ANTS_BUILD_TARGET").to_string(),
+            features: FEATURES.iter().filter(|(_, on)| *on).map(|(name, _)| name.to_string()).collect(),
+            node_index_bits: crate::node_id::NodeIndex::BITS,
+            defaults: EngineDefaults {
+                max_moves: args.max_moves,
+                scheduling: format!("{:?}", args.scheduling).to_lowercase(),
+                trapped_policy: format!("{:?}", args.trapped_policy).to_lowercase(),
+                placement: args.placement.to_string(),
+                strategy: args.strategy.to_string(),
+                blast_radius: args.blast_radius,
+                spawn_every: args.spawn_every,
+            },
+        }
+    }
+}
+
+/// The `--version` text: the version line, then one `key: value` line each
+impl fmt::Display for BuildInfo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let d = &self.defaults;
+        writeln!(f, "ant_mania {} ({}, {})", self.version, self.git_hash, self.profile)?;
+        writeln!(f, "target: {}", self.target)?;
+        writeln!(f, "features: {}", if self.features.is_empty() { "none".to_string() } else { self.features.join(", ") })?;
+        writeln!(f, "node ids: {}-bit", self.node_index_bits)?;
+        write!(
+            f,
+            "defaults: max_moves={} scheduling={} trapped_policy={} placement={} strategy={} blast_radius={} spawn_every={}",
+            d.max_moves, d.scheduling, d.trapped_policy, d.placement, d.strategy, d.blast_radius, d.spawn_every
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_current_build() {
+        let info = BuildInfo::current();
+
+        assert_eq!(info.version, env!("CARGO_PKG_VERSION"));
+        assert!(!info.git_hash.is_empty() && !info.target.is_empty());
+        assert_eq!(info.features.contains(&"cli".to_string()), cfg!(feature = "cli"));
+        assert_eq!(info.defaults.max_moves, 10_000);
+        assert_eq!(info.defaults.scheduling, "simultaneous");
+    }
+
+    #[test]
+    fn test_text_lines() {
+        let text = BuildInfo::current().to_string();
+        let lines: Vec<&str> = text.lines().collect();
+
+        assert!(lines[0].starts_with(&format!("ant_mania {} (", env!("CARGO_PKG_VERSION"))));
+        assert!(lines[4].starts_with("defaults: max_moves=10000 scheduling=simultaneous"));
+        assert_eq!(lines.len(), 5);
+    }
+}
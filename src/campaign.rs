@@ -0,0 +1,158 @@
+use crate::cli::Args;
+use crate::seeds::SubSeeds;
+use crate::simulation::{SimBuffers, SimulationEngine};
+use crate::world::{Placement, World};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+/// One wave of a campaign: `ANTS[,max-moves=N][,placement=MODE]`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct WaveSpec {
+    pub ants: usize,
+    /// Overrides the campaign's `--max-moves` for this wave
+    pub max_moves: Option<u32>,
+    /// Overrides the campaign's `--placement` for this wave
+    pub placement: Option<Placement>,
+}
+
+impl FromStr for WaveSpec {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parts = s.split(',').map(str::trim);
+        let ants = parts.next().unwrap_or_default();
+        let ants = ants.parse::<usize>().map_err(|e| format!("invalid ant count '{ants}': {e}"))?;
+        let mut wave = WaveSpec { ants, max_moves: None, placement: None };
+        for part in parts {
+            match part.split_once('=') {
+                Some(("max-moves", n)) => {
+                    wave.max_moves = Some(n.parse().map_err(|e| format!("invalid number '{n}': {e}"))?);
+                }
+                Some(("placement", mode)) => wave.placement = Some(mode.parse()?),
+                _ => return Err(format!("unknown wave option '{part}' (expected max-moves=N or placement=MODE)")),
+            }
+        }
+        Ok(wave)
+    }
+}
+
+impl fmt::Display for WaveSpec {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.ants)?;
+        if let Some(n) = self.max_moves {
+            write!(f, ",max-moves={n}")?;
+        }
+        if let Some(mode) = self.placement {
+            write!(f, ",placement={mode}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Outcome of one wave
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct WaveSummary {
+    /// 1-based wave number
+    pub wave: usize,
+    pub ants: usize,
+    /// Master seed of the wave
+    pub seed: u64,
+    pub ticks: u64,
+    /// Colonies this wave destroyed
+    pub destroyed: usize,
+    /// Colonies standing after the wave
+    pub survivors: usize,
+    /// Ants of this wave still alive when it ended
+    pub ants_alive: usize,
+    pub latency_ms: f64,
+}
+
+impl fmt::Display for WaveSummary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "wave {}: ants={} destroyed={} survivors={} ants_alive={} ticks={} latency={:.3} ms",
+            self.wave, self.ants, self.destroyed, self.survivors, self.ants_alive, self.ticks, self.latency_ms
+        )
+    }
+}
+
+/// Run `waves` one after another on `world`, each on the damage the
+/// previous ones left.
+///
+/// Every wave drops a fresh batch of ants (ants of earlier waves are gone)
+/// under `base`, with its own overrides, and runs to the end with events
+/// suppressed. Wave `i` (0-based) uses master seed `seed + i`, so the first
+/// wave matches a plain run with `--seed seed`. With `compact`, destroyed
+/// colonies are removed between waves. `on_wave` sees each summary as soon
+/// as its wave ends.
+pub fn run_campaign(
+    world: &mut World,
+    base: &Args,
+    waves: &[WaveSpec],
+    seed: u64,
+    compact: bool,
+    mut on_wave: impl FnMut(&WaveSummary),
+) -> Vec<WaveSummary> {
+    let mut args = base.clone();
+    args.suppress_events = true;
+    let mut buffers = SimBuffers::new();
+    let mut summaries = Vec::with_capacity(waves.len());
+
+    for (i, wave) in waves.iter().enumerate() {
+        args.ants = wave.ants;
+        args.max_moves = wave.max_moves.unwrap_or(base.max_moves);
+        args.placement = wave.placement.unwrap_or(base.placement);
+        let seeds = SubSeeds::derive(seed.wrapping_add(i as u64));
+
+        let before = world.count_survivors();
+        let mut ants = world.place_colony(args.ants, args.queen_count(), args.placement, &mut seeds.placement_rng());
+        let mut engine = SimulationEngine::new(world, ants.len())
+            .with_seeds(seeds)
+            .with_buffers(std::mem::take(&mut buffers));
+        let latency = engine.run_simulation(world, &mut ants, &args, &mut seeds.movement_rng());
+        let ticks = engine.ticks();
+        buffers = engine.into_buffers();
+
+        let survivors = world.count_survivors();
+        let summary = WaveSummary {
+            wave: i + 1,
+            ants: ants.len(),
+            seed: seeds.master,
+            ticks,
+            destroyed: before - survivors,
+            survivors,
+            ants_alive: ants.iter().filter(|a| a.is_alive()).count(),
+            latency_ms: latency.as_secs_f64() * 1000.0,
+        };
+        on_wave(&summary);
+        summaries.push(summary);
+        if compact {
+            world.compact();
+        }
+    }
+    summaries
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_wave_spec_round_trips() {
+        let wave: WaveSpec = "200, max-moves=50,placement=cluster:2".parse().unwrap();
+        assert_eq!(wave.ants, 200);
+        assert_eq!(wave.max_moves, Some(50));
+        assert_eq!(wave.placement, Some(Placement::Cluster { k: 2 }));
+        assert_eq!(wave.to_string(), "200,max-moves=50,placement=cluster:2");
+        assert_eq!("7".parse::<WaveSpec>().unwrap().to_string(), "7");
+    }
+
+    #[test]
+    fn test_wave_spec_rejects_bad_input() {
+        assert!("".parse::<WaveSpec>().is_err());
+        assert!("10,max-moves=x".parse::<WaveSpec>().is_err());
+        assert!("10,placement=cluster:0".parse::<WaveSpec>().is_err());
+    }
+}
@@ -1,26 +1,922 @@
-use clap::Parser;
+#[cfg(feature = "cli")]
+use crate::campaign::WaveSpec;
+use crate::dist::MaxMovesDist;
+#[cfg(feature = "cli")]
+use crate::limits::{parse_byte_size, parse_duration};
+use crate::simulation::{EventFilter, Reinforcement};
+use crate::strategy::{DirectionBias, Strategy};
+use crate::world::{ParseOptions, Placement};
+#[cfg(feature = "cli")]
+use clap::{Parser, Subcommand, ValueEnum};
+use std::time::Duration;
 
-/// CLI arguments for the ant simulation
+#[cfg(feature = "cli")]
+/// Top-level command line: run a simulation, or use one of the tools
 #[derive(Parser, Debug)]
-#[command(name = "ant_mania", about = "🐜 Ant invasion simulator on Hiveum")]
-pub struct Args {
+#[command(
+    name = "ant_mania",
+    about = "🐜 Ant invasion simulator on Hiveum",
+    version,
+    after_help = "`--version` alone also prints the commit, features and engine defaults; `--version --json` as JSON",
+    args_conflicts_with_subcommands = true
+)]
+pub struct Cli {
+    #[command(subcommand)]
+    pub command: Option<Command>,
+
+    #[command(flatten)]
+    pub run: Option<Args>,
+}
+
+#[cfg(feature = "cli")]
+impl Cli {
+    /// Map file the selected command reads, if any
+    pub fn map(&self) -> Option<&str> {
+        match (&self.command, &self.run) {
+            (Some(Command::ScaleMap(cmd)), _) => Some(&cmd.map),
+            (Some(Command::GenerateGrid(_)), _) => None,
+            (Some(Command::NormalizeMap(cmd)), _) => Some(&cmd.map),
+            (Some(Command::Sweep(cmd)), _) => Some(&cmd.map),
+            (Some(Command::Stats(cmd)), _) => Some(&cmd.map),
+            (Some(Command::Estimate(cmd)), _) => Some(&cmd.map),
+            (Some(Command::Query(cmd)), _) => Some(&cmd.map),
+            (Some(Command::Path(cmd)), _) => Some(&cmd.map),
+            (Some(Command::CheckDirections(cmd)), _) => Some(&cmd.map),
+            (Some(Command::Campaign(cmd)), _) => Some(&cmd.map),
+            (Some(Command::CompareRuns(_)), _) => None,
+            #[cfg(feature = "server")]
+            (Some(Command::Serve(_)), _) => None,
+            (None, run) => run.as_ref().and_then(|args| args.map.first()).map(String::as_str),
+        }
+    }
+}
+
+/// Whether a command line (program name excluded) only asks for the
+/// version: `Some(json)` for `--version` / `-V`, with or without `--json`
+pub fn version_request<S: AsRef<str>>(args: &[S]) -> Option<bool> {
+    let args: Vec<&str> = args.iter().map(AsRef::as_ref).collect();
+    match args[..] {
+        ["--version" | "-V"] => Some(false),
+        ["--version" | "-V", "--json"] | ["--json", "--version" | "-V"] => Some(true),
+        _ => None,
+    }
+}
+
+#[cfg(feature = "cli")]
+/// Tools that do not run a simulation
+#[derive(Subcommand, Debug)]
+pub enum Command {
+    /// Replicate a base map N times to build large benchmark worlds
+    ScaleMap(ScaleMapArgs),
+    /// Write a grid (or torus) map whose colonies carry x/y coordinates
+    GenerateGrid(GenerateGridArgs),
+    /// Rewrite a map in canonical form: sorted by name, one line per colony
+    NormalizeMap(NormalizeMapArgs),
+    /// Run every combination of ant counts and seeds on one parsed map
+    Sweep(SweepArgs),
+    /// Diff two JSON reports (survivors, destroyed colonies, latency)
+    CompareRuns(CompareRunsArgs),
+    /// Print structural statistics of a map without simulating
+    Stats(StatsArgs),
+    /// Estimate the memory and run time of a run from a short sampled start
+    Estimate(EstimateArgs),
+    /// Look up one colony: its tunnels, degree and reachability
+    Query(QueryArgs),
+    /// Print a shortest route between two colonies
+    Path(PathArgs),
+    /// Report tunnels that contradict each other geometrically, with their lines
+    CheckDirections(CheckDirectionsArgs),
+    /// Run waves of ants one after another on the same, increasingly damaged world
+    Campaign(CampaignArgs),
+    /// Serve uploads and seeded simulations over HTTP
+    #[cfg(feature = "server")]
+    Serve(ServeArgs),
+}
+
+#[cfg(feature = "cli")]
+/// Arguments for `scale-map`
+#[derive(clap::Args, Debug)]
+pub struct ScaleMapArgs {
+    /// Path to the base map file
+    #[arg(short = 'm', long = "map")]
+    pub map: String,
+
+    /// Number of copies
+    #[arg(short = 'c', long)]
+    pub copies: usize,
+
+    /// Join consecutive copies with a two-way east/west tunnel
+    #[arg(long, default_value_t = false)]
+    pub bridge: bool,
+
+    /// Output file (stdout if omitted)
+    #[arg(short = 'o', long)]
+    pub out: Option<String>,
+}
+
+#[cfg(feature = "cli")]
+/// Arguments for `generate-grid`
+#[derive(clap::Args, Debug)]
+pub struct GenerateGridArgs {
+    /// Colonies per row
+    #[arg(long)]
+    pub width: usize,
+
+    /// Number of rows
+    #[arg(long)]
+    pub height: usize,
+
+    /// Wrap the edges around: the last column leads east to the first, the last row south to the first
+    #[arg(long, default_value_t = false)]
+    pub torus: bool,
+
+    /// Output file (stdout if omitted)
+    #[arg(short = 'o', long)]
+    pub out: Option<String>,
+}
+
+#[cfg(feature = "cli")]
+/// Arguments for `normalize-map`
+#[derive(clap::Args, Debug)]
+pub struct NormalizeMapArgs {
+    /// Path to the map file (`.gz` / `.zst` with the matching feature)
+    #[arg(short = 'm', long = "map")]
+    pub map: String,
+
+    /// Add the missing way back of every one-way tunnel
+    #[arg(long, default_value_t = false)]
+    pub mirror: bool,
+
+    /// Merge colony names that differ only in ASCII case
+    #[arg(long, default_value_t = false)]
+    pub case_insensitive: bool,
+
+    /// Output file (stdout if omitted)
+    #[arg(short = 'o', long, conflicts_with = "check")]
+    pub out: Option<String>,
+
+    /// Write nothing; exit with status 1 unless the file is already normalized
+    #[arg(long, default_value_t = false)]
+    pub check: bool,
+}
+
+#[cfg(feature = "cli")]
+/// Arguments for `sweep`
+#[derive(clap::Args, Debug)]
+pub struct SweepArgs {
+    /// Path to the map file
+    #[arg(short = 'm', long = "map")]
+    pub map: String,
+
+    /// Ant counts: `N`, `A..B`, `A..B:STEP` (inclusive) or a comma list
+    #[arg(short = 'n', long = "ants", value_parser = parse_range_list)]
+    pub ants: ValueList,
+
+    /// Seeds, same syntax as `--ants`
+    #[arg(long, value_parser = parse_range_list)]
+    pub seeds: ValueList,
+
+    /// Maximum moves per ant
+    #[arg(long, default_value_t = 10_000)]
+    pub max_moves: u32,
+
+    /// Skip runs whose estimated memory exceeds this (`512M`, `2G`, ...) and stop any that outgrow it
+    #[arg(long, value_parser = parse_byte_size)]
+    pub memory_limit: Option<u64>,
+
+    /// Stop each run after this long (`500ms`, `30s`, `5m`, ...)
+    #[arg(long, value_parser = parse_duration)]
+    pub max_wall_time: Option<Duration>,
+
+    /// CSV output file (stdout if omitted)
+    #[arg(short = 'o', long)]
+    pub out: Option<String>,
+
+    /// Write the fraction of runs that destroyed each colony as CSV to this file
+    #[arg(long)]
+    pub destruction_csv: Option<String>,
+
+    /// Write the fraction of runs that destroyed each colony as JSON to this file
+    #[arg(long)]
+    pub destruction_json: Option<String>,
+
+    /// Append every run to this SQLite database; needs the `sqlite` feature
+    #[arg(long)]
+    pub db: Option<String>,
+}
+
+#[cfg(feature = "cli")]
+/// Arguments for `compare-runs`
+#[derive(clap::Args, Debug)]
+pub struct CompareRunsArgs {
+    /// Baseline report (`--report` output)
+    pub a: String,
+
+    /// Candidate report
+    pub b: String,
+
+    /// Also write the comparison as JSON to this file
+    #[arg(short = 'o', long)]
+    pub out: Option<String>,
+
+    /// Exit with status 1 when survivors or destroyed colonies differ
+    #[arg(long, default_value_t = false)]
+    pub check: bool,
+}
+
+#[cfg(feature = "cli")]
+/// Arguments for `stats`
+#[derive(clap::Args, Debug)]
+pub struct StatsArgs {
+    /// Path to the map file
+    #[arg(short = 'm', long = "map")]
+    pub map: String,
+
+    /// Also write the statistics as JSON to this file
+    #[arg(short = 'o', long)]
+    pub out: Option<String>,
+}
+
+#[cfg(feature = "cli")]
+/// Arguments for `estimate`
+#[derive(clap::Args, Debug)]
+pub struct EstimateArgs {
+    /// Path to the map file
+    #[arg(short = 'm', long = "map")]
+    pub map: String,
+
     /// Number of ants
     #[arg(short = 'n', long = "ants")]
     pub ants: usize,
 
+    /// Maximum moves per ant
+    #[arg(long, default_value_t = 10_000)]
+    pub max_moves: u32,
+
+    /// Seed of the sample (and of the run with `--run`); random if omitted
+    #[arg(long)]
+    pub seed: Option<u64>,
+
+    /// Ticks to run before projecting the rest
+    #[arg(long, default_value_t = 100, value_parser = clap::value_parser!(u32).range(1..))]
+    pub sample_ticks: u32,
+
+    /// Flag runs estimated to need more memory than this (`512M`, `2G`, ...)
+    #[arg(long, value_parser = parse_byte_size)]
+    pub memory_limit: Option<u64>,
+
+    /// Flag runs projected to take longer than this (`500ms`, `30s`, `5m`, ...)
+    #[arg(long, value_parser = parse_duration)]
+    pub max_wall_time: Option<Duration>,
+
+    /// Start the real run after the estimate, unless it is over a limit
+    #[arg(long, default_value_t = false)]
+    pub run: bool,
+
+    /// With `--run`, run even when the estimate is over a limit
+    #[arg(long, default_value_t = false, requires = "run")]
+    pub force: bool,
+
+    /// Print JSON instead of the `estimate ...` line
+    #[arg(long, default_value_t = false)]
+    pub json: bool,
+}
+
+#[cfg(feature = "cli")]
+/// Arguments for `query`
+#[derive(clap::Args, Debug)]
+pub struct QueryArgs {
     /// Path to the map file
     #[arg(short = 'm', long = "map")]
     pub map: String,
 
-    /// Maximum moves per ant
+    /// Name of the colony to look up
+    #[arg(short = 'c', long)]
+    pub colony: String,
+
+    /// Also tell whether the colony can be reached from this one
+    #[arg(long, value_name = "COLONY")]
+    pub from: Option<String>,
+
+    /// Print JSON instead of text
+    #[arg(long, default_value_t = false)]
+    pub json: bool,
+}
+
+#[cfg(feature = "cli")]
+/// Arguments for `path`
+#[derive(clap::Args, Debug)]
+pub struct PathArgs {
+    /// Path to the map file
+    #[arg(short = 'm', long = "map")]
+    pub map: String,
+
+    /// Colony the route starts from
+    #[arg(long, value_name = "COLONY")]
+    pub from: String,
+
+    /// Colony the route leads to
+    #[arg(long, value_name = "COLONY")]
+    pub to: String,
+
+    /// Print JSON instead of text
+    #[arg(long, default_value_t = false)]
+    pub json: bool,
+}
+
+#[cfg(feature = "cli")]
+/// Arguments for `check-directions`
+#[derive(clap::Args, Debug)]
+pub struct CheckDirectionsArgs {
+    /// Path to the map file
+    #[arg(short = 'm', long = "map")]
+    pub map: String,
+
+    /// Accept one-way tunnels; only report tunnels whose way back leads elsewhere
+    /// and colonies on both sides of another
+    #[arg(long, default_value_t = false)]
+    pub allow_one_way: bool,
+
+    /// Print JSON instead of text
+    #[arg(long, default_value_t = false)]
+    pub json: bool,
+}
+
+#[cfg(feature = "server")]
+/// Arguments for `serve`
+#[derive(clap::Args, Debug)]
+pub struct ServeArgs {
+    /// Address to listen on
+    #[arg(long, default_value = "127.0.0.1:8080")]
+    pub addr: std::net::SocketAddr,
+
+    /// Largest ant count a simulation request may ask for
+    #[arg(long, default_value_t = 100_000)]
+    pub max_ants: usize,
+
+    /// Simulations allowed to run at once; more are refused with a 503
+    #[arg(long, default_value_t = 4)]
+    pub max_running: usize,
+
+    /// Finished simulations kept for polling; the oldest are dropped past it
+    #[arg(long, default_value_t = 1000)]
+    pub keep_finished: usize,
+}
+
+#[cfg(feature = "cli")]
+/// Arguments for `campaign`
+#[derive(clap::Args, Debug)]
+pub struct CampaignArgs {
+    /// Path to the map file
+    #[arg(short = 'm', long = "map")]
+    pub map: String,
+
+    /// One wave per flag, in order: `ANTS[,max-moves=N][,placement=MODE]`
+    #[arg(short = 'w', long = "wave", required = true)]
+    pub waves: Vec<WaveSpec>,
+
+    /// Seed of the first wave; wave i uses seed + i (random if omitted)
+    #[arg(long)]
+    pub seed: Option<u64>,
+
+    /// Maximum moves per ant, unless a wave overrides it
     #[arg(long, default_value_t = 10_000)]
     pub max_moves: u32,
 
+    /// Where ants are dropped, unless a wave overrides it
+    #[arg(long, default_value_t = Placement::Uniform)]
+    pub placement: Placement,
+
+    /// Remove destroyed colonies between waves
+    #[arg(long, default_value_t = false)]
+    pub compact: bool,
+
+    /// Also write the per-wave summaries as JSON to this file
+    #[arg(short = 'o', long)]
+    pub out: Option<String>,
+}
+
+/// Expanded list of values from a range/list argument
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ValueList(pub Vec<u64>);
+
+/// Parse `N`, `A..B`, `A..B:STEP` (inclusive bounds) or `X,Y,Z`
+pub fn parse_range_list(s: &str) -> std::result::Result<ValueList, String> {
+    let num = |t: &str| t.trim().parse::<u64>().map_err(|e| format!("invalid number '{t}': {e}"));
+
+    if s.contains(',') {
+        return s.split(',').map(num).collect::<Result<_, _>>().map(ValueList);
+    }
+    let Some((start, rest)) = s.split_once("..") else {
+        return Ok(ValueList(vec![num(s)?]));
+    };
+    let (end, step) = match rest.split_once(':') {
+        Some((end, step)) => (end, num(step.trim_start_matches("step"))?),
+        None => (rest, 1),
+    };
+    let (start, end) = (num(start)?, num(end)?);
+    if step == 0 {
+        return Err("step must be positive".to_string());
+    }
+    if start > end {
+        return Err(format!("empty range {start}..{end}"));
+    }
+    Ok(ValueList((start..=end).step_by(step as usize).collect()))
+}
+
+/// Parse a probability in `0.0..=1.0`
+pub fn parse_probability(s: &str) -> std::result::Result<f64, String> {
+    let p: f64 = s.trim().parse().map_err(|e| format!("invalid probability '{s}': {e}"))?;
+    if !(0.0..=1.0).contains(&p) {
+        return Err(format!("probability {p} is outside 0.0..=1.0"));
+    }
+    Ok(p)
+}
+
+/// CLI arguments for the ant simulation
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "cli", derive(Parser))]
+#[cfg_attr(feature = "cli", command(name = "ant_mania", about = "🐜 Ant invasion simulator on Hiveum"))]
+pub struct Args {
+    /// Number of ants
+    #[cfg_attr(feature = "cli", arg(short = 'n', long = "ants"))]
+    pub ants: usize,
+
+    /// Path to the map file, or a directory of map fragments; repeat to
+    /// join several into one world, names resolving across files
+    #[cfg_attr(feature = "cli", arg(short = 'm', long = "map", required = true))]
+    pub map: Vec<String>,
+
+    /// Maximum moves per ant
+    #[cfg_attr(feature = "cli", arg(long, default_value_t = 10_000))]
+    pub max_moves: u32,
+
+    /// Draw each ant's move limit from `const:N`, `uniform:LO,HI` or `normal:MEAN,STD` (overrides `--max-moves`)
+    #[cfg_attr(feature = "cli", arg(long))]
+    pub max_moves_dist: Option<MaxMovesDist>,
+
+    /// End the run after this many ticks, even with ants that still have moves left
+    #[cfg_attr(feature = "cli", arg(long))]
+    pub max_ticks: Option<u64>,
+
+    /// Resolve colony names ignoring ASCII case
+    #[cfg_attr(feature = "cli", arg(long, default_value_t = false))]
+    pub case_insensitive: bool,
+
+    /// Add the missing way back of every one-way tunnel (`A east=B` implies `B west=A`)
+    #[cfg_attr(feature = "cli", arg(long, default_value_t = false))]
+    pub auto_mirror: bool,
+
+    /// Read the map as a previous run's final world: colonies only named in tunnels are ruins
+    #[cfg_attr(feature = "cli", arg(long, default_value_t = false))]
+    pub damaged: bool,
+
+    /// Skip malformed tokens and lines of the map with a warning each, instead of stopping at the first
+    #[cfg_attr(feature = "cli", arg(long, default_value_t = false))]
+    pub lenient: bool,
+
+    /// Reject colony names that are empty or hold `=` or whitespace
+    #[cfg_attr(feature = "cli", arg(long, default_value_t = false))]
+    pub validate_names: bool,
+
+    /// Reject colony names longer than N characters
+    #[cfg_attr(feature = "cli", arg(long, value_name = "N"))]
+    pub max_name_len: Option<usize>,
+
+    /// Original map of a `--damaged` one; the summary lists the colonies lost since
+    #[cfg_attr(feature = "cli", arg(long, value_name = "MAP", requires = "damaged"))]
+    pub original: Option<String>,
+
+    /// Write every ant (id, colony, moves, state) when the run ends, as CSV or binary for a `.bin` path
+    #[cfg_attr(feature = "cli", arg(long, value_name = "FILE"))]
+    pub export_ants: Option<String>,
+
+    /// Start from the ants of an `--export-ants` file, on their colonies by name; `-n` more fresh ants join them
+    #[cfg_attr(feature = "cli", arg(long, value_name = "FILE", conflicts_with = "bench"))]
+    pub import_ants: Option<String>,
+
+    /// Place ants on any colony even if the map tags some with `spawn`
+    #[cfg_attr(feature = "cli", arg(long, default_value_t = false))]
+    pub ignore_spawn_tags: bool,
+
+    /// Spread of the initial ants: `uniform`, `weighted-by-degree`, `cluster:K` or `poisson`
+    #[cfg_attr(feature = "cli", arg(long, default_value_t = Placement::Uniform))]
+    pub placement: Placement,
+
     /// Random seed
-    #[arg(long)]
+    #[cfg_attr(feature = "cli", arg(long))]
     pub seed: Option<u64>,
 
+    /// Destroy alive colonies within this many hops of a destroyed colony
+    #[cfg_attr(feature = "cli", arg(long, default_value_t = 0))]
+    pub blast_radius: u32,
+
+    /// Fraction of ants that are queens (0.0..=1.0)
+    #[cfg_attr(feature = "cli", arg(long, default_value_t = 0.0, value_parser = parse_probability))]
+    pub queen_ratio: f64,
+
+    /// After this many colonies fall in one tick, further fights that tick are called off
+    #[cfg_attr(feature = "cli", arg(long))]
+    pub max_destructions_per_tick: Option<u32>,
+
+    /// Chance that ants meeting on a colony fight; otherwise they pass each other by
+    #[cfg_attr(feature = "cli", arg(long, value_parser = parse_probability))]
+    pub collision_prob: Option<f64>,
+
+    /// Chance that a `fortified` colony kills an ant arriving alone
+    #[cfg_attr(feature = "cli", arg(long, default_value_t = 1.0, value_parser = parse_probability))]
+    pub fortress_defense: f64,
+
+    /// Chance per tick that each standing colony rebuilds one destroyed neighbor, with tunnels both ways
+    #[cfg_attr(feature = "cli", arg(long, value_parser = parse_probability))]
+    pub revival_prob: Option<f64>,
+
+    /// Chance per tick that a meteor destroys one standing colony at random, whoever stands there
+    #[cfg_attr(feature = "cli", arg(long, value_name = "P", value_parser = parse_probability))]
+    pub chaos: Option<f64>,
+
+    /// Fraction of the initial ants that are mega ants, which destroy any colony they enter alone and fall with it
+    #[cfg_attr(feature = "cli", arg(long, value_name = "P", value_parser = parse_probability))]
+    pub mega_fraction: Option<f64>,
+
+    /// Give each ant this much energy: every move costs 1, `food` colonies refill it, and an ant out of energy starves
+    #[cfg_attr(feature = "cli", arg(long, value_name = "N"))]
+    pub energy: Option<u32>,
+
+    /// Ticks between worker spawns for each moving queen
+    #[cfg_attr(feature = "cli", arg(long, default_value_t = 10, value_parser = clap::value_parser!(u32).range(1..)))]
+    pub spawn_every: u32,
+
+    /// Drop reinforcements into the run: `tick=T,ants=N` lands N ants on alive colonies once tick T ends (repeatable)
+    #[cfg_attr(feature = "cli", arg(long = "wave"))]
+    pub waves: Vec<Reinforcement>,
+
+    /// How ants pick their next colony: `random`, or `homing:H` to head back to the starting colony after H moves
+    #[cfg_attr(feature = "cli", arg(long, default_value_t = Strategy::Random))]
+    pub strategy: Strategy,
+
+    /// Weight random steps by direction, e.g. `north=0.4,south=0.2,east=0.2,west=0.2` (directions left out weigh 0)
+    #[cfg_attr(feature = "cli", arg(long, value_name = "WEIGHTS"))]
+    pub bias: Option<DirectionBias>,
+
+    /// How ants take turns within a tick
+    #[cfg_attr(feature = "cli", arg(long, value_enum, default_value_t = Scheduling::Simultaneous))]
+    pub scheduling: Scheduling,
+
+    /// Sleep this long between ticks (demo mode); excluded from the reported latency
+    #[cfg_attr(feature = "cli", arg(long, default_value_t = 0))]
+    pub tick_delay_ms: u64,
+
+    /// What happens to an ant once it has no alive exits
+    #[cfg_attr(feature = "cli", arg(long, value_enum, default_value_t = TrappedPolicy::Stay))]
+    pub trapped_policy: TrappedPolicy,
+
+    /// Print the surviving world in map format, to stdout or to PATH (`--print-world=PATH`)
+    #[cfg_attr(feature = "cli", arg(long, value_name = "PATH", num_args = 0..=1, require_equals = true))]
+    pub print_world: Option<Option<String>>,
+
+    /// Print the final world in this stable order (implies `--print-world` when it is not given)
+    #[cfg_attr(feature = "cli", arg(long, value_enum))]
+    pub sort_output: Option<SortOrder>,
+
+    /// Render the final world as an ASCII grid when the map is a lattice
+    #[cfg_attr(feature = "cli", arg(long, default_value_t = false))]
+    pub render_grid: bool,
+
+    /// Before running, warn about one-way traps (sink components) and the ants starting in them
+    #[cfg_attr(feature = "cli", arg(long, default_value_t = false))]
+    pub warn_sinks: bool,
+
+    /// Drive the run from stdin: pause, resume, step [N], dump FILE, reload, quit (starts paused)
+    #[cfg_attr(feature = "cli", arg(long, default_value_t = false))]
+    pub interactive: bool,
+
+    /// Reload the map whenever its file changes during an `--interactive` run (built with the `watch` feature)
+    #[cfg_attr(feature = "cli", arg(long, default_value_t = false, requires = "interactive"))]
+    pub watch_map: bool,
+
+    /// Refuse runs estimated to need more memory than this (`512M`, `2G`, ...) and stop runs that outgrow it
+    #[cfg_attr(feature = "cli", arg(long, value_parser = parse_byte_size))]
+    pub memory_limit: Option<u64>,
+
+    /// Stop the run after this much wall-clock time (`500ms`, `30s`, `5m`, ...), keeping partial results
+    #[cfg_attr(feature = "cli", arg(long, value_parser = parse_duration))]
+    pub max_wall_time: Option<Duration>,
+
+    /// Exit with status 10 when no colony survives (game-over check for scripts)
+    #[cfg_attr(feature = "cli", arg(long, default_value_t = false))]
+    pub wipeout_exit: bool,
+
+    /// Name ants from this file (one name per line) in events and reports; ants past its end keep `ant N`
+    #[cfg_attr(feature = "cli", arg(long, conflicts_with = "generate_ant_names"))]
+    pub ant_names: Option<String>,
+
+    /// Name ants like `ant-red-17` in events and reports
+    #[cfg_attr(feature = "cli", arg(long, default_value_t = false))]
+    pub generate_ant_names: bool,
+
+    /// Track per-colony visit counts and embed them in the JSON report
+    #[cfg_attr(feature = "cli", arg(long, default_value_t = false))]
+    pub heatmap: bool,
+
+    /// Write per-colony visit counts as CSV to this file (implies `--heatmap`)
+    #[cfg_attr(feature = "cli", arg(long))]
+    pub heatmap_csv: Option<String>,
+
+    /// Write per-tick colony occupancy frames to this file for an external animator (`.bin` for binary, else JSON lines)
+    #[cfg_attr(feature = "cli", arg(long, conflicts_with = "interactive"))]
+    pub frames: Option<String>,
+
+    /// Write a `--frames` frame every K ticks (the final tick is always written)
+    #[cfg_attr(feature = "cli", arg(long, value_name = "K", default_value_t = 1, value_parser = clap::value_parser!(u32).range(1..)))]
+    pub frame_every: u32,
+
+    /// Credit every colony-destroying fight to all ants in it and list the K most destructive ants in the summary and report
+    #[cfg_attr(feature = "cli", arg(long, value_name = "K"))]
+    pub top_killers: Option<usize>,
+
+    /// Write a JSON report (summary + destruction timeline) to this file (`-` for stdout)
+    #[cfg_attr(feature = "cli", arg(long))]
+    pub report: Option<String>,
+
+    /// Format of `--report`: the full JSON, the text summary, a CSV row or Markdown tables
+    #[cfg_attr(feature = "cli", arg(long, value_enum, default_value_t = ReportFormat::Json, requires = "report"))]
+    pub report_format: ReportFormat,
+
+    /// Append the run (parameters, colony outcomes, events) to this SQLite database; needs the `sqlite` feature
+    #[cfg_attr(feature = "cli", arg(long, conflicts_with = "bench"))]
+    pub db: Option<String>,
+
+    /// Word event lines with the `kind = template` lines of this file (e.g. a translation)
+    #[cfg_attr(feature = "cli", arg(long, value_name = "FILE"))]
+    pub message_template: Option<String>,
+
+    /// Only log these event kinds: comma list of fight, blast, averted, near-miss, repelled, meteor, trampled, destroyed (= fight,blast,meteor,trampled)
+    #[cfg_attr(feature = "cli", arg(long))]
+    pub log_filter: Option<EventFilter>,
+
+    /// Log arrivals, near misses and destructions on just these colonies (comma list of names), next to the event log
+    #[cfg_attr(feature = "cli", arg(long, value_name = "COLONIES", value_delimiter = ','))]
+    pub watch: Vec<String>,
+
+    /// Send events, the summary and other chatter to stderr, leaving stdout to the final world and `--report -`
+    #[cfg_attr(feature = "cli", arg(long, default_value_t = false))]
+    pub porcelain: bool,
+
+    /// Drop events, the summary and other chatter; stdout still gets the final world and `--report -`
+    #[cfg_attr(feature = "cli", arg(long, default_value_t = false))]
+    pub quiet: bool,
+
     /// Suppress fight logs (for benchmarks)
-    #[arg(long, default_value_t = false)]
+    #[cfg_attr(feature = "cli", arg(long, default_value_t = false))]
     pub suppress_events: bool,
+
+    /// Benchmark mode: no colors or events, print only one `bench ... median_ms=...` line
+    #[cfg_attr(feature = "cli", arg(long, default_value_t = false, conflicts_with = "interactive"))]
+    pub bench: bool,
+
+    /// In-process repetitions of a `--bench` run on the same parsed map
+    #[cfg_attr(feature = "cli", arg(long, default_value_t = 1, value_parser = clap::value_parser!(u32).range(1..), requires = "bench"))]
+    pub repeat: u32,
+
+    /// Pin the `--bench` thread to this CPU (built with the `pin` feature)
+    #[cfg_attr(feature = "cli", arg(long, requires = "bench"))]
+    pub pin_cpu: Option<usize>,
+
+    /// Emit each tick's events sorted by colony id instead of in resolution order
+    #[cfg_attr(feature = "cli", arg(long, default_value_t = false))]
+    pub stable_event_order: bool,
+
+    /// Clear tunnels into destroyed colonies as they fall, so the world never holds edges into ruins
+    #[cfg_attr(feature = "cli", arg(long, default_value_t = false))]
+    pub prune_dead_edges: bool,
+}
+
+impl Args {
+    /// The `--map` paths as one name for messages
+    pub fn map_label(&self) -> String {
+        self.map.join(" + ")
+    }
+
+    /// Map parsing options selected on the command line
+    pub fn parse_options(&self) -> ParseOptions {
+        ParseOptions {
+            case_insensitive: self.case_insensitive,
+            ignore_spawn_tags: self.ignore_spawn_tags,
+            auto_mirror: self.auto_mirror,
+            damaged: self.damaged,
+            lenient: self.lenient,
+            validate_names: self.validate_names,
+            max_name_len: self.max_name_len,
+        }
+    }
+
+    /// Arguments for `ants` ants on `map` with every other option at its
+    /// default; available without the `cli` feature, so it mirrors the
+    /// `default_value_t`s above
+    pub fn with_defaults(ants: usize, map: &str) -> Self {
+        Args {
+            ants,
+            map: vec![map.to_string()],
+            max_moves: 10_000,
+            max_moves_dist: None,
+            max_ticks: None,
+            case_insensitive: false,
+            ignore_spawn_tags: false,
+            auto_mirror: false,
+            damaged: false,
+            lenient: false,
+            validate_names: false,
+            max_name_len: None,
+            original: None,
+            export_ants: None,
+            import_ants: None,
+            placement: Placement::Uniform,
+            seed: None,
+            blast_radius: 0,
+            queen_ratio: 0.0,
+            max_destructions_per_tick: None,
+            collision_prob: None,
+            fortress_defense: 1.0,
+            revival_prob: None,
+            chaos: None,
+            mega_fraction: None,
+            energy: None,
+            spawn_every: 10,
+            waves: Vec::new(),
+            strategy: Strategy::Random,
+            bias: None,
+            scheduling: Scheduling::Simultaneous,
+            tick_delay_ms: 0,
+            trapped_policy: TrappedPolicy::Stay,
+            print_world: None,
+            sort_output: None,
+            render_grid: false,
+            warn_sinks: false,
+            interactive: false,
+            watch_map: false,
+            memory_limit: None,
+            max_wall_time: None,
+            wipeout_exit: false,
+            ant_names: None,
+            generate_ant_names: false,
+            heatmap: false,
+            heatmap_csv: None,
+            frames: None,
+            frame_every: 1,
+            top_killers: None,
+            report: None,
+            report_format: ReportFormat::Json,
+            db: None,
+            message_template: None,
+            log_filter: None,
+            watch: Vec::new(),
+            porcelain: false,
+            quiet: false,
+            suppress_events: false,
+            bench: false,
+            repeat: 1,
+            pin_cpu: None,
+            stable_event_order: false,
+            prune_dead_edges: false,
+        }
+    }
+
+    /// Where human-oriented output goes under `--porcelain` / `--quiet`
+    pub fn chatter(&self) -> Chatter {
+        if self.quiet {
+            Chatter::Off
+        } else if self.porcelain {
+            Chatter::Stderr
+        } else {
+            Chatter::Stdout
+        }
+    }
+
+    /// Locked stream for human-oriented output, `None` under `--quiet`
+    pub fn chatter_out(&self) -> Option<Box<dyn std::io::Write>> {
+        match self.chatter() {
+            Chatter::Stdout => Some(Box::new(std::io::stdout().lock())),
+            Chatter::Stderr => Some(Box::new(std::io::stderr().lock())),
+            Chatter::Off => None,
+        }
+    }
+
+    /// Whether the engine should count colony visits
+    pub fn track_visits(&self) -> bool {
+        self.heatmap || self.heatmap_csv.is_some()
+    }
+
+    /// Whether the engine should credit fights to the ants in them
+    pub fn track_kills(&self) -> bool {
+        self.top_killers.is_some()
+    }
+
+    /// Draw one ant's move limit; consumes no randomness without `--max-moves-dist`
+    pub fn sample_max_moves(&self, rng: &mut fastrand::Rng) -> u32 {
+        match &self.max_moves_dist {
+            Some(dist) => dist.sample(rng),
+            None => self.max_moves,
+        }
+    }
+
+    /// Number of initial ants that are queens
+    pub fn queen_count(&self) -> usize {
+        ((self.ants as f64) * self.queen_ratio).round() as usize
+    }
+
+    /// Number of initial ants that are mega ants
+    pub fn mega_count(&self) -> usize {
+        self.mega_fraction.map_or(0, |p| ((self.ants as f64) * p).round() as usize)
+    }
+}
+
+/// Destination of events, the summary and other human-oriented output
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Chatter {
+    /// Mixed with the results on stdout (default)
+    Stdout,
+    /// On stderr, so stdout carries only machine-readable results
+    Stderr,
+    /// Not written at all
+    Off,
+}
+
+/// Movement scheduling model
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "cli", derive(ValueEnum))]
+pub enum Scheduling {
+    /// All ants move at once; collisions resolve after everyone has moved
+    Simultaneous,
+    /// Ants move one at a time in id order; collisions resolve immediately
+    Sequential,
+}
+
+/// Line order for the final world
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "cli", derive(ValueEnum))]
+pub enum SortOrder {
+    /// Colony name, byte-wise
+    Name,
+    /// Colony id (first-seen order in the input)
+    Id,
+}
+
+/// How a run report is written, see [`crate::report_writer`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "cli", derive(ValueEnum))]
+pub enum ReportFormat {
+    /// The summary lines printed at the end of a run, without colors
+    Text,
+    /// Everything, timeline included, as pretty-printed JSON
+    Json,
+    /// A header and one row of counters
+    Csv,
+    /// Summary tables
+    Markdown,
+}
+
+/// Fate of an ant that finds no alive exit
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "cli", derive(ValueEnum))]
+pub enum TrappedPolicy {
+    /// Stays put as stationary stock; any ant that arrives fights it
+    Stay,
+    /// Dies on the spot without harming the colony
+    Die,
+    /// Stays alive but no longer takes part in collisions
+    Ghost,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_version_request() {
+        assert_eq!(version_request(&["--version"]), Some(false));
+        assert_eq!(version_request(&["--json", "-V"]), Some(true));
+        assert_eq!(version_request(&["--version", "-n", "3"]), None);
+        assert_eq!(version_request::<&str>(&[]), None);
+    }
+
+    #[test]
+    fn test_parse_range_list() {
+        assert_eq!(parse_range_list("5").unwrap().0, vec![5]);
+        assert_eq!(parse_range_list("1..4").unwrap().0, vec![1, 2, 3, 4]);
+        assert_eq!(parse_range_list("1000..3000:1000").unwrap().0, vec![1000, 2000, 3000]);
+        assert_eq!(parse_range_list("0..10:step 4").unwrap().0, vec![0, 4, 8]);
+        assert_eq!(parse_range_list("3,1,2").unwrap().0, vec![3, 1, 2]);
+
+        assert!(parse_range_list("5..1").is_err());
+        assert!(parse_range_list("1..5:0").is_err());
+        assert!(parse_range_list("x").is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "cli")]
+    fn test_with_defaults_matches_parsed_defaults() {
+        let parsed = Args::parse_from(["ant_mania", "--ants", "7", "--map", "m.txt"]);
+        assert_eq!(format!("{:?}", Args::with_defaults(7, "m.txt")), format!("{parsed:?}"));
+    }
 }
@@ -0,0 +1,170 @@
+use crate::report::{Report, TimelineCause};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use std::fmt;
+
+/// One numeric metric seen in both runs
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Delta<T> {
+    pub a: T,
+    pub b: T,
+}
+
+impl Delta<f64> {
+    /// Relative change from `a` to `b` in percent (`None` when `a` is zero)
+    pub fn percent(&self) -> Option<f64> {
+        (self.a != 0.0).then(|| (self.b - self.a) / self.a * 100.0)
+    }
+}
+
+/// Differences between two JSON run reports
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct RunComparison {
+    pub survivors: Delta<usize>,
+    pub ticks: Delta<u64>,
+    pub latency_ms: Delta<f64>,
+    /// Colonies destroyed in both runs
+    pub destroyed_both: usize,
+    /// Colonies destroyed only in run A
+    pub only_a: Vec<String>,
+    /// Colonies destroyed only in run B
+    pub only_b: Vec<String>,
+}
+
+impl RunComparison {
+    /// Compare run `a` against run `b`
+    pub fn new(a: &Report, b: &Report) -> Self {
+        let destroyed = |r: &Report| {
+            r.timeline
+                .iter()
+                .filter(|e| {
+                    !matches!(
+                        e.cause,
+                        TimelineCause::Averted { .. } | TimelineCause::NearMiss { .. } | TimelineCause::Repelled { .. }
+                    )
+                })
+                .map(|e| e.colony.clone())
+                .collect::<BTreeSet<_>>()
+        };
+        let (set_a, set_b) = (destroyed(a), destroyed(b));
+
+        Self {
+            survivors: Delta { a: a.survivors, b: b.survivors },
+            ticks: Delta { a: a.ticks, b: b.ticks },
+            latency_ms: Delta { a: a.latency_ms, b: b.latency_ms },
+            destroyed_both: set_a.intersection(&set_b).count(),
+            only_a: set_a.difference(&set_b).cloned().collect(),
+            only_b: set_b.difference(&set_a).cloned().collect(),
+        }
+    }
+
+    /// True when both runs ended with the same world (latency is ignored)
+    pub fn same_outcome(&self) -> bool {
+        self.survivors.a == self.survivors.b && self.only_a.is_empty() && self.only_b.is_empty()
+    }
+}
+
+impl fmt::Display for RunComparison {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let diff = |a: i64, b: i64| format!("{:+}", b - a);
+        writeln!(
+            f,
+            "survivors:  {} -> {} ({})",
+            self.survivors.a,
+            self.survivors.b,
+            diff(self.survivors.a as i64, self.survivors.b as i64)
+        )?;
+        writeln!(
+            f,
+            "ticks:      {} -> {} ({})",
+            self.ticks.a,
+            self.ticks.b,
+            diff(self.ticks.a as i64, self.ticks.b as i64)
+        )?;
+        write!(f, "latency:    {:.3} ms -> {:.3} ms", self.latency_ms.a, self.latency_ms.b)?;
+        match self.latency_ms.percent() {
+            Some(pct) => writeln!(f, " ({pct:+.1}%)")?,
+            None => writeln!(f)?,
+        }
+        writeln!(
+            f,
+            "destroyed:  {} in both, {} only in A, {} only in B",
+            self.destroyed_both,
+            self.only_a.len(),
+            self.only_b.len()
+        )?;
+        for name in &self.only_a {
+            writeln!(f, "  - {name}")?;
+        }
+        for name in &self.only_b {
+            writeln!(f, "  + {name}")?;
+        }
+        write!(f, "outcome:    {}", if self.same_outcome() { "identical" } else { "different" })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ant::KindStats;
+    use crate::report::TimelineEntry;
+
+    fn report(survivors: usize, latency_ms: f64, destroyed: &[&str]) -> Report {
+        Report {
+            ants: 10,
+            max_moves: 10_000,
+            max_moves_dist: None,
+            max_ticks: None,
+            seed: Some(1),
+            seeds: None,
+            ticks: 5,
+            last_destruction: None,
+            outcomes: Default::default(),
+            deaths: Default::default(),
+            start: Default::default(),
+            energy: None,
+            interrupted: false,
+            limit_exceeded: None,
+            latency_ms,
+            survivors,
+            workers: KindStats::default(),
+            queens: KindStats::default(),
+            timeline: destroyed
+                .iter()
+                .map(|name| TimelineEntry {
+                    tick: 1,
+                    colony: name.to_string(),
+                    cause: TimelineCause::Fight { ants: [0, 1] },
+                    ant_names: None,
+                })
+                .collect(),
+            lifetimes: Default::default(),
+            near_misses: None,
+            repelled: None,
+            revived: None,
+            meteors: None,
+            trampled: None,
+            heatmap: None,
+            top_killers: None,
+        }
+    }
+
+    #[test]
+    fn test_identical_outcome_ignores_latency() {
+        let cmp = RunComparison::new(&report(3, 10.0, &["A", "B"]), &report(3, 5.0, &["B", "A"]));
+
+        assert!(cmp.same_outcome());
+        assert_eq!(cmp.destroyed_both, 2);
+        assert_eq!(cmp.latency_ms.percent(), Some(-50.0));
+    }
+
+    #[test]
+    fn test_reports_destroyed_set_differences() {
+        let cmp = RunComparison::new(&report(3, 1.0, &["A", "B"]), &report(2, 1.0, &["B", "C", "D"]));
+
+        assert!(!cmp.same_outcome());
+        assert_eq!(cmp.only_a, ["A"]);
+        assert_eq!(cmp.only_b, ["C", "D"]);
+        assert!(cmp.to_string().contains("survivors:  3 -> 2 (-1)"));
+    }
+}
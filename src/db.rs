@@ -0,0 +1,238 @@
+use crate::cli::Args;
+use crate::report::{Report, TimelineCause};
+use crate::sweep::SweepRow;
+use crate::world::World;
+use clap::ValueEnum;
+use rusqlite::{params, Connection, Transaction};
+use std::collections::HashMap;
+
+/// Tables created on first use; later runs append to them
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS runs (
+    id INTEGER PRIMARY KEY,
+    map TEXT NOT NULL,
+    ants INTEGER NOT NULL,
+    ticks INTEGER NOT NULL,
+    survivors INTEGER NOT NULL,
+    latency_ms REAL NOT NULL,
+    status TEXT NOT NULL,
+    last_destruction INTEGER
+);
+CREATE TABLE IF NOT EXISTS parameters (
+    run_id INTEGER NOT NULL REFERENCES runs(id),
+    name TEXT NOT NULL,
+    value TEXT NOT NULL,
+    PRIMARY KEY (run_id, name)
+);
+CREATE TABLE IF NOT EXISTS colonies (
+    run_id INTEGER NOT NULL REFERENCES runs(id),
+    colony INTEGER NOT NULL,
+    name TEXT NOT NULL,
+    destroyed INTEGER NOT NULL,
+    destroyed_tick INTEGER,
+    PRIMARY KEY (run_id, colony)
+);
+CREATE TABLE IF NOT EXISTS events (
+    run_id INTEGER NOT NULL REFERENCES runs(id),
+    seq INTEGER NOT NULL,
+    tick INTEGER NOT NULL,
+    colony TEXT NOT NULL,
+    kind TEXT NOT NULL,
+    ant1 INTEGER,
+    ant2 INTEGER,
+    origin TEXT,
+    PRIMARY KEY (run_id, seq)
+);
+";
+
+/// SQLite database collecting the results of many runs, for `--db`.
+///
+/// Each run becomes one `runs` row (outcome and `status`: `ok`,
+/// `interrupted` or the limit that stopped or skipped it), its simulation
+/// options as `parameters` name/value rows, one `colonies` row per colony
+/// of the map and, for full runs, its `events`. Child rows point at
+/// `runs.id`, so a sweep is aggregated with plain SQL joins.
+pub struct ResultsDb {
+    conn: Connection,
+}
+
+impl ResultsDb {
+    /// Open (or create) the database at `path` and its tables
+    pub fn open(path: &str) -> rusqlite::Result<Self> {
+        let conn = Connection::open(path)?;
+        conn.execute_batch(SCHEMA)?;
+        Ok(Self { conn })
+    }
+
+    /// Record a finished run: `world` is its final world, `report` built from it
+    pub fn record_report(&mut self, map: &str, args: &Args, world: &World, report: &Report) -> rusqlite::Result<i64> {
+        let status = match (report.limit_exceeded, report.interrupted) {
+            (Some(limit), _) => limit.to_string(),
+            (None, true) => "interrupted".to_string(),
+            (None, false) => "ok".to_string(),
+        };
+        let mut fell_at: HashMap<&str, u64> = HashMap::new();
+        for entry in report.timeline.iter().filter(|e| e.cause.destroyed()) {
+            fell_at.entry(&entry.colony).or_insert(entry.tick);
+        }
+
+        let tx = self.conn.transaction()?;
+        tx.execute(
+            "INSERT INTO runs (map, ants, ticks, survivors, latency_ms, status, last_destruction)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+            params![
+                map,
+                int(report.ants as u64),
+                int(report.ticks),
+                int(report.survivors as u64),
+                report.latency_ms,
+                status,
+                report.last_destruction.map(int),
+            ],
+        )?;
+        let run = tx.last_insert_rowid();
+        insert_parameters(&tx, run, args)?;
+        {
+            let mut colony = tx.prepare(
+                "INSERT INTO colonies (run_id, colony, name, destroyed, destroyed_tick) VALUES (?1, ?2, ?3, ?4, ?5)",
+            )?;
+            for c in world.colonies() {
+                let tick = fell_at.get(c.name()).copied().map(int);
+                colony.execute(params![run, int(c.id().index() as u64), c.name(), !c.is_alive(), tick])?;
+            }
+            let mut event = tx.prepare(
+                "INSERT INTO events (run_id, seq, tick, colony, kind, ant1, ant2, origin)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+            )?;
+            for (seq, entry) in report.timeline.iter().enumerate() {
+                let (kind, ant1, ant2, origin) = match &entry.cause {
+                    TimelineCause::Fight { ants: [a, b] } => ("fight", Some(*a), Some(*b), None),
+                    TimelineCause::Blast { origin } => ("blast", None, None, Some(origin.as_str())),
+                    TimelineCause::Averted { ants: [a, b] } => ("averted", Some(*a), Some(*b), None),
+                    TimelineCause::NearMiss { ants: [a, b] } => ("near_miss", Some(*a), Some(*b), None),
+                    TimelineCause::Repelled { ant } => ("repelled", Some(*ant), None, None),
+                    TimelineCause::Meteor => ("meteor", None, None, None),
+                    TimelineCause::Trampled { ant } => ("trampled", Some(*ant), None, None),
+                };
+                event.execute(params![run, seq as i64, int(entry.tick), entry.colony, kind, ant1, ant2, origin])?;
+            }
+        }
+        tx.commit()?;
+        Ok(run)
+    }
+
+    /// Record one run of a sweep on `world` (the map before the run): its
+    /// outcome and colonies, without events. `base` holds the sweep-wide
+    /// options; the row's ant count and seed override it.
+    pub fn record_sweep_row(&mut self, map: &str, base: &Args, world: &World, row: &SweepRow) -> rusqlite::Result<i64> {
+        let mut args = base.clone();
+        args.ants = row.ants;
+        args.seed = Some(row.seed);
+
+        let tx = self.conn.transaction()?;
+        tx.execute(
+            "INSERT INTO runs (map, ants, ticks, survivors, latency_ms, status) VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            params![
+                map,
+                int(row.ants as u64),
+                int(row.ticks),
+                int(row.survivors as u64),
+                row.latency_ms,
+                row.limit.map_or_else(|| "ok".to_string(), |limit| limit.to_string()),
+            ],
+        )?;
+        let run = tx.last_insert_rowid();
+        insert_parameters(&tx, run, &args)?;
+        {
+            let mut colony =
+                tx.prepare("INSERT INTO colonies (run_id, colony, name, destroyed) VALUES (?1, ?2, ?3, ?4)")?;
+            for c in world.colonies() {
+                let destroyed = !c.is_alive() || row.destroyed.contains(&c.id());
+                colony.execute(params![run, int(c.id().index() as u64), c.name(), destroyed])?;
+            }
+        }
+        tx.commit()?;
+        Ok(run)
+    }
+
+    /// The underlying connection, for queries
+    pub fn connection(&self) -> &Connection {
+        &self.conn
+    }
+}
+
+/// The options that shape a run's outcome, as `parameters` rows; unset
+/// optional ones are left out
+fn insert_parameters(tx: &Transaction, run: i64, args: &Args) -> rusqlite::Result<()> {
+    let rows = [
+        ("ants", Some(args.ants.to_string())),
+        ("max_moves", Some(args.max_moves.to_string())),
+        ("max_moves_dist", args.max_moves_dist.map(|d| d.to_string())),
+        ("max_ticks", args.max_ticks.map(|n| n.to_string())),
+        ("seed", args.seed.map(|s| s.to_string())),
+        ("placement", Some(args.placement.to_string())),
+        ("scheduling", Some(value_name(args.scheduling))),
+        ("strategy", Some(args.strategy.to_string())),
+        ("bias", args.bias.map(|b| b.to_string())),
+        ("trapped_policy", Some(value_name(args.trapped_policy))),
+        ("blast_radius", Some(args.blast_radius.to_string())),
+        ("queen_ratio", Some(args.queen_ratio.to_string())),
+        ("spawn_every", Some(args.spawn_every.to_string())),
+        ("waves", (!args.waves.is_empty()).then(|| args.waves.iter().map(|w| w.to_string()).collect::<Vec<_>>().join(" "))),
+        ("energy", args.energy.map(|e| e.to_string())),
+        ("collision_prob", args.collision_prob.map(|p| p.to_string())),
+        ("fortress_defense", Some(args.fortress_defense.to_string())),
+        ("revival_prob", args.revival_prob.map(|p| p.to_string())),
+        ("chaos", args.chaos.map(|p| p.to_string())),
+        ("mega_fraction", args.mega_fraction.map(|p| p.to_string())),
+        ("max_destructions_per_tick", args.max_destructions_per_tick.map(|n| n.to_string())),
+    ];
+    let mut insert = tx.prepare("INSERT INTO parameters (run_id, name, value) VALUES (?1, ?2, ?3)")?;
+    for (name, value) in rows {
+        if let Some(value) = value {
+            insert.execute(params![run, name, value])?;
+        }
+    }
+    Ok(())
+}
+
+/// The command-line spelling of a flag value
+fn value_name<T: ValueEnum>(value: T) -> String {
+    value.to_possible_value().map_or_else(String::new, |v| v.get_name().to_string())
+}
+
+/// SQLite integers are signed 64-bit
+fn int(value: u64) -> i64 {
+    i64::try_from(value).unwrap_or(i64::MAX)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::world::parser::load_world_from_str;
+
+    #[test]
+    fn test_sweep_rows_append_runs() {
+        let world = load_world_from_str("A east=B\nB\n", &Default::default()).unwrap();
+        let mut base = Args::with_defaults(0, "map.txt");
+        base.max_ticks = Some(9);
+        let mut db = ResultsDb::open(":memory:").unwrap();
+        for (seed, destroyed) in [(1, vec![]), (2, vec![world.find("B").unwrap()])] {
+            let row = SweepRow { ants: 2, seed, survivors: 2, ticks: 3, latency_ms: 0.5, limit: None, destroyed };
+            db.record_sweep_row("map.txt", &base, &world, &row).unwrap();
+        }
+
+        let conn = db.connection();
+        let runs: i64 = conn.query_row("SELECT COUNT(*) FROM runs WHERE status = 'ok'", [], |r| r.get(0)).unwrap();
+        let seed: String = conn
+            .query_row("SELECT value FROM parameters WHERE run_id = 2 AND name = 'seed'", [], |r| r.get(0))
+            .unwrap();
+        let fallen: String = conn
+            .query_row("SELECT name FROM colonies WHERE destroyed", [], |r| r.get(0))
+            .unwrap();
+        let max_ticks: String = conn
+            .query_row("SELECT value FROM parameters WHERE run_id = 1 AND name = 'max_ticks'", [], |r| r.get(0))
+            .unwrap();
+        assert_eq!((runs, seed.as_str(), fallen.as_str(), max_ticks.as_str()), (2, "2", "B", "9"));
+    }
+}
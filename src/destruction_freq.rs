@@ -0,0 +1,94 @@
+use crate::node_id::NodeId;
+use crate::sweep::SweepRow;
+use crate::world::World;
+use serde::{Deserialize, Serialize};
+use std::io::Write;
+
+/// How often one colony fell over a batch of runs
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct DestructionFrequency {
+    pub colony: String,
+    /// Runs that ended with the colony destroyed
+    pub destroyed: usize,
+    /// Runs counted (sweep rows skipped for memory are left out)
+    pub runs: usize,
+    /// `destroyed / runs`, 0 when no run took place
+    pub fraction: f64,
+}
+
+impl DestructionFrequency {
+    pub const CSV_HEADER: &'static str = "colony,destroyed,runs,fraction";
+
+    /// Write this entry as one CSV line
+    pub fn write_csv<W: Write>(&self, out: &mut W) -> std::io::Result<()> {
+        writeln!(out, "{},{},{},{:.6}", self.colony, self.destroyed, self.runs, self.fraction)
+    }
+}
+
+/// Per-colony destruction frequency over the runs of a sweep on `world`,
+/// keyed by colony name in colony id order
+pub fn destruction_frequency(world: &World, rows: &[SweepRow]) -> Vec<DestructionFrequency> {
+    let mut destroyed = vec![0usize; world.nodes.len()];
+    let mut runs = 0;
+    for row in rows.iter().filter(|r| r.ran()) {
+        runs += 1;
+        for id in &row.destroyed {
+            destroyed[id.index()] += 1;
+        }
+    }
+    destroyed
+        .into_iter()
+        .enumerate()
+        .map(|(i, destroyed)| DestructionFrequency {
+            colony: world.get_colony_name(NodeId::from_index(i)).to_string(),
+            destroyed,
+            runs,
+            fraction: if runs == 0 { 0.0 } else { destroyed as f64 / runs as f64 },
+        })
+        .collect()
+}
+
+/// Write destruction frequencies as CSV, header first
+pub fn write_csv<W: Write>(entries: &[DestructionFrequency], out: &mut W) -> std::io::Result<()> {
+    writeln!(out, "{}", DestructionFrequency::CSV_HEADER)?;
+    for entry in entries {
+        entry.write_csv(out)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::limits::Limit;
+    use crate::node_id::NodeIndex;
+    use crate::world::parser::parse_world_from_str;
+
+    fn row(destroyed: &[NodeIndex], limit: Option<Limit>) -> SweepRow {
+        SweepRow {
+            ants: 2,
+            seed: 0,
+            survivors: 0,
+            ticks: 0,
+            latency_ms: 0.0,
+            limit,
+            destroyed: destroyed.iter().map(|&i| NodeId::new(i)).collect(),
+        }
+    }
+
+    #[test]
+    fn test_fraction_of_runs_per_colony() {
+        let (names, nodes) = parse_world_from_str("A east=B\nB west=A\n");
+        let world = World::new(names, nodes);
+        let rows = [row(&[0], None), row(&[0, 1], Some(Limit::WallTime)), row(&[], None), row(&[], Some(Limit::Memory))];
+
+        let entries = destruction_frequency(&world, &rows);
+        let mut out = Vec::new();
+        write_csv(&entries, &mut out).unwrap();
+
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "colony,destroyed,runs,fraction\nA,2,3,0.666667\nB,1,3,0.333333\n"
+        );
+    }
+}
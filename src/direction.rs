@@ -1,8 +1,10 @@
 use crate::error::ParseError;
+use serde::{Deserialize, Serialize};
 use std::str::FromStr;
 
 /// 4 fixed directions for tiny, predictable loops
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 #[repr(u8)]
 pub enum Direction {
     North = 0,
@@ -22,11 +24,24 @@ impl FromStr for Direction {
             b"south" => Ok(Direction::South),
             b"east" => Ok(Direction::East),
             b"west" => Ok(Direction::West),
-            _ => Err(ParseError::InvalidDirection(s.to_string())),
+            _ => Err(ParseError::invalid_direction(s)),
         }
     }
 }
 
+impl TryFrom<u8> for Direction {
+    type Error = ParseError;
+
+    /// Direction with this [`Direction::index`]
+    #[inline]
+    fn try_from(index: u8) -> Result<Self, Self::Error> {
+        Direction::ALL
+            .get(index as usize)
+            .copied()
+            .ok_or_else(|| ParseError::invalid_direction(index.to_string()))
+    }
+}
+
 impl Direction {
     /// All possible directions
     pub const ALL: [Direction; 4] = [
@@ -36,6 +51,34 @@ impl Direction {
         Direction::West,
     ];
 
+    /// All directions in index order
+    #[inline]
+    pub fn iter() -> impl Iterator<Item = Direction> {
+        Self::ALL.into_iter()
+    }
+
+    /// Direction of the tunnel back: north <-> south, east <-> west
+    #[inline]
+    pub const fn opposite(self) -> Direction {
+        match self {
+            Direction::North => Direction::South,
+            Direction::South => Direction::North,
+            Direction::East => Direction::West,
+            Direction::West => Direction::East,
+        }
+    }
+
+    /// Unit step on a grid as `(dx, dy)`, north being up (`dy = -1`)
+    #[inline]
+    pub const fn delta(self) -> (i64, i64) {
+        match self {
+            Direction::North => (0, -1),
+            Direction::South => (0, 1),
+            Direction::East => (1, 0),
+            Direction::West => (-1, 0),
+        }
+    }
+
     /// Get direction index for array indexing
     #[inline]
     pub const fn index(self) -> usize {
@@ -85,6 +128,26 @@ mod tests {
         assert_eq!(Direction::West.as_str(), "west");
     }
 
+    #[test]
+    fn test_opposite_and_delta() {
+        for d in Direction::iter() {
+            assert_ne!(d.opposite(), d);
+            assert_eq!(d.opposite().opposite(), d);
+            let ((dx, dy), (ox, oy)) = (d.delta(), d.opposite().delta());
+            assert_eq!((dx + ox, dy + oy), (0, 0));
+        }
+        assert_eq!(Direction::North.opposite(), Direction::South);
+        assert_eq!(Direction::East.delta(), (1, 0));
+    }
+
+    #[test]
+    fn test_try_from_index() {
+        for d in Direction::iter() {
+            assert_eq!(Direction::try_from(d.index() as u8).unwrap(), d);
+        }
+        assert!(Direction::try_from(4).is_err());
+    }
+
     #[test]
     fn test_all_directions() {
         assert_eq!(Direction::ALL.len(), 4);
@@ -0,0 +1,107 @@
+use std::fmt;
+use std::str::FromStr;
+
+/// Distribution of per-ant move limits (`--max-moves-dist`)
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum MaxMovesDist {
+    /// Every ant gets the same limit: `const:N`
+    Const(u32),
+    /// Inclusive uniform range: `uniform:LO,HI`
+    Uniform { lo: u32, hi: u32 },
+    /// Normal, rounded and clamped at zero: `normal:MEAN,STD`
+    Normal { mean: f64, std: f64 },
+}
+
+impl MaxMovesDist {
+    /// Draw one limit
+    pub fn sample(&self, rng: &mut fastrand::Rng) -> u32 {
+        match *self {
+            MaxMovesDist::Const(n) => n,
+            MaxMovesDist::Uniform { lo, hi } => rng.u32(lo..=hi),
+            MaxMovesDist::Normal { mean, std } => {
+                // Box-Muller; 1 - f64() keeps the log argument in (0, 1]
+                let u1 = 1.0 - rng.f64();
+                let u2 = rng.f64();
+                let z = (-2.0 * u1.ln()).sqrt() * (std::f64::consts::TAU * u2).cos();
+                (mean + std * z).round().clamp(0.0, u32::MAX as f64) as u32
+            }
+        }
+    }
+}
+
+impl FromStr for MaxMovesDist {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (kind, params) = s
+            .split_once(':')
+            .ok_or_else(|| format!("expected KIND:PARAMS, got '{s}'"))?;
+        let params: Vec<&str> = params.split(',').map(str::trim).collect();
+        let int = |t: &str| t.parse::<u32>().map_err(|e| format!("invalid number '{t}': {e}"));
+        let float = |t: &str| t.parse::<f64>().map_err(|e| format!("invalid number '{t}': {e}"));
+
+        match (kind, params.as_slice()) {
+            ("const", [n]) => Ok(MaxMovesDist::Const(int(n)?)),
+            ("uniform", [lo, hi]) => {
+                let (lo, hi) = (int(lo)?, int(hi)?);
+                if lo > hi {
+                    return Err(format!("empty range {lo},{hi}"));
+                }
+                Ok(MaxMovesDist::Uniform { lo, hi })
+            }
+            ("normal", [mean, std]) => {
+                let (mean, std) = (float(mean)?, float(std)?);
+                if !mean.is_finite() || !std.is_finite() || std < 0.0 {
+                    return Err("mean must be finite and std a non-negative number".to_string());
+                }
+                Ok(MaxMovesDist::Normal { mean, std })
+            }
+            _ => Err(format!(
+                "unknown distribution '{s}' (expected const:N, uniform:LO,HI or normal:MEAN,STD)"
+            )),
+        }
+    }
+}
+
+impl fmt::Display for MaxMovesDist {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MaxMovesDist::Const(n) => write!(f, "const:{n}"),
+            MaxMovesDist::Uniform { lo, hi } => write!(f, "uniform:{lo},{hi}"),
+            MaxMovesDist::Normal { mean, std } => write!(f, "normal:{mean},{std}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_and_display_round_trip() {
+        for s in ["const:7", "uniform:5,10", "normal:10000,1500"] {
+            assert_eq!(s.parse::<MaxMovesDist>().unwrap().to_string(), s);
+        }
+        assert!("normal:1".parse::<MaxMovesDist>().is_err());
+        assert!("uniform:9,3".parse::<MaxMovesDist>().is_err());
+        assert!("normal:10,-1".parse::<MaxMovesDist>().is_err());
+        assert!("poisson:3".parse::<MaxMovesDist>().is_err());
+    }
+
+    #[test]
+    fn test_samples_stay_in_range() {
+        let mut rng = fastrand::Rng::with_seed(3);
+        let uniform = MaxMovesDist::Uniform { lo: 5, hi: 10 };
+        assert!((0..1000).map(|_| uniform.sample(&mut rng)).all(|n| (5..=10).contains(&n)));
+
+        let normal = MaxMovesDist::Normal { mean: 1000.0, std: 100.0 };
+        let samples: Vec<u32> = (0..10_000).map(|_| normal.sample(&mut rng)).collect();
+        let mean = samples.iter().map(|&n| n as f64).sum::<f64>() / samples.len() as f64;
+        assert!((mean - 1000.0).abs() < 10.0, "{mean}");
+        assert!(samples.iter().any(|&n| n != samples[0]));
+
+        // Clamped at zero instead of wrapping
+        let wide = MaxMovesDist::Normal { mean: 0.0, std: 50.0 };
+        assert!((0..100).map(|_| wide.sample(&mut rng)).any(|n| n == 0));
+    }
+}
@@ -1,28 +1,214 @@
+use serde::{Deserialize, Serialize};
 use std::fmt;
 
+/// 1-based position of a token in a map file
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SourceLocation {
+    pub line: usize,
+    /// Column in characters, not bytes
+    pub column: usize,
+}
+
+impl fmt::Display for SourceLocation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}, column {}", self.line, self.column)
+    }
+}
+
 /// Custom error types for the ant simulation
 #[derive(Debug)]
 pub enum ParseError {
     /// IO operation failed
     IoError(std::io::Error),
     /// Invalid line format in map file
-    InvalidLine(String),
+    InvalidLine {
+        message: String,
+        location: Option<SourceLocation>,
+    },
     /// Invalid direction string
-    InvalidDirection(String),
+    InvalidDirection {
+        direction: String,
+        location: Option<SourceLocation>,
+    },
+    /// An alias was claimed by two different colonies (or by an alias itself)
+    ConflictingAlias {
+        alias: String,
+        first: String,
+        second: String,
+    },
+    /// A colony was given two different tunnels in the same direction
+    ConflictingTunnel {
+        colony: String,
+        direction: crate::direction::Direction,
+        first: String,
+        second: String,
+    },
+    /// Map file is compressed with a codec this build does not include
+    UnsupportedFormat(String),
+    /// A colony name exists in both worlds being merged
+    DuplicateColony(String),
+    /// A colony name does not exist in the world it should belong to
+    UnknownColony(String),
+    /// The map has more colonies than `NodeIndex` can address
+    TooManyColonies(usize),
+    /// A map directory holds no map files
+    NoMapFiles(String),
+    /// An error in one fragment of a map spread over several files
+    InFile { path: String, error: Box<ParseError> },
+    /// A colony name breaks `ParseOptions::validate_names` or `max_name_len`
+    InvalidColonyName {
+        name: String,
+        reason: String,
+        location: Option<SourceLocation>,
+    },
 }
 
 impl fmt::Display for ParseError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             ParseError::IoError(err) => write!(f, "IO error: {}", err),
-            ParseError::InvalidLine(msg) => write!(f, "Invalid line: {}", msg),
-            ParseError::InvalidDirection(dir) => write!(f, "Invalid direction: {}", dir),
+            ParseError::InvalidLine { message, location } => {
+                if let Some(at) = location {
+                    write!(f, "{}: ", at)?;
+                }
+                write!(f, "Invalid line: {}", message)
+            }
+            ParseError::InvalidDirection { direction, location } => {
+                if let Some(at) = location {
+                    write!(f, "{}: ", at)?;
+                }
+                write!(f, "Invalid direction: {}", direction)
+            }
+            ParseError::ConflictingAlias { alias, first, second } => write!(
+                f,
+                "Conflicting alias: {} is claimed by both {} and {}",
+                alias, first, second
+            ),
+            ParseError::ConflictingTunnel { colony, direction, first, second } => write!(
+                f,
+                "Conflicting tunnel: {} {} leads to both {} and {}",
+                colony,
+                direction.as_str(),
+                first,
+                second
+            ),
+            ParseError::UnsupportedFormat(codec) => write!(f, "Unsupported map format: {}", codec),
+            ParseError::DuplicateColony(name) => write!(f, "Duplicate colony: {}", name),
+            ParseError::UnknownColony(name) => write!(f, "Unknown colony: {}", name),
+            ParseError::TooManyColonies(count) => write!(
+                f,
+                "Too many colonies: {} (at most {}; build with the `wide-ids` feature for more)",
+                count,
+                crate::node_id::MAX_NODES
+            ),
+            ParseError::NoMapFiles(dir) => write!(f, "No map files in {}", dir),
+            ParseError::InFile { path, error } => write!(f, "{}: {}", path, error),
+            ParseError::InvalidColonyName { name, reason, location } => {
+                if let Some(at) = location {
+                    write!(f, "{}: ", at)?;
+                }
+                write!(f, "Invalid colony name '{}': {}", name, reason)
+            }
         }
     }
 }
 
 impl std::error::Error for ParseError {}
 
+impl ParseError {
+    /// Invalid line error without a location yet
+    pub fn invalid_line(message: impl Into<String>) -> Self {
+        ParseError::InvalidLine { message: message.into(), location: None }
+    }
+
+    /// Invalid direction error without a location yet
+    pub fn invalid_direction(direction: impl Into<String>) -> Self {
+        ParseError::InvalidDirection { direction: direction.into(), location: None }
+    }
+
+    /// Attach the map position where this error occurred (kept only by
+    /// the variants that carry a location)
+    pub fn at(mut self, at: SourceLocation) -> Self {
+        if let ParseError::InvalidLine { location, .. }
+        | ParseError::InvalidDirection { location, .. }
+        | ParseError::InvalidColonyName { location, .. } = &mut self
+        {
+            *location = Some(at);
+        }
+        self
+    }
+
+    /// Tag an error from the map fragment at `path`
+    pub fn in_file(self, path: impl Into<String>) -> Self {
+        ParseError::InFile { path: path.into(), error: Box::new(self) }
+    }
+
+    /// Where in the map this error occurred, if known
+    pub fn location(&self) -> Option<SourceLocation> {
+        match self {
+            ParseError::InvalidLine { location, .. }
+            | ParseError::InvalidDirection { location, .. }
+            | ParseError::InvalidColonyName { location, .. } => *location,
+            ParseError::InFile { error, .. } => error.location(),
+            _ => None,
+        }
+    }
+
+    /// The map fragment this error occurred in, when the map spans several
+    /// files; otherwise [`location`](Self::location) is within the one map file
+    pub fn file(&self) -> Option<&str> {
+        match self {
+            ParseError::InFile { path, .. } => Some(path),
+            _ => None,
+        }
+    }
+
+    /// Render the error with the offending map line and a caret under the
+    /// token, compiler style. `source_line` is the text of line
+    /// `self.location().line`; without a location this is just `Display`.
+    pub fn render(&self, source_line: &str) -> String {
+        self.render_as("error", source_line)
+    }
+
+    /// [`render`](Self::render) for an error a lenient parse skipped
+    pub fn render_warning(&self, source_line: &str) -> String {
+        self.render_as("warning", source_line)
+    }
+
+    fn render_as(&self, label: &str, source_line: &str) -> String {
+        let Some(at) = self.location() else {
+            return format!("{label}: {self}\n");
+        };
+        let (error, file) = match self {
+            ParseError::InFile { path, error } => (error.as_ref(), format!("{path}, ")),
+            _ => (self, String::new()),
+        };
+        let message = match error {
+            ParseError::InvalidLine { message, .. } => format!("Invalid line: {message}"),
+            ParseError::InvalidDirection { direction, .. } => format!("Invalid direction: {direction}"),
+            ParseError::InvalidColonyName { name, reason, .. } => format!("Invalid colony name '{name}': {reason}"),
+            _ => error.to_string(),
+        };
+        let line_no = at.line.to_string();
+        let gutter = " ".repeat(line_no.len());
+        let indent: String = source_line
+            .chars()
+            .take(at.column - 1)
+            .map(|c| if c == '\t' { '\t' } else { ' ' })
+            .collect();
+        let width = source_line
+            .chars()
+            .skip(at.column - 1)
+            .take_while(|c| !c.is_whitespace())
+            .count()
+            .max(1);
+        format!(
+            "{label}: {message}\n{gutter}--> {file}{at}\n{gutter} |\n{line_no} | {source_line}\n{gutter} | {indent}{}\n",
+            "^".repeat(width)
+        )
+    }
+}
+
 impl From<std::io::Error> for ParseError {
     fn from(err: std::io::Error) -> Self {
         ParseError::IoError(err)
@@ -31,3 +217,84 @@ impl From<std::io::Error> for ParseError {
 
 /// Result type alias for this crate
 pub type Result<T> = std::result::Result<T, ParseError>;
+
+/// Why the engine refused or failed to run a simulation
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SimulationError {
+    /// A living ant starts on a colony that was already destroyed
+    InvalidAntPlacement { ant: u32, colony: String },
+    /// An ant stands on a colony index past the end of the world
+    NodeIndexOutOfRange { ant: u32, index: usize, len: usize },
+    /// The world or the ants break an invariant the engine relies on
+    InconsistentState(String),
+    /// The run stopped at `tick` on an interrupt, or on `limit` when a
+    /// resource limit was hit; the world and ants hold the partial run
+    Aborted { tick: u64, limit: Option<crate::limits::Limit> },
+}
+
+impl fmt::Display for SimulationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SimulationError::InvalidAntPlacement { ant, colony } => {
+                write!(f, "Invalid ant placement: ant {} starts on destroyed colony {}", ant, colony)
+            }
+            SimulationError::NodeIndexOutOfRange { ant, index, len } => write!(
+                f,
+                "Node index out of range: ant {} stands on colony {} of a world with {}",
+                ant, index, len
+            ),
+            SimulationError::InconsistentState(message) => write!(f, "Inconsistent state: {}", message),
+            SimulationError::Aborted { tick, limit: Some(limit) } => {
+                write!(f, "Aborted at tick {}: {} limit exceeded", tick, limit)
+            }
+            SimulationError::Aborted { tick, limit: None } => write!(f, "Aborted at tick {}: interrupted", tick),
+        }
+    }
+}
+
+impl std::error::Error for SimulationError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_points_at_token() {
+        let err = ParseError::invalid_line("missing key in '=C'").at(SourceLocation { line: 12, column: 11 });
+        assert_eq!(err.to_string(), "line 12, column 11: Invalid line: missing key in '=C'");
+        assert_eq!(
+            err.render("A north=B =C west=D"),
+            concat!(
+                "error: Invalid line: missing key in '=C'\n",
+                "  --> line 12, column 11\n",
+                "   |\n",
+                "12 | A north=B =C west=D\n",
+                "   |           ^^\n",
+            )
+        );
+    }
+
+    #[test]
+    fn test_render_names_the_fragment() {
+        let err = ParseError::invalid_direction("up").at(SourceLocation { line: 3, column: 3 }).in_file("maps/b.txt");
+        assert_eq!(err.to_string(), "maps/b.txt: line 3, column 3: Invalid direction: up");
+        assert_eq!((err.file(), err.location().map(|at| at.line)), (Some("maps/b.txt"), Some(3)));
+        assert_eq!(
+            err.render("B up=C"),
+            concat!(
+                "error: Invalid direction: up\n",
+                " --> maps/b.txt, line 3, column 3\n",
+                "  |\n",
+                "3 | B up=C\n",
+                "  |   ^^^^\n",
+            )
+        );
+    }
+
+    #[test]
+    fn test_render_without_location_is_display() {
+        let err = ParseError::UnknownColony("Z".to_string());
+        assert_eq!(err.location(), None);
+        assert_eq!(err.render("ignored"), "error: Unknown colony: Z\n");
+    }
+}
@@ -0,0 +1,222 @@
+use crate::ant::Ant;
+use crate::cli::Args;
+use crate::limits::Limit;
+use crate::node_id::{NodeId, NodeIndex};
+use crate::seeds::SubSeeds;
+use crate::simulation::SimulationEngine;
+use crate::world::World;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::mem::size_of;
+use std::time::{Duration, Instant};
+
+/// Bytes a run is expected to hold, by owner. Map attributes and event
+/// records are not counted.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MemoryEstimate {
+    /// Node table, names and reverse edges
+    pub nodes: u64,
+    /// Scratch arrays of the engine, per colony and per ant
+    pub buffers: u64,
+    /// The ants themselves
+    pub ants: u64,
+}
+
+impl MemoryEstimate {
+    /// Memory of `ants` ants on `world`
+    pub fn of(world: &World, ants: usize) -> Self {
+        let colonies = world.nodes().len() as u64;
+        let tunnels = world.colonies().map(|c| c.neighbors().count() as u64).sum::<u64>();
+        let names = world.names().iter().map(|n| (size_of::<String>() + n.len()) as u64).sum::<u64>();
+        let row = (size_of::<NodeIndex>() + size_of::<[Option<NodeId>; 4]>() + size_of::<usize>()) as u64;
+        let ants = ants as u64;
+        Self {
+            nodes: colonies * row + colonies.div_ceil(64) * 8 + names + tunnels * size_of::<NodeId>() as u64,
+            // Nine `u32` arrays and one `bool` array per colony; an active
+            // slot, a next position and a trapped flag per ant
+            buffers: colonies * (9 * 4 + 1) + ants * (size_of::<usize>() + size_of::<NodeId>() + 1) as u64,
+            ants: ants * size_of::<Ant>() as u64,
+        }
+    }
+
+    pub fn total(&self) -> u64 {
+        self.nodes + self.buffers + self.ants
+    }
+}
+
+/// Expected cost of a run, from its sizes and a short sampled start
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Estimate {
+    pub colonies: usize,
+    pub ants: usize,
+    pub memory: MemoryEstimate,
+    /// Ticks actually run to time the start of the run
+    pub sampled_ticks: u64,
+    pub sample_ms: f64,
+    /// Whether the run ended within the sample, making the projection exact
+    pub finished: bool,
+    /// Ticks until the last moving ant of the sample runs out of moves
+    pub projected_ticks: u64,
+    /// Projected latency of the whole run
+    pub projected_ms: f64,
+}
+
+impl Estimate {
+    /// Estimate a run of `args` on `world` by running its first
+    /// `sample_ticks` ticks on a copy, seeded and placed as the real run
+    /// would be (`args.seed` should be set for the sample to be its start).
+    ///
+    /// The remaining ticks are projected at the pace of the last quarter of
+    /// the sample, as if every ant still moving kept moving until its move
+    /// limit (or `args.max_ticks`): fights only make the real run shorter and cheaper, while
+    /// workers laid by queens later on are not foreseen.
+    pub fn of(world: &World, args: &Args, sample_ticks: u32) -> Self {
+        let mut args = args.clone();
+        args.suppress_events = true;
+        let seeds = SubSeeds::derive(args.seed.unwrap_or_default());
+        let mut world = world.clone();
+        let mut ants = world.place_colony(args.ants, args.queen_count(), args.placement, &mut seeds.placement_rng());
+        let memory = MemoryEstimate::of(&world, ants.len());
+        let colonies = world.nodes().len();
+
+        let mut rng = seeds.movement_rng();
+        let mut engine = SimulationEngine::new(&world, ants.len()).with_seeds(seeds);
+        let start = Instant::now();
+        engine.begin(&mut world, &mut ants, &args, &mut rng);
+        let mut state = engine.run_state(&world, &ants, &args);
+        let mut tick_times = Vec::with_capacity(sample_ticks as usize);
+        let mut finished = false;
+        while !finished && tick_times.len() < sample_ticks.max(1) as usize {
+            let tick_start = Instant::now();
+            finished = !engine.step(&mut state, &mut world, &mut ants, &args, &mut rng);
+            tick_times.push(tick_start.elapsed());
+        }
+        engine.finish(&mut state);
+        let sample = start.elapsed();
+
+        let sampled_ticks = engine.ticks();
+        let (projected_ticks, projected) = if finished {
+            (sampled_ticks, sample)
+        } else {
+            let left = ants
+                .iter()
+                .filter(|a| a.is_alive() && !a.is_trapped())
+                .map(|a| a.max_moves.saturating_sub(a.moves))
+                .max()
+                .unwrap_or(0);
+            // `--max-ticks` may end the run before any ant runs out of moves
+            let left = args
+                .max_ticks
+                .map_or(left, |max| left.min(u32::try_from(max.saturating_sub(sampled_ticks)).unwrap_or(u32::MAX)));
+            let recent = &tick_times[tick_times.len() - tick_times.len().div_ceil(4)..];
+            let pace = recent.iter().sum::<Duration>() / recent.len() as u32;
+            (sampled_ticks.saturating_add(u64::from(left)), sample + pace * left)
+        };
+
+        Self {
+            colonies,
+            ants: args.ants,
+            memory,
+            sampled_ticks,
+            sample_ms: sample.as_secs_f64() * 1000.0,
+            finished,
+            projected_ticks,
+            projected_ms: projected.as_secs_f64() * 1000.0,
+        }
+    }
+
+    /// Limits this estimate goes over: `memory_limit` in bytes,
+    /// `time_limit` of projected latency
+    pub fn over_limits(&self, memory_limit: Option<u64>, time_limit: Option<Duration>) -> Vec<Limit> {
+        let mut over = Vec::new();
+        if memory_limit.is_some_and(|max| self.memory.total() > max) {
+            over.push(Limit::Memory);
+        }
+        if time_limit.is_some_and(|max| self.projected_ms > max.as_secs_f64() * 1000.0) {
+            over.push(Limit::WallTime);
+        }
+        over
+    }
+}
+
+/// One `key=value` line, stable for scripts to parse
+impl fmt::Display for Estimate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "estimate ants={} colonies={} memory_bytes={} nodes_bytes={} buffers_bytes={} ants_bytes={} \
+             sampled_ticks={} sample_ms={:.3} projected_ticks={} projected_ms={:.3}{}",
+            self.ants,
+            self.colonies,
+            self.memory.total(),
+            self.memory.nodes,
+            self.memory.buffers,
+            self.memory.ants,
+            self.sampled_ticks,
+            self.sample_ms,
+            self.projected_ticks,
+            self.projected_ms,
+            if self.finished { " finished" } else { "" }
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::world::parser::parse_world_from_str;
+
+    fn world(src: &str) -> World {
+        let (names, nodes) = parse_world_from_str(src);
+        World::new(names, nodes)
+    }
+
+    /// Two ants that never fight, so the run lasts until their move limit
+    fn args(max_moves: u32) -> Args {
+        let mut args = Args::with_defaults(2, "unused");
+        args.max_moves = max_moves;
+        args.collision_prob = Some(0.0);
+        args.seed = Some(3);
+        args
+    }
+
+    #[test]
+    fn test_memory_grows_with_ants_and_colonies() {
+        let small = world("A east=B\nB west=A\n");
+        let large = world("A east=B\nB west=A east=C\nC west=B\n");
+
+        let base = MemoryEstimate::of(&small, 10);
+        assert!(MemoryEstimate::of(&small, 20).total() > base.total());
+        assert!(MemoryEstimate::of(&large, 10).nodes > base.nodes);
+        assert_eq!(base.ants, 10 * size_of::<Ant>() as u64);
+    }
+
+    #[test]
+    fn test_short_run_is_measured_not_projected() {
+        let estimate = Estimate::of(&world("A east=B\nB west=A\n"), &args(5), 100);
+
+        assert!(estimate.finished);
+        assert_eq!((estimate.sampled_ticks, estimate.projected_ticks), (5, 5));
+        assert_eq!(estimate.projected_ms, estimate.sample_ms);
+    }
+
+    #[test]
+    fn test_long_run_is_projected_to_the_move_limit() {
+        let estimate = Estimate::of(&world("A east=B\nB west=A\n"), &args(1000), 10);
+
+        assert!(!estimate.finished);
+        assert_eq!(estimate.sampled_ticks, 10);
+        assert_eq!(estimate.projected_ticks, 1000);
+        assert!(estimate.projected_ms >= estimate.sample_ms);
+    }
+
+    #[test]
+    fn test_over_limits() {
+        let estimate = Estimate::of(&world("A east=B\nB west=A\n"), &args(5), 100);
+        let bytes = estimate.memory.total();
+
+        assert!(estimate.over_limits(Some(bytes), Some(Duration::from_secs(60))).is_empty());
+        assert_eq!(estimate.over_limits(Some(bytes - 1), None), [Limit::Memory]);
+        assert_eq!(estimate.over_limits(None, Some(Duration::ZERO)), [Limit::WallTime]);
+    }
+}
@@ -0,0 +1,40 @@
+//! Process exit codes of the `ants_code_challenge` binary, for scripting
+
+use crate::error::ParseError;
+
+/// Run finished normally
+pub const SUCCESS: u8 = 0;
+/// Any other failure (I/O while writing results, `compare-runs --check` mismatch, ...)
+pub const FAILURE: u8 = 1;
+/// The map could not be read or parsed
+pub const PARSE_ERROR: u8 = 2;
+/// Command-line arguments were rejected
+pub const INVALID_ARGS: u8 = 3;
+/// The run was stopped by a signal (Ctrl-C) before it finished, or refused
+/// to start over a `--memory-limit` or an `estimate` limit
+pub const ABORTED: u8 = 4;
+/// No colony survived (only with `--wipeout-exit`)
+pub const WIPEOUT: u8 = 10;
+
+/// Exit code for an error bubbled up to `main`
+pub fn for_error(err: &(dyn std::error::Error + 'static)) -> u8 {
+    if err.is::<ParseError>() {
+        PARSE_ERROR
+    } else {
+        FAILURE
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_errors_map_to_their_own_code() {
+        let parse: Box<dyn std::error::Error> = Box::new(ParseError::invalid_line("x"));
+        let io: Box<dyn std::error::Error> = Box::new(std::io::Error::other("disk full"));
+
+        assert_eq!(for_error(parse.as_ref()), PARSE_ERROR);
+        assert_eq!(for_error(io.as_ref()), FAILURE);
+    }
+}
@@ -0,0 +1,260 @@
+use crate::node_id::{NodeId, NodeIndex};
+use crate::simulation::TickSnapshot;
+use crate::world::World;
+use serde::{Deserialize, Serialize};
+use std::io::Write;
+
+/// Magic bytes opening a binary frame file
+pub const BINARY_MAGIC: &[u8; 4] = b"ANTF";
+/// Version of the binary layout, written after [`BINARY_MAGIC`]
+pub const BINARY_VERSION: u8 = 2;
+
+/// Encoding of a `--frames` file
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FrameFormat {
+    /// One JSON object per line: the [`FrameHeader`], then one [`Frame`] each
+    JsonLines,
+    /// Little-endian records, see [`FrameWriter`]
+    Binary,
+}
+
+impl FrameFormat {
+    /// `Binary` for paths ending in `.bin`, `JsonLines` otherwise
+    pub fn from_path(path: &str) -> Self {
+        if path.ends_with(".bin") {
+            FrameFormat::Binary
+        } else {
+            FrameFormat::JsonLines
+        }
+    }
+}
+
+/// First record of a frame file: the map the frames refer to
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FrameHeader {
+    /// Colony names by id
+    pub colonies: Vec<String>,
+    /// Tunnels of each colony by id, in direction order (north, south, east, west)
+    pub tunnels: Vec<[Option<NodeId>; 4]>,
+}
+
+impl FrameHeader {
+    pub fn new(world: &World) -> Self {
+        Self {
+            colonies: world.names.clone(),
+            tunnels: world.nodes.iter().map(|n| n.neighbors).collect(),
+        }
+    }
+}
+
+/// Colony occupancy after one tick
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Frame {
+    pub tick: u64,
+    /// `(colony, ants)` for every colony holding live ants, by colony id
+    pub occupancy: Vec<(NodeId, u32)>,
+    /// Colonies destroyed since the previous frame, in the order they fell
+    pub destroyed: Vec<NodeId>,
+}
+
+impl Frame {
+    /// Occupancy of `snapshot` on a map of `colonies` colonies
+    pub fn new(snapshot: &TickSnapshot, colonies: usize, destroyed: Vec<NodeId>) -> Self {
+        let mut counts = vec![0u32; colonies];
+        for pos in snapshot.positions.iter().flatten() {
+            counts[pos.index()] += 1;
+        }
+        let occupancy = counts
+            .iter()
+            .enumerate()
+            .filter(|&(_, &n)| n > 0)
+            .map(|(i, &n)| (NodeId::from_index(i), n))
+            .collect();
+        Self { tick: snapshot.tick, occupancy, destroyed }
+    }
+}
+
+/// Writes every `every`-th tick of a run as a [`Frame`], plus the final tick.
+///
+/// The binary layout is little-endian throughout: [`BINARY_MAGIC`], the
+/// version byte and the byte width of a colony id (4, or 8 with `wide-ids`);
+/// then the colony count, and per colony its name (u32 length + UTF-8) and
+/// four tunnel ids (all bits set for none). Each frame follows as the tick
+/// (u64), the occupied count (u32) and `(id, ants: u32)` pairs, then the
+/// destroyed count (u32) and ids.
+pub struct FrameWriter<W: Write> {
+    out: W,
+    format: FrameFormat,
+    every: u32,
+    colonies: usize,
+    /// Colonies fallen since the last frame written
+    destroyed: Vec<NodeId>,
+    /// Latest snapshot, held back until it is known whether it is the last
+    pending: Option<TickSnapshot>,
+}
+
+impl<W: Write> FrameWriter<W> {
+    /// Write the header for `world` and start taking snapshots
+    pub fn new(mut out: W, format: FrameFormat, every: u32, world: &World) -> std::io::Result<Self> {
+        let header = FrameHeader::new(world);
+        match format {
+            FrameFormat::JsonLines => {
+                serde_json::to_writer(&mut out, &header)?;
+                out.write_all(b"\n")?;
+            }
+            FrameFormat::Binary => {
+                out.write_all(BINARY_MAGIC)?;
+                out.write_all(&[BINARY_VERSION, std::mem::size_of::<NodeIndex>() as u8])?;
+                write_u32(&mut out, header.colonies.len() as u32)?;
+                for (name, tunnels) in header.colonies.iter().zip(&header.tunnels) {
+                    write_u32(&mut out, name.len() as u32)?;
+                    out.write_all(name.as_bytes())?;
+                    for t in tunnels {
+                        write_id(&mut out, t.map_or(NodeIndex::MAX, |id| id.get()))?;
+                    }
+                }
+            }
+        }
+        Ok(Self {
+            out,
+            format,
+            every: every.max(1),
+            colonies: world.nodes.len(),
+            destroyed: Vec::new(),
+            pending: None,
+        })
+    }
+
+    /// Take the snapshot of the next tick (tick 0 first), writing a frame on
+    /// every `every`-th tick
+    pub fn push(&mut self, snapshot: TickSnapshot) -> std::io::Result<()> {
+        self.destroyed.extend(snapshot.destroyed.iter().filter(|e| e.cause.destroyed()).map(|e| e.node_id));
+        if snapshot.tick.is_multiple_of(u64::from(self.every)) {
+            self.pending = None;
+            self.write(&snapshot)
+        } else {
+            self.pending = Some(snapshot);
+            Ok(())
+        }
+    }
+
+    /// Write the final tick if it was skipped, flush and return the sink
+    pub fn finish(mut self) -> std::io::Result<W> {
+        if let Some(snapshot) = self.pending.take() {
+            self.write(&snapshot)?;
+        }
+        self.out.flush()?;
+        Ok(self.out)
+    }
+
+    fn write(&mut self, snapshot: &TickSnapshot) -> std::io::Result<()> {
+        let frame = Frame::new(snapshot, self.colonies, std::mem::take(&mut self.destroyed));
+        let out = &mut self.out;
+        match self.format {
+            FrameFormat::JsonLines => {
+                serde_json::to_writer(&mut *out, &frame)?;
+                out.write_all(b"\n")
+            }
+            FrameFormat::Binary => {
+                out.write_all(&frame.tick.to_le_bytes())?;
+                write_u32(out, frame.occupancy.len() as u32)?;
+                for (id, ants) in &frame.occupancy {
+                    write_id(out, id.get())?;
+                    write_u32(out, *ants)?;
+                }
+                write_u32(out, frame.destroyed.len() as u32)?;
+                for id in &frame.destroyed {
+                    write_id(out, id.get())?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+#[inline]
+fn write_u32<W: Write>(out: &mut W, v: u32) -> std::io::Result<()> {
+    out.write_all(&v.to_le_bytes())
+}
+
+#[inline]
+fn write_id<W: Write>(out: &mut W, id: NodeIndex) -> std::io::Result<()> {
+    out.write_all(&id.to_le_bytes())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::simulation::{DestructionCause, DestructionEvent};
+    use crate::world::parser::parse_world_from_str;
+
+    fn snapshot(tick: u64, positions: &[Option<usize>], destroyed: &[usize]) -> TickSnapshot {
+        TickSnapshot {
+            tick,
+            positions: positions.iter().map(|p| p.map(NodeId::from_index)).collect(),
+            destroyed: destroyed
+                .iter()
+                .map(|&i| DestructionEvent {
+                    tick,
+                    node_id: NodeId::from_index(i),
+                    cause: DestructionCause::Fight { ant1: 0, ant2: 1 },
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_json_frames_skip_ticks_but_keep_the_last() {
+        let (names, nodes) = parse_world_from_str("A east=B\nB west=A\nC\n");
+        let world = World::new(names, nodes);
+        let mut frames = FrameWriter::new(Vec::new(), FrameFormat::JsonLines, 2, &world).unwrap();
+        frames.push(snapshot(0, &[Some(0), Some(0), Some(1)], &[])).unwrap();
+        frames.push(snapshot(1, &[None, None, Some(0)], &[2])).unwrap();
+        frames.push(snapshot(2, &[None, None, Some(1)], &[])).unwrap();
+        frames.push(snapshot(3, &[None, None, Some(0)], &[])).unwrap();
+        let out = String::from_utf8(frames.finish().unwrap()).unwrap();
+
+        let lines: Vec<&str> = out.lines().collect();
+        assert_eq!(lines[0], r#"{"colonies":["A","B","C"],"tunnels":[[null,null,1,null],[null,null,null,0],[null,null,null,null]]}"#);
+        assert_eq!(
+            &lines[1..],
+            [
+                r#"{"tick":0,"occupancy":[[0,2],[1,1]],"destroyed":[]}"#,
+                r#"{"tick":2,"occupancy":[[1,1]],"destroyed":[2]}"#,
+                r#"{"tick":3,"occupancy":[[0,1]],"destroyed":[]}"#,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_binary_layout() {
+        let (names, nodes) = parse_world_from_str("A east=B\nB\n");
+        let world = World::new(names, nodes);
+        let mut frames = FrameWriter::new(Vec::new(), FrameFormat::Binary, 1, &world).unwrap();
+        frames.push(snapshot(1, &[Some(1), None], &[0])).unwrap();
+        let out = frames.finish().unwrap();
+
+        let w = std::mem::size_of::<NodeIndex>();
+        let none = NodeIndex::MAX.to_le_bytes();
+        let mut expected = b"ANTF".to_vec();
+        expected.extend([2, w as u8]);
+        expected.extend(2u32.to_le_bytes());
+        expected.extend(1u32.to_le_bytes());
+        expected.extend(b"A");
+        expected.extend(none.iter().chain(&none));
+        expected.extend((1 as NodeIndex).to_le_bytes());
+        expected.extend(&none);
+        expected.extend(1u32.to_le_bytes());
+        expected.extend(b"B");
+        for _ in 0..4 {
+            expected.extend(&none);
+        }
+        expected.extend(1u64.to_le_bytes()); // tick
+        expected.extend(1u32.to_le_bytes());
+        expected.extend((1 as NodeIndex).to_le_bytes());
+        expected.extend(1u32.to_le_bytes());
+        expected.extend(1u32.to_le_bytes());
+        expected.extend((0 as NodeIndex).to_le_bytes());
+        assert_eq!(out, expected);
+    }
+}
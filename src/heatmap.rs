@@ -0,0 +1,64 @@
+use crate::world::World;
+use serde::{Deserialize, Serialize};
+use std::io::Write;
+
+/// Cumulative traffic through one colony
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HeatmapEntry {
+    pub colony: String,
+    /// Ants placed there at t=0 plus every arrival afterwards
+    pub visits: u32,
+    pub destroyed: bool,
+}
+
+impl HeatmapEntry {
+    pub const CSV_HEADER: &'static str = "colony,visits,destroyed";
+
+    /// Write this entry as one CSV line
+    pub fn write_csv<W: Write>(&self, out: &mut W) -> std::io::Result<()> {
+        writeln!(out, "{},{},{}", self.colony, self.visits, self.destroyed)
+    }
+}
+
+/// Pair engine visit counts with colony names, in colony id order
+pub fn heatmap(world: &World, visits: &[u32]) -> Vec<HeatmapEntry> {
+    world
+        .names
+        .iter()
+        .zip(world.nodes.iter())
+        .zip(visits)
+        .map(|((name, node), &visits)| HeatmapEntry {
+            colony: name.clone(),
+            visits,
+            destroyed: !node.is_alive(),
+        })
+        .collect()
+}
+
+/// Write a heatmap as CSV, header first
+pub fn write_csv<W: Write>(entries: &[HeatmapEntry], out: &mut W) -> std::io::Result<()> {
+    writeln!(out, "{}", HeatmapEntry::CSV_HEADER)?;
+    for entry in entries {
+        entry.write_csv(out)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::world::parser::parse_world_from_str;
+
+    #[test]
+    fn test_heatmap_csv() {
+        let (names, nodes) = parse_world_from_str("A east=B\nB\n");
+        let mut world = World::new(names, nodes);
+        world.nodes.destroy(1);
+
+        let entries = heatmap(&world, &[3, 1]);
+        let mut out = Vec::new();
+        write_csv(&entries, &mut out).unwrap();
+
+        assert_eq!(String::from_utf8(out).unwrap(), "colony,visits,destroyed\nA,3,false\nB,1,true\n");
+    }
+}
@@ -0,0 +1,50 @@
+use crate::ant_names::AntNames;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// One of the most destructive ants of a run
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KillerEntry {
+    pub ant: u32,
+    /// Display name, when ants are named
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// Colony-destroying fights the ant took part in
+    pub kills: u32,
+}
+
+impl fmt::Display for KillerEntry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.name {
+            Some(name) => write!(f, "{name} (ant {}): {} kills", self.ant, self.kills),
+            None => write!(f, "ant {}: {} kills", self.ant, self.kills),
+        }
+    }
+}
+
+/// The `k` ants with the most kills, most first; ties go to the lower id
+/// and ants without a kill are left out
+pub fn top_killers(kills: &[u32], k: usize, names: Option<&AntNames>) -> Vec<KillerEntry> {
+    let mut ranked: Vec<(u32, u32)> =
+        kills.iter().enumerate().filter(|&(_, &n)| n > 0).map(|(id, &n)| (id as u32, n)).collect();
+    ranked.sort_unstable_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+    ranked
+        .into_iter()
+        .take(k)
+        .map(|(ant, kills)| KillerEntry { ant, name: names.and_then(|n| n.get(ant)).map(str::to_string), kills })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_top_killers_ranks_and_breaks_ties_by_id() {
+        let top = top_killers(&[1, 0, 3, 1, 2], 3, None);
+        let ranked: Vec<(u32, u32)> = top.iter().map(|e| (e.ant, e.kills)).collect();
+        assert_eq!(ranked, [(2, 3), (4, 2), (0, 1)]);
+        assert_eq!(top[0].to_string(), "ant 2: 3 kills");
+        assert!(top_killers(&[0, 0], 5, None).is_empty());
+    }
+}
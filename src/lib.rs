@@ -6,21 +6,47 @@
 //! collisions, and colony destruction on a graph-based map.
 
 pub mod ant;
+pub mod ant_names;
+pub mod bench;
+pub mod build_info;
+pub mod campaign;
 pub mod cli;
+pub mod compare;
+#[cfg(feature = "sqlite")]
+pub mod db;
+pub mod destruction_freq;
 pub mod direction;
+pub mod dist;
 pub mod error;
+pub mod estimate;
+pub mod exit;
+pub mod frames;
+pub mod heatmap;
+pub mod kills;
+pub mod lifetimes;
+pub mod limits;
+pub mod node_id;
+pub mod population;
+pub mod report;
+pub mod report_writer;
+pub mod seeds;
+#[cfg(feature = "server")]
+pub mod server;
 pub mod simulation;
-pub mod utils;
+pub mod strategy;
+pub mod sweep;
 pub mod world;
 
-pub use ant::Ant;
+pub use ant::{Ant, AntKind, AntOutcomes, DeathCounts, DeathReason, KindStats};
+pub use ant_names::AntNames;
 pub use cli::Args;
 pub use direction::Direction;
-pub use error::{ParseError, Result};
+pub use error::{ParseError, Result, SimulationError};
+pub use node_id::{NodeId, NodeIndex};
 pub use simulation::SimulationEngine;
-pub use world::World;
+pub use world::{NodeRef, World};
 
 /// Re-export commonly used types
 pub mod prelude {
-    pub use crate::{Ant, Args, Direction, ParseError, Result, SimulationEngine, World};
+    pub use crate::{Ant, Args, Direction, NodeId, ParseError, Result, SimulationEngine, SimulationError, World};
 }
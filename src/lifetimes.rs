@@ -0,0 +1,56 @@
+use serde::{Deserialize, Serialize};
+
+/// Colonies that fell after standing the same number of ticks
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LifetimeBin {
+    /// Tick of the destruction (0 = placement)
+    pub ticks: u64,
+    pub colonies: u32,
+}
+
+/// How long the colonies of a run stood: a histogram of the tick each one
+/// fell on, plus the ones still standing at the end, whose lifetime is
+/// only known to be at least the run's length
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ColonyLifetimes {
+    /// Non-empty bins, shortest lifetime first
+    pub destroyed: Vec<LifetimeBin>,
+    pub survived: usize,
+}
+
+impl ColonyLifetimes {
+    /// Histogram of `destruction_ticks` (one entry per fallen colony), with
+    /// `survived` colonies left standing
+    pub fn of(destruction_ticks: &[u64], survived: usize) -> Self {
+        let mut ticks = destruction_ticks.to_vec();
+        ticks.sort_unstable();
+        let mut destroyed: Vec<LifetimeBin> = Vec::new();
+        for tick in ticks {
+            match destroyed.last_mut() {
+                Some(bin) if bin.ticks == tick => bin.colonies += 1,
+                _ => destroyed.push(LifetimeBin { ticks: tick, colonies: 1 }),
+            }
+        }
+        Self { destroyed, survived }
+    }
+
+    /// Colonies that fell during the run
+    pub fn fallen(&self) -> u32 {
+        self.destroyed.iter().map(|bin| bin.colonies).sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bins_count_each_tick() {
+        let lifetimes = ColonyLifetimes::of(&[0, 0, 3, 1, 3, 3], 4);
+        let bins: Vec<(u64, u32)> = lifetimes.destroyed.iter().map(|b| (b.ticks, b.colonies)).collect();
+
+        assert_eq!(bins, [(0, 2), (1, 1), (3, 3)]);
+        assert_eq!((lifetimes.fallen(), lifetimes.survived), (6, 4));
+        assert_eq!(ColonyLifetimes::of(&[], 2), ColonyLifetimes { destroyed: Vec::new(), survived: 2 });
+    }
+}
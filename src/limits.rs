@@ -0,0 +1,107 @@
+use crate::ant::Ant;
+use crate::node_id::NodeId;
+use crate::world::Node;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::time::Duration;
+
+/// Resource limit that cut a run short
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Limit {
+    /// `--memory-limit`
+    Memory,
+    /// `--max-wall-time`
+    WallTime,
+}
+
+impl fmt::Display for Limit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Limit::Memory => "memory",
+            Limit::WallTime => "wall_time",
+        })
+    }
+}
+
+/// Bytes held per colony: the node, its name handle, its reverse-edge
+/// offset and the scratch arrays
+pub const BYTES_PER_COLONY: u64 =
+    (std::mem::size_of::<Node>() + std::mem::size_of::<String>() + std::mem::size_of::<usize>() + 10 * 4 + 1) as u64;
+
+/// Bytes held per ant: the ant, its active-list slot and scratch entries
+pub const BYTES_PER_ANT: u64 =
+    (std::mem::size_of::<Ant>() + std::mem::size_of::<usize>() + std::mem::size_of::<NodeId>() + 1) as u64;
+
+/// Lower bound on the memory a run of `ants` ants on `colonies` colonies
+/// needs before its first tick, to refuse hopeless runs before allocating
+pub fn estimate_bytes(colonies: usize, ants: usize) -> u64 {
+    colonies as u64 * BYTES_PER_COLONY + ants as u64 * BYTES_PER_ANT
+}
+
+/// Parse a byte size: `4096`, `512K`, `64M`, `2G` (powers of 1024; an
+/// optional trailing `B` / `iB` is accepted)
+pub fn parse_byte_size(s: &str) -> Result<u64, String> {
+    let t = s.trim();
+    let t = t.strip_suffix("iB").or_else(|| t.strip_suffix('B')).unwrap_or(t);
+    let (digits, shift) = match t.char_indices().last() {
+        Some((i, 'K' | 'k')) => (&t[..i], 10),
+        Some((i, 'M' | 'm')) => (&t[..i], 20),
+        Some((i, 'G' | 'g')) => (&t[..i], 30),
+        Some((i, 'T' | 't')) => (&t[..i], 40),
+        _ => (t, 0),
+    };
+    let n: u64 = digits.trim().parse().map_err(|_| format!("invalid size '{s}' (e.g. 512M, 2G)"))?;
+    n.checked_mul(1 << shift).ok_or_else(|| format!("size '{s}' is too large"))
+}
+
+/// Parse a duration: `1500ms`, `30s`, `5m`, `1h`; a bare number is seconds
+pub fn parse_duration(s: &str) -> Result<Duration, String> {
+    let t = s.trim();
+    let invalid = || format!("invalid duration '{s}' (e.g. 500ms, 30s, 5m, 1h)");
+    let (digits, unit_ms) = if let Some(d) = t.strip_suffix("ms") {
+        (d, 1)
+    } else if let Some(d) = t.strip_suffix('s') {
+        (d, 1_000)
+    } else if let Some(d) = t.strip_suffix('m') {
+        (d, 60_000)
+    } else if let Some(d) = t.strip_suffix('h') {
+        (d, 3_600_000)
+    } else {
+        (t, 1_000)
+    };
+    let n: u64 = digits.trim().parse().map_err(|_| invalid())?;
+    Ok(Duration::from_millis(n.checked_mul(unit_ms).ok_or_else(invalid)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_byte_size() {
+        assert_eq!(parse_byte_size("4096"), Ok(4096));
+        assert_eq!(parse_byte_size("512K"), Ok(512 << 10));
+        assert_eq!(parse_byte_size("64MiB"), Ok(64 << 20));
+        assert_eq!(parse_byte_size("2g"), Ok(2 << 30));
+        assert_eq!(parse_byte_size("1GB"), Ok(1 << 30));
+        assert!(parse_byte_size("lots").is_err());
+        assert!(parse_byte_size("99999999999T").is_err());
+    }
+
+    #[test]
+    fn test_parse_duration() {
+        assert_eq!(parse_duration("1500ms"), Ok(Duration::from_millis(1500)));
+        assert_eq!(parse_duration("30s"), Ok(Duration::from_secs(30)));
+        assert_eq!(parse_duration("5m"), Ok(Duration::from_secs(300)));
+        assert_eq!(parse_duration("1h"), Ok(Duration::from_secs(3600)));
+        assert_eq!(parse_duration("2"), Ok(Duration::from_secs(2)));
+        assert!(parse_duration("soon").is_err());
+    }
+
+    #[test]
+    fn test_estimate_grows_with_ants() {
+        assert_eq!(estimate_bytes(0, 0), 0);
+        assert!(estimate_bytes(10, 1_000_000_000) > 1 << 30);
+    }
+}
@@ -1,28 +1,655 @@
 use ants_code_challenge::prelude::*;
-use ants_code_challenge::world::parse_world;
+use ants_code_challenge::AntNames;
+use ants_code_challenge::bench;
+use ants_code_challenge::build_info::BuildInfo;
+use ants_code_challenge::campaign::run_campaign;
+use ants_code_challenge::cli::{
+    version_request, Chatter, Cli, Command, SortOrder, CampaignArgs, CheckDirectionsArgs, CompareRunsArgs, EstimateArgs, GenerateGridArgs, NormalizeMapArgs, PathArgs,
+    QueryArgs, ScaleMapArgs, StatsArgs, SweepArgs,
+};
+#[cfg(feature = "server")]
+use ants_code_challenge::cli::ServeArgs;
+use ants_code_challenge::compare::RunComparison;
+#[cfg(feature = "sqlite")]
+use ants_code_challenge::db::ResultsDb;
+use ants_code_challenge::destruction_freq::{self, destruction_frequency};
+use ants_code_challenge::estimate::Estimate;
+use ants_code_challenge::exit;
+use ants_code_challenge::frames::{FrameFormat, FrameWriter};
+use ants_code_challenge::heatmap;
+use ants_code_challenge::limits;
+use ants_code_challenge::population::Population;
+use ants_code_challenge::report::Report;
+use ants_code_challenge::seeds::SubSeeds;
+use ants_code_challenge::simulation::{MessageTemplates, SimulationHooks, WatchLog};
+#[cfg(feature = "watch")]
+use ants_code_challenge::simulation::MapWatch;
+use ants_code_challenge::sweep::run_sweep;
+use ants_code_challenge::world::parser::source_line;
+use ants_code_challenge::world::{
+    consistency, load_world, load_world_with_sources, load_world_with_warnings, ColonyInfo, Contradiction, Damage,
+    GridLayout, ParseOptions, WorldStats,
+};
 use clap::Parser;
+use colored::Colorize;
+use std::fs::File;
+use std::io::{BufRead, BufWriter, Write};
+use std::process::ExitCode;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 
-fn main() -> std::result::Result<(), Box<dyn std::error::Error>> {
-    let args = Args::parse();
-    let mut rng = if let Some(seed) = args.seed {
-        fastrand::Rng::with_seed(seed)
-    } else {
-        fastrand::Rng::new()
+/// Exit status of a command that did not fail outright
+type Outcome = std::result::Result<u8, Box<dyn std::error::Error>>;
+
+fn main() -> ExitCode {
+    let argv: Vec<String> = std::env::args().skip(1).collect();
+    if let Some(json) = version_request(&argv) {
+        return ExitCode::from(print_version(json));
+    }
+    let cli = match Cli::try_parse() {
+        Ok(cli) => cli,
+        Err(err) => {
+            let _ = err.print();
+            // --help / --version also arrive here, on stdout
+            return ExitCode::from(if err.use_stderr() { exit::INVALID_ARGS } else { exit::SUCCESS });
+        }
+    };
+    let map = cli.map().map(str::to_string);
+    let outcome = match (cli.command, cli.run) {
+        (Some(Command::ScaleMap(cmd)), _) => scale_map(&cmd),
+        (Some(Command::GenerateGrid(cmd)), _) => generate_grid(&cmd),
+        (Some(Command::NormalizeMap(cmd)), _) => normalize_map(&cmd),
+        (Some(Command::Sweep(cmd)), _) => sweep(&cmd),
+        (Some(Command::CompareRuns(cmd)), _) => compare_runs(&cmd),
+        (Some(Command::Stats(cmd)), _) => stats(&cmd),
+        (Some(Command::Estimate(cmd)), _) => estimate(&cmd),
+        (Some(Command::Query(cmd)), _) => query(&cmd),
+        (Some(Command::Path(cmd)), _) => path(&cmd),
+        (Some(Command::CheckDirections(cmd)), _) => check_directions(&cmd),
+        (Some(Command::Campaign(cmd)), _) => campaign(&cmd),
+        #[cfg(feature = "server")]
+        (Some(Command::Serve(cmd)), _) => serve(&cmd),
+        (None, Some(args)) => simulate(&args),
+        // clap enforces the run arguments whenever no subcommand is given
+        (None, None) => unreachable!("missing run arguments"),
     };
+    match outcome {
+        Ok(code) => ExitCode::from(code),
+        Err(err) => {
+            report_error(err.as_ref(), map.as_deref());
+            ExitCode::from(exit::for_error(err.as_ref()))
+        }
+    }
+}
+
+/// Print the build information of `--version`, as JSON with `--json`
+fn print_version(json: bool) -> u8 {
+    let info = BuildInfo::current();
+    if json {
+        match serde_json::to_string_pretty(&info) {
+            Ok(text) => println!("{text}"),
+            Err(err) => {
+                eprintln!("error: {err}");
+                return exit::FAILURE;
+            }
+        }
+    } else {
+        println!("{info}");
+    }
+    exit::SUCCESS
+}
+
+/// Print an error, quoting the offending map line when it has a location
+fn report_error(err: &(dyn std::error::Error + 'static), map: Option<&str>) {
+    if let (Some(parse), Some(map)) = (err.downcast_ref::<ParseError>(), map) {
+        if let Some(at) = parse.location() {
+            if let Ok(Some(line)) = source_line(parse.file().unwrap_or(map), at.line) {
+                eprint!("{}", parse.render(&line));
+                return;
+            }
+        }
+    }
+    eprintln!("error: {err}");
+}
+
+/// Print what a `--lenient` parse of `map` skipped, quoting the lines like errors
+fn report_warnings(warnings: &[ParseError], map: &str) {
+    for warning in warnings {
+        let line = warning.location().and_then(|at| source_line(warning.file().unwrap_or(map), at.line).ok().flatten());
+        match line {
+            Some(line) => eprint!("{}", warning.render_warning(&line)),
+            None => eprintln!("warning: {warning}"),
+        }
+    }
+    if !warnings.is_empty() {
+        eprintln!("warning: skipped {} malformed map entries", warnings.len());
+    }
+}
+
+/// Run a simulation and print the results
+fn simulate(args: &Args) -> Outcome {
+    // Independent placement / movement / spawning streams from one master seed
+    let seeds = SubSeeds::derive(args.seed.unwrap_or_else(|| fastrand::u64(..)));
 
     // Parse world and create ants
-    let (names, nodes) = parse_world(&args.map)?;
-    let mut world = World::new(names, nodes);
-    let mut ants = world.create_ants(args.ants, &mut rng);
+    let (mut world, warnings) = load_world_with_warnings(&args.map, &args.parse_options())?;
+    report_warnings(&warnings, &args.map[0]);
+    // `-n` fresh ants join an imported population, so the run has both
+    let fresh = args;
+    let imported = args.import_ants.as_deref().map(Population::read_file).transpose()?;
+    let joined;
+    let args = match &imported {
+        Some(population) => {
+            joined = Args { ants: args.ants + population.len(), ..args.clone() };
+            &joined
+        }
+        None => args,
+    };
+    if let Some(max) = args.memory_limit {
+        let needed = limits::estimate_bytes(world.nodes().len(), args.ants);
+        if needed > max {
+            eprintln!(
+                "error: {} ants on {} colonies need about {} bytes, over the --memory-limit of {} bytes",
+                args.ants,
+                world.nodes().len(),
+                needed,
+                max
+            );
+            return Ok(exit::ABORTED);
+        }
+    }
+    if args.bench {
+        return bench(&world, args, seeds);
+    }
+    // Diffed before the run, while the world is still as the previous run left it
+    let damage = match &args.original {
+        Some(path) => {
+            let original = load_world(path, &ParseOptions { damaged: false, ..args.parse_options() })?;
+            let damage = Damage::between(&original, &world);
+            if !damage.unknown.is_empty() {
+                eprintln!("warning: {} is not the original of {}: it lacks {}", path, args.map_label(), damage.unknown.join(", "));
+            }
+            Some(damage)
+        }
+        None => None,
+    };
+    let mut ants = world.place_colony(fresh.ants, fresh.queen_count(), args.placement, &mut seeds.placement_rng());
+    if let Some(population) = imported {
+        let (mut population, lost) = population.into_ants(&world);
+        if lost > 0 {
+            eprintln!("warning: {lost} imported ants stood on colonies {} lacks or has lost; they died", args.map_label());
+        }
+        // Fresh ants take the ids after the imported ones
+        let offset = population.len() as u32;
+        for ant in &mut ants {
+            ant.id += offset;
+        }
+        population.append(&mut ants);
+        ants = population;
+    }
+    if args.warn_sinks {
+        warn_sinks(&world, &ants);
+    }
 
     // Run simulation
-    let mut engine = SimulationEngine::new(&world, args.ants);
-    let simulation_time = engine.run_simulation(&mut world, &mut ants, &args, &mut rng);
+    let interrupt = install_interrupt_handler()?;
+    let mut engine = SimulationEngine::new(&world, args.ants)
+        .with_interrupt(interrupt)
+        .with_seeds(seeds);
+    let names = match &args.ant_names {
+        Some(path) => Some(AntNames::read(path)?),
+        None => args.generate_ant_names.then(AntNames::generated),
+    };
+    if let Some(mut names) = names {
+        names.assign(&mut ants);
+        engine = engine.with_ant_names(names);
+    }
+    if let Some(path) = &args.message_template {
+        engine = engine.with_messages(MessageTemplates::read(path)?);
+    }
+    if !args.watch.is_empty() {
+        if let Some(name) = args.watch.iter().find(|name| world.find(name).is_none()) {
+            return Err(format!("no colony named '{name}' to watch").into());
+        }
+        let out: Box<dyn Write + Send> = match args.chatter() {
+            Chatter::Stdout => Box::new(std::io::stdout()),
+            Chatter::Stderr => Box::new(std::io::stderr()),
+            Chatter::Off => Box::new(std::io::sink()),
+        };
+        engine = engine.with_hooks(WatchLog::new(BufWriter::new(out)).watch(args.watch.clone()));
+    }
+    #[cfg(feature = "watch")]
+    let _watch = if args.watch_map {
+        let watch = MapWatch::start(&args.map)?;
+        engine = engine.with_map_watch(watch.flag());
+        Some(watch)
+    } else {
+        None
+    };
+    #[cfg(not(feature = "watch"))]
+    if args.watch_map {
+        return Err("map watching is not supported (build with --features watch)".into());
+    }
+    if args.db.is_some() {
+        check_db_support()?;
+    }
+    let simulation_time = if args.interactive {
+        let commands = spawn_stdin_reader();
+        let mut status = std::io::stderr();
+        engine.run_interactive(&mut world, &mut ants, args, &mut seeds.movement_rng(), &commands, &mut status)
+    } else if let Some(path) = &args.frames {
+        record_frames(&mut engine, &mut world, &mut ants, args, &mut seeds.movement_rng(), path)?
+    } else {
+        engine.run_simulation(&mut world, &mut ants, args, &mut seeds.movement_rng())
+    };
 
-    // Print results
-    engine.print_summary(&world, &args, simulation_time);
+    // Print results: the world (and `--report -`) on stdout, the rest where
+    // `--porcelain` / `--quiet` send chatter
+    if args.render_grid {
+        match (GridLayout::of(&world), args.chatter_out()) {
+            (Some(grid), Some(mut out)) => write!(out, "{}", grid.render(&world, &ants))?,
+            (Some(_), None) => {}
+            (None, _) => eprintln!("map is not a lattice; skipping grid render"),
+        }
+    }
+    print_world(&world, args)?;
+    engine.print_summary(&world, &ants, args, simulation_time);
+    if let (Some(damage), Some(mut out)) = (&damage, args.chatter_out()) {
+        writeln!(
+            out,
+            "{} {}",
+            "🏚️  Lost before this run:".green().bold(),
+            format!("{} colonies ({:.1}%)", damage.lost.len(), damage.lost_fraction() * 100.0).cyan(),
+        )?;
+    }
+    if args.report.is_some() || args.db.is_some() {
+        let report = Report::new(&world, &ants, args, &engine, simulation_time);
+        if let Some(path) = &args.report {
+            report.write(path, args.report_format)?;
+        }
+        #[cfg(feature = "sqlite")]
+        if let Some(path) = &args.db {
+            ResultsDb::open(path)?.record_report(&args.map_label(), args, &world, &report)?;
+        }
+    }
+    if let Some(path) = &args.heatmap_csv {
+        let mut out = BufWriter::new(File::create(path)?);
+        heatmap::write_csv(&heatmap::heatmap(&world, engine.visits()), &mut out)?;
+        out.flush()?;
+    }
+    if let Some(path) = &args.export_ants {
+        Population::of(&world, &ants).write_file(path)?;
+    }
+    if engine.interrupted() {
+        return Ok(exit::ABORTED);
+    }
+    if args.wipeout_exit && world.count_survivors() == 0 {
+        return Ok(exit::WIPEOUT);
+    }
 
-    Ok(())
+    Ok(exit::SUCCESS)
 }
 
+/// Run tick by tick, writing `--frames` as it goes; the latency includes
+/// writing the frames
+fn record_frames(
+    engine: &mut SimulationEngine,
+    world: &mut World,
+    ants: &mut Vec<Ant>,
+    args: &Args,
+    rng: &mut fastrand::Rng,
+    path: &str,
+) -> std::io::Result<Duration> {
+    let out = BufWriter::new(File::create(path)?);
+    let mut frames = FrameWriter::new(out, FrameFormat::from_path(path), args.frame_every, world)?;
+    let start = Instant::now();
+    let ticks = engine.iter_ticks(world, ants, args, rng);
+    frames.push(ticks.current())?;
+    for snapshot in ticks {
+        frames.push(snapshot)?;
+    }
+    let elapsed = start.elapsed();
+    frames.finish()?;
+    Ok(elapsed)
+}
+
+/// Time repeated runs on one parsed map and print only the `--bench` line
+fn bench(world: &World, args: &Args, seeds: SubSeeds) -> Outcome {
+    colored::control::set_override(false);
+    if let Some(cpu) = args.pin_cpu {
+        bench::pin_current_thread(cpu)?;
+    }
+    println!("{}", bench::run_bench(world, args, seeds, args.repeat));
+    Ok(exit::SUCCESS)
+}
+
+/// Write the surviving world where `--print-world` / `--sort-output` ask for it
+fn print_world(world: &World, args: &Args) -> std::io::Result<()> {
+    let order = args.sort_output.unwrap_or(SortOrder::Id);
+    match &args.print_world {
+        Some(Some(path)) => {
+            let mut out = BufWriter::new(File::create(path)?);
+            world.write_sorted(&mut out, order)?;
+            out.flush()
+        }
+        Some(None) => world.print_world(order),
+        None if args.sort_output.is_some() => world.print_world(order),
+        None => Ok(()),
+    }
+}
+
+/// Report sink components (enterable, never left) and how many ants start inside one
+fn warn_sinks(world: &World, ants: &[Ant]) {
+    let sinks = world.sink_components();
+    if sinks.is_empty() {
+        return;
+    }
+    let trapped = world.ants_in_sinks(&sinks, ants);
+    let colonies: usize = sinks.iter().map(Vec::len).sum();
+    eprintln!(
+        "warning: {} sink component(s) covering {} colonies; {} of {} ants start inside one",
+        sinks.len(),
+        colonies,
+        trapped,
+        ants.len()
+    );
+    for sink in &sinks {
+        let names: Vec<&str> = sink.iter().map(|&id| world.get_colony_name(id)).collect();
+        eprintln!("  sink: {}", names.join(", "));
+    }
+}
+
+/// Write `copies` replicas of a base map
+fn scale_map(cmd: &ScaleMapArgs) -> Outcome {
+    let scaled = load_world(&cmd.map, &ParseOptions::default())?.replicate(cmd.copies, cmd.bridge);
+    write_generated(&scaled, cmd.out.as_deref())
+}
+
+/// Write a `width` x `height` grid or torus with its coordinates
+fn generate_grid(cmd: &GenerateGridArgs) -> Outcome {
+    let grid = World::lattice(cmd.width, cmd.height, cmd.torus)?;
+    write_generated(&grid, cmd.out.as_deref())
+}
+
+/// Write a generated map to `path`, or to stdout without one
+fn write_generated(world: &World, path: Option<&str>) -> Outcome {
+    match path {
+        Some(path) => {
+            let mut out = BufWriter::new(File::create(path)?);
+            world.write_map(&mut out)?;
+            out.flush()?;
+        }
+        None => {
+            let mut out = BufWriter::new(std::io::stdout().lock());
+            world.write_map(&mut out)?;
+            out.flush()?;
+        }
+    }
+    Ok(exit::SUCCESS)
+}
+
+/// Write a map sorted by colony name, aliases resolved, tunnels in
+/// north/south/east/west order and attributes by key
+fn normalize_map(cmd: &NormalizeMapArgs) -> Outcome {
+    let options = ParseOptions { case_insensitive: cmd.case_insensitive, auto_mirror: cmd.mirror, ..Default::default() };
+    let world = load_world(&cmd.map, &options)?;
+    let mut normalized = Vec::new();
+    world.write_sorted(&mut normalized, SortOrder::Name)?;
+    if cmd.check {
+        if std::fs::read(&cmd.map)? == normalized {
+            return Ok(exit::SUCCESS);
+        }
+        eprintln!("{} is not normalized", cmd.map);
+        return Ok(exit::FAILURE);
+    }
+    match &cmd.out {
+        Some(path) => std::fs::write(path, &normalized)?,
+        None => std::io::stdout().lock().write_all(&normalized)?,
+    }
+    Ok(exit::SUCCESS)
+}
 
+/// Run a parameter sweep over one parsed map and write CSV rows
+fn sweep(cmd: &SweepArgs) -> Outcome {
+    let world = load_world(&cmd.map, &ParseOptions::default())?;
+    let mut base = Args::with_defaults(0, &cmd.map);
+    base.max_moves = cmd.max_moves;
+    base.memory_limit = cmd.memory_limit;
+    base.max_wall_time = cmd.max_wall_time;
+    let ants: Vec<usize> = cmd.ants.0.iter().map(|&n| n as usize).collect();
+    if cmd.db.is_some() {
+        check_db_support()?;
+    }
+
+    let rows = match &cmd.out {
+        Some(path) => {
+            let mut out = BufWriter::new(File::create(path)?);
+            let rows = run_sweep(&world, &base, &ants, &cmd.seeds.0, &mut out)?;
+            out.flush()?;
+            rows
+        }
+        None => run_sweep(&world, &base, &ants, &cmd.seeds.0, &mut std::io::stdout().lock())?,
+    };
+
+    #[cfg(feature = "sqlite")]
+    if let Some(path) = &cmd.db {
+        let mut db = ResultsDb::open(path)?;
+        for row in &rows {
+            db.record_sweep_row(&cmd.map, &base, &world, row)?;
+        }
+    }
+    if cmd.destruction_csv.is_some() || cmd.destruction_json.is_some() {
+        let entries = destruction_frequency(&world, &rows);
+        if let Some(path) = &cmd.destruction_csv {
+            let mut out = BufWriter::new(File::create(path)?);
+            destruction_freq::write_csv(&entries, &mut out)?;
+            out.flush()?;
+        }
+        if let Some(path) = &cmd.destruction_json {
+            let mut out = BufWriter::new(File::create(path)?);
+            serde_json::to_writer_pretty(&mut out, &entries)?;
+            out.write_all(b"\n")?;
+            out.flush()?;
+        }
+    }
+    Ok(exit::SUCCESS)
+}
+
+/// Fail up front when `--db` is given to a build without SQLite
+fn check_db_support() -> std::result::Result<(), Box<dyn std::error::Error>> {
+    if cfg!(feature = "sqlite") {
+        Ok(())
+    } else {
+        Err("results databases are not supported (build with --features sqlite)".into())
+    }
+}
+
+/// Diff two JSON reports and print the differences
+fn compare_runs(cmd: &CompareRunsArgs) -> Outcome {
+    let cmp = RunComparison::new(&Report::read_json(&cmd.a)?, &Report::read_json(&cmd.b)?);
+    println!("{cmp}");
+    if let Some(path) = &cmd.out {
+        let mut out = BufWriter::new(File::create(path)?);
+        serde_json::to_writer_pretty(&mut out, &cmp)?;
+        out.write_all(b"\n")?;
+        out.flush()?;
+    }
+    if cmd.check && !cmp.same_outcome() {
+        return Ok(exit::FAILURE);
+    }
+    Ok(exit::SUCCESS)
+}
+
+/// Print map statistics, optionally exporting them as JSON
+fn stats(cmd: &StatsArgs) -> Outcome {
+    let stats = WorldStats::of(&load_world(&cmd.map, &ParseOptions::default())?);
+    println!("{stats}");
+    if let Some(path) = &cmd.out {
+        let mut out = BufWriter::new(File::create(path)?);
+        serde_json::to_writer_pretty(&mut out, &stats)?;
+        out.write_all(b"\n")?;
+        out.flush()?;
+    }
+    Ok(exit::SUCCESS)
+}
+
+/// Estimate a run before making it, with status 4 when it is over a limit;
+/// with `--run`, then make it, unless over a limit without `--force`
+fn estimate(cmd: &EstimateArgs) -> Outcome {
+    let world = load_world(&cmd.map, &ParseOptions::default())?;
+    let mut args = Args::with_defaults(cmd.ants, &cmd.map);
+    args.max_moves = cmd.max_moves;
+    // Fixed up front so the sample is the start of the real run
+    args.seed = Some(cmd.seed.unwrap_or_else(|| fastrand::u64(..)));
+    let estimate = Estimate::of(&world, &args, cmd.sample_ticks);
+    if cmd.json {
+        let mut out = std::io::stdout().lock();
+        serde_json::to_writer_pretty(&mut out, &estimate)?;
+        out.write_all(b"\n")?;
+    } else {
+        println!("{estimate}");
+    }
+
+    let over = estimate.over_limits(cmd.memory_limit, cmd.max_wall_time);
+    for limit in &over {
+        match limit {
+            limits::Limit::Memory => eprintln!(
+                "warning: about {} bytes needed, over the --memory-limit of {} bytes",
+                estimate.memory.total(),
+                cmd.memory_limit.unwrap_or_default()
+            ),
+            limits::Limit::WallTime => eprintln!(
+                "warning: projected to take {:.0} ms, over the --max-wall-time of {} ms",
+                estimate.projected_ms,
+                cmd.max_wall_time.unwrap_or_default().as_millis()
+            ),
+        }
+    }
+    if !cmd.run {
+        return Ok(if over.is_empty() { exit::SUCCESS } else { exit::ABORTED });
+    }
+    if !over.is_empty() && !cmd.force {
+        eprintln!("error: refusing to run {} over its limits (pass --force to run anyway)", cmd.map);
+        return Ok(exit::ABORTED);
+    }
+    simulate(&args)
+}
+
+/// Describe one colony of a map
+fn query(cmd: &QueryArgs) -> Outcome {
+    let world = load_world(&cmd.map, &ParseOptions::default())?;
+    let find = |name: &str| world.find(name).ok_or_else(|| format!("no colony named '{name}' in {}", cmd.map));
+    let mut info = ColonyInfo::of(&world, find(&cmd.colony)?);
+    if let Some(from) = &cmd.from {
+        info = info.with_reachability(&world, find(from)?);
+    }
+    if cmd.json {
+        let mut out = std::io::stdout().lock();
+        serde_json::to_writer_pretty(&mut out, &info)?;
+        out.write_all(b"\n")?;
+    } else {
+        println!("{info}");
+    }
+    Ok(exit::SUCCESS)
+}
+
+/// Print a shortest route between two colonies; status 1 when there is none
+fn path(cmd: &PathArgs) -> Outcome {
+    let world = load_world(&cmd.map, &ParseOptions::default())?;
+    let find = |name: &str| world.find(name).ok_or_else(|| format!("no colony named '{name}' in {}", cmd.map));
+    let route: Option<Vec<&str>> = world
+        .shortest_path(find(&cmd.from)?, find(&cmd.to)?)
+        .map(|path| path.into_iter().map(|id| world.get_colony_name(id)).collect());
+    if cmd.json {
+        let json = serde_json::json!({ "from": cmd.from, "to": cmd.to, "path": route });
+        println!("{}", serde_json::to_string_pretty(&json)?);
+    } else {
+        match &route {
+            Some(route) => println!("{} ({} hops)", route.join(" -> "), route.len() - 1),
+            None => println!("no path from {} to {}", cmd.from, cmd.to),
+        }
+    }
+    Ok(if route.is_some() { exit::SUCCESS } else { exit::FAILURE })
+}
+
+/// List geometric contradictions between tunnels; fails when there are any
+fn check_directions(cmd: &CheckDirectionsArgs) -> Outcome {
+    let (world, tunnels) = load_world_with_sources(&cmd.map, &ParseOptions::default())?;
+    let found: Vec<Contradiction> = consistency::check_directions(&world, &tunnels)
+        .into_iter()
+        .filter(|c| !(cmd.allow_one_way && c.is_one_way()))
+        .collect();
+    if cmd.json {
+        println!("{}", serde_json::to_string_pretty(&found)?);
+    } else if found.is_empty() {
+        println!("{}: {} tunnels, no contradictions", cmd.map, tunnels.len());
+    } else {
+        for contradiction in &found {
+            println!("{}: {contradiction}", cmd.map);
+        }
+    }
+    Ok(if found.is_empty() { exit::SUCCESS } else { exit::FAILURE })
+}
+
+/// Run waves on one world, printing a line per wave as it ends
+fn campaign(cmd: &CampaignArgs) -> Outcome {
+    let mut world = load_world(&cmd.map, &ParseOptions::default())?;
+    let mut base = Args::with_defaults(0, &cmd.map);
+    base.max_moves = cmd.max_moves;
+    base.placement = cmd.placement;
+    let seed = cmd.seed.unwrap_or_else(|| fastrand::u64(..));
+
+    let summaries = run_campaign(&mut world, &base, &cmd.waves, seed, cmd.compact, |wave| println!("{wave}"));
+    if let Some(path) = &cmd.out {
+        let mut out = BufWriter::new(File::create(path)?);
+        serde_json::to_writer_pretty(&mut out, &summaries)?;
+        out.write_all(b"\n")?;
+        out.flush()?;
+    }
+    Ok(exit::SUCCESS)
+}
+
+/// Listen for map uploads and simulation requests until killed
+#[cfg(feature = "server")]
+fn serve(cmd: &ServeArgs) -> Outcome {
+    use ants_code_challenge::server::{self, ServerOptions};
+    let runtime = tokio::runtime::Runtime::new()?;
+    runtime.block_on(async {
+        let listener = tokio::net::TcpListener::bind(cmd.addr).await?;
+        eprintln!("listening on http://{}", listener.local_addr()?);
+        server::serve(listener, ServerOptions {
+            max_ants: cmd.max_ants,
+            max_running: cmd.max_running,
+            keep_finished: cmd.keep_finished,
+        }).await
+    })?;
+    Ok(exit::SUCCESS)
+}
+
+/// Forward stdin lines to a channel so the run can poll for commands between ticks
+fn spawn_stdin_reader() -> Receiver<String> {
+    let (tx, rx) = mpsc::channel();
+    std::thread::spawn(move || {
+        for line in std::io::stdin().lock().lines() {
+            let Ok(line) = line else { break };
+            if tx.send(line).is_err() {
+                break;
+            }
+        }
+    });
+    rx
+}
+
+/// First Ctrl-C asks the engine to stop and report; a second one exits immediately
+fn install_interrupt_handler() -> std::result::Result<Arc<AtomicBool>, ctrlc::Error> {
+    let flag = Arc::new(AtomicBool::new(false));
+    let handler_flag = Arc::clone(&flag);
+    ctrlc::set_handler(move || {
+        if handler_flag.swap(true, Ordering::Relaxed) {
+            std::process::exit(exit::ABORTED as i32);
+        }
+    })?;
+    Ok(flag)
+}
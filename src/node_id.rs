@@ -0,0 +1,127 @@
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+
+/// Integer type of colony indices: `u32`, or `u64` with the `wide-ids`
+/// feature for maps past ~4.29 billion colonies
+#[cfg(not(feature = "wide-ids"))]
+pub type NodeIndex = u32;
+/// Integer type of colony indices: `u32`, or `u64` with the `wide-ids`
+/// feature for maps past ~4.29 billion colonies
+#[cfg(feature = "wide-ids")]
+pub type NodeIndex = u64;
+
+#[cfg(not(feature = "wide-ids"))]
+type RawId = std::num::NonZeroU32;
+#[cfg(feature = "wide-ids")]
+type RawId = std::num::NonZeroU64;
+
+/// Most colonies a world can hold (`NodeIndex::MAX` is not a valid index)
+pub const MAX_NODES: usize = NodeIndex::MAX as usize;
+
+/// Index of a colony in `World::nodes`.
+///
+/// Stored as `index + 1` in a non-zero integer, so `Option<NodeId>` is the
+/// size of a [`NodeIndex`] and replaces the old `u32::MAX` "no tunnel"
+/// sentinel: a missing neighbor is `None` and can no longer be fed to
+/// `get_unchecked` by mistake.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[repr(transparent)]
+pub struct NodeId(RawId);
+
+impl NodeId {
+    /// Create an id for the node at `index`
+    ///
+    /// # Panics
+    /// If `index` is `NodeIndex::MAX` (not representable)
+    #[inline]
+    pub const fn new(index: NodeIndex) -> Self {
+        match RawId::new(index.wrapping_add(1)) {
+            Some(raw) => Self(raw),
+            None => panic!("node index out of range"),
+        }
+    }
+
+    /// Create an id from a `usize` array index
+    #[inline]
+    pub const fn from_index(index: usize) -> Self {
+        debug_assert!(index < MAX_NODES, "node index does not fit NodeIndex");
+        Self::new(index as NodeIndex)
+    }
+
+    /// Whether every colony of a `count`-colony world gets an id
+    #[inline]
+    pub fn can_address(count: usize) -> bool {
+        NodeIndex::try_from(count).is_ok()
+    }
+
+    /// Position in `World::nodes`
+    #[inline(always)]
+    pub const fn index(self) -> usize {
+        (self.0.get() - 1) as usize
+    }
+
+    /// Position in `World::nodes` as a [`NodeIndex`]
+    #[inline(always)]
+    pub const fn get(self) -> NodeIndex {
+        self.0.get() - 1
+    }
+}
+
+impl fmt::Debug for NodeId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "NodeId({})", self.get())
+    }
+}
+
+impl fmt::Display for NodeId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.get())
+    }
+}
+
+impl Serialize for NodeId {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.get().serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for NodeId {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let index = NodeIndex::deserialize(deserializer)?;
+        if index == NodeIndex::MAX {
+            return Err(serde::de::Error::custom("node index out of range"));
+        }
+        Ok(Self::new(index))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_node_id_round_trip() {
+        assert_eq!(NodeId::new(0).get(), 0);
+        assert_eq!(NodeId::new(41).index(), 41);
+        assert_eq!(NodeId::from_index(7), NodeId::new(7));
+    }
+
+    #[test]
+    fn test_option_node_id_is_niche_optimized() {
+        assert_eq!(std::mem::size_of::<Option<NodeId>>(), std::mem::size_of::<NodeIndex>());
+    }
+
+    #[test]
+    fn test_can_address() {
+        assert!(NodeId::can_address(0));
+        assert!(NodeId::can_address(MAX_NODES));
+        #[cfg(not(feature = "wide-ids"))]
+        assert!(!NodeId::can_address(MAX_NODES + 1));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_node_id_rejects_max() {
+        NodeId::new(NodeIndex::MAX);
+    }
+}
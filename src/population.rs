@@ -0,0 +1,363 @@
+use crate::ant::{Ant, AntKind, DeathReason};
+use crate::node_id::NodeId;
+use crate::world::World;
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, BufWriter, Read, Write};
+
+/// Magic bytes opening a binary population file
+pub const BINARY_MAGIC: &[u8; 4] = b"ANTP";
+/// Version of the binary layout, written after [`BINARY_MAGIC`]
+pub const BINARY_VERSION: u8 = 1;
+
+/// Encoding of an `--export-ants` / `--import-ants` file
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PopulationFormat {
+    /// A header line, then one [`AntRecord`] per line
+    Csv,
+    /// Little-endian records, see [`Population::write`]
+    Binary,
+}
+
+impl PopulationFormat {
+    /// `Binary` for paths ending in `.bin`, `Csv` otherwise
+    pub fn from_path(path: &str) -> Self {
+        if path.ends_with(".bin") {
+            PopulationFormat::Binary
+        } else {
+            PopulationFormat::Csv
+        }
+    }
+}
+
+/// One ant as written to a population file, its colonies named so the
+/// file can be read back onto another map
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AntRecord {
+    pub id: u32,
+    pub kind: AntKind,
+    /// Colony the ant stands on (or died on)
+    pub colony: String,
+    /// Colony it started from, for `--strategy homing:H`
+    pub home: String,
+    pub moves: u32,
+    pub alive: bool,
+    pub trapped: bool,
+    pub death: Option<DeathReason>,
+}
+
+impl AntRecord {
+    pub const CSV_HEADER: &'static str = "id,kind,colony,home,moves,alive,trapped,death";
+
+    pub fn of(ant: &Ant, world: &World) -> Self {
+        Self {
+            id: ant.id,
+            kind: ant.kind,
+            colony: world.get_colony_name(ant.pos).to_string(),
+            home: world.get_colony_name(ant.home).to_string(),
+            moves: ant.moves,
+            alive: ant.is_alive(),
+            trapped: ant.is_trapped(),
+            death: ant.death,
+        }
+    }
+
+    fn parse_csv(line: &str) -> Result<Self, String> {
+        let fields: Vec<&str> = line.split(',').collect();
+        let [id, kind, colony, home, moves, alive, trapped, death] = fields[..] else {
+            return Err(format!("expected {} fields", Self::CSV_HEADER.split(',').count()));
+        };
+        let number = |s: &str| s.parse::<u32>().map_err(|e| format!("invalid number '{s}': {e}"));
+        let flag = |s: &str| s.parse::<bool>().map_err(|_| format!("invalid flag '{s}' (expected true or false)"));
+        Ok(Self {
+            id: number(id)?,
+            kind: match kind {
+                "worker" => AntKind::Worker,
+                "queen" => AntKind::Queen,
+                "mega" => AntKind::Mega,
+                _ => return Err(format!("unknown kind '{kind}' (expected worker, queen or mega)")),
+            },
+            colony: colony.to_string(),
+            home: home.to_string(),
+            moves: number(moves)?,
+            alive: flag(alive)?,
+            trapped: flag(trapped)?,
+            death: match death {
+                "" => None,
+                _ => Some(
+                    DeathReason::ALL
+                        .into_iter()
+                        .find(|r| r.to_string() == death)
+                        .ok_or_else(|| format!("unknown death reason '{death}'"))?,
+                ),
+            },
+        })
+    }
+}
+
+/// Ants carried from the end of one run to the start of another, by id
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Population {
+    /// Ant `i` is the one with id `i`
+    pub ants: Vec<AntRecord>,
+}
+
+impl Population {
+    /// Every ant of a run on `world`, dead ones included so ids stay dense
+    pub fn of(world: &World, ants: &[Ant]) -> Self {
+        Self { ants: ants.iter().map(|a| AntRecord::of(a, world)).collect() }
+    }
+
+    pub fn len(&self) -> usize {
+        self.ants.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ants.is_empty()
+    }
+
+    /// Ants standing on `world`, and how many of the alive ones died on the
+    /// way in because `world` has no standing colony of their name.
+    ///
+    /// As on a map `reload`, an ant keeps its colony by name; one whose
+    /// colony is missing or destroyed dies with it. Its home falls back to
+    /// where it stands. Moves, castes and trapped flags carry over (move
+    /// limits and energy are set again when the run begins).
+    pub fn into_ants(self, world: &World) -> (Vec<Ant>, usize) {
+        let ids: HashMap<&str, NodeId> = (0..world.nodes.len())
+            .map(|i| (world.get_colony_name(NodeId::from_index(i)), NodeId::from_index(i)))
+            .collect();
+        let mut lost = 0;
+        let ants = self
+            .ants
+            .into_iter()
+            .map(|r| {
+                let found = ids.get(r.colony.as_str()).copied();
+                let pos = found.or_else(|| ids.get(r.home.as_str()).copied()).unwrap_or(NodeId::from_index(0));
+                let mut ant = match r.kind {
+                    AntKind::Worker => Ant::new(r.id, pos),
+                    AntKind::Queen => Ant::queen(r.id, pos),
+                    AntKind::Mega => Ant::mega(r.id, pos),
+                };
+                ant.home = ids.get(r.home.as_str()).copied().unwrap_or(pos);
+                ant.moves = r.moves;
+                ant.set_trapped(r.trapped);
+                match (r.alive, r.death) {
+                    (true, _) if found.is_some_and(|id| world.is_alive(id)) => {}
+                    (true, _) => {
+                        ant.die(DeathReason::VanishedWithColony);
+                        lost += 1;
+                    }
+                    (false, Some(reason)) => ant.die(reason),
+                    (false, None) => ant.set_alive(false),
+                }
+                ant
+            })
+            .collect();
+        (ants, lost)
+    }
+
+    /// Write the population in `format`.
+    ///
+    /// The binary layout is little-endian: [`BINARY_MAGIC`], the version
+    /// byte and the ant count (u32); then per ant its id and moves (u32),
+    /// kind (0 worker, 1 queen, 2 mega), state (bit 0 alive, bit 1 trapped) and
+    /// death reason (0 none, else its [`DeathReason::ALL`] index + 1) as
+    /// bytes, and its colony and home names (u32 length + UTF-8).
+    pub fn write<W: Write>(&self, out: &mut W, format: PopulationFormat) -> io::Result<()> {
+        match format {
+            PopulationFormat::Csv => {
+                writeln!(out, "{}", AntRecord::CSV_HEADER)?;
+                for r in &self.ants {
+                    let kind = match r.kind {
+                        AntKind::Worker => "worker",
+                        AntKind::Queen => "queen",
+                        AntKind::Mega => "mega",
+                    };
+                    let death = r.death.map(|d| d.to_string()).unwrap_or_default();
+                    writeln!(
+                        out,
+                        "{},{},{},{},{},{},{},{}",
+                        r.id, kind, r.colony, r.home, r.moves, r.alive, r.trapped, death
+                    )?;
+                }
+            }
+            PopulationFormat::Binary => {
+                out.write_all(BINARY_MAGIC)?;
+                out.write_all(&[BINARY_VERSION])?;
+                out.write_all(&(self.ants.len() as u32).to_le_bytes())?;
+                for r in &self.ants {
+                    out.write_all(&r.id.to_le_bytes())?;
+                    out.write_all(&r.moves.to_le_bytes())?;
+                    let death = r.death.map_or(0, |d| DeathReason::ALL.iter().position(|&x| x == d).unwrap() as u8 + 1);
+                    out.write_all(&[r.kind as u8, r.alive as u8 | (r.trapped as u8) << 1, death])?;
+                    for name in [&r.colony, &r.home] {
+                        out.write_all(&(name.len() as u32).to_le_bytes())?;
+                        out.write_all(name.as_bytes())?;
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Read a population written by [`Population::write`]; ids must run
+    /// from 0 in order, as the engine indexes ants by id
+    pub fn read<R: BufRead>(input: &mut R, format: PopulationFormat) -> io::Result<Self> {
+        let ants = match format {
+            PopulationFormat::Csv => read_csv(input)?,
+            PopulationFormat::Binary => read_binary(input)?,
+        };
+        if let Some((i, r)) = ants.iter().enumerate().find(|(i, r)| r.id as usize != *i) {
+            return Err(invalid(format!("ant {} is listed where ant {} belongs (ids must run from 0 in order)", r.id, i)));
+        }
+        Ok(Self { ants })
+    }
+
+    /// Write to `path`, in the format its extension selects
+    pub fn write_file(&self, path: &str) -> io::Result<()> {
+        let mut out = BufWriter::new(File::create(path)?);
+        self.write(&mut out, PopulationFormat::from_path(path))?;
+        out.flush()
+    }
+
+    /// Read from `path`, in the format its extension selects
+    pub fn read_file(path: &str) -> io::Result<Self> {
+        Self::read(&mut BufReader::new(File::open(path)?), PopulationFormat::from_path(path))
+            .map_err(|err| io::Error::new(err.kind(), format!("{path}: {err}")))
+    }
+}
+
+fn invalid(message: String) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+fn read_csv<R: BufRead>(input: &mut R) -> io::Result<Vec<AntRecord>> {
+    let mut lines = input.lines();
+    match lines.next().transpose()? {
+        Some(header) if header.trim_end() == AntRecord::CSV_HEADER => {}
+        _ => return Err(invalid(format!("missing header '{}'", AntRecord::CSV_HEADER))),
+    }
+    let mut ants = Vec::new();
+    for (i, line) in lines.enumerate() {
+        let line = line?;
+        if !line.trim().is_empty() {
+            ants.push(AntRecord::parse_csv(line.trim_end()).map_err(|e| invalid(format!("line {}: {e}", i + 2)))?);
+        }
+    }
+    Ok(ants)
+}
+
+fn read_binary<R: Read>(input: &mut R) -> io::Result<Vec<AntRecord>> {
+    let mut head = [0u8; 5];
+    input.read_exact(&mut head)?;
+    if &head[..4] != BINARY_MAGIC || head[4] != BINARY_VERSION {
+        return Err(invalid("not a version 1 binary population file".to_string()));
+    }
+    let count = read_u32(input)?;
+    let mut ants = Vec::with_capacity(count.min(1 << 20) as usize);
+    for _ in 0..count {
+        let id = read_u32(input)?;
+        let moves = read_u32(input)?;
+        let mut bytes = [0u8; 3];
+        input.read_exact(&mut bytes)?;
+        let [kind, state, death] = bytes;
+        ants.push(AntRecord {
+            id,
+            kind: match kind {
+                0 => AntKind::Worker,
+                1 => AntKind::Queen,
+                2 => AntKind::Mega,
+                _ => return Err(invalid(format!("ant {id}: unknown kind {kind}"))),
+            },
+            colony: read_name(input)?,
+            home: read_name(input)?,
+            moves,
+            alive: state & 1 != 0,
+            trapped: state & 2 != 0,
+            death: match death {
+                0 => None,
+                _ => Some(
+                    *DeathReason::ALL
+                        .get(death as usize - 1)
+                        .ok_or_else(|| invalid(format!("ant {id}: unknown death reason {death}")))?,
+                ),
+            },
+        });
+    }
+    Ok(ants)
+}
+
+fn read_u32<R: Read>(input: &mut R) -> io::Result<u32> {
+    let mut bytes = [0u8; 4];
+    input.read_exact(&mut bytes)?;
+    Ok(u32::from_le_bytes(bytes))
+}
+
+fn read_name<R: Read>(input: &mut R) -> io::Result<String> {
+    let mut name = vec![0u8; read_u32(input)? as usize];
+    input.read_exact(&mut name)?;
+    String::from_utf8(name).map_err(|e| invalid(e.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::world::load_world_from_str;
+
+    fn population() -> (World, Population) {
+        let world = load_world_from_str("A east=B\nB west=A\n", &Default::default()).unwrap();
+        let mut ants = vec![Ant::queen(0, NodeId::new(1)), Ant::new(1, NodeId::new(0)), Ant::new(2, NodeId::new(0))];
+        ants[0].move_to(NodeId::new(1));
+        ants[1].set_trapped(true);
+        ants[2].die(DeathReason::Collision);
+        let population = Population::of(&world, &ants);
+        (world, population)
+    }
+
+    #[test]
+    fn test_round_trips_in_both_formats() {
+        let (_, population) = population();
+        for format in [PopulationFormat::Csv, PopulationFormat::Binary] {
+            let mut bytes = Vec::new();
+            population.write(&mut bytes, format).unwrap();
+            assert_eq!(Population::read(&mut bytes.as_slice(), format).unwrap(), population, "{format:?}");
+        }
+    }
+
+    #[test]
+    fn test_csv_layout() {
+        let (_, population) = population();
+        let mut bytes = Vec::new();
+        population.write(&mut bytes, PopulationFormat::Csv).unwrap();
+        assert_eq!(
+            String::from_utf8(bytes).unwrap(),
+            concat!(
+                "id,kind,colony,home,moves,alive,trapped,death\n",
+                "0,queen,B,B,1,true,false,\n",
+                "1,worker,A,A,0,true,true,\n",
+                "2,worker,A,A,0,false,false,collision\n",
+            )
+        );
+    }
+
+    #[test]
+    fn test_ants_land_by_name_and_die_with_missing_colonies() {
+        let (_, population) = population();
+        let world = load_world_from_str("B east=C\nC west=B\n", &Default::default()).unwrap();
+        let (ants, lost) = population.into_ants(&world);
+
+        assert_eq!(lost, 1);
+        assert!(ants[0].is_queen() && ants[0].is_alive() && ants[0].moves == 1);
+        assert_eq!(ants[0].pos, world.find("B").unwrap());
+        assert_eq!(ants[1].death, Some(DeathReason::VanishedWithColony));
+        assert_eq!(ants[2].death, Some(DeathReason::Collision));
+    }
+
+    #[test]
+    fn test_ids_must_be_dense() {
+        let csv = "id,kind,colony,home,moves,alive,trapped,death\n1,worker,A,A,0,true,false,\n";
+        let err = Population::read(&mut csv.as_bytes(), PopulationFormat::Csv).unwrap_err();
+        assert!(err.to_string().contains("ids must run from 0"), "{err}");
+    }
+}
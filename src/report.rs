@@ -0,0 +1,230 @@
+use crate::ant::{Ant, AntKind, AntOutcomes, DeathCounts, KindStats};
+use crate::ant_names::AntNames;
+use crate::cli::{Args, ReportFormat};
+use crate::heatmap::{heatmap, HeatmapEntry};
+use crate::kills::{top_killers, KillerEntry};
+use crate::lifetimes::ColonyLifetimes;
+use crate::limits::Limit;
+use crate::report_writer::{JsonWriter, ReportWriter};
+use crate::seeds::SubSeeds;
+use crate::simulation::event::DestructionCause;
+use crate::simulation::SimulationEngine;
+use crate::world::World;
+use serde::{Deserialize, Serialize};
+use std::fs::File;
+use std::io::{BufReader, BufWriter, Write};
+use std::time::Duration;
+
+/// Machine-readable summary of a finished run
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Report {
+    pub ants: usize,
+    pub max_moves: u32,
+    /// Per-ant limit distribution, when `--max-moves-dist` overrode `max_moves`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_moves_dist: Option<String>,
+    /// `--max-ticks` bound of the run, if any
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_ticks: Option<u64>,
+    pub seed: Option<u64>,
+    /// Stream seeds actually used (the master is random when `seed` is null)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub seeds: Option<SubSeeds>,
+    pub ticks: u64,
+    /// Tick on which the last colony fell (0 = placement), null if none did
+    #[serde(default)]
+    pub last_destruction: Option<u64>,
+    /// Why each ant stopped
+    #[serde(default)]
+    pub outcomes: AntOutcomes,
+    /// Why the dead ants died
+    #[serde(default)]
+    pub deaths: DeathCounts,
+    /// What placement cost before the first move
+    #[serde(default)]
+    pub start: StartCasualties,
+    /// Starting energy of every ant, under `--energy`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub energy: Option<u32>,
+    pub interrupted: bool,
+    /// Resource limit that cut the run short, if that is why it was interrupted
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub limit_exceeded: Option<Limit>,
+    pub latency_ms: f64,
+    pub survivors: usize,
+    pub workers: KindStats,
+    pub queens: KindStats,
+    pub timeline: Vec<TimelineEntry>,
+    /// How many ticks the colonies stood before falling
+    #[serde(default)]
+    pub lifetimes: ColonyLifetimes,
+    /// Meetings that did not turn into fights, present with `--collision-prob`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub near_misses: Option<usize>,
+    /// Lone ants killed by fortified colonies, present when the map has any
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub repelled: Option<usize>,
+    /// Colonies rebuilt by their neighbors, present with `--revival-prob`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub revived: Option<usize>,
+    /// Colonies destroyed by meteors, present with `--chaos`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub meteors: Option<usize>,
+    /// Colonies trampled by mega ants, present with `--mega-fraction`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub trampled: Option<usize>,
+    /// Per-colony visit counts, present when `--heatmap` is on
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub heatmap: Option<Vec<HeatmapEntry>>,
+    /// Most destructive ants, present with `--top-killers`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub top_killers: Option<Vec<KillerEntry>>,
+}
+
+/// One colony loss, resolved to names for readability
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct TimelineEntry {
+    pub tick: u64,
+    pub colony: String,
+    #[serde(flatten)]
+    pub cause: TimelineCause,
+    /// Names of the two ants, when the run named its ants
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ant_names: Option<[String; 2]>,
+}
+
+/// Casualties of t=0, when ants placed on the same colony fight before
+/// anyone moves; with far more ants than colonies this is most of the run
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StartCasualties {
+    /// Colonies that fell at t=0, to fights or their blasts
+    pub colonies: usize,
+    /// Ants that died with them
+    pub ants: usize,
+    /// Ants still alive once t=0 was resolved
+    pub survivors: usize,
+}
+
+/// Report-side view of [`DestructionCause`]
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case", tag = "kind")]
+pub enum TimelineCause {
+    Fight { ants: [u32; 2] },
+    Blast { origin: String },
+    Averted { ants: [u32; 2] },
+    NearMiss { ants: [u32; 2] },
+    Repelled { ant: u32 },
+    Meteor,
+    Trampled { ant: u32 },
+}
+
+impl TimelineCause {
+    /// Whether the colony actually fell, as [`DestructionCause::destroyed`]
+    pub fn destroyed(&self) -> bool {
+        matches!(
+            self,
+            TimelineCause::Fight { .. } | TimelineCause::Blast { .. } | TimelineCause::Meteor | TimelineCause::Trampled { .. }
+        )
+    }
+}
+
+impl Report {
+    /// Build a report from the engine state after a run
+    pub fn new(
+        world: &World,
+        ants: &[Ant],
+        args: &Args,
+        engine: &SimulationEngine,
+        latency: Duration,
+    ) -> Self {
+        let label = |id: u32| AntNames::label(engine.ant_names(), id).to_string();
+        let timeline = engine
+            .events()
+            .iter()
+            .map(|ev| TimelineEntry {
+                tick: ev.tick,
+                colony: world.get_colony_name(ev.node_id).to_string(),
+                ant_names: match ev.cause {
+                    DestructionCause::Fight { ant1, ant2 }
+                    | DestructionCause::Averted { ant1, ant2 }
+                    | DestructionCause::NearMiss { ant1, ant2 } => {
+                        engine.ant_names().map(|_| [label(ant1), label(ant2)])
+                    }
+                    DestructionCause::Blast { .. }
+                    | DestructionCause::Repelled { .. }
+                    | DestructionCause::Meteor
+                    | DestructionCause::Trampled { .. } => None,
+                },
+                cause: match ev.cause {
+                    DestructionCause::Fight { ant1, ant2 } => TimelineCause::Fight { ants: [ant1, ant2] },
+                    DestructionCause::Blast { origin } => TimelineCause::Blast {
+                        origin: world.get_colony_name(origin).to_string(),
+                    },
+                    DestructionCause::Averted { ant1, ant2 } => TimelineCause::Averted { ants: [ant1, ant2] },
+                    DestructionCause::NearMiss { ant1, ant2 } => TimelineCause::NearMiss { ants: [ant1, ant2] },
+                    DestructionCause::Repelled { ant } => TimelineCause::Repelled { ant },
+                    DestructionCause::Meteor => TimelineCause::Meteor,
+                    DestructionCause::Trampled { ant } => TimelineCause::Trampled { ant },
+                },
+            })
+            .collect();
+
+        Self {
+            ants: args.ants,
+            max_moves: args.max_moves,
+            max_moves_dist: args.max_moves_dist.map(|d| d.to_string()),
+            max_ticks: args.max_ticks,
+            seed: args.seed,
+            seeds: engine.seeds(),
+            ticks: engine.ticks(),
+            last_destruction: engine.last_destruction(),
+            outcomes: AntOutcomes::of(ants),
+            deaths: DeathCounts::of(ants),
+            start: engine.start_casualties(),
+            energy: args.energy,
+            interrupted: engine.interrupted(),
+            limit_exceeded: engine.limit_exceeded(),
+            latency_ms: latency.as_secs_f64() * 1000.0,
+            survivors: world.count_survivors(),
+            workers: KindStats::of(ants, AntKind::Worker),
+            queens: KindStats::of(ants, AntKind::Queen),
+            timeline,
+            lifetimes: ColonyLifetimes::of(engine.destruction_ticks(), world.alive_count()),
+            near_misses: args.collision_prob.map(|_| engine.near_misses()),
+            repelled: engine.has_fortified().then(|| engine.repelled()),
+            revived: args.revival_prob.map(|_| engine.revived()),
+            meteors: args.chaos.map(|_| engine.meteors()),
+            trampled: args.mega_fraction.map(|_| engine.trampled()),
+            heatmap: args.track_visits().then(|| heatmap(world, engine.visits())),
+            top_killers: args.top_killers.map(|k| top_killers(engine.kills(), k, engine.ant_names())),
+        }
+    }
+
+    /// Load a report previously written by [`Report::write_json`]
+    pub fn read_json(path: &str) -> std::io::Result<Self> {
+        Ok(serde_json::from_reader(BufReader::new(File::open(path)?))?)
+    }
+
+    /// Write the report as pretty-printed JSON, to stdout when `path` is `-`
+    pub fn write_json(&self, path: &str) -> std::io::Result<()> {
+        self.write(path, ReportFormat::Json)
+    }
+
+    /// Write the report as pretty-printed JSON to `out`
+    pub fn write_json_to<W: Write>(&self, out: &mut W) -> std::io::Result<()> {
+        JsonWriter.write(self, out)?;
+        out.flush()
+    }
+
+    /// Write the report in `format`, to stdout when `path` is `-` (where
+    /// the text format keeps its colors)
+    pub fn write(&self, path: &str, format: ReportFormat) -> std::io::Result<()> {
+        let (mut out, color): (BufWriter<Box<dyn Write>>, bool) = if path == "-" {
+            (BufWriter::new(Box::new(std::io::stdout().lock())), true)
+        } else {
+            (BufWriter::new(Box::new(File::create(path)?)), false)
+        };
+        format.writer(color).write(self, &mut out)?;
+        out.flush()
+    }
+}
@@ -0,0 +1,360 @@
+//! Renderings of a run [`Report`]: the summary printed at the end of a run
+//! and the formats `--report-format` can write
+use crate::ant::DeathReason;
+use crate::cli::ReportFormat;
+use crate::limits::Limit;
+use crate::report::Report;
+use std::io::{self, Write};
+
+/// Writes a [`Report`] in one format
+pub trait ReportWriter {
+    fn write(&self, report: &Report, out: &mut dyn Write) -> io::Result<()>;
+}
+
+impl ReportFormat {
+    /// Writer for this format; `color` lets the text format use colors
+    /// (still subject to `colored`'s terminal detection)
+    pub fn writer(self, color: bool) -> Box<dyn ReportWriter> {
+        match self {
+            ReportFormat::Text => Box::new(TextWriter { color }),
+            ReportFormat::Json => Box::new(JsonWriter),
+            ReportFormat::Csv => Box::new(CsvWriter),
+            ReportFormat::Markdown => Box::new(MarkdownWriter),
+        }
+    }
+}
+
+/// The human summary lines (`⏱️  Simulation Latency: ...`)
+pub struct TextWriter {
+    pub color: bool,
+}
+
+/// How a segment of the text summary is colored
+#[derive(Clone, Copy)]
+enum Tone {
+    Rule,
+    Label,
+    Value,
+    Note,
+    Muted,
+    WarnLabel,
+    Warn,
+}
+
+impl TextWriter {
+    fn paint(&self, text: &str, tone: Tone) -> String {
+        #[cfg(feature = "cli")]
+        if self.color {
+            use colored::Colorize;
+            return match tone {
+                Tone::Rule => text.bright_blue().bold(),
+                Tone::Label => text.green().bold(),
+                Tone::Value => text.cyan(),
+                Tone::Note => text.green(),
+                Tone::Muted => text.dimmed(),
+                Tone::WarnLabel => text.yellow().bold(),
+                Tone::Warn => text.yellow(),
+            }
+            .to_string();
+        }
+        let _ = tone;
+        text.to_string()
+    }
+}
+
+impl ReportWriter for TextWriter {
+    fn write(&self, report: &Report, out: &mut dyn Write) -> io::Result<()> {
+        let p = |text: &str, tone| self.paint(text, tone);
+        let max_moves = match &report.max_moves_dist {
+            Some(dist) => format!("max_moves={dist}"),
+            None => format!("max_moves={}", report.max_moves),
+        };
+        writeln!(
+            out,
+            "\n{}\n{} {:.3} ms {} {} {} {} {}",
+            p("===", Tone::Rule),
+            p("⏱️  Simulation Latency:", Tone::Label),
+            report.latency_ms,
+            p("(map loaded)", Tone::Muted),
+            p("|", Tone::Muted),
+            p(&format!("ants={}", report.ants), Tone::Value),
+            p(&max_moves, Tone::Value),
+            p(&format!("survivors={}", report.survivors), Tone::Value),
+        )?;
+        let o = &report.outcomes;
+        let outcomes = match report.energy {
+            Some(_) => format!(
+                "max_moves={} died={} starved={} trapped={} moving={}",
+                o.max_moves, o.died, o.starved, o.trapped, o.moving
+            ),
+            None => format!("max_moves={} died={} trapped={} moving={}", o.max_moves, o.died, o.trapped, o.moving),
+        };
+        let last = match report.last_destruction {
+            Some(tick) => format!("last_destruction=t{tick}"),
+            None => "last_destruction=none".to_string(),
+        };
+        writeln!(
+            out,
+            "{} {} {} {} {}",
+            p("🏁 Ants stopped:", Tone::Label),
+            p(&outcomes, Tone::Value),
+            p("|", Tone::Muted),
+            p(&format!("ticks={}", report.ticks), Tone::Value),
+            p(&last, Tone::Value),
+        )?;
+        if report.deaths.total() > 0 {
+            writeln!(out, "{} {}", p("💀 Deaths:", Tone::Label), p(&report.deaths.to_string(), Tone::Value))?;
+        }
+        if report.start.colonies > 0 {
+            let s = &report.start;
+            let text = format!("{} colonies fell and {} ants died before the first move; {} ants left", s.colonies, s.ants, s.survivors);
+            writeln!(out, "{} {}", p("💥 At t=0:", Tone::Label), p(&text, Tone::Note))?;
+        }
+        if let Some(near_misses) = report.near_misses {
+            let text = format!("{near_misses} meetings ended without a fight");
+            writeln!(out, "{} {}", p("💨 Near misses:", Tone::Label), p(&text, Tone::Note))?;
+        }
+        if let Some(repelled) = report.repelled {
+            let text = format!("{repelled} lone ants killed by fortified colonies");
+            writeln!(out, "{} {}", p("🛡️  Repelled:", Tone::Label), p(&text, Tone::Note))?;
+        }
+        if let Some(revived) = report.revived {
+            let text = format!("{revived} colonies rebuilt by their neighbors");
+            writeln!(out, "{} {}", p("🧱 Revived:", Tone::Label), p(&text, Tone::Note))?;
+        }
+        if let Some(meteors) = report.meteors {
+            let text = format!("{meteors} colonies struck by meteors");
+            writeln!(out, "{} {}", p("☄️  Meteors:", Tone::Label), p(&text, Tone::Note))?;
+        }
+        if let Some(trampled) = report.trampled {
+            let text = format!("{trampled} colonies trampled by mega ants");
+            writeln!(out, "{} {}", p("🦣 Trampled:", Tone::Label), p(&text, Tone::Note))?;
+        }
+        if let Some(top) = &report.top_killers {
+            writeln!(out, "{}", p("🗡️  Most destructive ants:", Tone::Label))?;
+            if top.is_empty() {
+                writeln!(out, "   {}", p("no colony fell in a fight", Tone::Muted))?;
+            }
+            for entry in top {
+                writeln!(out, "   {}", p(&entry.to_string(), Tone::Value))?;
+            }
+        }
+        if report.interrupted {
+            let reason = match report.limit_exceeded {
+                Some(Limit::Memory) => " (memory limit reached)",
+                Some(Limit::WallTime) => " (wall-time limit reached)",
+                None => "",
+            };
+            let text = format!("partial results after {} ticks{}", report.ticks, reason);
+            writeln!(out, "{} {}", p("⚠️  Interrupted:", Tone::WarnLabel), p(&text, Tone::Warn))?;
+        }
+        if report.queens.total > 0 {
+            let (queens, workers) = (report.queens, report.workers);
+            writeln!(
+                out,
+                "{} {} {}",
+                p("👑 Castes:", Tone::Label),
+                p(&format!("queens={}/{} alive", queens.alive, queens.total), Tone::Value),
+                p(&format!("workers={}/{} alive", workers.alive, workers.total), Tone::Value),
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// The whole report as pretty-printed JSON, as [`Report::read_json`] reads it back
+pub struct JsonWriter;
+
+impl ReportWriter for JsonWriter {
+    fn write(&self, report: &Report, out: &mut dyn Write) -> io::Result<()> {
+        serde_json::to_writer_pretty(&mut *out, report)?;
+        out.write_all(b"\n")
+    }
+}
+
+/// A header and one row of the report's counters (no timeline), so the
+/// rows of several runs can be appended into one table
+pub struct CsvWriter;
+
+impl CsvWriter {
+    pub const CSV_HEADER: &'static str = "ants,max_moves,max_moves_dist,seed,ticks,last_destruction,latency_ms,\
+        survivors,destroyed,interrupted,limit_exceeded,stopped_max_moves,died,starved,trapped,moving,\
+        collision_at_start,collision,vanished_with_colony,stepped_into_ruins,trapped_deaths,repelled_deaths,\
+        starved_deaths,workers,workers_alive,queens,queens_alive,near_misses,repelled";
+}
+
+impl ReportWriter for CsvWriter {
+    fn write(&self, report: &Report, out: &mut dyn Write) -> io::Result<()> {
+        let opt = |v: Option<String>| v.unwrap_or_default();
+        let o = &report.outcomes;
+        let deaths = DeathReason::ALL.map(|r| report.deaths.get(r).to_string()).join(",");
+        writeln!(out, "{}", Self::CSV_HEADER)?;
+        writeln!(
+            out,
+            "{},{},{},{},{},{},{:.3},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{}",
+            report.ants,
+            report.max_moves,
+            // Distributions such as `uniform:5,50` hold commas
+            opt(report.max_moves_dist.as_ref().map(|d| format!("\"{d}\""))),
+            opt(report.seed.map(|s| s.to_string())),
+            report.ticks,
+            opt(report.last_destruction.map(|t| t.to_string())),
+            report.latency_ms,
+            report.survivors,
+            destroyed(report),
+            report.interrupted,
+            opt(report.limit_exceeded.map(|l| l.to_string())),
+            o.max_moves,
+            o.died,
+            o.starved,
+            o.trapped,
+            o.moving,
+            deaths,
+            report.workers.total,
+            report.workers.alive,
+            report.queens.total,
+            report.queens.alive,
+            opt(report.near_misses.map(|n| n.to_string())),
+            opt(report.repelled.map(|n| n.to_string())),
+        )
+    }
+}
+
+/// Markdown tables of the run, the ants' outcomes and the non-zero death
+/// reasons, for pasting into issues and notes
+pub struct MarkdownWriter;
+
+impl ReportWriter for MarkdownWriter {
+    fn write(&self, report: &Report, out: &mut dyn Write) -> io::Result<()> {
+        let max_moves = report.max_moves_dist.clone().unwrap_or_else(|| report.max_moves.to_string());
+        writeln!(out, "## Run summary\n")?;
+        writeln!(out, "| ants | max moves | seed | ticks | last destruction | colonies destroyed | survivors | latency (ms) |")?;
+        writeln!(out, "|---:|---:|---:|---:|---:|---:|---:|---:|")?;
+        writeln!(
+            out,
+            "| {} | {} | {} | {} | {} | {} | {} | {:.3} |",
+            report.ants,
+            max_moves,
+            report.seed.map_or_else(|| "random".to_string(), |s| s.to_string()),
+            report.ticks,
+            report.last_destruction.map_or_else(|| "none".to_string(), |t| format!("t{t}")),
+            destroyed(report),
+            report.survivors,
+            report.latency_ms,
+        )?;
+        if report.interrupted {
+            let reason = match report.limit_exceeded {
+                Some(Limit::Memory) => " (memory limit reached)",
+                Some(Limit::WallTime) => " (wall-time limit reached)",
+                None => "",
+            };
+            writeln!(out, "\n**Interrupted** after {} ticks{reason}.", report.ticks)?;
+        }
+
+        let o = &report.outcomes;
+        writeln!(out, "\n### Ants stopped\n")?;
+        writeln!(out, "| max moves | died | starved | trapped | moving |")?;
+        writeln!(out, "|---:|---:|---:|---:|---:|")?;
+        writeln!(out, "| {} | {} | {} | {} | {} |", o.max_moves, o.died, o.starved, o.trapped, o.moving)?;
+
+        if report.deaths.total() > 0 {
+            writeln!(out, "\n### Deaths\n")?;
+            writeln!(out, "| reason | ants |")?;
+            writeln!(out, "|---|---:|")?;
+            for reason in DeathReason::ALL.into_iter().filter(|&r| report.deaths.get(r) > 0) {
+                writeln!(out, "| {} | {} |", reason, report.deaths.get(reason))?;
+            }
+        }
+        if report.queens.total > 0 {
+            writeln!(out, "\n### Castes\n")?;
+            writeln!(out, "| kind | alive | total |")?;
+            writeln!(out, "|---|---:|---:|")?;
+            writeln!(out, "| queens | {} | {} |", report.queens.alive, report.queens.total)?;
+            writeln!(out, "| workers | {} | {} |", report.workers.alive, report.workers.total)?;
+        }
+        if let Some(top) = report.top_killers.as_ref().filter(|top| !top.is_empty()) {
+            writeln!(out, "\n### Most destructive ants\n")?;
+            writeln!(out, "| ant | name | kills |")?;
+            writeln!(out, "|---:|---|---:|")?;
+            for entry in top {
+                writeln!(out, "| {} | {} | {} |", entry.ant, entry.name.as_deref().unwrap_or(""), entry.kills)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Colonies that fell during the run (fights and blasts)
+fn destroyed(report: &Report) -> usize {
+    report.timeline.iter().filter(|e| e.cause.destroyed()).count()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cli::Args;
+    use crate::simulation::SimulationEngine;
+    use crate::world::parser::parse_world_from_str;
+    use crate::world::World;
+    use std::time::Duration;
+
+    /// Three ants on a three-colony row, so some meet at t=0
+    fn report() -> Report {
+        let (names, nodes) = parse_world_from_str("A east=B\nB west=A east=C\nC west=B\n");
+        let mut world = World::new(names, nodes);
+        let mut args = Args::with_defaults(3, "unused");
+        args.suppress_events = true;
+        args.max_moves = 5;
+        let mut ants = world.place_colony(3, 0, args.placement, &mut fastrand::Rng::with_seed(2));
+        let mut engine = SimulationEngine::new(&world, ants.len());
+        engine.run_simulation(&mut world, &mut ants, &args, &mut fastrand::Rng::with_seed(2));
+        Report::new(&world, &ants, &args, &engine, Duration::from_millis(2))
+    }
+
+    fn render(format: ReportFormat, report: &Report) -> String {
+        let mut out = Vec::new();
+        format.writer(false).write(report, &mut out).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn test_text_is_the_plain_summary() {
+        let report = report();
+        let text = render(ReportFormat::Text, &report);
+
+        assert!(text.starts_with("\n===\n⏱️  Simulation Latency: 2.000 ms (map loaded) | ants=3 max_moves=5 survivors="));
+        assert!(text.contains("🏁 Ants stopped: max_moves="));
+        assert!(!text.contains('\x1b'));
+    }
+
+    #[test]
+    fn test_json_reads_back() {
+        let report = report();
+        let back: Report = serde_json::from_str(&render(ReportFormat::Json, &report)).unwrap();
+
+        assert_eq!((back.survivors, back.ticks, back.deaths), (report.survivors, report.ticks, report.deaths));
+    }
+
+    #[test]
+    fn test_csv_row_matches_header() {
+        let report = report();
+        let csv = render(ReportFormat::Csv, &report);
+        let lines: Vec<&str> = csv.lines().collect();
+
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0], CsvWriter::CSV_HEADER);
+        assert_eq!(lines[1].split(',').count(), lines[0].split(',').count());
+        assert!(lines[1].starts_with("3,5,,,"));
+    }
+
+    #[test]
+    fn test_markdown_tables() {
+        let report = report();
+        let md = render(ReportFormat::Markdown, &report);
+
+        assert!(md.starts_with("## Run summary\n\n| ants |"));
+        assert!(md.contains(&format!("| 3 | 5 | random | {} |", report.ticks)));
+        assert!(md.contains("### Ants stopped"));
+        assert_eq!(md.contains("### Deaths"), report.deaths.total() > 0);
+    }
+}
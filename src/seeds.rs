@@ -0,0 +1,91 @@
+use serde::{Deserialize, Serialize};
+
+// Domain tags mixed into the master seed, one per random stream
+const PLACEMENT: u64 = 0x5041_4345_4d45_4e54; // "PACEMENT"
+const MOVEMENT: u64 = 0x4d4f_5645_4d45_4e54; // "MOVEMENT"
+const SPAWNING: u64 = 0x5350_4157_4e49_4e47; // "SPAWNING"
+const COLLISION: u64 = 0x434f_4c4c_4953_494f; // "COLLISIO"
+const DEFENSE: u64 = 0x4445_4645_4e53_4553; // "DEFENSES"
+const REVIVAL: u64 = 0x5245_5649_5649_4e47; // "REVIVING"
+const CHAOS: u64 = 0x4d45_5445_4f52_5320; // "METEORS "
+
+/// One step of SplitMix64: a well-mixed, bijective 64-bit hash
+pub fn splitmix64(x: u64) -> u64 {
+    let mut z = x.wrapping_add(0x9e37_79b9_7f4a_7c15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^ (z >> 31)
+}
+
+/// Independent seeds for each random stream of a run.
+///
+/// Every stream is derived from the master seed alone, so changing how much
+/// one stream consumes (e.g. placing more ants) leaves the others untouched.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SubSeeds {
+    pub master: u64,
+    /// Initial ant positions
+    pub placement: u64,
+    /// Tunnel choices during the run
+    pub movement: u64,
+    /// Per-ant move limits and anything else drawn when ants are created
+    pub spawning: u64,
+    /// Fight-or-miss draws under `--collision-prob`
+    #[serde(default)]
+    pub collision: u64,
+    /// Kill-or-spare draws of fortified colonies under `--fortress-defense`
+    #[serde(default)]
+    pub defense: u64,
+    /// Rebuild draws of standing colonies under `--revival-prob`
+    #[serde(default)]
+    pub revival: u64,
+    /// Meteor strikes under `--chaos`
+    #[serde(default)]
+    pub chaos: u64,
+}
+
+impl SubSeeds {
+    /// Derive all stream seeds from `master`
+    pub fn derive(master: u64) -> Self {
+        Self {
+            master,
+            placement: splitmix64(master ^ PLACEMENT),
+            movement: splitmix64(master ^ MOVEMENT),
+            spawning: splitmix64(master ^ SPAWNING),
+            collision: splitmix64(master ^ COLLISION),
+            defense: splitmix64(master ^ DEFENSE),
+            revival: splitmix64(master ^ REVIVAL),
+            chaos: splitmix64(master ^ CHAOS),
+        }
+    }
+
+    pub fn placement_rng(&self) -> fastrand::Rng {
+        fastrand::Rng::with_seed(self.placement)
+    }
+
+    pub fn movement_rng(&self) -> fastrand::Rng {
+        fastrand::Rng::with_seed(self.movement)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_splitmix64_reference_values() {
+        // First outputs of the reference SplitMix64 generator seeded with 0
+        assert_eq!(splitmix64(0), 0xe220_a839_7b1d_cdaf);
+        assert_eq!(splitmix64(0x9e37_79b9_7f4a_7c15), 0x6e78_9e6a_a1b9_65f4);
+    }
+
+    #[test]
+    fn test_derive_is_deterministic_and_separates_streams() {
+        let seeds = SubSeeds::derive(42);
+        assert_eq!(seeds, SubSeeds::derive(42));
+        assert_eq!(seeds.master, 42);
+        assert_ne!(seeds.placement, seeds.movement);
+        assert_ne!(seeds.movement, seeds.spawning);
+        assert_ne!(SubSeeds::derive(43).movement, seeds.movement);
+    }
+}
@@ -0,0 +1,335 @@
+use crate::ant::Ant;
+use crate::cli::Args;
+use crate::report::Report;
+use crate::seeds::SubSeeds;
+use crate::simulation::{SimulationEngine, SimulationHooks, TickView};
+use crate::world::{load_world_from_str, ParseOptions, World};
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use axum::routing::{get, post};
+use axum::{Json, Router};
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex, MutexGuard, OnceLock, PoisonError};
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+
+/// Limits of a `serve` instance, shared by everyone who uses it
+#[derive(Clone, Copy, Debug)]
+pub struct ServerOptions {
+    /// Largest `ants` a simulation request may ask for
+    pub max_ants: usize,
+    /// Simulations allowed to run at once; requests past it get a `503`
+    pub max_running: usize,
+    /// Finished simulations kept for polling; the oldest are dropped past it
+    pub keep_finished: usize,
+}
+
+/// Body of `POST /simulations`
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SimulationRequest {
+    /// Id returned by `POST /maps`
+    pub map: usize,
+    pub ants: usize,
+    pub seed: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_moves: Option<u32>,
+}
+
+/// Reply of `POST /maps`
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MapCreated {
+    pub id: usize,
+    pub colonies: usize,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RunState {
+    Running,
+    Done,
+    /// The run panicked; `error` says so
+    Failed,
+}
+
+/// Reply of `POST /simulations` and `GET /simulations/{id}`
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SimulationStatus {
+    pub id: usize,
+    pub map: usize,
+    pub state: RunState,
+    /// Last tick finished so far (the final tick once done)
+    pub tick: u64,
+    /// Alive colonies at the end, once done
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub survivors: Option<usize>,
+    /// Why the run failed, once failed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// A requested run: its progress while it runs, its report (or why it
+/// failed) once it is over
+struct Simulation {
+    request: SimulationRequest,
+    tick: AtomicU64,
+    outcome: OnceLock<Result<Report, String>>,
+}
+
+impl Simulation {
+    fn new(request: SimulationRequest) -> Self {
+        Self { request, tick: AtomicU64::new(0), outcome: OnceLock::new() }
+    }
+
+    fn is_over(&self) -> bool {
+        self.outcome.get().is_some()
+    }
+
+    fn status(&self, id: usize) -> SimulationStatus {
+        let tick = self.tick.load(Ordering::Relaxed);
+        let (state, tick, survivors, error) = match self.outcome.get() {
+            None => (RunState::Running, tick, None, None),
+            Some(Ok(report)) => (RunState::Done, report.ticks, Some(report.survivors), None),
+            Some(Err(err)) => (RunState::Failed, tick, None, Some(err.clone())),
+        };
+        SimulationStatus { id, map: self.request.map, state, tick, survivors, error }
+    }
+
+    /// Run `job` on the blocking pool, holding `permit` until it is over, and
+    /// keep its report, or record the failure if it panicked
+    async fn run_to_end(&self, job: impl FnOnce() -> Report + Send + 'static, permit: OwnedSemaphorePermit) {
+        let outcome = tokio::task::spawn_blocking(job).await.map_err(|err| {
+            if err.is_panic() {
+                "the simulation panicked".to_string()
+            } else {
+                "the simulation was cancelled".to_string()
+            }
+        });
+        drop(permit);
+        let _ = self.outcome.set(outcome);
+    }
+}
+
+/// Publishes the tick a run has reached for status polls
+struct Progress(Arc<Simulation>);
+
+impl SimulationHooks for Progress {
+    fn on_tick_end(&mut self, view: &TickView<'_>) {
+        self.0.tick.store(view.tick, Ordering::Relaxed);
+    }
+}
+
+/// Uploaded maps, by id (their index), and requested simulations, by id.
+/// Maps stay for the life of the server; simulation ids are never reused,
+/// but the oldest finished ones are dropped past `keep_finished`
+#[derive(Default)]
+struct Registry {
+    maps: Vec<Arc<World>>,
+    simulations: BTreeMap<usize, Arc<Simulation>>,
+    next_simulation: usize,
+}
+
+impl Registry {
+    /// Add `simulation` under a fresh id, dropping the oldest finished
+    /// simulations beyond `keep_finished`
+    fn insert(&mut self, simulation: Arc<Simulation>, keep_finished: usize) -> usize {
+        let finished: Vec<usize> =
+            self.simulations.iter().filter(|(_, sim)| sim.is_over()).map(|(&id, _)| id).collect();
+        for id in &finished[..finished.len().saturating_sub(keep_finished)] {
+            self.simulations.remove(id);
+        }
+        let id = self.next_simulation;
+        self.next_simulation += 1;
+        self.simulations.insert(id, simulation);
+        id
+    }
+}
+
+#[derive(Clone)]
+struct AppState {
+    options: ServerOptions,
+    registry: Arc<Mutex<Registry>>,
+    running: Arc<Semaphore>,
+}
+
+impl AppState {
+    /// The registry, even if a handler panicked while holding it: every
+    /// change to it is a single push or insert, so it is never left half-done
+    fn registry(&self) -> MutexGuard<'_, Registry> {
+        self.registry.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+/// An error reply: `{"error": "..."}` with `status`
+struct ApiError(StatusCode, String);
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        (self.0, Json(serde_json::json!({ "error": self.1 }))).into_response()
+    }
+}
+
+/// Routes of the service:
+///
+/// - `POST /maps`: upload a map (the body, in map file format) → `201` [`MapCreated`]
+/// - `POST /simulations`: start a [`SimulationRequest`] in the background → `202` [`SimulationStatus`]
+/// - `GET /simulations/{id}`: poll its [`SimulationStatus`]
+/// - `GET /simulations/{id}/result`: its [`Report`] once done, `409` while it runs
+///
+/// Errors come back as `{"error": "..."}` with a 4xx status, `503` when
+/// `max_running` simulations are already running and `500` for a run that
+/// panicked.
+pub fn router(options: ServerOptions) -> Router {
+    let state = AppState { options, registry: Arc::default(), running: Arc::new(Semaphore::new(options.max_running)) };
+    Router::new()
+        .route("/maps", post(upload_map))
+        .route("/simulations", post(start_simulation))
+        .route("/simulations/{id}", get(simulation_status))
+        .route("/simulations/{id}/result", get(simulation_result))
+        .with_state(state)
+}
+
+/// Serve [`router`] on `listener` until the process ends
+pub async fn serve(listener: tokio::net::TcpListener, options: ServerOptions) -> std::io::Result<()> {
+    axum::serve(listener, router(options)).await
+}
+
+/// Run `request` on a copy of `world` the way a seeded CLI run would, with
+/// events suppressed
+pub fn run(world: &World, request: &SimulationRequest, hooks: impl SimulationHooks + Send + 'static) -> Report {
+    let mut args = Args::with_defaults(request.ants, "upload");
+    args.seed = Some(request.seed);
+    args.suppress_events = true;
+    if let Some(max_moves) = request.max_moves {
+        args.max_moves = max_moves;
+    }
+    let mut world = world.clone();
+    let seeds = SubSeeds::derive(request.seed);
+    let mut ants: Vec<Ant> =
+        world.place_colony(args.ants, args.queen_count(), args.placement, &mut seeds.placement_rng());
+    let mut engine = SimulationEngine::new(&world, args.ants).with_seeds(seeds).with_hooks(hooks);
+    let latency = engine.run_simulation(&mut world, &mut ants, &args, &mut seeds.movement_rng());
+    Report::new(&world, &ants, &args, &engine, latency)
+}
+
+async fn upload_map(State(state): State<AppState>, body: String) -> Result<(StatusCode, Json<MapCreated>), ApiError> {
+    let world = load_world_from_str(&body, &ParseOptions::default())
+        .map_err(|err| ApiError(StatusCode::BAD_REQUEST, err.to_string()))?;
+    let colonies = world.nodes.len();
+    let mut registry = state.registry();
+    registry.maps.push(Arc::new(world));
+    Ok((StatusCode::CREATED, Json(MapCreated { id: registry.maps.len() - 1, colonies })))
+}
+
+async fn start_simulation(
+    State(state): State<AppState>,
+    Json(request): Json<SimulationRequest>,
+) -> Result<(StatusCode, Json<SimulationStatus>), ApiError> {
+    if request.ants > state.options.max_ants {
+        return Err(ApiError(
+            StatusCode::BAD_REQUEST,
+            format!("{} ants requested, this server allows at most {}", request.ants, state.options.max_ants),
+        ));
+    }
+    let (id, world, simulation, permit) = {
+        let mut registry = state.registry();
+        let world = registry
+            .maps
+            .get(request.map)
+            .cloned()
+            .ok_or_else(|| ApiError(StatusCode::NOT_FOUND, format!("no map {}", request.map)))?;
+        let permit = Arc::clone(&state.running).try_acquire_owned().map_err(|_| {
+            ApiError(
+                StatusCode::SERVICE_UNAVAILABLE,
+                format!("{} simulations are already running, try again later", state.options.max_running),
+            )
+        })?;
+        let simulation = Arc::new(Simulation::new(request));
+        let id = registry.insert(Arc::clone(&simulation), state.options.keep_finished);
+        (id, world, simulation, permit)
+    };
+    let status = simulation.status(id);
+    tokio::spawn(async move {
+        let progress = Progress(Arc::clone(&simulation));
+        let request = simulation.request.clone();
+        simulation.run_to_end(move || run(&world, &request, progress), permit).await;
+    });
+    Ok((StatusCode::ACCEPTED, Json(status)))
+}
+
+fn find(state: &AppState, id: usize) -> Result<Arc<Simulation>, ApiError> {
+    state
+        .registry()
+        .simulations
+        .get(&id)
+        .cloned()
+        .ok_or_else(|| ApiError(StatusCode::NOT_FOUND, format!("no simulation {id}")))
+}
+
+async fn simulation_status(
+    State(state): State<AppState>,
+    Path(id): Path<usize>,
+) -> Result<Json<SimulationStatus>, ApiError> {
+    Ok(Json(find(&state, id)?.status(id)))
+}
+
+async fn simulation_result(State(state): State<AppState>, Path(id): Path<usize>) -> Result<Json<Report>, ApiError> {
+    match find(&state, id)?.outcome.get() {
+        Some(Ok(report)) => Ok(Json(report.clone())),
+        Some(Err(err)) => Err(ApiError(StatusCode::INTERNAL_SERVER_ERROR, format!("simulation {id} failed: {err}"))),
+        None => Err(ApiError(StatusCode::CONFLICT, format!("simulation {id} is still running"))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_run_is_seeded_and_reports_progress() {
+        let world = load_world_from_str("A east=B\nB west=A east=C\nC west=B\n", &ParseOptions::default()).unwrap();
+        let request = SimulationRequest { map: 0, ants: 4, seed: 9, max_moves: Some(30) };
+        let simulation = Arc::new(Simulation::new(request.clone()));
+
+        let report = run(&world, &request, Progress(Arc::clone(&simulation)));
+        assert_eq!(report.seed, Some(9));
+        assert_eq!(report.max_moves, 30);
+        assert_eq!(simulation.tick.load(Ordering::Relaxed), report.ticks);
+        let again = run(&world, &request, Progress(simulation));
+        assert_eq!((again.ticks, again.survivors), (report.ticks, report.survivors));
+        // The uploaded map is left untouched for later runs
+        assert_eq!(world.count_survivors(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_a_panicking_run_is_failed_and_frees_its_slot() {
+        let running = Arc::new(Semaphore::new(1));
+        let request = SimulationRequest { map: 0, ants: 1, seed: 1, max_moves: None };
+        let simulation = Simulation::new(request);
+        let permit = Arc::clone(&running).try_acquire_owned().unwrap();
+        assert!(Arc::clone(&running).try_acquire_owned().is_err());
+
+        simulation.run_to_end(|| panic!("boom"), permit).await;
+        let status = simulation.status(0);
+        assert_eq!(status.state, RunState::Failed);
+        assert_eq!(status.error.as_deref(), Some("the simulation panicked"));
+        assert_eq!(running.available_permits(), 1);
+    }
+
+    #[test]
+    fn test_registry_drops_the_oldest_finished_simulations() {
+        let request = SimulationRequest { map: 0, ants: 1, seed: 1, max_moves: None };
+        let mut registry = Registry::default();
+        for _ in 0..3 {
+            let simulation = Arc::new(Simulation::new(request.clone()));
+            let _ = simulation.outcome.set(Err("over".to_string()));
+            registry.insert(simulation, 2);
+        }
+        let running = registry.insert(Arc::new(Simulation::new(request.clone())), 2);
+        let last = registry.insert(Arc::new(Simulation::new(request)), 2);
+        // Three finished, two kept: id 0 goes, running ones always stay
+        assert_eq!(registry.simulations.keys().copied().collect::<Vec<_>>(), [1, 2, running, last]);
+        assert_eq!(last, 4);
+    }
+}
@@ -0,0 +1,174 @@
+use crate::ant::Ant;
+use crate::cli::Args;
+use crate::simulation::engine::{RunState, SimulationEngine};
+use crate::simulation::event::DestructionEvent;
+use crate::simulation::ticks::TickDelta;
+use crate::world::World;
+use futures_core::Stream;
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+/// One item of a [`run_stream`]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case", tag = "type")]
+pub enum SimulationEvent {
+    /// An event recorded by the engine (t=0 included); averted fights, near
+    /// misses and repelled ants come through here too, see
+    /// [`DestructionCause::destroyed`](crate::simulation::DestructionCause::destroyed)
+    Destruction(DestructionEvent),
+    /// What changed during a tick (t=0 included), after its events; only
+    /// from a stream made [`with_deltas`](RunStream::with_deltas)
+    Delta(TickDelta),
+    /// A tick has finished, after its events
+    Tick { tick: u64, ants_alive: usize },
+    /// The run is over; always the last item
+    Finished { ticks: u64, survivors: usize, interrupted: bool },
+}
+
+/// Run a simulation as a [`Stream`] of [`SimulationEvent`]s.
+///
+/// Nothing happens until the stream is first polled; each poll then runs
+/// ticks until an event is ready. After `yield_every` ticks in a row
+/// (at least 1) the stream returns `Pending` once and wakes itself, so a
+/// long run shares its executor thread with other tasks instead of holding
+/// it to the end. The run follows `args` as [`SimulationEngine::iter_ticks`]
+/// does, event log included: set `args.suppress_events` to keep stdout
+/// quiet. Dropping the stream early abandons the run.
+pub fn run_stream(
+    engine: SimulationEngine,
+    world: World,
+    ants: Vec<Ant>,
+    args: Args,
+    rng: fastrand::Rng,
+    yield_every: u32,
+) -> RunStream {
+    RunStream {
+        engine,
+        world,
+        ants,
+        args,
+        rng,
+        yield_every: yield_every.max(1),
+        since_yield: 0,
+        state: None,
+        queue: VecDeque::new(),
+        done: false,
+        deltas: false,
+    }
+}
+
+/// Stream returned by [`run_stream`]; it owns the run, which stays
+/// readable through [`RunStream::engine`], [`RunStream::world`] and
+/// [`RunStream::ants`] once the stream has ended
+pub struct RunStream {
+    engine: SimulationEngine,
+    world: World,
+    ants: Vec<Ant>,
+    args: Args,
+    rng: fastrand::Rng,
+    yield_every: u32,
+    /// Ticks run since the stream last returned `Pending`
+    since_yield: u32,
+    /// `None` until the first poll starts the run
+    state: Option<RunState>,
+    /// Events of the last tick not handed out yet
+    queue: VecDeque<SimulationEvent>,
+    /// Set once the run is over and `Finished` is queued
+    done: bool,
+    /// Queue a `Delta` after each tick's events
+    deltas: bool,
+}
+
+impl RunStream {
+    /// Also yield a [`SimulationEvent::Delta`] for t=0 and after every
+    /// tick's events, so a client can follow the map and the ants without
+    /// asking for snapshots
+    pub fn with_deltas(mut self) -> Self {
+        self.deltas = true;
+        self
+    }
+
+    pub fn engine(&self) -> &SimulationEngine {
+        &self.engine
+    }
+
+    pub fn world(&self) -> &World {
+        &self.world
+    }
+
+    pub fn ants(&self) -> &[Ant] {
+        &self.ants
+    }
+
+    /// Start the run, or run its next tick, queueing what happened
+    fn advance(&mut self) {
+        let Some(state) = self.state.as_mut() else {
+            if self.deltas {
+                self.engine.track_deltas();
+            }
+            self.engine.begin(&mut self.world, &mut self.ants, &self.args, &mut self.rng);
+            self.state = Some(self.engine.run_state(&self.world, &self.ants, &self.args));
+            self.queue_tick_events();
+            return;
+        };
+        if self.engine.step(state, &mut self.world, &mut self.ants, &self.args, &mut self.rng) {
+            self.since_yield += 1;
+            self.queue_tick_events();
+            self.queue.push_back(SimulationEvent::Tick {
+                tick: self.engine.ticks(),
+                ants_alive: self.engine.ants_alive(),
+            });
+        } else {
+            self.engine.finish(state);
+            self.done = true;
+            if let Some(delta) = self.deltas.then(|| self.engine.take_final_delta()).flatten() {
+                self.queue.push_back(SimulationEvent::Delta(delta));
+            }
+            self.queue.push_back(SimulationEvent::Finished {
+                ticks: self.engine.ticks(),
+                survivors: self.world.count_survivors(),
+                interrupted: self.engine.interrupted(),
+            });
+        }
+    }
+
+    fn queue_tick_events(&mut self) {
+        let events = self.engine.tick_events().iter().map(|&e| SimulationEvent::Destruction(e));
+        self.queue.extend(events);
+        if self.deltas {
+            self.queue.push_back(SimulationEvent::Delta(self.engine.take_delta()));
+        }
+    }
+}
+
+impl Stream for RunStream {
+    type Item = SimulationEvent;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<SimulationEvent>> {
+        let this = self.get_mut();
+        loop {
+            if let Some(event) = this.queue.pop_front() {
+                return Poll::Ready(Some(event));
+            }
+            if this.done {
+                return Poll::Ready(None);
+            }
+            if this.since_yield >= this.yield_every {
+                this.since_yield = 0;
+                cx.waker().wake_by_ref();
+                return Poll::Pending;
+            }
+            this.advance();
+        }
+    }
+}
+
+impl Drop for RunStream {
+    fn drop(&mut self) {
+        if let (Some(state), false) = (self.state.as_mut(), self.done) {
+            self.engine.finish(state);
+        }
+    }
+}
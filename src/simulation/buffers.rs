@@ -0,0 +1,139 @@
+use crate::ant::Ant;
+use crate::node_id::NodeId;
+
+/// Scratch memory of a run, allocated once and reused.
+///
+/// Every per-colony and per-ant array the engine needs lives here. Each run
+/// only clears and refills them, so after the first run on a map of a given
+/// size the engine stops allocating. Hand one arena from run to run with
+/// [`SimulationEngine::with_buffers`](crate::simulation::SimulationEngine::with_buffers)
+/// and [`SimulationEngine::into_buffers`](crate::simulation::SimulationEngine::into_buffers).
+#[derive(Debug, Default)]
+pub struct SimBuffers {
+    // Per colony
+    pub(crate) gen: Vec<u32>,
+    pub(crate) occ_count: Vec<u32>,
+    pub(crate) occ_first: Vec<u32>,
+    pub(crate) occ_second: Vec<u32>,
+    /// Id of the last active ant counted on each colony this tick
+    pub(crate) occ_arrival: Vec<u32>,
+    pub(crate) base_occ: Vec<u32>,
+    pub(crate) base_first: Vec<u32>,
+    pub(crate) base_second: Vec<u32>,
+    pub(crate) averted_gen: Vec<u32>,
+    /// Ant index holding each colony (sequential model, `u32::MAX` if free)
+    pub(crate) occupant: Vec<u32>,
+    /// Colonies destroyed at t=0
+    pub(crate) destroyed: Vec<bool>,
+    // Touched lists
+    pub(crate) touched_nodes: Vec<usize>,
+    pub(crate) base_touched: Vec<usize>,
+    pub(crate) blasted: Vec<usize>,
+    // Per ant
+    pub(crate) active: Vec<usize>,
+    pub(crate) next_pos: Vec<NodeId>,
+    pub(crate) trapped_now: Vec<bool>,
+}
+
+impl SimBuffers {
+    /// Empty arena; the first run sizes it
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Arena pre-sized for `nodes` colonies and `ants` ants
+    pub fn with_capacity(nodes: usize, ants: usize) -> Self {
+        Self {
+            gen: Vec::with_capacity(nodes),
+            occ_count: Vec::with_capacity(nodes),
+            occ_first: Vec::with_capacity(nodes),
+            occ_second: Vec::with_capacity(nodes),
+            occ_arrival: Vec::with_capacity(nodes),
+            base_occ: Vec::with_capacity(nodes),
+            base_first: Vec::with_capacity(nodes),
+            base_second: Vec::with_capacity(nodes),
+            averted_gen: Vec::with_capacity(nodes),
+            occupant: Vec::with_capacity(nodes),
+            destroyed: Vec::with_capacity(nodes),
+            touched_nodes: Vec::with_capacity(4096),
+            base_touched: Vec::with_capacity(1024),
+            blasted: Vec::new(),
+            active: Vec::with_capacity(ants),
+            next_pos: Vec::with_capacity(ants),
+            trapped_now: Vec::with_capacity(ants),
+        }
+    }
+
+    /// Size the arena for `nodes` colonies and `ants` ants and write every
+    /// slot once, so its pages are mapped before a timed run
+    pub fn touch(&mut self, nodes: usize, ants: usize) {
+        for v in [
+            &mut self.gen,
+            &mut self.occ_count,
+            &mut self.occ_first,
+            &mut self.occ_second,
+            &mut self.occ_arrival,
+            &mut self.base_occ,
+            &mut self.base_first,
+            &mut self.base_second,
+            &mut self.averted_gen,
+            &mut self.occupant,
+        ] {
+            refill(v, nodes, 0);
+        }
+        refill(&mut self.destroyed, nodes, false);
+        refill(&mut self.active, ants, 0);
+        refill(&mut self.next_pos, ants, NodeId::from_index(0));
+        refill(&mut self.trapped_now, ants, false);
+    }
+
+    /// Colonies the arena can hold without reallocating
+    pub fn node_capacity(&self) -> usize {
+        self.gen.capacity()
+    }
+
+    /// Clear everything and size it for a run over `nodes` colonies and `ants`
+    pub(crate) fn reset(&mut self, nodes: usize, ants: &[Ant]) {
+        for v in [
+            &mut self.gen,
+            &mut self.occ_count,
+            &mut self.base_occ,
+            &mut self.averted_gen,
+        ] {
+            refill(v, nodes, 0);
+        }
+        for v in [
+            &mut self.occ_first,
+            &mut self.occ_second,
+            &mut self.occ_arrival,
+            &mut self.base_first,
+            &mut self.base_second,
+            &mut self.occupant,
+        ] {
+            refill(v, nodes, u32::MAX);
+        }
+        refill(&mut self.destroyed, nodes, false);
+        self.touched_nodes.clear();
+        self.base_touched.clear();
+        self.blasted.clear();
+
+        // Ants that may still move: alive, free and below their move limit
+        self.active.clear();
+        self.active.extend(
+            ants.iter()
+                .enumerate()
+                .filter(|(_, a)| a.is_alive() && !a.is_trapped() && !a.has_max_moves())
+                .map(|(i, _)| i),
+        );
+        self.next_pos.clear();
+        self.next_pos.extend(ants.iter().map(|a| a.pos));
+        refill(&mut self.trapped_now, ants.len(), false);
+    }
+}
+
+/// Overwrite `v` with `len` copies of `value`, keeping its allocation
+#[inline]
+fn refill<T: Copy>(v: &mut Vec<T>, len: usize, value: T) {
+    v.clear();
+    v.resize(len, value);
+}
@@ -0,0 +1,299 @@
+use crate::ant::Ant;
+use crate::cli::Args;
+use crate::simulation::engine::SimulationEngine;
+use crate::simulation::engine::RunState;
+use crate::world::{load_world_from_paths, World};
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::str::FromStr;
+use std::sync::mpsc::{Receiver, RecvTimeoutError};
+#[cfg(feature = "watch")]
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc,
+};
+use std::time::{Duration, Instant};
+
+/// How often a paused run with a map watch looks at it between commands
+const WATCH_POLL: Duration = Duration::from_millis(100);
+
+/// A command typed on stdin during an interactive run
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ControlCommand {
+    /// Stop ticking until `resume` or `step`
+    Pause,
+    /// Run freely until the end (or the next `pause`)
+    Resume,
+    /// Run this many ticks, then pause
+    Step(u64),
+    /// Write the current world, in map format, to a file
+    Dump(String),
+    /// Re-read the map file, keeping ants on their colonies by name
+    Reload,
+    /// Abandon the run; it ends as interrupted
+    Quit,
+}
+
+impl FromStr for ControlCommand {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut words = s.split_whitespace();
+        let command = match (words.next(), words.next()) {
+            (Some("pause"), None) => ControlCommand::Pause,
+            (Some("resume"), None) => ControlCommand::Resume,
+            (Some("step"), None) => ControlCommand::Step(1),
+            (Some("step"), Some(n)) => {
+                let n: u64 = n.parse().map_err(|_| format!("invalid tick count '{n}'"))?;
+                ControlCommand::Step(n)
+            }
+            (Some("dump"), Some(path)) => ControlCommand::Dump(path.to_string()),
+            (Some("reload"), None) => ControlCommand::Reload,
+            (Some("quit"), None) => ControlCommand::Quit,
+            _ => return Err(format!("unknown command '{}' (pause, resume, step [N], dump FILE, reload, quit)", s.trim())),
+        };
+        if words.next().is_some() {
+            return Err(format!("too many arguments in '{}'", s.trim()));
+        }
+        Ok(command)
+    }
+}
+
+impl SimulationEngine {
+    /// Run a simulation driven by text commands, one per `commands` line.
+    ///
+    /// The run starts paused at tick 0. While paused the loop blocks on the
+    /// next command; after `resume` it picks up commands between ticks, and
+    /// `step N` runs its N ticks before reading the next one.
+    /// Status lines and command errors go to `status`. A closed channel
+    /// counts as `quit` once nothing is left to run. Returns the time spent
+    /// ticking, so pauses do not count as latency.
+    ///
+    /// `reload` (or a change reported through [`with_map_watch`](Self::with_map_watch),
+    /// between ticks and while paused) re-reads `args.map` and carries the
+    /// run over to it; a map that fails to parse leaves the run as it was.
+    pub fn run_interactive<W: Write>(
+        &mut self,
+        world: &mut World,
+        ants: &mut Vec<Ant>,
+        args: &Args,
+        rng: &mut fastrand::Rng,
+        commands: &Receiver<String>,
+        status: &mut W,
+    ) -> Duration {
+        self.begin(world, ants, args, rng);
+        let mut state = self.run_state(world, ants, args);
+        let mut running = false;
+        // Ticks left before pausing again (`step N`)
+        let mut budget: Option<u64> = None;
+        let mut busy = Duration::ZERO;
+        let _ = writeln!(status, "⏸️  paused at tick 0 (pause, resume, step [N], dump FILE, reload, quit)");
+
+        loop {
+            if self.map_changed() {
+                self.reload(&mut state, world, ants, args, status);
+            }
+            // A `step N` burst runs to completion before the next command
+            let line = if budget.is_some() {
+                None
+            } else if running {
+                // Nothing typed (or stdin closed): keep running
+                commands.try_recv().ok()
+            } else if self.watches_map() {
+                // Wake up now and then to pick up map changes
+                match commands.recv_timeout(WATCH_POLL) {
+                    Ok(line) => Some(line),
+                    Err(RecvTimeoutError::Timeout) => continue,
+                    Err(RecvTimeoutError::Disconnected) => {
+                        self.abort();
+                        break;
+                    }
+                }
+            } else {
+                match commands.recv() {
+                    Ok(line) => Some(line),
+                    Err(_) => {
+                        self.abort();
+                        break;
+                    }
+                }
+            };
+
+            if let Some(line) = line {
+                if line.trim().is_empty() {
+                    continue;
+                }
+                match line.parse::<ControlCommand>() {
+                    Ok(ControlCommand::Pause) => {
+                        running = false;
+                        budget = None;
+                        let _ = writeln!(status, "⏸️  paused at tick {}", self.ticks());
+                    }
+                    Ok(ControlCommand::Resume) => {
+                        running = true;
+                        budget = None;
+                    }
+                    Ok(ControlCommand::Step(0)) => {}
+                    Ok(ControlCommand::Step(n)) => {
+                        running = false;
+                        budget = Some(n);
+                    }
+                    Ok(ControlCommand::Dump(path)) => match dump(world, &path) {
+                        Ok(()) => {
+                            let _ = writeln!(status, "💾 world at tick {} written to {}", self.ticks(), path);
+                        }
+                        Err(err) => {
+                            let _ = writeln!(status, "error: cannot write {}: {}", path, err);
+                        }
+                    },
+                    Ok(ControlCommand::Reload) => self.reload(&mut state, world, ants, args, status),
+                    Ok(ControlCommand::Quit) => {
+                        self.abort();
+                        break;
+                    }
+                    Err(msg) => {
+                        let _ = writeln!(status, "error: {}", msg);
+                    }
+                }
+                continue;
+            }
+
+            let start = Instant::now();
+            let ticked = self.step(&mut state, world, ants, args, rng);
+            busy += start.elapsed();
+            if !ticked {
+                let _ = writeln!(status, "🏁 run finished after {} ticks", self.ticks());
+                break;
+            }
+            if let Some(left) = budget.as_mut() {
+                *left -= 1;
+                if *left == 0 {
+                    budget = None;
+                    let _ = writeln!(status, "⏸️  paused at tick {}", self.ticks());
+                }
+            }
+        }
+
+        self.finish(&mut state);
+        busy
+    }
+
+    /// Re-read `args.map` and move the run onto it, reporting on `status`
+    fn reload<W: Write>(
+        &mut self,
+        state: &mut RunState,
+        world: &mut World,
+        ants: &mut [Ant],
+        args: &Args,
+        status: &mut W,
+    ) {
+        match load_world_from_paths(&args.map, &args.parse_options()) {
+            Ok(next) => {
+                let (kept, lost) = self.switch_world(state, world, ants, args, next);
+                let _ = writeln!(
+                    status,
+                    "🔄 map reloaded at tick {}: {} colonies, {} ants kept, {} lost",
+                    self.ticks(),
+                    world.nodes.len(),
+                    kept,
+                    lost
+                );
+            }
+            Err(err) => {
+                let _ = writeln!(status, "error: cannot reload {}: {}", args.map_label(), err);
+            }
+        }
+    }
+}
+
+/// Watches the map files for `--watch-map`, raising a flag for
+/// [`SimulationEngine::with_map_watch`] whenever one is written or
+/// replaced; the watch ends when this is dropped
+#[cfg(feature = "watch")]
+pub struct MapWatch {
+    flag: Arc<AtomicBool>,
+    _watchers: Vec<notify::RecommendedWatcher>,
+}
+
+#[cfg(feature = "watch")]
+impl MapWatch {
+    /// Start watching `paths`, as given to `--map`. The directory of a file
+    /// is watched rather than the file itself, so editors that save by
+    /// replacing the file are still seen; a directory of fragments raises
+    /// the flag for any visible file in it.
+    pub fn start<S: AsRef<str>>(paths: &[S]) -> Result<Self, String> {
+        let flag = Arc::new(AtomicBool::new(false));
+        let watchers = paths
+            .iter()
+            .map(|path| Self::watch(std::path::Path::new(path.as_ref()), Arc::clone(&flag)))
+            .collect::<Result<_, _>>()?;
+        Ok(Self { flag, _watchers: watchers })
+    }
+
+    fn watch(path: &std::path::Path, raised: Arc<AtomicBool>) -> Result<notify::RecommendedWatcher, String> {
+        use notify::{EventKind, RecursiveMode, Watcher};
+        // `None` for a directory: every file in it counts
+        let (dir, name) = if path.is_dir() {
+            (path, None)
+        } else {
+            let name = path.file_name().ok_or_else(|| format!("cannot watch {}: not a file", path.display()))?;
+            match path.parent() {
+                Some(dir) if !dir.as_os_str().is_empty() => (dir, Some(name.to_owned())),
+                _ => (std::path::Path::new("."), Some(name.to_owned())),
+            }
+        };
+        let mut watcher = notify::recommended_watcher(move |event: notify::Result<notify::Event>| {
+            let Ok(event) = event else { return };
+            let watched = |p: &std::path::PathBuf| match (&name, p.file_name()) {
+                (Some(name), file) => file == Some(name.as_os_str()),
+                (None, Some(file)) => !file.to_string_lossy().starts_with('.'),
+                (None, None) => false,
+            };
+            if matches!(event.kind, EventKind::Create(_) | EventKind::Modify(_)) && event.paths.iter().any(watched) {
+                raised.store(true, Ordering::Relaxed);
+            }
+        })
+        .map_err(|err| format!("cannot watch {}: {}", path.display(), err))?;
+        watcher
+            .watch(dir, RecursiveMode::NonRecursive)
+            .map_err(|err| format!("cannot watch {}: {}", path.display(), err))?;
+        Ok(watcher)
+    }
+
+    /// The flag to hand to [`SimulationEngine::with_map_watch`]
+    pub fn flag(&self) -> Arc<AtomicBool> {
+        Arc::clone(&self.flag)
+    }
+}
+
+/// Write the current world to `path` in map format
+fn dump(world: &World, path: &str) -> std::io::Result<()> {
+    let mut out = BufWriter::new(File::create(path)?);
+    world.write_map(&mut out)?;
+    out.flush()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_commands() {
+        assert_eq!("pause".parse(), Ok(ControlCommand::Pause));
+        assert_eq!(" resume ".parse(), Ok(ControlCommand::Resume));
+        assert_eq!("step".parse(), Ok(ControlCommand::Step(1)));
+        assert_eq!("step 10".parse(), Ok(ControlCommand::Step(10)));
+        assert_eq!("dump world.txt".parse(), Ok(ControlCommand::Dump("world.txt".to_string())));
+        assert_eq!("reload".parse(), Ok(ControlCommand::Reload));
+        assert_eq!("quit".parse(), Ok(ControlCommand::Quit));
+    }
+
+    #[test]
+    fn test_rejects_bad_commands() {
+        assert!("step ten".parse::<ControlCommand>().is_err());
+        assert!("dump".parse::<ControlCommand>().is_err());
+        assert!("pause now".parse::<ControlCommand>().is_err());
+        assert!("reload map.txt".parse::<ControlCommand>().is_err());
+        assert!("jump".parse::<ControlCommand>().is_err());
+    }
+}
@@ -1,20 +1,505 @@
-use crate::ant::Ant;
-use crate::cli::Args;
-use crate::world::World;
-use colored::Colorize;
-use std::time::Instant;
+use crate::ant::{Ant, AntKind, DeathReason};
+use crate::ant_names::AntNames;
+use crate::cli::{Args, Chatter, Scheduling, TrappedPolicy};
+use crate::error::SimulationError;
+use crate::limits::{self, Limit};
+use crate::simulation::buffers::SimBuffers;
+use crate::simulation::event::{DestructionCause, DestructionEvent};
+use crate::simulation::homing::Homing;
+use crate::simulation::hooks::{SimulationHooks, TickView};
+use crate::simulation::log::EventLog;
+use crate::simulation::messages::MessageTemplates;
+use crate::simulation::movement::MoveStrategy;
+use crate::simulation::phases::{DecisionPhase, Phase, TickContext, RESOLVE};
+use crate::simulation::reinforcements::Reinforcement;
+use crate::simulation::rules::{DestructionRule, Occupancy, StopCondition};
+use crate::simulation::ticks::{TickDelta, TickDeltas, Ticks};
+use crate::node_id::NodeId;
+#[cfg(feature = "cli")]
+use crate::report::Report;
+use crate::report::StartCasualties;
+#[cfg(feature = "cli")]
+use crate::report_writer::{ReportWriter, TextWriter};
+use crate::seeds::SubSeeds;
+use crate::strategy::{DirectionBias, Strategy};
+use crate::world::parser::{FOOD_TAG, FORTIFIED_TAG};
+use crate::world::{World, WorldView};
+#[cfg(feature = "cli")]
+use std::io::Write;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// Alive colonies per ant below which a crowded world is handled per
+/// colony: the t=0 collisions sweep every colony instead of sorting the
+/// occupied ones, and a tick's occupancy resets every colony at once
+/// instead of stamping each as an ant reaches it
+pub(crate) const DENSE_RATIO: usize = 4;
+
+/// Per-run state of the tick-synchronous model
+pub(crate) struct SimultaneousState {
+    /// Set once the run is over
+    done: bool,
+    // Per-node "generation" trick avoids clearing large arrays (same as original)
+    cur_gen: u32,
+    bufs: SimBuffers,
+}
+
+/// Per-run state of the sequential model
+pub(crate) struct SequentialState {
+    done: bool,
+    bufs: SimBuffers,
+}
+
+/// State of a run in progress, for whichever scheduling model it uses
+pub(crate) enum RunState {
+    Simultaneous(SimultaneousState),
+    Sequential(SequentialState),
+}
+
+impl RunState {
+    /// Move the scratch arena out, leaving an empty one behind
+    fn take_buffers(&mut self) -> SimBuffers {
+        match self {
+            RunState::Simultaneous(st) => std::mem::take(&mut st.bufs),
+            RunState::Sequential(st) => std::mem::take(&mut st.bufs),
+        }
+    }
+
+    /// Let a run that ran out of moving ants go on (someone arrived)
+    fn resume(&mut self) {
+        match self {
+            RunState::Simultaneous(st) => st.done = false,
+            RunState::Sequential(st) => st.done = false,
+        }
+    }
+
+    /// Whether the run has ended (or has no moving ant left)
+    fn is_done(&self) -> bool {
+        match self {
+            RunState::Simultaneous(st) => st.done || st.bufs.active.is_empty(),
+            RunState::Sequential(st) => st.done || st.bufs.active.is_empty(),
+        }
+    }
+
+    fn bufs_mut(&mut self) -> &mut SimBuffers {
+        match self {
+            RunState::Simultaneous(st) => &mut st.bufs,
+            RunState::Sequential(st) => &mut st.bufs,
+        }
+    }
+}
 
 /// Main simulation engine that coordinates the ant simulation
 /// Optimized to match original performance while maintaining modularity
-pub struct SimulationEngine;
+pub struct SimulationEngine {
+    /// Current tick (0 = initial placement)
+    tick: u64,
+    /// Destructions of the last run, in order (at most `event_limit`)
+    events: Vec<DestructionEvent>,
+    /// Cap on retained events; later ones are counted in `events_dropped`
+    event_limit: Option<usize>,
+    events_dropped: usize,
+    /// External stop request (e.g. Ctrl-C), polled once per tick
+    interrupt: Option<Arc<AtomicBool>>,
+    /// Set when the map file of an interactive run changes on disk
+    map_changed: Option<Arc<AtomicBool>>,
+    /// Whether the last run stopped early because of `interrupt`
+    interrupted: bool,
+    /// Buffered event output, open only while a run is logging
+    log: EventLog,
+    /// Time spent sleeping for `--tick-delay-ms`, excluded from latency
+    paused: Duration,
+    /// Per-colony placements + arrivals, tracked only for heatmaps
+    visits: Vec<u32>,
+    /// Fights each ant (by id) took part in, tracked only for `--top-killers`
+    kills: Vec<u32>,
+    /// Whether fights are credited to their participants this run
+    track_kills: bool,
+    /// Destructions of the current tick, for tick snapshots
+    tick_events: Vec<DestructionEvent>,
+    /// Stream seeds of the run, when the caller derived them
+    seeds: Option<SubSeeds>,
+    /// Draws move limits for initial ants and newborns
+    spawn_rng: fastrand::Rng,
+    /// Colonies fallen so far this tick, for `--max-destructions-per-tick`
+    fallen: u32,
+    /// Scratch arrays, kept between runs
+    buffers: SimBuffers,
+    /// Decides whether meeting ants fight under `--collision-prob`
+    collision_rng: fastrand::Rng,
+    /// Meetings of the last run that did not turn into fights
+    near_misses: usize,
+    /// Colonies tagged `fortified` in the map (empty when none is)
+    fortified: Vec<bool>,
+    /// Decides whether a fortified colony kills a lone arrival under `--fortress-defense`
+    defense_rng: fastrand::Rng,
+    /// Lone ants killed by fortified colonies in the last run
+    repelled: usize,
+    /// Decides which standing colonies rebuild a neighbor under `--revival-prob`
+    revival_rng: fastrand::Rng,
+    /// Colonies rebuilt in the last run
+    revived: usize,
+    /// Decides when and where meteors strike under `--chaos`
+    chaos_rng: fastrand::Rng,
+    /// Colonies meteors destroyed in the last run
+    meteors: usize,
+    /// Colonies mega ants trampled in the last run
+    trampled: usize,
+    /// What t=0 cost in the last run
+    start: StartCasualties,
+    /// Energy ants start with (and `food` refills) under `--energy`
+    energy: Option<u32>,
+    /// Colonies tagged `food` in the map, tracked only under `--energy`
+    food: Vec<bool>,
+    /// Tick of the last colony destruction in the last run
+    last_destruction: Option<u64>,
+    /// Tick of every colony destruction in the last run, in order
+    destruction_ticks: Vec<u64>,
+    /// Alive ants of the current run, kept up to date at every death and
+    /// birth so the per-tick early-exit check does not scan `ants`
+    ants_alive: usize,
+    /// When the current run began, for `--max-wall-time`
+    started: Instant,
+    /// Resource limit that stopped the last run, if any
+    limit_exceeded: Option<Limit>,
+    /// Display names for ants in logs and reports
+    ant_names: Option<AntNames>,
+    /// Observer callbacks, if any
+    hooks: Option<Box<dyn SimulationHooks + Send>>,
+    /// Custom rule for which meetings destroy a colony (every one without it)
+    destruction_rule: Option<Box<dyn DestructionRule + Send>>,
+    /// Hold each tick's events back and emit them sorted by colony id
+    stable_order: bool,
+    /// Events held back under `stable_order` until the tick ends
+    pending: Vec<DestructionEvent>,
+    /// End the run once at most one ant is alive (off when ants can arrive
+    /// from outside, as in a [`Multiverse`](crate::simulation::Multiverse))
+    lone_exit: bool,
+    /// Custom movement, replacing `--strategy` and `--bias` when set
+    move_strategy: Option<Box<dyn MoveStrategy + Send>>,
+    /// Custom early end, asked before every tick
+    stop_condition: Option<Box<dyn StopCondition + Send>>,
+    /// Changes of the current tick, gathered only while a run hands out
+    /// [`TickDelta`]s
+    delta: Option<TickDelta>,
+    /// Gather deltas in the next run, set before it begins
+    want_deltas: bool,
+    /// Shortest-path state for `--strategy homing:H`, if selected
+    homing: Option<Homing>,
+    /// Direction weights of random steps under `--bias`
+    bias: Option<DirectionBias>,
+    /// `--wave`s still to land, the next one last
+    reinforcements: Vec<Reinforcement>,
+}
 
 impl SimulationEngine {
     /// Create a new simulation engine
     pub fn new(_world: &World, _ant_count: usize) -> Self {
-        Self
+        Self {
+            tick: 0,
+            events: Vec::new(),
+            event_limit: None,
+            events_dropped: 0,
+            interrupt: None,
+            map_changed: None,
+            interrupted: false,
+            log: EventLog::closed(),
+            paused: Duration::ZERO,
+            visits: Vec::new(),
+            kills: Vec::new(),
+            track_kills: false,
+            tick_events: Vec::new(),
+            seeds: None,
+            spawn_rng: fastrand::Rng::with_seed(0),
+            fallen: 0,
+            buffers: SimBuffers::new(),
+            collision_rng: fastrand::Rng::with_seed(0),
+            near_misses: 0,
+            fortified: Vec::new(),
+            defense_rng: fastrand::Rng::with_seed(0),
+            repelled: 0,
+            revival_rng: fastrand::Rng::with_seed(0),
+            revived: 0,
+            chaos_rng: fastrand::Rng::with_seed(0),
+            meteors: 0,
+            trampled: 0,
+            start: StartCasualties::default(),
+            energy: None,
+            food: Vec::new(),
+            last_destruction: None,
+            destruction_ticks: Vec::new(),
+            ants_alive: 0,
+            started: Instant::now(),
+            limit_exceeded: None,
+            ant_names: None,
+            hooks: None,
+            destruction_rule: None,
+            stable_order: false,
+            pending: Vec::new(),
+            lone_exit: true,
+            move_strategy: None,
+            stop_condition: None,
+            delta: None,
+            want_deltas: false,
+            homing: None,
+            bias: None,
+            reinforcements: Vec::new(),
+        }
+    }
+
+    /// Stop the run at the next tick boundary once `flag` becomes true
+    pub fn with_interrupt(mut self, flag: Arc<AtomicBool>) -> Self {
+        self.interrupt = Some(flag);
+        self
+    }
+
+    /// Reload the map of an interactive run whenever `flag` becomes true
+    /// (it is cleared on each reload)
+    pub fn with_map_watch(mut self, flag: Arc<AtomicBool>) -> Self {
+        self.map_changed = Some(flag);
+        self
+    }
+
+    /// Whether the run has a map watch
+    pub(crate) fn watches_map(&self) -> bool {
+        self.map_changed.is_some()
+    }
+
+    /// Whether the map watch fired since the last check, clearing it
+    pub(crate) fn map_changed(&self) -> bool {
+        self.map_changed.as_ref().is_some_and(|flag| flag.swap(false, Ordering::Relaxed))
+    }
+
+    /// Whether the last run was cut short by an interrupt
+    pub fn interrupted(&self) -> bool {
+        self.interrupted
+    }
+
+    /// Resource limit (`--memory-limit`, `--max-wall-time`) that stopped the last run
+    pub fn limit_exceeded(&self) -> Option<Limit> {
+        self.limit_exceeded
+    }
+
+    /// End the run early as if it had been interrupted
+    pub(crate) fn abort(&mut self) {
+        self.interrupted = true;
+    }
+
+    /// Check `--max-ticks` and the [`StopCondition`], then poll the interrupt
+    /// flag and the resource limits, remembering if one of those fired
+    #[inline]
+    fn should_stop(&mut self, world: &World, ants: &[Ant], args: &Args) -> bool {
+        if args.max_ticks.is_some_and(|max| self.tick >= max) {
+            return true;
+        }
+        if let Some(condition) = self.stop_condition.as_mut() {
+            if condition.stop(&TickView { tick: self.tick, world: WorldView::new(world), ants }) {
+                return true;
+            }
+        }
+        if let Some(flag) = &self.interrupt {
+            if flag.load(Ordering::Relaxed) {
+                self.interrupted = true;
+                return true;
+            }
+        }
+        let limit = if args.max_wall_time.is_some_and(|max| self.started.elapsed() >= max) {
+            Some(Limit::WallTime)
+        } else if args.memory_limit.is_some_and(|max| self.memory_in_use(world, ants) > max) {
+            Some(Limit::Memory)
+        } else {
+            None
+        };
+        if limit.is_some() {
+            self.limit_exceeded = limit;
+            self.interrupted = true;
+            return true;
+        }
+        false
+    }
+
+    /// Estimated bytes held by the run: world, ants, scratch arrays and records
+    fn memory_in_use(&self, world: &World, ants: &[Ant]) -> u64 {
+        let records = self.events.capacity() * std::mem::size_of::<DestructionEvent>()
+            + self.visits.capacity() * std::mem::size_of::<u32>()
+            + self.kills.capacity() * std::mem::size_of::<u32>();
+        // Queens' offspring grow the ant table (and its scratch entries) mid-run
+        limits::estimate_bytes(world.nodes.len(), ants.len()) + records as u64
+    }
+
+    /// Between ticks, flush events and sleep for `args.tick_delay_ms`
+    #[inline]
+    fn pace(&mut self, args: &Args) {
+        if args.tick_delay_ms == 0 || self.tick == 0 {
+            return;
+        }
+        self.log.flush();
+        let start = Instant::now();
+        std::thread::sleep(Duration::from_millis(args.tick_delay_ms));
+        self.paused += start.elapsed();
+    }
+
+    /// Number of ticks completed by the last run
+    pub fn ticks(&self) -> u64 {
+        self.tick
+    }
+
+    /// Destructions of the last run in the order they happened, logged or not
+    pub fn events(&self) -> &[DestructionEvent] {
+        &self.events
+    }
+
+    /// Destructions not kept in [`SimulationEngine::events`] because of the limit
+    pub fn events_dropped(&self) -> usize {
+        self.events_dropped
+    }
+
+    /// Keep only the first `limit` destruction events of each run
+    pub fn with_event_limit(mut self, limit: usize) -> Self {
+        self.event_limit = Some(limit);
+        self
+    }
+
+    /// Run in `buffers` instead of allocating fresh scratch arrays
+    pub fn with_buffers(mut self, buffers: SimBuffers) -> Self {
+        self.buffers = buffers;
+        self
+    }
+
+    /// Give back the scratch arena for the next engine
+    pub fn into_buffers(self) -> SimBuffers {
+        self.buffers
+    }
+
+    /// Draw move limits from `seeds.spawning` instead of the movement stream
+    pub fn with_seeds(mut self, seeds: SubSeeds) -> Self {
+        self.seeds = Some(seeds);
+        self
+    }
+
+    /// Show ants by name in event logs and reports; workers born during the
+    /// run are named from the same table
+    pub fn with_ant_names(mut self, names: AntNames) -> Self {
+        self.ant_names = Some(names);
+        self
+    }
+
+    /// Word event lines with `messages` (e.g. a translation) instead of the
+    /// built-in English; events, hooks and reports are unaffected
+    pub fn with_messages(mut self, messages: MessageTemplates) -> Self {
+        self.log.set_messages(messages);
+        self
+    }
+
+    /// Ant name table given to [`SimulationEngine::with_ant_names`]
+    pub fn ant_names(&self) -> Option<&AntNames> {
+        self.ant_names.as_ref()
+    }
+
+    /// Call `hooks` at tick boundaries, on every recorded event and on every death.
+    /// Hooks must be `Send` so the engine can move between threads
+    pub fn with_hooks(mut self, hooks: impl SimulationHooks + Send + 'static) -> Self {
+        self.hooks = Some(Box::new(hooks));
+        self
+    }
+
+    /// Let `rule` decide which meetings destroy a colony, instead of every
+    /// meeting of two or more ants; see [`DestructionRule`]
+    pub fn with_destruction_rule(mut self, rule: impl DestructionRule + Send + 'static) -> Self {
+        self.destruction_rule = Some(Box::new(rule));
+        self
+    }
+
+    /// Let `strategy` move the ants, instead of `--strategy` and `--bias`;
+    /// see [`MoveStrategy`]
+    pub fn with_move_strategy(mut self, strategy: impl MoveStrategy + Send + 'static) -> Self {
+        self.move_strategy = Some(Box::new(strategy));
+        self
+    }
+
+    /// End the run before any tick `condition` answers `true` for; see
+    /// [`StopCondition`]
+    pub fn with_stop_condition(mut self, condition: impl StopCondition + Send + 'static) -> Self {
+        self.stop_condition = Some(Box::new(condition));
+        self
+    }
+
+    /// Stream seeds given to [`SimulationEngine::with_seeds`]
+    pub fn seeds(&self) -> Option<SubSeeds> {
+        self.seeds
+    }
+
+    /// Meetings of the last run that `--collision-prob` turned into near misses
+    pub fn near_misses(&self) -> usize {
+        self.near_misses
+    }
+
+    /// Whether the map of the last run tagged any colony `fortified`
+    pub fn has_fortified(&self) -> bool {
+        !self.fortified.is_empty()
+    }
+
+    /// Lone ants that fortified colonies killed in the last run
+    pub fn repelled(&self) -> usize {
+        self.repelled
+    }
+
+    /// Colonies and ants lost at t=0 of the last run, before anyone moved
+    pub fn start_casualties(&self) -> StartCasualties {
+        self.start
+    }
+
+    /// Colonies that `--revival-prob` rebuilt in the last run
+    pub fn revived(&self) -> usize {
+        self.revived
+    }
+
+    /// Colonies that `--chaos` meteors destroyed in the last run
+    pub fn meteors(&self) -> usize {
+        self.meteors
+    }
+
+    /// Colonies that mega ants trampled in the last run
+    pub fn trampled(&self) -> usize {
+        self.trampled
+    }
+
+    /// Tick on which the last colony fell in the last run (0 = placement),
+    /// `None` if none did; averted fights, near misses and repelled ants
+    /// do not count
+    pub fn last_destruction(&self) -> Option<u64> {
+        self.last_destruction
+    }
+
+    /// Tick of every colony destruction in the last run, in order; unlike
+    /// [`SimulationEngine::events`] these are all kept whatever the event limit
+    pub fn destruction_ticks(&self) -> &[u64] {
+        &self.destruction_ticks
+    }
+
+    /// Ants alive in the current (or last) run, in O(1)
+    pub fn ants_alive(&self) -> usize {
+        self.ants_alive
+    }
+
+    /// Cumulative visits per colony id (empty unless heatmap tracking is on)
+    pub fn visits(&self) -> &[u32] {
+        &self.visits
+    }
+
+    /// Colony-destroying fights each ant id took part in, counting every
+    /// ant on the colony, not only the two in the event (empty unless kill
+    /// tracking is on)
+    pub fn kills(&self) -> &[u32] {
+        &self.kills
     }
 
     /// Run the complete simulation - optimized version that closely matches original
+    ///
+    /// # Panics
+    /// If [`check`](Self::check) rejects the world and ants; use
+    /// [`try_run_simulation`](Self::try_run_simulation) to get the error back
     pub fn run_simulation(
         &mut self,
         world: &mut World,
@@ -22,265 +507,1230 @@ impl SimulationEngine {
         args: &Args,
         rng: &mut fastrand::Rng,
     ) -> std::time::Duration {
+        self.begin(world, ants, args, rng);
+
+        let sim_start = Instant::now();
+        let mut state = self.run_state(world, ants, args);
+        while self.step(&mut state, world, ants, args, rng) {}
+        let elapsed = sim_start.elapsed().saturating_sub(self.paused);
+
+        self.finish(&mut state);
+        elapsed
+    }
+
+    /// Like [`run_simulation`](Self::run_simulation), but hands back what
+    /// [`check`](Self::check) found instead of panicking, and reports a run
+    /// cut short by an interrupt or a resource limit as
+    /// [`SimulationError::Aborted`] (the world and ants keep the partial run)
+    pub fn try_run_simulation(
+        &mut self,
+        world: &mut World,
+        ants: &mut Vec<Ant>,
+        args: &Args,
+        rng: &mut fastrand::Rng,
+    ) -> std::result::Result<Duration, SimulationError> {
+        Self::check(world, ants)?;
+        let elapsed = self.run_simulation(world, ants, args, rng);
+        if self.interrupted {
+            return Err(SimulationError::Aborted { tick: self.tick, limit: self.limit_exceeded });
+        }
+        Ok(elapsed)
+    }
+
+    /// Whether `ants` can run on `world`: every ant stands on a colony of
+    /// the world, living ones on standing colonies, ant ids fit in `u32`
+    /// and match the ant's index, and every tunnel leads to a colony of the
+    /// world. The tick loop reads
+    /// the tables unchecked, so a run is only started on what passes.
+    pub fn check(world: &World, ants: &[Ant]) -> std::result::Result<(), SimulationError> {
+        let len = world.nodes.len();
+        if ants.len() >= u32::MAX as usize {
+            return Err(SimulationError::InconsistentState(format!("{} ants, ant ids are u32", ants.len())));
+        }
+        for i in 0..len {
+            if let Some(to) = world.nodes.neighbors(i).into_iter().flatten().find(|to| to.index() >= len) {
+                return Err(SimulationError::InconsistentState(format!(
+                    "colony {} has a tunnel to colony {} of a world with {}",
+                    world.get_colony_name(NodeId::from_index(i)),
+                    to.index(),
+                    len
+                )));
+            }
+        }
+        for (i, ant) in ants.iter().enumerate() {
+            if ant.id as usize != i {
+                return Err(SimulationError::InconsistentState(format!("ant {} is at index {i} of the ants", ant.id)));
+            }
+            let index = ant.pos.index();
+            if index >= len {
+                return Err(SimulationError::NodeIndexOutOfRange { ant: ant.id, index, len });
+            }
+            if ant.is_alive() && !world.is_alive(ant.pos) {
+                let colony = world.get_colony_name(ant.pos).to_string();
+                return Err(SimulationError::InvalidAntPlacement { ant: ant.id, colony });
+            }
+        }
+        Ok(())
+    }
+
+    /// Reset per-run state, assign move limits and resolve t=0 collisions
+    pub(crate) fn begin(
+        &mut self,
+        world: &mut World,
+        ants: &mut [Ant],
+        args: &Args,
+        rng: &mut fastrand::Rng,
+    ) {
+        if let Err(err) = Self::check(world, ants) {
+            panic!("{err}");
+        }
+        self.tick = 0;
+        self.events.clear();
+        self.tick_events.clear();
+        self.delta = std::mem::take(&mut self.want_deltas).then(TickDelta::default);
+        self.events_dropped = 0;
+        self.interrupted = false;
+        self.limit_exceeded = None;
+        self.started = Instant::now();
+        self.paused = Duration::ZERO;
+        self.near_misses = 0;
+        self.repelled = 0;
+        self.revived = 0;
+        self.meteors = 0;
+        self.trampled = 0;
+        self.last_destruction = None;
+        self.destruction_ticks.clear();
+        self.ants_alive = ants.iter().filter(|a| a.is_alive()).count();
+        self.energy = args.energy;
+        self.read_tags(world);
+        if args.prune_dead_edges {
+            world.prune_dead_edges();
+        }
+        self.stable_order = args.stable_event_order;
+        self.pending.clear();
+        self.bias = args.bias;
+        self.homing = match args.strategy {
+            Strategy::Random => None,
+            Strategy::Homing { after } => Some(Homing::new(after, args.bias, world)),
+        };
+        self.reinforcements = args.waves.clone();
+        self.reinforcements.sort_by_key(|wave| wave.tick);
+        self.reinforcements.reverse();
+        self.visits.clear();
+        if args.track_visits() {
+            self.visits.resize(world.nodes.len(), 0);
+            for a in ants.iter() {
+                self.visits[a.pos.index()] = self.visits[a.pos.index()].saturating_add(1);
+            }
+        }
+        self.track_kills = args.track_kills();
+        self.kills.clear();
+        if self.track_kills {
+            self.kills.resize(ants.len(), 0);
+        }
+        if !args.suppress_events && args.chatter() != Chatter::Off {
+            self.log.open(args.log_filter.unwrap_or_default(), args.chatter() == Chatter::Stderr);
+        }
+
+        // Per-ant move limits; a plain --max-moves draws no randomness, so
+        // the movement stream is only forked when there are no sub-seeds
+        // (waves also draw their landing colonies from this stream)
+        if let Some(seeds) = &self.seeds {
+            self.spawn_rng = fastrand::Rng::with_seed(seeds.spawning);
+        } else if args.max_moves_dist.is_some() || !self.reinforcements.is_empty() {
+            self.spawn_rng = rng.fork();
+        }
+        for a in ants.iter_mut() {
+            a.max_moves = args.sample_max_moves(&mut self.spawn_rng);
+            a.energy = self.energy.unwrap_or(u32::MAX);
+        }
+        // Positions are random, so the highest worker ids make as good mega
+        // ants as any; ants that already are (an imported population) count
+        let megas = args.mega_count().saturating_sub(ants.iter().filter(|a| a.is_mega()).count());
+        for a in ants.iter_mut().rev().filter(|a| a.kind == AntKind::Worker).take(megas) {
+            a.kind = AntKind::Mega;
+        }
+        if let Some(seeds) = &self.seeds {
+            self.collision_rng = fastrand::Rng::with_seed(seeds.collision);
+        } else if args.collision_prob.is_some() {
+            self.collision_rng = rng.fork();
+        }
+        if let Some(seeds) = &self.seeds {
+            self.defense_rng = fastrand::Rng::with_seed(seeds.defense);
+        } else if self.has_fortified() && args.fortress_defense < 1.0 {
+            self.defense_rng = rng.fork();
+        }
+        if let Some(seeds) = &self.seeds {
+            self.revival_rng = fastrand::Rng::with_seed(seeds.revival);
+        } else if args.revival_prob.is_some() {
+            self.revival_rng = rng.fork();
+        }
+        if let Some(seeds) = &self.seeds {
+            self.chaos_rng = fastrand::Rng::with_seed(seeds.chaos);
+        } else if args.chaos.is_some() {
+            self.chaos_rng = rng.fork();
+        }
+
         // Handle initial collisions at t=0 (same as original)
-        self.handle_initial_collisions(world, ants, args);
+        let mut bufs = std::mem::take(&mut self.buffers);
+        bufs.reset(world.nodes.len(), ants);
+        let placed = self.ants_alive;
+        self.handle_initial_collisions(world, ants, args, &mut bufs);
+        self.buffers = bufs;
+        self.start = StartCasualties {
+            colonies: self.destruction_ticks.len(),
+            ants: placed - self.ants_alive,
+            survivors: self.ants_alive,
+        };
+        self.flush_events(world);
+    }
 
-        // Initialize active ants list
-        let mut active: Vec<usize> = Vec::with_capacity(args.ants);
-        active.extend(ants.iter().enumerate().filter_map(|(i, a)| {
-            if a.is_alive() && !a.is_trapped() && a.moves < args.max_moves {
-                Some(i)
-            } else {
-                None
+    /// Pick up the colonies tagged `fortified` and (under `--energy`) `food` in `world`
+    fn read_tags(&mut self, world: &World) {
+        self.fortified.clear();
+        if let Some(tags) = world.attr::<u32>(FORTIFIED_TAG) {
+            if tags.iter().any(|t| t.is_some_and(|v| v != 0)) {
+                self.fortified.extend(tags.iter().map(|t| t.is_some_and(|v| v != 0)));
             }
-        }));
+        }
+        self.food.clear();
+        if let (Some(_), Some(tags)) = (self.energy, world.attr::<u32>(FOOD_TAG)) {
+            self.food.extend(tags.iter().map(|t| t.is_some_and(|v| v != 0)));
+        }
+    }
 
-        let sim_start = Instant::now();
+    /// Carry a run in progress over to `next`, a fresh parse of its map.
+    ///
+    /// Alive ants keep their colony (and home) by name; those whose colony is
+    /// missing or destroyed in `next` die. Ants left sharing a colony fight
+    /// as they would at tick 0. Visit counts follow their colony by name.
+    /// Returns how many ants were kept and how many were lost.
+    pub(crate) fn switch_world(
+        &mut self,
+        state: &mut RunState,
+        world: &mut World,
+        ants: &mut [Ant],
+        args: &Args,
+        mut next: World,
+    ) -> (usize, usize) {
+        let (mut kept, mut lost) = (0, 0);
+        for ant in ants.iter_mut().filter(|a| a.is_alive()) {
+            match next.find(world.get_colony_name(ant.pos)).filter(|&id| next.is_alive(id)) {
+                Some(pos) => {
+                    ant.home = next.find(world.get_colony_name(ant.home)).unwrap_or(pos);
+                    ant.pos = pos;
+                    // The new map may have opened a way out
+                    ant.set_trapped(false);
+                    kept += 1;
+                }
+                None => {
+                    self.kill(ant, DeathReason::VanishedWithColony);
+                    lost += 1;
+                }
+            }
+        }
+        if !self.visits.is_empty() {
+            let mut visits = vec![0; next.nodes.len()];
+            for (i, &count) in self.visits.iter().enumerate() {
+                if let Some(id) = next.find(world.get_colony_name(NodeId::from_index(i))) {
+                    visits[id.index()] = count;
+                }
+            }
+            self.visits = visits;
+        }
+        if args.prune_dead_edges {
+            next.prune_dead_edges();
+        }
+        *world = next;
+        self.read_tags(world);
+        if let Strategy::Homing { after } = args.strategy {
+            self.homing = Some(Homing::new(after, args.bias, world));
+        }
 
-        // Per-node "generation" trick avoids clearing large arrays (same as original)
-        let n_nodes = world.nodes.len();
-        let mut gen = vec![0u32; n_nodes];
-        let mut occ_count = vec![0u32; n_nodes];
-        let mut occ_first = vec![u32::MAX; n_nodes];
-        let mut occ_second = vec![u32::MAX; n_nodes];
-        let mut cur_gen: u32 = 1;
-
-        // Stationary stock (same as original)
-        let mut base_occ = vec![0u32; n_nodes];
-        let mut base_first = vec![u32::MAX; n_nodes];
-        let mut base_second = vec![u32::MAX; n_nodes];
-
-        // "Touched" node lists (same as original)
-        let mut touched_nodes: Vec<usize> = Vec::with_capacity(4096);
-        let mut base_touched: Vec<usize> = Vec::with_capacity(1024);
-
-        // Per-ant buffers (same as original)
-        let mut next_pos: Vec<u32> = ants.iter().map(|a| a.pos).collect();
-        let mut trapped_now: Vec<bool> = vec![false; ants.len()];
-
-        // Main simulation loop (identical to original)
-        while !active.is_empty() {
-            cur_gen = cur_gen.wrapping_add(1);
-            touched_nodes.clear();
-            base_touched.clear();
-
-            // (1) Decide destinations for active ants
-            let mut i = 0;
-            while i < active.len() {
-                let ai = active[i];
-                let a = &ants[ai];
-                if !a.is_alive() || a.moves >= args.max_moves || a.is_trapped() {
-                    active.swap_remove(i);
-                    continue;
+        let mut bufs = state.take_buffers();
+        bufs.reset(world.nodes.len(), ants);
+        self.handle_initial_collisions(world, ants, args, &mut bufs);
+        self.buffers = bufs;
+        self.flush_events(world);
+        *state = self.run_state(world, ants, args);
+        (kept, lost)
+    }
+
+    /// Hand the scratch arena, reset for this run, to the selected scheduling model
+    pub(crate) fn run_state(&mut self, world: &World, ants: &[Ant], args: &Args) -> RunState {
+        let mut bufs = std::mem::take(&mut self.buffers);
+        bufs.reset(world.nodes.len(), ants);
+        match args.scheduling {
+            Scheduling::Simultaneous => RunState::Simultaneous(SimultaneousState { done: false, cur_gen: 1, bufs }),
+            Scheduling::Sequential => {
+                for (i, a) in ants.iter().enumerate() {
+                    if a.is_alive() {
+                        bufs.occupant[a.pos.index()] = i as u32;
+                    }
                 }
-                let (np, became_trapped) = world.choose_next_position(a.pos, rng);
-                next_pos[ai] = np;
-                trapped_now[ai] = became_trapped;
-                i += 1;
+                RunState::Sequential(SequentialState { done: false, bufs })
             }
-            if active.is_empty() {
-                break;
+        }
+    }
+
+    /// Run one tick; returns false once the run is over and no tick ran
+    pub(crate) fn step(
+        &mut self,
+        state: &mut RunState,
+        world: &mut World,
+        ants: &mut Vec<Ant>,
+        args: &Args,
+        rng: &mut fastrand::Rng,
+    ) -> bool {
+        self.tick_events.clear();
+        if let Some(delta) = self.delta.as_mut() {
+            delta.clear();
+        }
+        self.fallen = 0;
+        self.reinforce(state, world, ants, args);
+        let ticked = match state {
+            RunState::Simultaneous(st) => self.step_simultaneous(st, world, ants, args, rng),
+            RunState::Sequential(st) => self.step_sequential(st, world, ants, args, rng),
+        };
+        if ticked {
+            self.strike(state, world, ants, args);
+            self.flush_events(world);
+            self.revive(world, args);
+            if let Some(hooks) = self.hooks.as_mut() {
+                hooks.on_tick_end(&TickView { tick: self.tick, world: WorldView::new(world), ants });
             }
+        }
+        ticked
+    }
 
-            // (2) Build occupancy (initialize from stationary, then add active)
-            for &ai in &active {
-                let a = &ants[ai];
-                if !a.is_alive() {
-                    continue;
+    /// Land every `--wave` due by the current tick, then settle the colonies
+    /// it landed on. A run left without moving ants skips ahead to its next
+    /// wave, unless `--max-ticks` ends it first.
+    fn reinforce(&mut self, state: &mut RunState, world: &mut World, ants: &mut Vec<Ant>, args: &Args) {
+        let Some(next) = self.reinforcements.last().map(|wave| wave.tick) else {
+            return;
+        };
+        if self.interrupted {
+            return;
+        }
+        if state.is_done() && next > self.tick {
+            if args.max_ticks.is_some_and(|max| next > max) {
+                self.reinforcements.clear();
+                return;
+            }
+            self.catch_up(next);
+        }
+        let mut landed = Vec::new();
+        while let Some(wave) = self.reinforcements.pop_if(|wave| wave.tick <= self.tick) {
+            for pos in world.place(wave.ants, args.placement, &mut self.spawn_rng) {
+                let mut ant = Ant::new(0, pos);
+                ant.max_moves = args.sample_max_moves(&mut self.spawn_rng);
+                ant.energy = self.energy.unwrap_or(u32::MAX);
+                let index = self.admit(state, ants, ant);
+                ants[index].name = self.ant_names.as_mut().and_then(|names| names.name_for(index as u32));
+                if let Some(visits) = self.visits.get_mut(pos.index()) {
+                    *visits = visits.saturating_add(1);
                 }
-                let nid = next_pos[ai] as usize;
-
-                if gen[nid] != cur_gen {
-                    gen[nid] = cur_gen;
-                    occ_count[nid] = base_occ[nid];
-                    occ_first[nid] = base_first[nid];
-                    occ_second[nid] = base_second[nid];
-                    touched_nodes.push(nid);
+                landed.push(pos.index());
+            }
+        }
+        if !landed.is_empty() {
+            landed.sort_unstable();
+            landed.dedup();
+            self.meet(state, world, ants, args, &landed);
+        }
+    }
+
+    /// With chance `--chaos`, drop a meteor on one standing colony picked at
+    /// random once the ants have moved: it falls with every ant on it, and
+    /// no blast spreads from it
+    fn strike(&mut self, state: &mut RunState, world: &mut World, ants: &mut [Ant], args: &Args) {
+        let Some(p) = args.chaos else {
+            return;
+        };
+        let standing = world.alive_count();
+        if standing == 0 || self.chaos_rng.f64() >= p {
+            return;
+        }
+        let Some(id) = world.alive_nodes().nth(self.chaos_rng.usize(..standing)).map(|c| c.id()) else {
+            return;
+        };
+        let nid = id.index();
+        self.record(world, nid, DestructionCause::Meteor);
+        world.destroy(id);
+        self.meteors += 1;
+
+        let sequential = matches!(state, RunState::Sequential(_));
+        let SimBuffers { base_occ, base_first, base_second, occupant, .. } = state.bufs_mut();
+        if sequential {
+            occupant[nid] = u32::MAX;
+        } else {
+            base_occ[nid] = 0;
+            base_first[nid] = u32::MAX;
+            base_second[nid] = u32::MAX;
+        }
+        for a in ants.iter_mut() {
+            if a.is_alive() && a.pos == id {
+                self.kill(a, DeathReason::VanishedWithColony);
+                a.set_trapped(false);
+            }
+        }
+    }
+
+    /// Let each colony standing at the end of the tick rebuild, with chance
+    /// `--revival-prob`, one destroyed colony it shares a tunnel with (either
+    /// way). Colonies rebuilt this tick wait for the next one to rebuild others.
+    fn revive(&mut self, world: &mut World, args: &Args) {
+        let Some(p) = args.revival_prob else {
+            return;
+        };
+        if world.alive_count() == world.nodes.len() {
+            return;
+        }
+        let standing: Vec<NodeId> = world.alive_nodes().map(|c| c.id()).collect();
+        let mut ruins = Vec::new();
+        for from in standing {
+            ruins.clear();
+            ruins.extend(world.nodes.neighbors(from.index()).into_iter().flatten());
+            ruins.extend_from_slice(world.in_neighbors(from));
+            ruins.retain(|&n| !world.is_alive(n));
+            if ruins.is_empty() || self.revival_rng.f64() >= p {
+                continue;
+            }
+            ruins.sort_unstable();
+            ruins.dedup();
+            let id = ruins[self.revival_rng.usize(..ruins.len())];
+            if world.rebuild(id, from) {
+                self.revived += 1;
+                if let Some(delta) = self.delta.as_mut() {
+                    delta.revived.push(id);
                 }
+            }
+        }
+    }
 
-                match occ_count[nid] {
-                    0 => {
-                        occ_first[nid] = a.id;
-                        occ_count[nid] = 1;
-                    }
-                    1 => {
-                        if occ_first[nid] == u32::MAX {
-                            occ_first[nid] = a.id;
-                        } else {
-                            occ_second[nid] = a.id;
-                        }
-                        occ_count[nid] = 2;
-                    }
-                    _ => {
-                        occ_count[nid] += 1;
+    /// Tell the hooks a tick is starting
+    #[inline]
+    fn tick_started(&mut self, world: &World, ants: &[Ant]) {
+        if let Some(hooks) = self.hooks.as_mut() {
+            hooks.on_tick_start(&TickView { tick: self.tick, world: WorldView::new(world), ants });
+        }
+    }
+
+    /// Next position for `ant` under the run's [`MoveStrategy`], or else its
+    /// `--strategy` and `--bias`, as [`World::choose_next_position`]
+    #[inline]
+    pub(crate) fn choose_move(&mut self, world: &World, ant: &Ant, rng: &mut fastrand::Rng) -> (NodeId, bool) {
+        if let Some(strategy) = self.move_strategy.as_mut() {
+            return strategy.next(WorldView::new(world), ant, rng);
+        }
+        match &mut self.homing {
+            Some(homing) => homing.choose(world, ant, rng),
+            None => world.random_step(ant.pos, self.bias.as_ref(), rng),
+        }
+    }
+
+    /// Count an arrival on colony `nid` when tracking visits
+    #[inline]
+    pub(crate) fn count_visit(&mut self, nid: usize) {
+        if !self.visits.is_empty() {
+            self.visits[nid] = self.visits[nid].saturating_add(1);
+        }
+    }
+
+    /// Whether fights are credited to their participants this run
+    #[inline]
+    pub(crate) fn tracks_kills(&self) -> bool {
+        self.track_kills
+    }
+
+    /// Credit ant `id` with a colony-destroying fight when tracking kills
+    #[inline]
+    pub(crate) fn credit_kill(&mut self, id: u32) {
+        if self.track_kills {
+            let i = id as usize;
+            // Newborns and admitted ants join after the run began
+            if i >= self.kills.len() {
+                self.kills.resize(i + 1, 0);
+            }
+            self.kills[i] = self.kills[i].saturating_add(1);
+        }
+    }
+
+    /// Mark `ant` dead of `reason` and tell the hooks
+    #[inline]
+    pub(crate) fn kill(&mut self, ant: &mut Ant, reason: DeathReason) {
+        if let (Some(delta), true) = (self.delta.as_mut(), ant.is_alive()) {
+            delta.ants_died.push(ant.id);
+        }
+        self.ants_alive -= ant.is_alive() as usize;
+        match reason {
+            DeathReason::Starved => ant.starve(),
+            reason => ant.die(reason),
+        }
+        if let Some(hooks) = self.hooks.as_mut() {
+            hooks.on_ant_dead(ant, self.tick);
+        }
+    }
+
+    /// Whether a moving `ant` is out of energy; if so it starves, which
+    /// the hooks see as a death
+    #[inline]
+    pub(crate) fn starves(&mut self, ant: &mut Ant) -> bool {
+        if ant.energy != 0 {
+            return false;
+        }
+        self.kill(ant, DeathReason::Starved);
+        true
+    }
+
+    /// Count `ant`'s arrival on colony `nid`, refilling its energy there if
+    /// the colony is tagged `food`
+    #[inline]
+    pub(crate) fn arrive(&mut self, ant: &mut Ant, nid: usize) {
+        self.count_visit(nid);
+        if let (Some(energy), Some(true)) = (self.energy, self.food.get(nid)) {
+            ant.energy = energy;
+        }
+    }
+
+    /// Keep running while a single ant is left, for runs that ants can join
+    pub(crate) fn keep_lone_ant(&mut self) {
+        self.lone_exit = false;
+    }
+
+    /// Count ticks from `tick` on if this run fell behind (it sat idle while
+    /// other worlds of a multiverse kept ticking)
+    pub(crate) fn catch_up(&mut self, tick: u64) {
+        self.tick = self.tick.max(tick);
+    }
+
+    /// Take the moving ant at `index` out of the run (it left through a
+    /// portal). Its entry stays in `ants`, no longer alive, so indices hold;
+    /// the returned copy is the ant that left.
+    pub(crate) fn release(&mut self, state: &mut RunState, ants: &mut [Ant], index: usize) -> Ant {
+        let leaving = ants[index].clone();
+        self.ants_alive -= leaving.is_alive() as usize;
+        ants[index].set_alive(false);
+        let bufs = state.bufs_mut();
+        bufs.active.retain(|&ai| ai != index);
+        if bufs.occupant.get(leaving.pos.index()) == Some(&(index as u32)) {
+            bufs.occupant[leaving.pos.index()] = u32::MAX;
+        }
+        leaving
+    }
+
+    /// Add `ant`, arriving from outside the run on `ant.pos`, as a moving
+    /// ant under the next free id; it moves from the next tick, even if the
+    /// run had stopped for lack of moving ants. Call
+    /// [`SimulationEngine::meet`] on its colony once every arrival is in.
+    pub(crate) fn admit(&mut self, state: &mut RunState, ants: &mut Vec<Ant>, mut ant: Ant) -> usize {
+        debug_assert!(ants.len() < u32::MAX as usize, "ant ids are u32");
+        let index = ants.len();
+        ant.id = index as u32;
+        ant.name = None;
+        ant.home = ant.pos;
+        state.resume();
+        let bufs = state.bufs_mut();
+        bufs.active.push(index);
+        bufs.next_pos.push(ant.pos);
+        bufs.trapped_now.push(false);
+        self.ants_alive += ant.is_alive() as usize;
+        if let Some(delta) = self.delta.as_mut() {
+            delta.ants_born.push(ant.id);
+        }
+        ants.push(ant);
+        index
+    }
+
+    /// Resolve `colonies` between ticks, after ants were admitted onto them:
+    /// two or more ants on one colony fight (or near-miss) as they would at t=0.
+    /// `colonies` is sorted and deduped, so each ant finds its own by binary search
+    pub(crate) fn meet(
+        &mut self,
+        state: &mut RunState,
+        world: &mut World,
+        ants: &mut [Ant],
+        args: &Args,
+        colonies: &[usize],
+    ) {
+        // (count, first, second) ant index per colony, in one pass over the ants
+        let mut here = vec![(0u32, usize::MAX, usize::MAX); colonies.len()];
+        let ghosts = args.trapped_policy == TrappedPolicy::Ghost;
+        for (i, a) in ants.iter().enumerate() {
+            if !a.is_alive() || (ghosts && a.is_trapped()) {
+                continue;
+            }
+            let Ok(c) = colonies.binary_search(&a.pos.index()) else { continue };
+            let (count, first, second) = &mut here[c];
+            match count {
+                0 => *first = i,
+                1 => *second = i,
+                _ => {}
+            }
+            *count = count.saturating_add(1);
+        }
+
+        let sequential = matches!(state, RunState::Sequential(_));
+        let SimBuffers { base_occ, base_first, base_second, occupant, blasted, .. } = state.bufs_mut();
+        let mut fights = Vec::new();
+        for (&nid, &(count, first, second)) in colonies.iter().zip(&here) {
+            if sequential && count > 0 && occupant[nid] == u32::MAX {
+                occupant[nid] = first as u32;
+            }
+            if count < 2 || !world.nodes.is_alive(nid) {
+                continue;
+            }
+            let (ant1, ant2) = (ants[first].id, ants[second].id);
+            if !self.destroys(nid, count, ant1, ant2) {
+                continue;
+            }
+            if self.near_miss(args) {
+                self.record(world, nid, DestructionCause::NearMiss { ant1, ant2 });
+                continue;
+            }
+            self.destroy_colony(world, args, nid, ant1, ant2, blasted);
+            if self.track_kills {
+                for a in ants.iter() {
+                    if a.is_alive() && a.pos.index() == nid && !(ghosts && a.is_trapped()) {
+                        self.credit_kill(a.id);
                     }
                 }
             }
+            blasted.push(nid);
+            fights.push(nid);
+        }
+        if fights.is_empty() {
+            self.flush_events(world);
+            return;
+        }
 
-            // (3) Destroy collided colonies
-            for &nid in &touched_nodes {
-                if occ_count[nid] >= 2 && world.nodes[nid].is_alive() {
-                    self.log_destruction(args, world, nid, occ_first[nid], occ_second[nid]);
-                    world.nodes[nid].destroy();
-                    base_occ[nid] = 0;
-                    base_first[nid] = u32::MAX;
-                    base_second[nid] = u32::MAX;
-                }
+        for &b in blasted.iter() {
+            if sequential {
+                occupant[b] = u32::MAX;
+            } else {
+                base_occ[b] = 0;
+                base_first[b] = u32::MAX;
+                base_second[b] = u32::MAX;
             }
+        }
+        blasted.clear();
+        for a in ants.iter_mut() {
+            if a.is_alive() && !world.nodes.is_alive(a.pos.index()) {
+                let reason = if fights.contains(&a.pos.index()) {
+                    DeathReason::Collision
+                } else {
+                    DeathReason::VanishedWithColony
+                };
+                self.kill(a, reason);
+                a.set_trapped(false);
+            }
+        }
+        self.flush_events(world);
+    }
+
+    /// Destructions of the last tick run by [`SimulationEngine::step`]
+    pub(crate) fn tick_events(&self) -> &[DestructionEvent] {
+        &self.tick_events
+    }
+
+    /// Gather a [`TickDelta`] per tick in the run about to begin
+    pub(crate) fn track_deltas(&mut self) {
+        self.want_deltas = true;
+    }
+
+    /// Changes of the last tick run (or of t=0 before the first), when
+    /// gathering them; later ticks start from empty lists
+    pub(crate) fn take_delta(&mut self) -> TickDelta {
+        let delta = self.delta.as_mut().map(std::mem::take).unwrap_or_default();
+        TickDelta { tick: self.tick, ..delta }
+    }
+
+    /// Changes left over once the run is over: the last moving ants starved
+    /// as a tick began, which then never ran. Stamped with that tick, as the
+    /// hooks saw the deaths; `None` if nothing is left.
+    pub(crate) fn take_final_delta(&mut self) -> Option<TickDelta> {
+        let delta = self.take_delta();
+        (!delta.is_empty()).then(|| TickDelta { tick: self.tick + 1, ..delta })
+    }
+
+    /// Note that alive `ant` stopped moving this tick, when gathering deltas
+    #[inline]
+    pub(crate) fn stopped(&mut self, ant: &Ant) {
+        if let Some(delta) = self.delta.as_mut() {
+            delta.ants_stopped.push(ant.id);
+        }
+    }
+
+    /// Flush and release the event log and take the scratch arena back
+    pub(crate) fn finish(&mut self, state: &mut RunState) {
+        self.log.close();
+        self.buffers = state.take_buffers();
+    }
+
+    /// Advance one tick of the tick-synchronous model: every active ant
+    /// moves at once, then collisions resolve. Returns whether a tick ran.
+    fn step_simultaneous(
+        &mut self,
+        st: &mut SimultaneousState,
+        world: &mut World,
+        ants: &mut Vec<Ant>,
+        args: &Args,
+        rng: &mut fastrand::Rng,
+    ) -> bool {
+        let SimultaneousState { done, cur_gen, bufs } = st;
+        if *done || bufs.active.is_empty() {
+            *done = true;
+            return false;
+        }
+        self.pace(args);
+        if self.should_stop(world, ants, args) {
+            *done = true;
+            return false;
+        }
+        *cur_gen = cur_gen.wrapping_add(1);
+        if *cur_gen == 0 {
+            // 0 is what untouched colonies hold; start a fresh epoch instead
+            bufs.gen.fill(0);
+            *cur_gen = 1;
+        }
+        self.tick += 1;
+
+        let mut ctx = TickContext { engine: self, world, ants, args, rng, bufs, cur_gen: *cur_gen };
+        if DecisionPhase.run(&mut ctx).is_break() {
+            self.tick -= 1;
+            *done = true;
+            return false;
+        }
+        self.tick_started(world, ants);
+        let mut ctx = TickContext { engine: self, world, ants, args, rng, bufs, cur_gen: *cur_gen };
+        for phase in RESOLVE {
+            if phase.run(&mut ctx).is_break() {
+                break;
+            }
+        }
+
+        let SimBuffers { active, next_pos, trapped_now, .. } = bufs;
+        // Queens lay workers; newborns start moving next tick
+        let spawned = self.spawn_workers(ants, active, args);
+        if spawned > 0 {
+            next_pos.extend(ants[ants.len() - spawned..].iter().map(|a| a.pos));
+            trapped_now.resize(ants.len(), false);
+        }
+
+        // Early exit, unless a wave could still bring a fight
+        debug_assert_eq!(self.ants_alive, ants.iter().filter(|a| a.is_alive()).count());
+        *done = self.lone_exit && self.ants_alive <= 1 && self.reinforcements.is_empty();
+        true
+    }
 
-            // (4) Commit ant state + register new stationaries
-            let mut j = 0;
-            while j < active.len() {
-                let ai = active[j];
-                let nid = next_pos[ai] as usize;
-                let node_alive = world.nodes[nid].is_alive();
+    /// Advance one tick of the sequential model: ants move one at a time in
+    /// id order and a collision resolves the moment an ant steps onto an
+    /// occupied colony. Returns whether a tick ran.
+    ///
+    /// After t=0 every alive colony holds at most one ant, so a single
+    /// occupant slot per node is enough to detect collisions. Under
+    /// `--collision-prob` an ant that near-misses never takes the slot, and
+    /// a mega ant that gets in without a fight tramples the colony.
+    fn step_sequential(
+        &mut self,
+        st: &mut SequentialState,
+        world: &mut World,
+        ants: &mut Vec<Ant>,
+        args: &Args,
+        rng: &mut fastrand::Rng,
+    ) -> bool {
+        let SequentialState { done, bufs } = st;
+        let SimBuffers {
+            active,
+            occupant,
+            blasted,
+            ..
+        } = bufs;
 
-                let a = &mut ants[ai];
-                if !a.is_alive() {
-                    active.swap_remove(j);
+        if *done || active.is_empty() {
+            *done = true;
+            return false;
+        }
+        self.pace(args);
+        if self.should_stop(world, ants, args) {
+            *done = true;
+            return false;
+        }
+        self.tick += 1;
+        self.tick_started(world, ants);
+        for &ai in active.iter() {
+            let a = &ants[ai];
+            if !a.is_alive() || a.is_trapped() || a.has_max_moves() {
+                continue;
+            }
+            let from = a.pos;
+            // Newborns share their queen's colony without owning its slot,
+            // so a fight there may have happened while they waited
+            if !world.nodes.is_alive(from.index()) {
+                self.kill(&mut ants[ai], DeathReason::VanishedWithColony);
+                continue;
+            }
+            if self.starves(&mut ants[ai]) {
+                if occupant[from.index()] == ai as u32 {
+                    occupant[from.index()] = u32::MAX;
+                }
+                continue;
+            }
+            let (np, became_trapped) = self.choose_move(world, &ants[ai], rng);
+            let nid = np.index();
+            if became_trapped && args.trapped_policy != TrappedPolicy::Stay {
+                // Die or turn ghost: either way the ant gives up its slot
+                match args.trapped_policy {
+                    TrappedPolicy::Die => self.kill(&mut ants[ai], DeathReason::Trapped),
+                    _ => ants[ai].set_trapped(true),
+                }
+                if occupant[nid] == ai as u32 {
+                    occupant[nid] = u32::MAX;
+                }
+                continue;
+            }
+            if became_trapped {
+                ants[ai].set_trapped(true);
+                // A newborn trapped next to its queen settles in and fights her
+                if occupant[nid] == u32::MAX {
+                    occupant[nid] = ai as u32;
+                }
+                if occupant[nid] == ai as u32 {
                     continue;
                 }
-
-                if !node_alive {
-                    a.set_alive(false);
-                    a.set_trapped(false);
-                    active.swap_remove(j);
+                if self.armistice(args) {
+                    let cause = DestructionCause::Averted { ant1: ants[occupant[nid] as usize].id, ant2: ants[ai].id };
+                    self.record(world, nid, cause);
                     continue;
                 }
-
-                if !trapped_now[ai] && nid as u32 != a.pos {
-                    a.move_to(nid as u32);
-
-                    if a.has_max_moves(args.max_moves) {
-                        match base_occ[nid] {
-                            0 => base_first[nid] = a.id,
-                            1 => base_second[nid] = a.id,
-                            _ => {}
-                        }
-                        if base_occ[nid] < 2 {
-                            base_touched.push(nid);
-                        }
-                        base_occ[nid] += 1;
-                        active.swap_remove(j);
-                        continue;
-                    }
-                } else if trapped_now[ai] && !a.is_trapped() {
-                    a.set_trapped(true);
-                    match base_occ[nid] {
-                        0 => base_first[nid] = a.id,
-                        1 => base_second[nid] = a.id,
-                        _ => {}
+            } else {
+                if self.defends(args, nid) {
+                    // Killed on the doorstep: the ant never takes the slot
+                    if occupant[from.index()] == ai as u32 {
+                        occupant[from.index()] = u32::MAX;
                     }
-                    if base_occ[nid] < 2 {
-                        base_touched.push(nid);
+                    self.repel(world, &mut ants[ai], nid);
+                    continue;
+                }
+                let other = occupant[nid];
+                if other != u32::MAX && other != ai as u32 && self.armistice(args) {
+                    // Bounce back: the ant stays where it was
+                    let cause = DestructionCause::Averted { ant1: ants[other as usize].id, ant2: ants[ai].id };
+                    self.record(world, nid, cause);
+                    continue;
+                }
+                if occupant[from.index()] == ai as u32 {
+                    occupant[from.index()] = u32::MAX;
+                }
+                ants[ai].move_to(np);
+                self.arrive(&mut ants[ai], nid);
+            }
+            let other = occupant[nid];
+            if other == u32::MAX {
+                occupant[nid] = ai as u32;
+            } else if self.destroys(nid, 2, ants[other as usize].id, ants[ai].id) {
+                if self.near_miss(args) {
+                    // The colony keeps its first occupant; the newcomer just passes through
+                    let cause = DestructionCause::NearMiss { ant1: ants[other as usize].id, ant2: ants[ai].id };
+                    self.record(world, nid, cause);
+                } else {
+                    self.destroy_colony(world, args, nid, ants[other as usize].id, ants[ai].id, blasted);
+                    occupant[nid] = u32::MAX;
+                    for victim in [other as usize, ai] {
+                        self.credit_kill(ants[victim].id);
+                        self.kill(&mut ants[victim], DeathReason::Collision);
+                        ants[victim].set_trapped(false);
                     }
-                    base_occ[nid] += 1;
-                    active.swap_remove(j);
+                    self.clear_fallout(ants, occupant, blasted);
                     continue;
                 }
-
-                j += 1;
             }
-
-            // (5) Pure-stationary destruction
-            for &nid in &base_touched {
-                if base_occ[nid] >= 2 && world.nodes[nid].is_alive() {
-                    self.log_destruction(args, world, nid, base_first[nid], base_second[nid]);
-                    world.nodes[nid].destroy();
-                    base_occ[nid] = 0;
-                    base_first[nid] = u32::MAX;
-                    base_second[nid] = u32::MAX;
+            if ants[ai].is_mega() && !became_trapped {
+                // In without a fight: the colony falls with whoever stands there
+                if occupant[nid] == ai as u32 {
+                    occupant[nid] = u32::MAX;
                 }
+                self.trample(world, args, &mut ants[ai], nid, blasted);
+                self.clear_fallout(ants, occupant, blasted);
             }
+        }
 
-            // (6) Early exit
-            let alive_ants = ants.iter().filter(|a| a.is_alive()).count();
-            if alive_ants <= 1 {
-                break;
+        active.retain(|&ai| {
+            let a = &ants[ai];
+            let moving = !a.is_trapped() && !a.has_max_moves();
+            // Ants that never had a move to make did not stop this tick
+            if a.is_alive() && !moving && (a.is_trapped() || a.moves > 0) {
+                self.stopped(a);
+            }
+            a.is_alive() && moving
+        });
+        self.spawn_workers(ants, active, args);
+
+        debug_assert_eq!(self.ants_alive, ants.iter().filter(|a| a.is_alive()).count());
+        *done = self.lone_exit && self.ants_alive <= 1 && self.reinforcements.is_empty();
+        true
+    }
+
+    /// Kill the occupants of the `blasted` colonies under sequential
+    /// scheduling and empty the list
+    fn clear_fallout(&mut self, ants: &mut [Ant], occupant: &mut [u32], blasted: &mut Vec<usize>) {
+        for &b in blasted.iter() {
+            let o = occupant[b];
+            if o != u32::MAX {
+                self.kill(&mut ants[o as usize], DeathReason::VanishedWithColony);
+                ants[o as usize].set_trapped(false);
+                occupant[b] = u32::MAX;
             }
         }
+        blasted.clear();
+    }
 
-        sim_start.elapsed()
+    /// Every `args.spawn_every` ticks, each queen that is still moving lays a
+    /// worker on her current colony. Newborns are appended to `ants` and
+    /// `active`; returns how many were spawned.
+    ///
+    /// Only moving queens spawn, so a run still ends once every queen has
+    /// been killed, trapped, or reached her move limit. Newborns draw their
+    /// own limit from `args`.
+    fn spawn_workers(&mut self, ants: &mut Vec<Ant>, active: &mut Vec<usize>, args: &Args) -> usize {
+        if !self.tick.is_multiple_of(u64::from(args.spawn_every)) {
+            return 0;
+        }
+        let before = ants.len();
+        for i in 0..active.len() {
+            let queen = &ants[active[i]];
+            if !queen.is_queen()
+                || !queen.is_alive()
+                || queen.is_trapped()
+                || queen.has_max_moves()
+            {
+                continue;
+            }
+            debug_assert!(ants.len() < u32::MAX as usize, "ant ids are u32");
+            let mut worker = Ant::new(ants.len() as u32, queen.pos);
+            worker.max_moves = args.sample_max_moves(&mut self.spawn_rng);
+            worker.energy = self.energy.unwrap_or(u32::MAX);
+            worker.name = self.ant_names.as_mut().and_then(|names| names.name_for(worker.id));
+            active.push(ants.len());
+            ants.push(worker);
+        }
+        self.ants_alive += ants.len() - before;
+        if let Some(delta) = self.delta.as_mut() {
+            delta.ants_born.extend(before as u32..ants.len() as u32);
+        }
+        ants.len() - before
     }
 
-    /// Handle initial collisions at t=0
-    fn handle_initial_collisions(&self, world: &mut World, ants: &mut [Ant], args: &Args) {
-        let n = world.nodes.len();
-        let mut occ_count = vec![0u32; n];
-        let mut occ_first = vec![u32::MAX; n];
-        let mut occ_second = vec![u32::MAX; n];
-        let mut destroyed = vec![false; n];
+    /// Handle initial collisions at t=0.
+    ///
+    /// A crowded start, with fewer than [`DENSE_RATIO`] alive colonies per
+    /// placed ant, sweeps every colony in id order; a sparse one visits only
+    /// the occupied colonies, sorted into the same order, so both resolve
+    /// alike. One more pass over the ants then credits the fights and
+    /// buries everyone standing on a fallen colony.
+    fn handle_initial_collisions(&mut self, world: &mut World, ants: &mut [Ant], args: &Args, bufs: &mut SimBuffers) {
+        let SimBuffers {
+            occ_count,
+            occ_first,
+            occ_second,
+            destroyed,
+            blasted,
+            touched_nodes,
+            ..
+        } = bufs;
 
+        let mut placed: usize = 0;
         for a in ants.iter() {
             if a.is_alive() {
-                let nid = a.pos as usize;
+                placed += 1;
+                let nid = a.pos.index();
                 match occ_count[nid] {
                     0 => {
                         occ_first[nid] = a.id;
                         occ_count[nid] = 1;
+                        touched_nodes.push(nid);
                     }
                     1 => {
                         occ_second[nid] = a.id;
                         occ_count[nid] = 2;
                     }
                     _ => {
-                        occ_count[nid] += 1;
+                        occ_count[nid] = occ_count[nid].saturating_add(1);
                     }
                 }
             }
         }
 
-        for nid in 0..n {
-            if occ_count[nid] >= 2 && world.nodes[nid].is_alive() {
-                self.log_destruction(args, world, nid, occ_first[nid], occ_second[nid]);
-                world.nodes[nid].destroy();
+        let (n, dense) = (world.nodes.len(), world.alive_count() < placed.saturating_mul(DENSE_RATIO));
+        let mut fought = false;
+        let mut resolve = |nid: usize| {
+            if occ_count[nid] >= 2 && world.nodes.is_alive(nid) {
+                if !self.destroys(nid, occ_count[nid], occ_first[nid], occ_second[nid]) {
+                    return;
+                }
+                if self.near_miss(args) {
+                    let cause = DestructionCause::NearMiss { ant1: occ_first[nid], ant2: occ_second[nid] };
+                    self.record(world, nid, cause);
+                    return;
+                }
+                self.destroy_colony(world, args, nid, occ_first[nid], occ_second[nid], blasted);
                 destroyed[nid] = true;
+                fought = true;
             }
+        };
+        if dense {
+            (0..n).for_each(&mut resolve);
+        } else {
+            touched_nodes.sort_unstable();
+            touched_nodes.iter().for_each(|&nid| resolve(nid));
+        }
+        touched_nodes.clear();
+        if !fought {
+            return;
         }
 
-        for a in ants.iter_mut() {
-            if destroyed[a.pos as usize] {
-                a.set_alive(false);
-                a.set_trapped(false);
+        // Everyone on a fought colony took part; the rest of the dead stood
+        // where the blasts reached
+        blasted.sort_unstable();
+        for a in ants.iter_mut().filter(|a| a.is_alive()) {
+            let nid = a.pos.index();
+            let reason = if destroyed[nid] {
+                self.credit_kill(a.id);
+                DeathReason::CollisionAtStart
+            } else if !world.nodes.is_alive(nid) && blasted.binary_search(&nid).is_ok() {
+                DeathReason::VanishedWithColony
+            } else {
+                continue;
+            };
+            self.kill(a, reason);
+            a.set_trapped(false);
+        }
+        blasted.clear();
+    }
+
+    /// Destroy a collided colony, log the fight and propagate any blast
+    #[inline]
+    pub(crate) fn destroy_colony(
+        &mut self,
+        world: &mut World,
+        args: &Args,
+        node_id: usize,
+        ant1: u32,
+        ant2: u32,
+        blasted: &mut Vec<usize>,
+    ) {
+        self.record(world, node_id, DestructionCause::Fight { ant1, ant2 });
+        world.destroy(NodeId::from_index(node_id));
+        self.propagate_blast(world, node_id, args, blasted);
+    }
+
+    /// Destroy every alive colony within `args.blast_radius` hops of `origin`.
+    ///
+    /// The blast follows outgoing tunnels breadth-first and only travels
+    /// through colonies it destroys itself, so already-dead colonies act as
+    /// firebreaks. Collateral colonies are appended to `blasted`.
+    fn propagate_blast(
+        &mut self,
+        world: &mut World,
+        origin: usize,
+        args: &Args,
+        blasted: &mut Vec<usize>,
+    ) {
+        if args.blast_radius == 0 {
+            return;
+        }
+        let mut frontier: Vec<usize> = vec![origin];
+        let mut next: Vec<usize> = Vec::new();
+        for _ in 0..args.blast_radius {
+            for &nid in &frontier {
+                for neighbor in world.nodes.neighbors(nid).into_iter().flatten() {
+                    if !world.is_alive(neighbor) {
+                        continue;
+                    }
+                    let victim = neighbor.index();
+                    let origin = NodeId::from_index(origin);
+                    self.record(world, victim, DestructionCause::Blast { origin });
+                    world.destroy(neighbor);
+                    blasted.push(victim);
+                    next.push(victim);
+                }
             }
+            if next.is_empty() {
+                break;
+            }
+            std::mem::swap(&mut frontier, &mut next);
+            next.clear();
         }
     }
 
-    /// Log colony destruction event
+    /// Whether this tick already hit `--max-destructions-per-tick`
     #[inline]
-    fn log_destruction(&self, args: &Args, world: &World, node_id: usize, ant1: u32, ant2: u32) {
-        if args.suppress_events {
+    pub(crate) fn armistice(&self, args: &Args) -> bool {
+        args.max_destructions_per_tick.is_some_and(|cap| self.fallen >= cap)
+    }
+
+    /// Whether colony `nid` is fortified and kills the ant arriving there
+    /// alone; draws only when `--fortress-defense` is below 1
+    #[inline]
+    pub(crate) fn defends(&mut self, args: &Args, nid: usize) -> bool {
+        if !self.fortified.get(nid).is_some_and(|&f| f) {
+            return false;
+        }
+        args.fortress_defense >= 1.0 || self.defense_rng.f64() < args.fortress_defense
+    }
+
+    /// Mega ant `ant` just entered colony `nid` and tramples it, falling
+    /// with it; `nid` and the blast fallout are appended to `blasted`
+    pub(crate) fn trample(&mut self, world: &mut World, args: &Args, ant: &mut Ant, nid: usize, blasted: &mut Vec<usize>) {
+        self.record(world, nid, DestructionCause::Trampled { ant: ant.id });
+        world.destroy(NodeId::from_index(nid));
+        self.trampled += 1;
+        blasted.push(nid);
+        self.propagate_blast(world, nid, args, blasted);
+        self.credit_kill(ant.id);
+        self.kill(ant, DeathReason::VanishedWithColony);
+        ant.set_trapped(false);
+    }
+
+    /// A fortified colony `nid` kills `ant`, which arrived there alone
+    #[inline]
+    pub(crate) fn repel(&mut self, world: &World, ant: &mut Ant, nid: usize) {
+        self.record(world, nid, DestructionCause::Repelled { ant: ant.id });
+        self.kill(ant, DeathReason::Repelled);
+    }
+
+    /// Whether `ants` ants meeting on colony `nid` (`ant1` and `ant2` first)
+    /// destroy it, as the [`DestructionRule`] has it
+    #[inline]
+    pub(crate) fn destroys(&mut self, nid: usize, ants: u32, ant1: u32, ant2: u32) -> bool {
+        let tick = self.tick;
+        self.destruction_rule.as_mut().is_none_or(|rule| {
+            rule.destroys(&Occupancy { tick, colony: NodeId::from_index(nid), ants, ant1, ant2 })
+        })
+    }
+
+    /// Draw whether ants meeting under `--collision-prob` pass each other by
+    #[inline]
+    pub(crate) fn near_miss(&mut self, args: &Args) -> bool {
+        args.collision_prob.is_some_and(|p| self.collision_rng.f64() >= p)
+    }
+
+    /// Log a destruction and append it to the event history (up to the
+    /// limit), or hold it back until the tick ends under `--stable-event-order`
+    #[inline]
+    pub(crate) fn record(&mut self, world: &World, node_id: usize, cause: DestructionCause) {
+        let event = DestructionEvent {
+            tick: self.tick,
+            node_id: NodeId::from_index(node_id),
+            cause,
+        };
+        self.fallen += cause.destroyed() as u32;
+        if cause.destroyed() {
+            self.last_destruction = Some(self.tick);
+            self.destruction_ticks.push(self.tick);
+            if let Some(delta) = self.delta.as_mut() {
+                delta.destroyed.push(event.node_id);
+            }
+        }
+        self.near_misses += matches!(cause, DestructionCause::NearMiss { .. }) as usize;
+        self.repelled += matches!(cause, DestructionCause::Repelled { .. }) as usize;
+        if self.stable_order {
+            self.pending.push(event);
+        } else {
+            self.emit(world, event);
+        }
+    }
+
+    /// Emit the events held back under `--stable-event-order`, ordered by
+    /// tick and colony id (events on one colony keep their order)
+    pub(crate) fn flush_events(&mut self, world: &World) {
+        if self.pending.is_empty() {
             return;
         }
-        println!(
-            "{} {} {} {}",
-            "💥".red(),
-            world.get_colony_name(node_id as u32).bright_red(),
-            "has been destroyed by".red(),
-            format!("ant {} and ant {}", ant1, ant2).yellow()
-        );
+        let mut pending = std::mem::take(&mut self.pending);
+        pending.sort_by_key(|e| (e.tick, e.node_id));
+        for event in pending.drain(..) {
+            self.emit(world, event);
+        }
+        self.pending = pending;
+    }
+
+    /// Hand one event to the log, the hooks and the histories
+    #[inline]
+    fn emit(&mut self, world: &World, event: DestructionEvent) {
+        self.log.write(&event, world, self.ant_names.as_ref());
+        if let Some(hooks) = self.hooks.as_mut() {
+            hooks.on_destruction(&event, WorldView::new(world));
+        }
+        self.tick_events.push(event);
+        if self.event_limit.is_some_and(|limit| self.events.len() >= limit) {
+            self.events_dropped += 1;
+        } else {
+            self.events.push(event);
+        }
+    }
+
+    /// Step through a run one tick at a time.
+    ///
+    /// Placement limits and t=0 collisions are resolved immediately; each
+    /// call to `next` then runs one tick and yields a [`TickSnapshot`](crate::simulation::TickSnapshot). The
+    /// iterator ends when the run would have stopped, and dropping it early
+    /// simply abandons the run. Latency is not measured in this mode.
+    pub fn iter_ticks<'a>(
+        &'a mut self,
+        world: &'a mut World,
+        ants: &'a mut Vec<Ant>,
+        args: &'a Args,
+        rng: &'a mut fastrand::Rng,
+    ) -> Ticks<'a> {
+        Ticks::new(self, world, ants, args, rng)
     }
 
-    /// Print simulation summary
+    /// Step through a run one tick at a time, like
+    /// [`iter_ticks`](Self::iter_ticks), yielding only what changed.
+    ///
+    /// The first [`TickDelta`] is t=0's (placement fights), then one comes
+    /// per tick. Memory stays flat however large the world: no per-ant
+    /// snapshot is taken, so this suits visualizers that apply each delta to
+    /// the world and ants they started from.
+    pub fn iter_deltas<'a>(
+        &'a mut self,
+        world: &'a mut World,
+        ants: &'a mut Vec<Ant>,
+        args: &'a Args,
+        rng: &'a mut fastrand::Rng,
+    ) -> TickDeltas<'a> {
+        TickDeltas::new(self, world, ants, args, rng)
+    }
+
+    /// Print the summary lines where `--porcelain` / `--quiet` send chatter
+    /// (the surviving world is printed separately, see [`World::print_world`])
+    #[cfg(feature = "cli")]
     pub fn print_summary(
         &self,
         world: &World,
+        ants: &[Ant],
         args: &Args,
         simulation_time: std::time::Duration,
     ) {
-        world.print_world();
-
-        let survivors = world.count_survivors();
-        println!(
-            "\n{}\n{} {:.3} ms {} {} {} {} {}",
-            "===".bright_blue().bold(),
-            "⏱️  Simulation Latency:".green().bold(),
-            simulation_time.as_secs_f64() * 1000.0,
-            "(map loaded)".dimmed(),
-            "|".dimmed(),
-            format!("ants={}", args.ants).cyan(),
-            format!("max_moves={}", args.max_moves).cyan(),
-            format!("survivors={}", survivors).cyan(),
-        );
+        if let Some(mut out) = args.chatter_out() {
+            let _ = self.write_summary(&mut out, world, ants, args, simulation_time);
+        }
+    }
+
+    /// Write the summary lines to `out`, as the text [`ReportWriter`] renders
+    /// this run's [`Report`]
+    #[cfg(feature = "cli")]
+    pub fn write_summary<W: Write + ?Sized>(
+        &self,
+        mut out: &mut W,
+        world: &World,
+        ants: &[Ant],
+        args: &Args,
+        simulation_time: std::time::Duration,
+    ) -> std::io::Result<()> {
+        let report = Report::new(world, ants, args, self, simulation_time);
+        TextWriter { color: true }.write(&report, &mut out)
     }
 }
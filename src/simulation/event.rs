@@ -0,0 +1,43 @@
+use crate::node_id::NodeId;
+use serde::{Deserialize, Serialize};
+
+/// Why a colony was destroyed
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case", tag = "kind")]
+pub enum DestructionCause {
+    /// Two (or more) ants fought there; the first two are recorded
+    Fight { ant1: u32, ant2: u32 },
+    /// Collateral damage from a blast that started at `origin`
+    Blast { origin: NodeId },
+    /// A fight was called off by the per-tick destruction cap; the colony
+    /// survives and the arriving ants bounced back
+    Averted { ant1: u32, ant2: u32 },
+    /// Ants met but, under `--collision-prob`, did not fight; both carry on
+    NearMiss { ant1: u32, ant2: u32 },
+    /// A fortified colony killed `ant`, which arrived alone; the colony stands
+    Repelled { ant: u32 },
+    /// Struck by a meteor under `--chaos`, whoever stood there
+    Meteor,
+    /// Mega ant `ant` entered alone and went down with the colony
+    Trampled { ant: u32 },
+}
+
+impl DestructionCause {
+    /// Whether the colony actually fell
+    #[inline]
+    pub fn destroyed(&self) -> bool {
+        !matches!(
+            self,
+            DestructionCause::Averted { .. } | DestructionCause::NearMiss { .. } | DestructionCause::Repelled { .. }
+        )
+    }
+}
+
+/// A colony destruction, stamped with the tick it happened on (0 = initial placement)
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DestructionEvent {
+    pub tick: u64,
+    pub node_id: NodeId,
+    #[serde(flatten)]
+    pub cause: DestructionCause,
+}
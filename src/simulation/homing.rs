@@ -0,0 +1,93 @@
+use crate::ant::Ant;
+use crate::node_id::NodeId;
+use crate::strategy::DirectionBias;
+use crate::world::World;
+use std::collections::VecDeque;
+
+/// Movement under `--strategy homing:H`: after `after` moves an ant steps to
+/// an exit that is one hop closer to its [`Ant::home`], picked at random
+/// among the equally good ones.
+///
+/// Hop counts come from a breadth-first search over the reverse tunnels of
+/// each home, run the first time an ant of that home turns back and kept
+/// for the rest of the run; they follow only colonies that were alive then.
+/// An ant with no closer exit (already home, cut off, or blocked by a
+/// colony that fell since) takes a random step instead, so raiders that
+/// made it back keep circling their home. Random steps follow `--bias`.
+pub(crate) struct Homing {
+    after: u32,
+    bias: Option<DirectionBias>,
+    /// Hops to each colony's home, indexed by home id, filled on demand
+    hops: Vec<Option<Box<[u32]>>>,
+}
+
+impl Homing {
+    pub(crate) fn new(after: u32, bias: Option<DirectionBias>, world: &World) -> Self {
+        Self { after, bias, hops: vec![None; world.nodes.len()] }
+    }
+
+    /// Next position for `ant`, as [`World::random_step`]
+    #[inline]
+    pub(crate) fn choose(&mut self, world: &World, ant: &Ant, rng: &mut fastrand::Rng) -> (NodeId, bool) {
+        if ant.moves < self.after || ant.pos == ant.home {
+            return world.random_step(ant.pos, self.bias.as_ref(), rng);
+        }
+        let hops = self.hops_to(world, ant.home);
+        let here = hops[ant.pos.index()];
+        let mut opts = [ant.pos; 4];
+        let mut k = 0;
+        for n in world.nodes.neighbors(ant.pos.index()).into_iter().flatten() {
+            if hops[n.index()] < here && world.is_alive(n) {
+                opts[k] = n;
+                k += 1;
+            }
+        }
+        match k {
+            0 => world.random_step(ant.pos, self.bias.as_ref(), rng),
+            1 => (opts[0], false),
+            _ => (opts[rng.usize(..k)], false),
+        }
+    }
+
+    /// Hops from every colony to `home` (`u32::MAX` when there is no path)
+    fn hops_to(&mut self, world: &World, home: NodeId) -> &[u32] {
+        self.hops[home.index()].get_or_insert_with(|| {
+            let mut hops = vec![u32::MAX; world.nodes.len()].into_boxed_slice();
+            let mut queue = VecDeque::from([home]);
+            hops[home.index()] = 0;
+            while let Some(node) = queue.pop_front() {
+                let next = hops[node.index()] + 1;
+                for &src in world.in_neighbors(node) {
+                    if hops[src.index()] == u32::MAX && world.is_alive(src) {
+                        hops[src.index()] = next;
+                        queue.push_back(src);
+                    }
+                }
+            }
+            hops
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::world::parser::parse_world_from_str;
+
+    #[test]
+    fn test_heads_home_along_shortest_path() {
+        // A - B - C - D in a line, plus a shortcut D -> B
+        let (names, nodes) = parse_world_from_str("A east=B\nB west=A east=C\nC west=B east=D\nD west=C north=B\n");
+        let world = World::new(names, nodes);
+        let mut homing = Homing::new(2, None, &world);
+        let mut rng = fastrand::Rng::with_seed(1);
+
+        let mut ant = Ant::new(0, NodeId::new(3));
+        ant.home = NodeId::new(0);
+        ant.moves = 2;
+        assert_eq!(homing.choose(&world, &ant, &mut rng), (NodeId::new(1), false));
+        ant.pos = NodeId::new(1);
+        assert_eq!(homing.choose(&world, &ant, &mut rng), (NodeId::new(0), false));
+        assert_eq!(homing.hops_to(&world, NodeId::new(0)), [0, 1, 2, 2]);
+    }
+}
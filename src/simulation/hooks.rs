@@ -0,0 +1,49 @@
+use crate::ant::Ant;
+use crate::node_id::NodeId;
+use crate::simulation::event::DestructionEvent;
+use crate::simulation::watch::ColonyWatch;
+use crate::world::WorldView;
+
+/// Read-only view of a run at a tick boundary
+#[derive(Clone, Copy, Debug)]
+pub struct TickView<'a> {
+    pub tick: u64,
+    pub world: WorldView<'a>,
+    pub ants: &'a [Ant],
+}
+
+/// Observer callbacks the engine calls during a run.
+///
+/// Every method does nothing by default, so an implementation only writes
+/// the ones it needs. Set one on the engine with
+/// [`SimulationEngine::with_hooks`](crate::simulation::SimulationEngine::with_hooks).
+/// Hooks only see shared references and a [`WorldView`], and cannot change the run; use them
+/// for metrics, progress bars or custom logs. They must be `Send`, since
+/// the engine carrying them may move between threads.
+pub trait SimulationHooks {
+    /// A tick is about to move ants (`view.tick` is the new tick number)
+    fn on_tick_start(&mut self, _view: &TickView<'_>) {}
+
+    /// A tick has finished, with newborn workers already added
+    fn on_tick_end(&mut self, _view: &TickView<'_>) {}
+
+    /// An event was recorded (t=0 included). Averted fights and near misses
+    /// come through here too; `event.cause.destroyed()` tells them apart.
+    fn on_destruction(&mut self, _event: &DestructionEvent, _world: WorldView<'_>) {}
+
+    /// `ant` just died on tick `tick`; `ant.death` says why
+    fn on_ant_dead(&mut self, _ant: &Ant, _tick: u64) {}
+
+    /// `ants` moved onto the watched `colony` during tick `tick`; only a
+    /// [`ColonyWatch`] calls this, after the tick's events
+    fn on_arrivals(&mut self, _tick: u64, _colony: NodeId, _ants: u32, _world: WorldView<'_>) {}
+
+    /// Narrow these hooks to the colonies named in `colonies`, with arrival
+    /// counts on top; see [`ColonyWatch`]
+    fn watch<S: Into<String>>(self, colonies: impl IntoIterator<Item = S>) -> ColonyWatch<Self>
+    where
+        Self: Sized,
+    {
+        ColonyWatch::new(self, colonies)
+    }
+}
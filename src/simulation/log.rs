@@ -0,0 +1,430 @@
+use crate::ant_names::AntNames;
+use crate::simulation::event::{DestructionCause, DestructionEvent};
+use crate::simulation::messages::{MessageTemplates, Piece, Slot, Template};
+use crate::world::World;
+use std::fmt;
+use std::io::{BufWriter, Stderr, Stdout, Write};
+use std::str::FromStr;
+
+const RESET: &str = "\x1b[0m";
+const DIM: &str = "\x1b[2m";
+const RED: &str = "\x1b[31m";
+const BRIGHT_RED: &str = "\x1b[91m";
+const YELLOW: &str = "\x1b[33m";
+const GREEN: &str = "\x1b[32m";
+
+/// Formats a destruction event as a log line without allocating
+pub struct EventLine<'a> {
+    pub event: &'a DestructionEvent,
+    pub world: &'a World,
+    /// Ant names to show instead of `ant N`
+    pub names: Option<&'a AntNames>,
+    /// Wording to use instead of the built-in English lines
+    pub messages: Option<&'a MessageTemplates>,
+    pub color: bool,
+}
+
+impl EventLine<'_> {
+    /// Write `text` wrapped in an ANSI style when color is enabled
+    #[inline]
+    fn styled(&self, f: &mut fmt::Formatter<'_>, style: &str, text: impl fmt::Display) -> fmt::Result {
+        if self.color {
+            write!(f, "{style}{text}{RESET}")
+        } else {
+            write!(f, "{text}")
+        }
+    }
+
+    /// Write `A and B` for the two ants of a meeting
+    #[inline]
+    fn pair(&self, f: &mut fmt::Formatter<'_>, ant1: u32, ant2: u32) -> fmt::Result {
+        let (a, b) = (AntNames::label(self.names, ant1), AntNames::label(self.names, ant2));
+        self.styled(f, YELLOW, format_args!("{} and {}", a, b))
+    }
+
+    /// Write the event through `template`, styling its pieces like the
+    /// built-in line of the same kind
+    fn render(&self, f: &mut fmt::Formatter<'_>, template: &Template) -> fmt::Result {
+        let ev = self.event;
+        let (text, colony) = if ev.cause.destroyed() { (RED, BRIGHT_RED) } else { (GREEN, GREEN) };
+        let (ant1, ant2) = match ev.cause {
+            DestructionCause::Fight { ant1, ant2 }
+            | DestructionCause::Averted { ant1, ant2 }
+            | DestructionCause::NearMiss { ant1, ant2 } => (ant1, ant2),
+            DestructionCause::Repelled { ant } | DestructionCause::Trampled { ant } => (ant, ant),
+            DestructionCause::Blast { .. } | DestructionCause::Meteor => (u32::MAX, u32::MAX),
+        };
+        for piece in template.pieces() {
+            match piece {
+                Piece::Text(t) => self.styled(f, text, t)?,
+                Piece::Slot(Slot::Tick) => self.styled(f, DIM, ev.tick)?,
+                Piece::Slot(Slot::Colony) => self.styled(f, colony, self.world.get_colony_name(ev.node_id))?,
+                Piece::Slot(Slot::Ant1 | Slot::Ant) => self.styled(f, YELLOW, AntNames::label(self.names, ant1))?,
+                Piece::Slot(Slot::Ant2) => self.styled(f, YELLOW, AntNames::label(self.names, ant2))?,
+                Piece::Slot(Slot::Origin) => {
+                    if let DestructionCause::Blast { origin } = ev.cause {
+                        self.styled(f, YELLOW, self.world.get_colony_name(origin))?;
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for EventLine<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let ev = self.event;
+        self.styled(f, DIM, format_args!("[t={}]", ev.tick))?;
+        f.write_str(" ")?;
+        if let Some(template) = self.messages.and_then(|m| m.get(&ev.cause)) {
+            return self.render(f, template);
+        }
+        match ev.cause {
+            DestructionCause::Fight { ant1, ant2 } => {
+                self.styled(f, RED, "💥")?;
+                f.write_str(" ")?;
+                self.styled(f, BRIGHT_RED, self.world.get_colony_name(ev.node_id))?;
+                f.write_str(" ")?;
+                self.styled(f, RED, "has been destroyed by")?;
+                f.write_str(" ")?;
+                self.pair(f, ant1, ant2)
+            }
+            DestructionCause::Blast { origin } => {
+                self.styled(f, RED, "🔥")?;
+                f.write_str(" ")?;
+                self.styled(f, BRIGHT_RED, self.world.get_colony_name(ev.node_id))?;
+                f.write_str(" ")?;
+                self.styled(f, RED, "has been destroyed by the blast from")?;
+                f.write_str(" ")?;
+                self.styled(f, YELLOW, self.world.get_colony_name(origin))
+            }
+            DestructionCause::Averted { ant1, ant2 } => {
+                self.styled(f, GREEN, "🕊️")?;
+                f.write_str(" ")?;
+                self.styled(f, GREEN, self.world.get_colony_name(ev.node_id))?;
+                f.write_str(" ")?;
+                self.styled(f, GREEN, "was spared: armistice between")?;
+                f.write_str(" ")?;
+                self.pair(f, ant1, ant2)
+            }
+            DestructionCause::NearMiss { ant1, ant2 } => {
+                self.styled(f, GREEN, "💨")?;
+                f.write_str(" ")?;
+                self.styled(f, GREEN, self.world.get_colony_name(ev.node_id))?;
+                f.write_str(" ")?;
+                self.styled(f, GREEN, "was spared: near miss between")?;
+                f.write_str(" ")?;
+                self.pair(f, ant1, ant2)
+            }
+            DestructionCause::Repelled { ant } => {
+                self.styled(f, GREEN, "🛡️")?;
+                f.write_str(" ")?;
+                self.styled(f, GREEN, self.world.get_colony_name(ev.node_id))?;
+                f.write_str(" ")?;
+                self.styled(f, GREEN, "repelled")?;
+                f.write_str(" ")?;
+                self.styled(f, YELLOW, AntNames::label(self.names, ant))
+            }
+            DestructionCause::Meteor => {
+                self.styled(f, RED, "☄️")?;
+                f.write_str(" ")?;
+                self.styled(f, BRIGHT_RED, self.world.get_colony_name(ev.node_id))?;
+                f.write_str(" ")?;
+                self.styled(f, RED, "has been struck by a meteor")
+            }
+            DestructionCause::Trampled { ant } => {
+                self.styled(f, RED, "🦣")?;
+                f.write_str(" ")?;
+                self.styled(f, BRIGHT_RED, self.world.get_colony_name(ev.node_id))?;
+                f.write_str(" ")?;
+                self.styled(f, RED, "has been trampled by mega")?;
+                f.write_str(" ")?;
+                self.styled(f, YELLOW, AntNames::label(self.names, ant))
+            }
+        }
+    }
+}
+
+/// Set of event kinds to log: `fight`, `blast`, `averted`, `near-miss`,
+/// `repelled`, `meteor`, `trampled`, or `destroyed` as shorthand for
+/// `fight,blast,meteor,trampled`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct EventFilter(u8);
+
+impl EventFilter {
+    pub const NONE: Self = Self(0);
+    pub const FIGHT: Self = Self(0b001);
+    pub const BLAST: Self = Self(0b010);
+    pub const AVERTED: Self = Self(0b100);
+    pub const NEAR_MISS: Self = Self(0b1000);
+    pub const REPELLED: Self = Self(0b1_0000);
+    pub const METEOR: Self = Self(0b10_0000);
+    pub const TRAMPLED: Self = Self(0b100_0000);
+    pub const DESTROYED: Self = Self(Self::FIGHT.0 | Self::BLAST.0 | Self::METEOR.0 | Self::TRAMPLED.0);
+    pub const ALL: Self = Self(0b111_1111);
+
+    /// Union of two filters
+    pub const fn with(self, other: Self) -> Self {
+        Self(self.0 | other.0)
+    }
+
+    /// Whether events with this cause pass the filter
+    #[inline]
+    pub fn allows(&self, cause: &DestructionCause) -> bool {
+        let kind = match cause {
+            DestructionCause::Fight { .. } => Self::FIGHT,
+            DestructionCause::Blast { .. } => Self::BLAST,
+            DestructionCause::Averted { .. } => Self::AVERTED,
+            DestructionCause::NearMiss { .. } => Self::NEAR_MISS,
+            DestructionCause::Repelled { .. } => Self::REPELLED,
+            DestructionCause::Meteor => Self::METEOR,
+            DestructionCause::Trampled { .. } => Self::TRAMPLED,
+        };
+        self.0 & kind.0 != 0
+    }
+}
+
+impl Default for EventFilter {
+    fn default() -> Self {
+        Self::ALL
+    }
+}
+
+impl FromStr for EventFilter {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.split(',').map(str::trim).filter(|t| !t.is_empty()).try_fold(Self::NONE, |acc, kind| {
+            let kind = match kind {
+                "fight" => Self::FIGHT,
+                "blast" => Self::BLAST,
+                "averted" => Self::AVERTED,
+                "near-miss" => Self::NEAR_MISS,
+                "repelled" => Self::REPELLED,
+                "meteor" => Self::METEOR,
+                "trampled" => Self::TRAMPLED,
+                "destroyed" => Self::DESTROYED,
+                "all" => Self::ALL,
+                other => {
+                    return Err(format!(
+                        "unknown event kind '{other}' (expected fight, blast, averted, near-miss, repelled, meteor, trampled, destroyed or all)"
+                    ))
+                }
+            };
+            Ok(acc.with(kind))
+        })
+    }
+}
+
+/// Standard stream the event lines go to; it is locked per buffered write
+/// rather than for the whole run, so a run paused between ticks (e.g. an
+/// async `RunStream` waiting to be polled) does not block other threads'
+/// output
+enum Sink {
+    Stdout(Stdout),
+    Stderr(Stderr),
+}
+
+impl Write for Sink {
+    #[inline]
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        match self {
+            Sink::Stdout(out) => out.write(buf),
+            Sink::Stderr(out) => out.write(buf),
+        }
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        match self {
+            Sink::Stdout(out) => out.flush(),
+            Sink::Stderr(out) => out.flush(),
+        }
+    }
+}
+
+/// Buffered sink for event lines, open while a run is logging
+pub struct EventLog {
+    out: Option<BufWriter<Sink>>,
+    color: bool,
+    filter: EventFilter,
+    messages: Option<MessageTemplates>,
+}
+
+impl EventLog {
+    /// A log that drops everything until [`EventLog::open`] is called
+    pub fn closed() -> Self {
+        Self {
+            out: None,
+            color: false,
+            filter: EventFilter::ALL,
+            messages: None,
+        }
+    }
+
+    /// Word event lines with `messages` instead of the built-in English
+    pub fn set_messages(&mut self, messages: MessageTemplates) {
+        self.messages = Some(messages);
+    }
+
+    /// Start logging events that pass `filter` to stdout (stderr if
+    /// `stderr` is set), honoring `colored`'s color detection (always plain
+    /// without the `cli` feature)
+    pub fn open(&mut self, filter: EventFilter, stderr: bool) {
+        self.filter = filter;
+        #[cfg(feature = "cli")]
+        {
+            self.color = colored::control::SHOULD_COLORIZE.should_colorize();
+        }
+        let sink = if stderr { Sink::Stderr(std::io::stderr()) } else { Sink::Stdout(std::io::stdout()) };
+        self.out = Some(BufWriter::with_capacity(64 * 1024, sink));
+    }
+
+    /// Flush pending lines and release stdout
+    pub fn close(&mut self) {
+        if let Some(mut out) = self.out.take() {
+            let _ = out.flush();
+        }
+    }
+
+    /// Push pending lines out without releasing stdout
+    pub fn flush(&mut self) {
+        if let Some(out) = &mut self.out {
+            let _ = out.flush();
+        }
+    }
+
+    /// Write one event line; logging is best-effort and never fails the run
+    #[inline]
+    pub fn write(&mut self, event: &DestructionEvent, world: &World, names: Option<&AntNames>) {
+        if let Some(out) = &mut self.out {
+            if !self.filter.allows(&event.cause) {
+                return;
+            }
+            let line = EventLine {
+                event,
+                world,
+                names,
+                messages: self.messages.as_ref(),
+                color: self.color,
+            };
+            let _ = writeln!(out, "{}", line);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::node_id::NodeId;
+    use crate::world::parser::parse_world_from_str;
+
+    #[test]
+    fn test_plain_event_lines() {
+        let (names, nodes) = parse_world_from_str("Fizz north=Buzz\n");
+        let world = World::new(names, nodes);
+
+        let fight = DestructionEvent {
+            tick: 3,
+            node_id: NodeId::new(0),
+            cause: DestructionCause::Fight { ant1: 10, ant2: 34 },
+        };
+        let line = EventLine { event: &fight, world: &world, names: None, messages: None, color: false };
+        assert_eq!(line.to_string(), "[t=3] 💥 Fizz has been destroyed by ant 10 and ant 34");
+
+        let blast = DestructionEvent {
+            tick: 3,
+            node_id: NodeId::new(1),
+            cause: DestructionCause::Blast { origin: NodeId::new(0) },
+        };
+        let line = EventLine { event: &blast, world: &world, names: None, messages: None, color: false };
+        assert_eq!(line.to_string(), "[t=3] 🔥 Buzz has been destroyed by the blast from Fizz");
+
+        let averted = DestructionEvent {
+            tick: 4,
+            node_id: NodeId::new(0),
+            cause: DestructionCause::Averted { ant1: 1, ant2: 2 },
+        };
+        let line = EventLine { event: &averted, world: &world, names: None, messages: None, color: false };
+        assert_eq!(line.to_string(), "[t=4] 🕊️ Fizz was spared: armistice between ant 1 and ant 2");
+
+        let near_miss = DestructionEvent {
+            tick: 5,
+            node_id: NodeId::new(1),
+            cause: DestructionCause::NearMiss { ant1: 3, ant2: 4 },
+        };
+        let line = EventLine { event: &near_miss, world: &world, names: None, messages: None, color: false };
+        assert_eq!(line.to_string(), "[t=5] 💨 Buzz was spared: near miss between ant 3 and ant 4");
+
+        let repelled = DestructionEvent {
+            tick: 6,
+            node_id: NodeId::new(0),
+            cause: DestructionCause::Repelled { ant: 7 },
+        };
+        let line = EventLine { event: &repelled, world: &world, names: None, messages: None, color: false };
+        assert_eq!(line.to_string(), "[t=6] 🛡️ Fizz repelled ant 7");
+    }
+
+    #[test]
+    fn test_event_lines_use_ant_names() {
+        let (names, nodes) = parse_world_from_str("Fizz\n");
+        let world = World::new(names, nodes);
+        let mut ant_names = AntNames::generated();
+        ant_names.name_for(2);
+        let fight = DestructionEvent {
+            tick: 1,
+            node_id: NodeId::new(0),
+            cause: DestructionCause::Fight { ant1: 2, ant2: 9 },
+        };
+        let line = EventLine { event: &fight, world: &world, names: Some(&ant_names), messages: None, color: false };
+        assert_eq!(line.to_string(), "[t=1] 💥 Fizz has been destroyed by ant-green-2 and ant 9");
+    }
+
+    #[test]
+    fn test_templated_event_lines() {
+        let (names, nodes) = parse_world_from_str("Fizz north=Buzz\n");
+        let world = World::new(names, nodes);
+        let messages: MessageTemplates =
+            "fight = 💥 {colony} fue destruida por {ant1} y {ant2}\nblast = 🔥 {colony} ardió por {origin}".parse().unwrap();
+        let line = |cause| {
+            let event = DestructionEvent { tick: 2, node_id: NodeId::new(1), cause };
+            EventLine { event: &event, world: &world, names: None, messages: Some(&messages), color: false }.to_string()
+        };
+        assert_eq!(line(DestructionCause::Fight { ant1: 1, ant2: 5 }), "[t=2] 💥 Buzz fue destruida por ant 1 y ant 5");
+        assert_eq!(line(DestructionCause::Blast { origin: NodeId::new(0) }), "[t=2] 🔥 Buzz ardió por Fizz");
+        // Kinds without a template keep the English line
+        assert_eq!(line(DestructionCause::Repelled { ant: 3 }), "[t=2] 🛡️ Buzz repelled ant 3");
+    }
+
+    #[test]
+    fn test_event_filter_parse_and_allows() {
+        let fight = DestructionCause::Fight { ant1: 0, ant2: 1 };
+        let blast = DestructionCause::Blast { origin: NodeId::new(0) };
+        let averted = DestructionCause::Averted { ant1: 0, ant2: 1 };
+
+        let destroyed: EventFilter = "destroyed".parse().unwrap();
+        assert!(destroyed.allows(&fight) && destroyed.allows(&blast) && !destroyed.allows(&averted));
+
+        let some: EventFilter = "blast, averted".parse().unwrap();
+        assert!(!some.allows(&fight) && some.allows(&blast) && some.allows(&averted));
+
+        assert_eq!("".parse::<EventFilter>().unwrap(), EventFilter::NONE);
+        assert_eq!("fight,blast,averted,near-miss,repelled,meteor,trampled".parse::<EventFilter>().unwrap(), EventFilter::ALL);
+        assert!(!EventFilter::DESTROYED.allows(&DestructionCause::NearMiss { ant1: 0, ant2: 1 }));
+        assert!("trapped".parse::<EventFilter>().is_err());
+    }
+
+    #[test]
+    fn test_colored_event_line_wraps_segments() {
+        let (names, nodes) = parse_world_from_str("Fizz\n");
+        let world = World::new(names, nodes);
+        let fight = DestructionEvent {
+            tick: 0,
+            node_id: NodeId::new(0),
+            cause: DestructionCause::Fight { ant1: 1, ant2: 2 },
+        };
+        let line = EventLine { event: &fight, world: &world, names: None, messages: None, color: true }.to_string();
+        assert!(line.starts_with("\x1b[2m[t=0]\x1b[0m "));
+        assert!(line.contains("\x1b[91mFizz\x1b[0m"));
+    }
+}
@@ -0,0 +1,204 @@
+use crate::simulation::event::DestructionCause;
+use std::str::FromStr;
+
+/// Value a message template can splice in with `{name}`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Slot {
+    /// `{tick}`: tick of the event
+    Tick,
+    /// `{colony}`: colony the event happened on
+    Colony,
+    /// `{ant1}` / `{ant2}`: the two ants of a meeting
+    Ant1,
+    Ant2,
+    /// `{ant}`: the ant a fortified colony repelled
+    Ant,
+    /// `{origin}`: colony a blast started from
+    Origin,
+}
+
+impl Slot {
+    fn from_name(name: &str) -> Option<Self> {
+        Some(match name {
+            "tick" => Slot::Tick,
+            "colony" => Slot::Colony,
+            "ant1" => Slot::Ant1,
+            "ant2" => Slot::Ant2,
+            "ant" => Slot::Ant,
+            "origin" => Slot::Origin,
+            _ => return None,
+        })
+    }
+}
+
+/// Piece of a parsed template
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Piece {
+    Text(String),
+    Slot(Slot),
+}
+
+/// One event message: literal text with `{slot}` placeholders (`{{` and
+/// `}}` for literal braces)
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Template {
+    pieces: Vec<Piece>,
+}
+
+impl Template {
+    /// Parse `src`, accepting only the placeholders in `slots`
+    pub fn parse(src: &str, slots: &[Slot]) -> Result<Self, String> {
+        let mut pieces = Vec::new();
+        let mut text = String::new();
+        let mut chars = src.chars().peekable();
+        while let Some(c) = chars.next() {
+            match c {
+                '{' if chars.peek() == Some(&'{') => {
+                    chars.next();
+                    text.push('{');
+                }
+                '}' if chars.peek() == Some(&'}') => {
+                    chars.next();
+                    text.push('}');
+                }
+                '{' => {
+                    let mut name = String::new();
+                    loop {
+                        match chars.next() {
+                            Some('}') => break,
+                            Some(c) => name.push(c),
+                            None => return Err(format!("unclosed placeholder '{{{name}'")),
+                        }
+                    }
+                    let slot = Slot::from_name(&name)
+                        .filter(|slot| slots.contains(slot))
+                        .ok_or_else(|| format!("unknown placeholder '{{{name}}}'"))?;
+                    if !text.is_empty() {
+                        pieces.push(Piece::Text(std::mem::take(&mut text)));
+                    }
+                    pieces.push(Piece::Slot(slot));
+                }
+                '}' => return Err("unmatched '}' (write '}}' for a literal brace)".to_string()),
+                c => text.push(c),
+            }
+        }
+        if !text.is_empty() {
+            pieces.push(Piece::Text(text));
+        }
+        Ok(Self { pieces })
+    }
+
+    pub fn pieces(&self) -> &[Piece] {
+        &self.pieces
+    }
+}
+
+/// Event messages that replace the built-in English lines, one template per
+/// event kind; kinds left unset keep the English line.
+///
+/// The source has one `kind = template` line per kind (`fight`, `blast`,
+/// `averted`, `near-miss`, `repelled`, `meteor`, `trampled`); blank lines and lines starting with
+/// `#` are skipped. Templates word the part after the `[t=N]` prefix.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct MessageTemplates {
+    fight: Option<Template>,
+    blast: Option<Template>,
+    averted: Option<Template>,
+    near_miss: Option<Template>,
+    repelled: Option<Template>,
+    meteor: Option<Template>,
+    trampled: Option<Template>,
+}
+
+impl MessageTemplates {
+    /// Templates read from a file, as [`MessageTemplates::from_str`]
+    pub fn read(path: &str) -> std::io::Result<Self> {
+        std::fs::read_to_string(path)?
+            .parse()
+            .map_err(|err| std::io::Error::new(std::io::ErrorKind::InvalidData, format!("{path}: {err}")))
+    }
+
+    /// Template for events with this cause, if one was given
+    #[inline]
+    pub fn get(&self, cause: &DestructionCause) -> Option<&Template> {
+        match cause {
+            DestructionCause::Fight { .. } => self.fight.as_ref(),
+            DestructionCause::Blast { .. } => self.blast.as_ref(),
+            DestructionCause::Averted { .. } => self.averted.as_ref(),
+            DestructionCause::NearMiss { .. } => self.near_miss.as_ref(),
+            DestructionCause::Repelled { .. } => self.repelled.as_ref(),
+            DestructionCause::Meteor => self.meteor.as_ref(),
+            DestructionCause::Trampled { .. } => self.trampled.as_ref(),
+        }
+    }
+}
+
+impl FromStr for MessageTemplates {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        const MEETING: &[Slot] = &[Slot::Tick, Slot::Colony, Slot::Ant1, Slot::Ant2];
+        let mut templates = Self::default();
+        for (i, line) in s.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let at = |err: String| format!("line {}: {err}", i + 1);
+            let (kind, template) = line.split_once('=').ok_or_else(|| at("expected 'kind = template'".to_string()))?;
+            let (slot, slots): (&mut Option<Template>, &[Slot]) = match kind.trim() {
+                "fight" => (&mut templates.fight, MEETING),
+                "blast" => (&mut templates.blast, &[Slot::Tick, Slot::Colony, Slot::Origin]),
+                "averted" => (&mut templates.averted, MEETING),
+                "near-miss" => (&mut templates.near_miss, MEETING),
+                "repelled" => (&mut templates.repelled, &[Slot::Tick, Slot::Colony, Slot::Ant]),
+                "meteor" => (&mut templates.meteor, &[Slot::Tick, Slot::Colony]),
+                "trampled" => (&mut templates.trampled, &[Slot::Tick, Slot::Colony, Slot::Ant]),
+                other => {
+                    return Err(at(format!(
+                        "unknown event kind '{other}' (expected fight, blast, averted, near-miss, repelled, meteor or trampled)"
+                    )))
+                }
+            };
+            *slot = Some(Template::parse(template.trim(), slots).map_err(at)?);
+        }
+        Ok(templates)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_template_parse_splits_text_and_slots() {
+        let t = Template::parse("{colony} cayó ante {ant1} y {ant2} {{t}}", &[Slot::Colony, Slot::Ant1, Slot::Ant2]).unwrap();
+        assert_eq!(
+            t.pieces(),
+            [
+                Piece::Slot(Slot::Colony),
+                Piece::Text(" cayó ante ".to_string()),
+                Piece::Slot(Slot::Ant1),
+                Piece::Text(" y ".to_string()),
+                Piece::Slot(Slot::Ant2),
+                Piece::Text(" {t}".to_string()),
+            ]
+        );
+        assert!(Template::parse("{origin}", &[Slot::Colony]).is_err());
+        assert!(Template::parse("a } b", &[]).is_err());
+        assert!(Template::parse("{colony", &[Slot::Colony]).is_err());
+    }
+
+    #[test]
+    fn test_message_templates_from_str() {
+        let src = "# Español\nfight = {colony} destruida\n\nrepelled = {colony} rechazó a {ant}\n";
+        let templates: MessageTemplates = src.parse().unwrap();
+        assert!(templates.get(&DestructionCause::Fight { ant1: 0, ant2: 1 }).is_some());
+        assert!(templates.get(&DestructionCause::NearMiss { ant1: 0, ant2: 1 }).is_none());
+
+        let err = "blast = {ant1}".parse::<MessageTemplates>().unwrap_err();
+        assert!(err.starts_with("line 1: unknown placeholder"), "{err}");
+        assert!("boom = x".parse::<MessageTemplates>().is_err());
+        assert!("fight".parse::<MessageTemplates>().is_err());
+    }
+}
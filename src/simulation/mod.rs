@@ -1,3 +1,33 @@
+#[cfg(feature = "async")]
+pub mod r#async;
+pub mod buffers;
+pub mod control;
 pub mod engine;
+pub mod event;
+pub mod hooks;
+pub(crate) mod homing;
+pub mod log;
+pub mod messages;
+pub mod movement;
+pub mod multiverse;
+pub(crate) mod phases;
+pub mod reinforcements;
+pub mod rules;
+pub mod ticks;
+pub mod watch;
 
+pub use buffers::SimBuffers;
+pub use control::ControlCommand;
+#[cfg(feature = "watch")]
+pub use control::MapWatch;
 pub use engine::SimulationEngine;
+pub use event::{DestructionCause, DestructionEvent};
+pub use hooks::{SimulationHooks, TickView};
+pub use log::EventFilter;
+pub use messages::MessageTemplates;
+pub use movement::{MoveStrategy, ScriptedStrategy};
+pub use multiverse::{Multiverse, MultiverseReport, Portal, PortalEnd, UniverseSummary};
+pub use reinforcements::Reinforcement;
+pub use rules::{DestructionRule, MinAnts, Occupancy, StopCondition};
+pub use ticks::{TickDelta, TickDeltas, TickSnapshot, Ticks};
+pub use watch::{ColonyWatch, WatchLog};
@@ -0,0 +1,94 @@
+use crate::ant::Ant;
+use crate::direction::Direction;
+use crate::node_id::NodeId;
+use crate::world::WorldView;
+
+/// Picks where a moving ant goes next, in place of `--strategy`.
+///
+/// The engine asks once per ant and tick, for every ant still moving (alive,
+/// not trapped and under its move limit). It answers the next colony and
+/// whether the ant is trapped; a trapped ant's colony is its own, and
+/// `--trapped-policy` applies to it as to an ant with no alive exit. The
+/// colony should be an alive neighbor of `ant.pos` unless the ant is
+/// trapped. Set one with
+/// [`SimulationEngine::with_move_strategy`](crate::simulation::SimulationEngine::with_move_strategy).
+pub trait MoveStrategy {
+    fn next(&mut self, world: WorldView<'_>, ant: &Ant, rng: &mut fastrand::Rng) -> (NodeId, bool);
+}
+
+/// Any closure over the world, the ant and the movement stream is a strategy
+impl<F: FnMut(WorldView<'_>, &Ant, &mut fastrand::Rng) -> (NodeId, bool)> MoveStrategy for F {
+    #[inline]
+    fn next(&mut self, world: WorldView<'_>, ant: &Ant, rng: &mut fastrand::Rng) -> (NodeId, bool) {
+        self(world, ant, rng)
+    }
+}
+
+/// Moves written out ahead, per ant id, for tests that need ants to meet at
+/// a given colony and tick.
+///
+/// Move `k` of an ant's script is the direction it takes once it has made
+/// `k` moves. An ant whose script is used up (or that has none) is
+/// trapped, as is one whose next direction has no alive tunnel.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ScriptedStrategy {
+    /// Indexed by ant id
+    scripts: Vec<Vec<Direction>>,
+}
+
+impl ScriptedStrategy {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Script the moves of ant `id`, replacing any script it had
+    pub fn ant(mut self, id: u32, moves: impl IntoIterator<Item = Direction>) -> Self {
+        let i = id as usize;
+        if i >= self.scripts.len() {
+            self.scripts.resize(i + 1, Vec::new());
+        }
+        self.scripts[i] = moves.into_iter().collect();
+        self
+    }
+
+    /// The moves scripted for ant `id`
+    pub fn moves(&self, id: u32) -> &[Direction] {
+        self.scripts.get(id as usize).map_or(&[], Vec::as_slice)
+    }
+}
+
+impl MoveStrategy for ScriptedStrategy {
+    fn next(&mut self, world: WorldView<'_>, ant: &Ant, _rng: &mut fastrand::Rng) -> (NodeId, bool) {
+        let step = self.moves(ant.id).get(ant.moves as usize);
+        match step.and_then(|dir| world.neighbors(ant.pos)[dir.index()]) {
+            Some(to) if world.is_alive(to) => (to, false),
+            _ => (ant.pos, true),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::world::load_world_from_str;
+
+    #[test]
+    fn test_script_then_trapped() {
+        let world = load_world_from_str("A east=B\nB west=A east=C\nC west=B\n", &Default::default()).unwrap();
+        let mut script = ScriptedStrategy::new().ant(1, [Direction::East, Direction::West, Direction::North]);
+        let mut rng = fastrand::Rng::with_seed(0);
+        let mut ant = Ant::new(1, NodeId::new(0));
+        let view = WorldView::new(&world);
+
+        assert_eq!(script.next(view, &ant, &mut rng), (NodeId::new(1), false));
+        ant.move_to(NodeId::new(1));
+        assert_eq!(script.next(view, &ant, &mut rng), (NodeId::new(0), false));
+        ant.move_to(NodeId::new(0));
+        // No tunnel north, then the script runs out
+        assert_eq!(script.next(view, &ant, &mut rng), (NodeId::new(0), true));
+        ant.moves = 3;
+        assert_eq!(script.next(view, &ant, &mut rng), (NodeId::new(0), true));
+        // Unscripted ants never leave
+        assert_eq!(script.next(view, &Ant::new(0, NodeId::new(2)), &mut rng), (NodeId::new(2), true));
+    }
+}
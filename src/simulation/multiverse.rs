@@ -0,0 +1,274 @@
+use crate::ant::Ant;
+use crate::cli::Args;
+use crate::node_id::NodeId;
+use crate::simulation::engine::{RunState, SimulationEngine};
+use crate::world::World;
+use serde::{Deserialize, Serialize};
+use std::time::Instant;
+
+/// One end of a portal: a colony in one of the multiverse's worlds
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PortalEnd {
+    /// Index returned by [`Multiverse::add_world`]
+    pub world: usize,
+    pub colony: NodeId,
+}
+
+/// A one-way link: moving ants standing on `from` after a tick reappear on `to`
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Portal {
+    pub from: PortalEnd,
+    pub to: PortalEnd,
+}
+
+/// One world of a multiverse and the run going on in it
+struct Universe {
+    world: World,
+    ants: Vec<Ant>,
+    engine: SimulationEngine,
+    rng: fastrand::Rng,
+    departures: usize,
+    arrivals: usize,
+}
+
+/// Several worlds simulated side by side, with ants migrating between them
+/// through portal colonies.
+///
+/// Every world runs its own engine under the same [`Args`]. The worlds
+/// advance in lockstep, one tick each, and after every tick each moving
+/// ant standing on a portal's `from` colony is moved to its `to` colony
+/// (keeping its kind and move count, under a new id there). Arrivals then
+/// share their colony with whoever already stands on it, so two or more
+/// ants there fight as they would at t=0. A portal is closed while either
+/// of its colonies is destroyed.
+///
+/// A world whose ants all stopped moving wakes up again when someone
+/// arrives, and the whole run ends once every world has stopped or at most
+/// one ant is left alive across all of them.
+///
+/// Runs are deterministic: each world draws from its own seed, and
+/// migrations happen in portal order.
+#[derive(Default)]
+pub struct Multiverse {
+    universes: Vec<Universe>,
+    portals: Vec<Portal>,
+}
+
+/// Outcome of one world in a [`MultiverseReport`]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct UniverseSummary {
+    pub ticks: u64,
+    pub survivors: usize,
+    /// Ants alive in this world at the end
+    pub ants_alive: usize,
+    /// Ants that left through a portal
+    pub departures: usize,
+    /// Ants that came in through a portal
+    pub arrivals: usize,
+    pub interrupted: bool,
+}
+
+/// Combined outcome of a [`Multiverse::run`]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct MultiverseReport {
+    /// Ticks until the last world stopped
+    pub ticks: u64,
+    pub latency_ms: f64,
+    /// Portal crossings over the whole run
+    pub migrations: usize,
+    /// One entry per world, in [`Multiverse::add_world`] order
+    pub worlds: Vec<UniverseSummary>,
+}
+
+impl MultiverseReport {
+    /// Surviving colonies over every world
+    pub fn survivors(&self) -> usize {
+        self.worlds.iter().map(|w| w.survivors).sum()
+    }
+
+    /// Ants alive over every world
+    pub fn ants_alive(&self) -> usize {
+        self.worlds.iter().map(|w| w.ants_alive).sum()
+    }
+}
+
+impl Multiverse {
+    /// Empty multiverse; add worlds and portals before running it
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add `world` with its `ants`, moving on a stream seeded by `seed`;
+    /// returns the world's index for [`PortalEnd`]
+    pub fn add_world(&mut self, world: World, ants: Vec<Ant>, seed: u64) -> usize {
+        let mut engine = SimulationEngine::new(&world, ants.len());
+        engine.keep_lone_ant();
+        self.universes.push(Universe {
+            world,
+            ants,
+            engine,
+            rng: fastrand::Rng::with_seed(seed),
+            departures: 0,
+            arrivals: 0,
+        });
+        self.universes.len() - 1
+    }
+
+    /// Add a one-way portal; link both ways with two calls.
+    ///
+    /// # Panics
+    /// If either end names a world or colony that does not exist.
+    pub fn add_portal(&mut self, from: PortalEnd, to: PortalEnd) {
+        for end in [from, to] {
+            let universe = self.universes.get(end.world).expect("portal names an unknown world");
+            assert!(end.colony.index() < universe.world.nodes.len(), "portal names an unknown colony");
+        }
+        self.portals.push(Portal { from, to });
+    }
+
+    /// Number of worlds
+    pub fn len(&self) -> usize {
+        self.universes.len()
+    }
+
+    /// Whether no world has been added
+    pub fn is_empty(&self) -> bool {
+        self.universes.is_empty()
+    }
+
+    /// Portals in the order they migrate
+    pub fn portals(&self) -> &[Portal] {
+        &self.portals
+    }
+
+    /// World `index`, as left by the last run
+    pub fn world(&self, index: usize) -> &World {
+        &self.universes[index].world
+    }
+
+    /// Ants of world `index`; ants that left are kept as non-alive entries
+    pub fn ants(&self, index: usize) -> &[Ant] {
+        &self.universes[index].ants
+    }
+
+    /// Engine of world `index`, for its events and counters
+    pub fn engine(&self, index: usize) -> &SimulationEngine {
+        &self.universes[index].engine
+    }
+
+    /// Run every world to the end under `args` and report the combined outcome
+    pub fn run(&mut self, args: &Args) -> MultiverseReport {
+        let start = Instant::now();
+        for u in &mut self.universes {
+            u.departures = 0;
+            u.arrivals = 0;
+            u.engine.begin(&mut u.world, &mut u.ants, args, &mut u.rng);
+        }
+        let mut states: Vec<RunState> = self
+            .universes
+            .iter_mut()
+            .map(|u| u.engine.run_state(&u.world, &u.ants, args))
+            .collect();
+
+        let mut migrations = 0;
+        let mut tick = 0;
+        loop {
+            let mut ticked = false;
+            for (u, state) in self.universes.iter_mut().zip(&mut states) {
+                ticked |= u.engine.step(state, &mut u.world, &mut u.ants, args, &mut u.rng);
+            }
+            if !ticked {
+                break;
+            }
+            tick += 1;
+            migrations += self.migrate(&mut states, args, tick);
+            // A lone ant has nobody left to meet, in any world
+            if self.universes.iter().map(|u| u.engine.ants_alive()).sum::<usize>() <= 1 {
+                break;
+            }
+        }
+
+        for (u, state) in self.universes.iter_mut().zip(&mut states) {
+            u.engine.finish(state);
+        }
+        MultiverseReport {
+            ticks: tick,
+            latency_ms: start.elapsed().as_secs_f64() * 1000.0,
+            migrations,
+            worlds: self
+                .universes
+                .iter()
+                .map(|u| UniverseSummary {
+                    ticks: u.engine.ticks(),
+                    survivors: u.world.count_survivors(),
+                    ants_alive: u.engine.ants_alive(),
+                    departures: u.departures,
+                    arrivals: u.arrivals,
+                    interrupted: u.engine.interrupted(),
+                })
+                .collect(),
+        }
+    }
+
+    /// Send moving ants through every open portal, then settle the colonies
+    /// they landed on after `tick`; returns how many crossed
+    fn migrate(&mut self, states: &mut [RunState], args: &Args, tick: u64) -> usize {
+        let open = |end: PortalEnd| self.universes[end.world].world.is_alive(end.colony);
+        let open: Vec<bool> = self.portals.iter().map(|p| open(p.from) && open(p.to)).collect();
+
+        // Moving ants on an open portal's colony, one pass per world; an ant
+        // on a colony with several portals takes the first one
+        let mut leaving: Vec<Vec<usize>> = vec![Vec::new(); self.portals.len()];
+        for (w, u) in self.universes.iter().enumerate() {
+            let exits: Vec<usize> = (0..self.portals.len()).filter(|&p| open[p] && self.portals[p].from.world == w).collect();
+            if exits.is_empty() {
+                continue;
+            }
+            for (i, a) in u.ants.iter().enumerate() {
+                if !a.is_alive() || a.is_trapped() || a.has_max_moves() {
+                    continue;
+                }
+                if let Some(&p) = exits.iter().find(|&&p| self.portals[p].from.colony == a.pos) {
+                    leaving[p].push(i);
+                }
+            }
+        }
+
+        // Everyone leaves before anyone lands, so nobody crosses twice a tick
+        let mut crossing: Vec<(PortalEnd, Vec<Ant>)> = Vec::new();
+        for (&Portal { from, to }, leaving) in self.portals.iter().zip(leaving) {
+            if leaving.is_empty() {
+                continue;
+            }
+            let source = &mut self.universes[from.world];
+            source.departures += leaving.len();
+            let travellers = leaving
+                .into_iter()
+                .map(|i| source.engine.release(&mut states[from.world], &mut source.ants, i))
+                .collect();
+            crossing.push((to, travellers));
+        }
+
+        let mut crossed = 0;
+        let mut landed: Vec<Vec<usize>> = vec![Vec::new(); self.universes.len()];
+        for (to, travellers) in crossing {
+            let target = &mut self.universes[to.world];
+            target.arrivals += travellers.len();
+            target.engine.catch_up(tick);
+            crossed += travellers.len();
+            for mut ant in travellers {
+                ant.pos = to.colony;
+                target.engine.admit(&mut states[to.world], &mut target.ants, ant);
+            }
+            landed[to.world].push(to.colony.index());
+        }
+
+        for (w, colonies) in landed.iter_mut().enumerate().filter(|(_, c)| !c.is_empty()) {
+            colonies.sort_unstable();
+            colonies.dedup();
+            let u = &mut self.universes[w];
+            u.engine.meet(&mut states[w], &mut u.world, &mut u.ants, args, colonies);
+        }
+        crossed
+    }
+}
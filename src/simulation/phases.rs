@@ -0,0 +1,451 @@
+use crate::ant::{Ant, DeathReason};
+use crate::cli::{Args, TrappedPolicy};
+use crate::node_id::NodeId;
+use crate::simulation::buffers::SimBuffers;
+use crate::simulation::engine::{SimulationEngine, DENSE_RATIO};
+use crate::simulation::event::DestructionCause;
+use crate::world::World;
+use std::ops::ControlFlow;
+
+/// Everything a phase may read or change during one simultaneous tick
+pub(crate) struct TickContext<'a> {
+    pub engine: &'a mut SimulationEngine,
+    pub world: &'a mut World,
+    pub ants: &'a mut Vec<Ant>,
+    pub args: &'a Args,
+    pub rng: &'a mut fastrand::Rng,
+    pub bufs: &'a mut SimBuffers,
+    /// Generation stamp of this tick, for the per-node `gen` trick
+    pub cur_gen: u32,
+}
+
+/// One step of a simultaneous tick.
+///
+/// A tick runs [`DecisionPhase`], [`OccupancyPhase`], the arrivals
+/// [`DestructionPhase`], [`CommitPhase`] and the settled
+/// [`DestructionPhase`], in that order, all sharing one [`TickContext`].
+/// A new rule (teams, fight thresholds, ...) replaces the phase it changes
+/// instead of copying the whole tick.
+pub(crate) trait Phase {
+    /// Run the phase; `Break` means there is nothing left to do and the
+    /// tick does not happen
+    fn run(&self, ctx: &mut TickContext<'_>) -> ControlFlow<()>;
+}
+
+/// Phases that resolve a tick once [`DecisionPhase`] has picked destinations
+pub(crate) const RESOLVE: [&dyn Phase; 4] = [
+    &OccupancyPhase,
+    &DestructionPhase::ARRIVALS,
+    &CommitPhase,
+    &DestructionPhase::SETTLED,
+];
+
+/// (1) Pick every active ant's next colony, dropping ants that can no longer move
+pub(crate) struct DecisionPhase;
+
+impl Phase for DecisionPhase {
+    #[inline]
+    fn run(&self, ctx: &mut TickContext<'_>) -> ControlFlow<()> {
+        let SimBuffers { active, next_pos, trapped_now, .. } = &mut *ctx.bufs;
+        let mut i = 0;
+        while i < active.len() {
+            let ai = active[i];
+            let a = &ctx.ants[ai];
+            if !a.is_alive() || a.has_max_moves() || a.is_trapped() || ctx.engine.starves(&mut ctx.ants[ai]) {
+                active.swap_remove(i);
+                continue;
+            }
+            let a = &ctx.ants[ai];
+            let (np, became_trapped) = ctx.engine.choose_move(ctx.world, a, ctx.rng);
+            next_pos[ai] = np;
+            trapped_now[ai] = became_trapped;
+            i += 1;
+        }
+        if active.is_empty() {
+            ControlFlow::Break(())
+        } else {
+            ControlFlow::Continue(())
+        }
+    }
+}
+
+/// (2) Count who ends up where: stationary ants first, then the arrivals.
+/// Only `stay` lets a newly trapped ant hold its ground this tick.
+///
+/// With fewer than [`DENSE_RATIO`] alive colonies per active ant, every
+/// colony is reset from the stationary stock up front rather than on the
+/// first arrival; colonies are listed in arrival order either way.
+pub(crate) struct OccupancyPhase;
+
+impl Phase for OccupancyPhase {
+    #[inline]
+    fn run(&self, ctx: &mut TickContext<'_>) -> ControlFlow<()> {
+        let cur_gen = ctx.cur_gen;
+        let SimBuffers {
+            active,
+            gen,
+            occ_count,
+            occ_first,
+            occ_second,
+            occ_arrival,
+            base_occ,
+            base_first,
+            base_second,
+            touched_nodes,
+            next_pos,
+            trapped_now,
+            ..
+        } = &mut *ctx.bufs;
+        touched_nodes.clear();
+        let dense = ctx.world.alive_count() < active.len().saturating_mul(DENSE_RATIO);
+        if dense {
+            occ_count.copy_from_slice(base_occ);
+            occ_first.copy_from_slice(base_first);
+            occ_second.copy_from_slice(base_second);
+        }
+        for &ai in active.iter() {
+            let a = &ctx.ants[ai];
+            if !a.is_alive() || (trapped_now[ai] && ctx.args.trapped_policy != TrappedPolicy::Stay) {
+                continue;
+            }
+            let nid = next_pos[ai].index();
+
+            if dense {
+                if occ_count[nid] == base_occ[nid] {
+                    touched_nodes.push(nid);
+                }
+            } else if gen[nid] != cur_gen {
+                gen[nid] = cur_gen;
+                occ_count[nid] = base_occ[nid];
+                occ_first[nid] = base_first[nid];
+                occ_second[nid] = base_second[nid];
+                touched_nodes.push(nid);
+            }
+
+            occ_arrival[nid] = a.id;
+            match occ_count[nid] {
+                0 => {
+                    occ_first[nid] = a.id;
+                    occ_count[nid] = 1;
+                }
+                1 => {
+                    if occ_first[nid] == u32::MAX {
+                        occ_first[nid] = a.id;
+                    } else {
+                        occ_second[nid] = a.id;
+                    }
+                    occ_count[nid] = 2;
+                }
+                // Only "two or more" matters; saturate rather than wrap with >4B ants
+                _ => {
+                    occ_count[nid] = occ_count[nid].saturating_add(1);
+                }
+            }
+        }
+        ControlFlow::Continue(())
+    }
+}
+
+/// Which head count a [`DestructionPhase`] resolves
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum Tally {
+    /// Occupancy after this tick's moves (stationary ants plus arrivals)
+    Arrivals,
+    /// Stationary ants only, after this tick's commits
+    Settled,
+}
+
+/// (3) / (5) Fight wherever two or more ants share an alive colony.
+///
+/// A fortified colony first gets a shot at an ant that arrives alone: if
+/// the defense kills it, whoever already stood there is left in peace.
+/// Under a destruction cap, arrivals are resolved in colony id order and
+/// fights past the cap are averted (the arrivals bounce back). The settled
+/// pass also clears the blast fallout of the whole tick.
+pub(crate) struct DestructionPhase {
+    pub tally: Tally,
+}
+
+impl DestructionPhase {
+    pub const ARRIVALS: Self = Self { tally: Tally::Arrivals };
+    pub const SETTLED: Self = Self { tally: Tally::Settled };
+}
+
+impl Phase for DestructionPhase {
+    #[inline]
+    fn run(&self, ctx: &mut TickContext<'_>) -> ControlFlow<()> {
+        let TickContext { engine, world, ants, args, bufs, cur_gen, .. } = ctx;
+        let SimBuffers {
+            occ_count,
+            occ_first,
+            occ_second,
+            occ_arrival,
+            base_occ,
+            base_first,
+            base_second,
+            touched_nodes,
+            base_touched,
+            blasted,
+            averted_gen,
+            active,
+            next_pos,
+            trapped_now,
+            ..
+        } = &mut **bufs;
+        let arrivals = self.tally == Tally::Arrivals;
+        if arrivals && args.max_destructions_per_tick.is_some() {
+            touched_nodes.sort_unstable();
+        }
+        let nodes: &[usize] = if arrivals { touched_nodes } else { base_touched };
+        // Colonies that fell in a fight, credited together once all are resolved
+        let mut fought = Vec::new();
+
+        for &nid in nodes {
+            let (count, ant1, ant2) = if arrivals {
+                (occ_count[nid], occ_first[nid], occ_second[nid])
+            } else {
+                (base_occ[nid], base_first[nid], base_second[nid])
+            };
+            if arrivals && count - base_occ[nid] == 1 && world.nodes.is_alive(nid) {
+                // With one active ant counted here, it is the last one recorded
+                let lone = occ_arrival[nid] as usize;
+                if next_pos[lone] != ants[lone].pos && engine.defends(args, nid) {
+                    engine.repel(world, &mut ants[lone], nid);
+                    continue;
+                }
+            }
+            if count < 2 || !world.nodes.is_alive(nid) || !engine.destroys(nid, count, ant1, ant2) {
+                continue;
+            }
+            if engine.armistice(args) {
+                engine.record(world, nid, DestructionCause::Averted { ant1, ant2 });
+                if arrivals {
+                    averted_gen[nid] = *cur_gen;
+                }
+                continue;
+            }
+            if engine.near_miss(args) {
+                // Everyone carries on; arrivals still complete their moves
+                engine.record(world, nid, DestructionCause::NearMiss { ant1, ant2 });
+                continue;
+            }
+            engine.destroy_colony(world, args, nid, ant1, ant2, blasted);
+            if engine.tracks_kills() {
+                fought.push(nid);
+            }
+            base_occ[nid] = 0;
+            base_first[nid] = u32::MAX;
+            base_second[nid] = u32::MAX;
+        }
+        if !fought.is_empty() {
+            fought.sort_unstable();
+            credit_fights(engine, ants, args, active, next_pos, trapped_now, arrivals, &fought);
+        }
+
+        // Blast fallout: clear stationary stock and kill ants standing on
+        // collateral colonies (moving ants heading there died in the commit)
+        if !arrivals && !blasted.is_empty() {
+            blasted.sort_unstable();
+            for &nid in blasted.iter() {
+                base_occ[nid] = 0;
+                base_first[nid] = u32::MAX;
+                base_second[nid] = u32::MAX;
+            }
+            for a in ants.iter_mut() {
+                if a.is_alive() && !world.nodes.is_alive(a.pos.index()) {
+                    let reason = if blasted.binary_search(&a.pos.index()).is_ok() {
+                        DeathReason::VanishedWithColony
+                    } else {
+                        DeathReason::Collision
+                    };
+                    engine.kill(a, reason);
+                    a.set_trapped(false);
+                }
+            }
+            blasted.clear();
+        }
+        ControlFlow::Continue(())
+    }
+}
+
+/// Credit the fights on the `fought` colonies (sorted) to every ant counted
+/// there: the stationary stock, plus this tick's arrivals when `arrivals`
+/// is set. One pass over the ants however many colonies fell, so a crowded
+/// tick costs no more than a quiet one; it only runs when tracking kills.
+#[allow(clippy::too_many_arguments)]
+fn credit_fights(
+    engine: &mut SimulationEngine,
+    ants: &[Ant],
+    args: &Args,
+    active: &[usize],
+    next_pos: &[NodeId],
+    trapped_now: &[bool],
+    arrivals: bool,
+    fought: &[usize],
+) {
+    let stay = args.trapped_policy == TrappedPolicy::Stay;
+    let fell = |nid: NodeId| fought.binary_search(&nid.index()).is_ok();
+    for a in ants {
+        let settled = a.has_max_moves() || (stay && a.is_trapped());
+        if a.is_alive() && settled && fell(a.pos) {
+            engine.credit_kill(a.id);
+        }
+    }
+    if arrivals {
+        for &ai in active {
+            let a = &ants[ai];
+            if a.is_alive() && fell(next_pos[ai]) && (stay || !trapped_now[ai]) {
+                engine.credit_kill(a.id);
+            }
+        }
+    }
+}
+
+/// (4) Apply the moves: ants heading into a fallen colony die, bounced
+/// ants stay put, and ants that stop moving join the stationary stock.
+/// A mega ant that makes it in (no fight, no fortified defense stopped
+/// it) tramples the colony, taking down whoever else stood there.
+pub(crate) struct CommitPhase;
+
+impl Phase for CommitPhase {
+    #[inline]
+    fn run(&self, ctx: &mut TickContext<'_>) -> ControlFlow<()> {
+        let TickContext { engine, world, ants, args, bufs, cur_gen, .. } = ctx;
+        let SimBuffers {
+            active,
+            base_occ,
+            base_first,
+            base_second,
+            base_touched,
+            averted_gen,
+            next_pos,
+            trapped_now,
+            blasted,
+            ..
+        } = &mut **bufs;
+        base_touched.clear();
+        // Kept sorted for the lookups below, trampled colonies included
+        blasted.sort_unstable();
+
+        let mut j = 0;
+        while j < active.len() {
+            let ai = active[j];
+            let nid = next_pos[ai].index();
+            let node_alive = world.nodes.is_alive(nid);
+
+            let a = &mut ants[ai];
+            if !a.is_alive() {
+                active.swap_remove(j);
+                continue;
+            }
+
+            if !node_alive {
+                // Exits are picked among alive colonies, so `nid` fell this
+                // tick: in the ant's own fight, or to a blast
+                let reason = match (blasted.binary_search(&nid).is_ok(), next_pos[ai] != a.pos) {
+                    (false, _) => DeathReason::Collision,
+                    (true, true) => DeathReason::SteppedIntoRuins,
+                    (true, false) => DeathReason::VanishedWithColony,
+                };
+                engine.kill(a, reason);
+                a.set_trapped(false);
+                active.swap_remove(j);
+                continue;
+            }
+
+            // Bounced back from an averted fight: no move this tick
+            if averted_gen[nid] == *cur_gen && !trapped_now[ai] && next_pos[ai] != a.pos {
+                j += 1;
+                continue;
+            }
+
+            if !trapped_now[ai] && next_pos[ai] != a.pos {
+                a.move_to(next_pos[ai]);
+                engine.arrive(a, nid);
+                if a.is_mega() {
+                    // Ants on `nid` and its blast fallout die in the settled pass
+                    engine.trample(world, args, a, nid, blasted);
+                    blasted.sort_unstable();
+                    active.swap_remove(j);
+                    continue;
+                }
+
+                if !a.has_max_moves() {
+                    j += 1;
+                    continue;
+                }
+            } else if trapped_now[ai] && !a.is_trapped() {
+                // Only `stay` joins the stationary stock that later arrivals fight
+                match args.trapped_policy {
+                    TrappedPolicy::Stay => a.set_trapped(true),
+                    TrappedPolicy::Die => {
+                        engine.kill(a, DeathReason::Trapped);
+                        active.swap_remove(j);
+                        continue;
+                    }
+                    TrappedPolicy::Ghost => {
+                        a.set_trapped(true);
+                        engine.stopped(a);
+                        active.swap_remove(j);
+                        continue;
+                    }
+                }
+            } else {
+                j += 1;
+                continue;
+            }
+
+            // Out of moves or trapped: the ant settles on `nid`
+            engine.stopped(a);
+            match base_occ[nid] {
+                0 => base_first[nid] = a.id,
+                1 => base_second[nid] = a.id,
+                _ => {}
+            }
+            if base_occ[nid] < 2 {
+                base_touched.push(nid);
+            }
+            base_occ[nid] = base_occ[nid].saturating_add(1);
+            active.swap_remove(j);
+        }
+        ControlFlow::Continue(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::world::parser::parse_world_from_str;
+
+    #[test]
+    fn test_arrivals_destroy_shared_colony() {
+        let (names, nodes) = parse_world_from_str("A east=B\nB\nC west=B\n");
+        let mut world = World::new(names, nodes);
+        let mut ants = vec![Ant::new(0, NodeId::new(0)), Ant::new(1, NodeId::new(2))];
+        let mut args = Args::with_defaults(2, "unused");
+        args.suppress_events = true;
+        let mut engine = SimulationEngine::new(&world, 2);
+        engine.begin(&mut world, &mut ants, &args, &mut fastrand::Rng::with_seed(0));
+        let mut bufs = SimBuffers::new();
+        bufs.reset(world.nodes.len(), &ants);
+        let mut ctx = TickContext {
+            engine: &mut engine,
+            world: &mut world,
+            ants: &mut ants,
+            args: &args,
+            rng: &mut fastrand::Rng::with_seed(0),
+            bufs: &mut bufs,
+            cur_gen: 1,
+        };
+
+        assert_eq!(DecisionPhase.run(&mut ctx), ControlFlow::Continue(()));
+        let _ = OccupancyPhase.run(&mut ctx);
+        assert_eq!(ctx.bufs.occ_count[1], 2);
+        let _ = DestructionPhase::ARRIVALS.run(&mut ctx);
+        assert!(!ctx.world.nodes.is_alive(1));
+        let _ = CommitPhase.run(&mut ctx);
+        assert!(ctx.ants.iter().all(|a| !a.is_alive()));
+        assert!(ctx.bufs.active.is_empty());
+        assert_eq!(DecisionPhase.run(&mut ctx), ControlFlow::Break(()));
+    }
+}
@@ -0,0 +1,54 @@
+use std::fmt;
+use std::str::FromStr;
+
+/// Ants dropped into a run in progress: `tick=T,ants=N` for `--wave`.
+///
+/// The wave lands once tick `T` has ended (at once for `T = 0`, after the
+/// t=0 collisions), placed on alive colonies as `--placement` says; ants
+/// meeting on landing fight as at t=0, and the rest move from tick `T + 1`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Reinforcement {
+    pub tick: u64,
+    pub ants: usize,
+}
+
+impl FromStr for Reinforcement {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (mut tick, mut ants) = (None, None);
+        for part in s.split(',').map(str::trim) {
+            match part.split_once('=') {
+                Some(("tick", n)) => tick = Some(n.parse().map_err(|e| format!("invalid tick '{n}': {e}"))?),
+                Some(("ants", n)) => ants = Some(n.parse().map_err(|e| format!("invalid ant count '{n}': {e}"))?),
+                _ => return Err(format!("unknown wave option '{part}' (expected tick=T or ants=N)")),
+            }
+        }
+        match (tick, ants) {
+            (Some(tick), Some(ants)) => Ok(Reinforcement { tick, ants }),
+            _ => Err(format!("wave '{s}' needs both tick=T and ants=N")),
+        }
+    }
+}
+
+impl fmt::Display for Reinforcement {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "tick={},ants={}", self.tick, self.ants)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_reinforcement() {
+        let wave: Reinforcement = "tick=500, ants=1000".parse().unwrap();
+        assert_eq!(wave, Reinforcement { tick: 500, ants: 1000 });
+        assert_eq!(wave.to_string().parse(), Ok(wave));
+
+        assert!("tick=5".parse::<Reinforcement>().unwrap_err().contains("needs both"));
+        assert!("tick=5,ants=x".parse::<Reinforcement>().unwrap_err().contains("invalid ant count"));
+        assert!("tick=5,ants=1,queens=2".parse::<Reinforcement>().unwrap_err().contains("unknown wave option"));
+    }
+}
@@ -0,0 +1,76 @@
+use crate::node_id::NodeId;
+use crate::simulation::hooks::TickView;
+
+/// Where ants meet on one colony, as a [`DestructionRule`] sees it
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Occupancy {
+    /// Tick of the meeting (0 = initial placement)
+    pub tick: u64,
+    pub colony: NodeId,
+    /// Ants counted there, always at least 2
+    pub ants: u32,
+    /// Ids of the first two ants counted there
+    pub ant1: u32,
+    pub ant2: u32,
+}
+
+/// Decides whether ants meeting on a colony fight and destroy it.
+///
+/// The engine consults the rule wherever two or more ants share an alive
+/// colony: at t=0, on every tick and when ants are admitted between ticks.
+/// A colony the rule spares stands, and its ants carry on as after a near
+/// miss (no event is recorded). The rule runs before `--collision-prob`
+/// and the destruction cap, which still apply to the fights it allows.
+/// Rules take `&mut self`, so they can keep state such as colony hit
+/// points or their own random stream. Set one with
+/// [`SimulationEngine::with_destruction_rule`](crate::simulation::SimulationEngine::with_destruction_rule);
+/// without one the engine fights on every meeting, as [`MinAnts`]`(2)`.
+///
+/// Sequential scheduling keeps a single occupant per colony, so it only
+/// ever presents pairs (`ants == 2`).
+pub trait DestructionRule {
+    fn destroys(&mut self, occupancy: &Occupancy) -> bool;
+}
+
+/// A colony falls once at least this many ants share it
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MinAnts(pub u32);
+
+impl Default for MinAnts {
+    fn default() -> Self {
+        MinAnts(2)
+    }
+}
+
+impl DestructionRule for MinAnts {
+    #[inline]
+    fn destroys(&mut self, occupancy: &Occupancy) -> bool {
+        occupancy.ants >= self.0
+    }
+}
+
+/// Any closure over the occupancy is a rule
+impl<F: FnMut(&Occupancy) -> bool> DestructionRule for F {
+    #[inline]
+    fn destroys(&mut self, occupancy: &Occupancy) -> bool {
+        self(occupancy)
+    }
+}
+
+/// Ends a run early once it answers `true`.
+///
+/// The engine asks before every tick, right after `--max-ticks`, with
+/// `view.tick` the ticks completed so far. A run it stops ends like one
+/// that ran out of ticks: it is not counted as interrupted. Set one with
+/// [`SimulationEngine::with_stop_condition`](crate::simulation::SimulationEngine::with_stop_condition).
+pub trait StopCondition {
+    fn stop(&mut self, view: &TickView<'_>) -> bool;
+}
+
+/// Any closure over the tick view is a stop condition
+impl<F: FnMut(&TickView<'_>) -> bool> StopCondition for F {
+    #[inline]
+    fn stop(&mut self, view: &TickView<'_>) -> bool {
+        self(view)
+    }
+}
@@ -0,0 +1,169 @@
+use crate::ant::Ant;
+use crate::cli::Args;
+use crate::node_id::NodeId;
+use crate::simulation::engine::{RunState, SimulationEngine};
+use crate::simulation::event::DestructionEvent;
+use crate::world::World;
+use serde::{Deserialize, Serialize};
+
+/// State of the world right after one tick
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TickSnapshot {
+    pub tick: u64,
+    /// Position of every ant by index; `None` once the ant is dead
+    pub positions: Vec<Option<NodeId>>,
+    /// Destructions (and averted fights) of this tick, in order
+    pub destroyed: Vec<DestructionEvent>,
+}
+
+/// What changed during one tick, for consumers that follow a run without
+/// holding a snapshot of every ant: replay the lists onto the world and ants
+/// they started from. Ants are listed by id, in the order things happened.
+///
+/// Births (queens' workers, `--wave` landings) and `--revival-prob`
+/// rebuilds are listed too, so replay holds under every option; an ant
+/// born and killed in the same tick shows up in both lists.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TickDelta {
+    pub tick: u64,
+    /// Colonies that fell this tick (averted fights and the like left out)
+    pub destroyed: Vec<NodeId>,
+    /// Ants that died this tick
+    pub ants_died: Vec<u32>,
+    /// Ants that stopped moving this tick and are still alive: out of moves,
+    /// or trapped under `--trapped-policy stay` or `ghost`
+    pub ants_stopped: Vec<u32>,
+    /// Ants that joined the run this tick, at the end of the ant list
+    #[serde(default)]
+    pub ants_born: Vec<u32>,
+    /// Destroyed colonies rebuilt this tick, as [`World::rebuild`] does
+    #[serde(default)]
+    pub revived: Vec<NodeId>,
+}
+
+impl TickDelta {
+    /// Whether nothing changed
+    pub fn is_empty(&self) -> bool {
+        self.destroyed.is_empty()
+            && self.ants_died.is_empty()
+            && self.ants_stopped.is_empty()
+            && self.ants_born.is_empty()
+            && self.revived.is_empty()
+    }
+
+    /// Forget the lists, keeping their memory
+    pub(crate) fn clear(&mut self) {
+        self.destroyed.clear();
+        self.ants_died.clear();
+        self.ants_stopped.clear();
+        self.ants_born.clear();
+        self.revived.clear();
+    }
+}
+
+/// Iterator returned by [`SimulationEngine::iter_ticks`]
+pub struct Ticks<'a> {
+    engine: &'a mut SimulationEngine,
+    world: &'a mut World,
+    ants: &'a mut Vec<Ant>,
+    args: &'a Args,
+    rng: &'a mut fastrand::Rng,
+    state: RunState,
+    finished: bool,
+}
+
+impl<'a> Ticks<'a> {
+    pub(crate) fn new(
+        engine: &'a mut SimulationEngine,
+        world: &'a mut World,
+        ants: &'a mut Vec<Ant>,
+        args: &'a Args,
+        rng: &'a mut fastrand::Rng,
+    ) -> Self {
+        engine.begin(world, ants, args, rng);
+        let state = engine.run_state(world, ants, args);
+        Self {
+            engine,
+            world,
+            ants,
+            args,
+            rng,
+            state,
+            finished: false,
+        }
+    }
+
+    /// Run the next tick; `false` (for good) once the run is over
+    fn advance(&mut self) -> bool {
+        if self.finished {
+            return false;
+        }
+        if !self.engine.step(&mut self.state, self.world, self.ants, self.args, self.rng) {
+            self.finished = true;
+            self.engine.finish(&mut self.state);
+            return false;
+        }
+        true
+    }
+
+    /// Snapshot of the run as it stands: tick 0 (after t=0 collisions)
+    /// before the first call to `next`, else the last tick yielded
+    pub fn current(&self) -> TickSnapshot {
+        TickSnapshot {
+            tick: self.engine.ticks(),
+            positions: self.ants.iter().map(|a| a.is_alive().then_some(a.pos)).collect(),
+            destroyed: self.engine.tick_events().to_vec(),
+        }
+    }
+}
+
+impl Iterator for Ticks<'_> {
+    type Item = TickSnapshot;
+
+    fn next(&mut self) -> Option<TickSnapshot> {
+        self.advance().then(|| self.current())
+    }
+}
+
+/// Iterator returned by [`SimulationEngine::iter_deltas`]: the t=0 delta
+/// first, then one per tick, and one last delta stamped with the tick after
+/// the last if the final moving ants starved as it began
+pub struct TickDeltas<'a> {
+    ticks: Ticks<'a>,
+    started: bool,
+}
+
+impl<'a> TickDeltas<'a> {
+    pub(crate) fn new(
+        engine: &'a mut SimulationEngine,
+        world: &'a mut World,
+        ants: &'a mut Vec<Ant>,
+        args: &'a Args,
+        rng: &'a mut fastrand::Rng,
+    ) -> Self {
+        engine.track_deltas();
+        Self {
+            ticks: Ticks::new(engine, world, ants, args, rng),
+            started: false,
+        }
+    }
+}
+
+impl Iterator for TickDeltas<'_> {
+    type Item = TickDelta;
+
+    fn next(&mut self) -> Option<TickDelta> {
+        if !std::mem::replace(&mut self.started, true) || self.ticks.advance() {
+            return Some(self.ticks.engine.take_delta());
+        }
+        self.ticks.engine.take_final_delta()
+    }
+}
+
+impl Drop for Ticks<'_> {
+    fn drop(&mut self) {
+        if !self.finished {
+            self.engine.finish(&mut self.state);
+        }
+    }
+}
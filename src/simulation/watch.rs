@@ -0,0 +1,160 @@
+use crate::ant::Ant;
+use crate::node_id::NodeId;
+use crate::simulation::event::DestructionEvent;
+use crate::simulation::hooks::{SimulationHooks, TickView};
+use crate::simulation::log::EventLine;
+use crate::world::WorldView;
+use std::io::Write;
+
+/// Hooks narrowed to a few colonies, made by [`SimulationHooks::watch`].
+///
+/// Events, near misses included, reach the inner hooks only when they
+/// happen on a watched colony, and deaths only when `ant.pos` is one; tick
+/// boundaries always do. After every tick the inner hooks also get
+/// [`on_arrivals`](SimulationHooks::on_arrivals) for each watched colony that
+/// ants moved onto, counted like `--heatmap` visits: ants killed arriving
+/// show up in the fight instead. Names that are not in the world are ignored.
+pub struct ColonyWatch<H> {
+    inner: H,
+    names: Vec<String>,
+    /// Watched flag per colony, resolved from `names` on first use
+    watched: Vec<bool>,
+    /// Position of every ant when the tick started
+    before: Vec<NodeId>,
+    /// Arrivals per colony this tick, for the watched ones
+    arrivals: Vec<u32>,
+}
+
+impl<H: SimulationHooks> ColonyWatch<H> {
+    pub fn new<S: Into<String>>(inner: H, colonies: impl IntoIterator<Item = S>) -> Self {
+        Self {
+            inner,
+            names: colonies.into_iter().map(Into::into).collect(),
+            watched: Vec::new(),
+            before: Vec::new(),
+            arrivals: Vec::new(),
+        }
+    }
+
+    /// The hooks events are forwarded to
+    pub fn inner(&self) -> &H {
+        &self.inner
+    }
+
+    /// Look the names up in `world` unless already done for a world this size
+    fn resolve(&mut self, world: WorldView<'_>) {
+        if self.watched.len() == world.len() {
+            return;
+        }
+        self.watched = vec![false; world.len()];
+        for id in self.names.iter().filter_map(|name| world.find(name)) {
+            self.watched[id.index()] = true;
+        }
+        self.arrivals = vec![0; world.len()];
+    }
+
+    fn is_watched(&self, id: NodeId) -> bool {
+        self.watched.get(id.index()).copied().unwrap_or(false)
+    }
+}
+
+impl<H: SimulationHooks> SimulationHooks for ColonyWatch<H> {
+    fn on_tick_start(&mut self, view: &TickView<'_>) {
+        self.resolve(view.world);
+        self.before.clear();
+        self.before.extend(view.ants.iter().map(|a| a.pos));
+        self.inner.on_tick_start(view);
+    }
+
+    fn on_tick_end(&mut self, view: &TickView<'_>) {
+        self.resolve(view.world);
+        // Ants born this tick did not arrive anywhere
+        for (ant, &from) in view.ants.iter().zip(&self.before) {
+            if ant.pos != from && self.is_watched(ant.pos) {
+                self.arrivals[ant.pos.index()] += 1;
+            }
+        }
+        for i in 0..self.arrivals.len() {
+            let ants = std::mem::take(&mut self.arrivals[i]);
+            if ants > 0 {
+                self.inner.on_arrivals(view.tick, NodeId::from_index(i), ants, view.world);
+            }
+        }
+        self.inner.on_tick_end(view);
+    }
+
+    fn on_destruction(&mut self, event: &DestructionEvent, world: WorldView<'_>) {
+        self.resolve(world);
+        if self.is_watched(event.node_id) {
+            self.inner.on_destruction(event, world);
+        }
+    }
+
+    fn on_ant_dead(&mut self, ant: &Ant, tick: u64) {
+        if self.is_watched(ant.pos) {
+            self.inner.on_ant_dead(ant, tick);
+        }
+    }
+
+    fn on_arrivals(&mut self, tick: u64, colony: NodeId, ants: u32, world: WorldView<'_>) {
+        self.inner.on_arrivals(tick, colony, ants, world);
+    }
+}
+
+/// Hooks that write what they see as log lines: `--watch` puts them behind
+/// a [`ColonyWatch`]. Event lines read like the event log's; arrivals read
+/// `[t=3] 👀 Fizz: 2 ants arrived`.
+pub struct WatchLog<W: Write> {
+    out: W,
+}
+
+impl<W: Write> WatchLog<W> {
+    pub fn new(out: W) -> Self {
+        Self { out }
+    }
+}
+
+impl<W: Write> SimulationHooks for WatchLog<W> {
+    fn on_tick_end(&mut self, _view: &TickView<'_>) {
+        let _ = self.out.flush();
+    }
+
+    fn on_destruction(&mut self, event: &DestructionEvent, world: WorldView<'_>) {
+        let line = EventLine { event, world: world.world(), names: None, messages: None, color: false };
+        let _ = writeln!(self.out, "{line}");
+    }
+
+    fn on_arrivals(&mut self, tick: u64, colony: NodeId, ants: u32, world: WorldView<'_>) {
+        let noun = if ants == 1 { "ant" } else { "ants" };
+        let _ = writeln!(self.out, "[t={tick}] 👀 {}: {ants} {noun} arrived", world.name(colony));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::world::parser::parse_world_from_str;
+    use crate::world::World;
+
+    #[test]
+    fn test_watch_forwards_only_watched_colonies() {
+        let (names, nodes) = parse_world_from_str("Fizz east=Buzz\nBuzz west=Fizz\n");
+        let world = World::new(names, nodes);
+        let mut watch = WatchLog::new(Vec::new()).watch(["Buzz", "Nowhere"]);
+        let mut ants = vec![Ant::new(0, NodeId::new(0)), Ant::new(1, NodeId::new(0))];
+
+        watch.on_tick_start(&TickView { tick: 1, world: WorldView::new(&world), ants: &ants });
+        ants[0].pos = NodeId::new(1);
+        ants[1].pos = NodeId::new(1);
+        for node_id in [NodeId::new(0), NodeId::new(1)] {
+            let cause = crate::simulation::DestructionCause::NearMiss { ant1: 0, ant2: 1 };
+            watch.on_destruction(&DestructionEvent { tick: 1, node_id, cause }, WorldView::new(&world));
+        }
+        watch.on_tick_end(&TickView { tick: 1, world: WorldView::new(&world), ants: &ants });
+
+        assert_eq!(
+            String::from_utf8(watch.inner().out.clone()).unwrap(),
+            "[t=1] 💨 Buzz was spared: near miss between ant 0 and ant 1\n[t=1] 👀 Buzz: 2 ants arrived\n"
+        );
+    }
+}
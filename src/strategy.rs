@@ -0,0 +1,126 @@
+use crate::direction::Direction;
+use std::fmt;
+use std::str::FromStr;
+
+/// How ants pick their next colony (`--strategy`)
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Strategy {
+    /// Uniformly random alive exit: `random`
+    #[default]
+    Random,
+    /// Random walk for the first `after` moves, then head back to the
+    /// starting colony along shortest paths: `homing:H`
+    Homing { after: u32 },
+}
+
+impl FromStr for Strategy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.split_once(':') {
+            None if s == "random" => Ok(Strategy::Random),
+            Some(("homing", h)) => {
+                let after = h.trim().parse::<u32>().map_err(|e| format!("invalid number '{h}': {e}"))?;
+                Ok(Strategy::Homing { after })
+            }
+            _ => Err(format!("unknown strategy '{s}' (expected random or homing:H)")),
+        }
+    }
+}
+
+impl fmt::Display for Strategy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Strategy::Random => write!(f, "random"),
+            Strategy::Homing { after } => write!(f, "homing:{after}"),
+        }
+    }
+}
+
+/// Relative weight of each direction when an ant picks among several alive
+/// exits (`--bias`), indexed by [`Direction::index`]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct DirectionBias {
+    weights: [f64; 4],
+}
+
+impl DirectionBias {
+    /// Weights by direction; each must be finite and non-negative, and at
+    /// least one positive
+    pub fn new(weights: [f64; 4]) -> Result<Self, String> {
+        if let Some(w) = weights.iter().find(|w| !w.is_finite() || **w < 0.0) {
+            return Err(format!("invalid weight {w} (expected a non-negative number)"));
+        }
+        if weights.iter().all(|&w| w == 0.0) {
+            return Err("at least one direction needs a positive weight".to_string());
+        }
+        Ok(Self { weights })
+    }
+
+    #[inline]
+    pub fn weight(&self, direction: Direction) -> f64 {
+        self.weights[direction.index()]
+    }
+
+    /// Weights by [`Direction::index`]
+    #[inline]
+    pub fn weights(&self) -> &[f64; 4] {
+        &self.weights
+    }
+}
+
+impl FromStr for DirectionBias {
+    type Err = String;
+
+    /// `north=0.4,south=0.2,east=0.2,west=0.2`; directions left out weigh 0
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut weights = [0.0; 4];
+        for part in s.split(',') {
+            let (dir, w) = part.split_once('=').ok_or_else(|| format!("expected DIRECTION=WEIGHT, got '{part}'"))?;
+            let dir: Direction = dir.trim().parse().map_err(|_| format!("unknown direction '{}'", dir.trim()))?;
+            weights[dir.index()] = w.trim().parse::<f64>().map_err(|e| format!("invalid weight '{w}': {e}"))?;
+        }
+        Self::new(weights)
+    }
+}
+
+impl fmt::Display for DirectionBias {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, dir) in Direction::iter().enumerate() {
+            if i > 0 {
+                f.write_str(",")?;
+            }
+            write!(f, "{}={}", dir.as_str(), self.weight(dir))?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_and_display_round_trip() {
+        for s in ["random", "homing:0", "homing:250"] {
+            assert_eq!(s.parse::<Strategy>().unwrap().to_string(), s);
+        }
+        assert!("homing".parse::<Strategy>().is_err());
+        assert!("homing:-1".parse::<Strategy>().is_err());
+        assert!("levy:2".parse::<Strategy>().is_err());
+    }
+
+    #[test]
+    fn test_parse_direction_bias() {
+        let bias: DirectionBias = "north=0.4, south=0.2,east=0.2,west=0.2".parse().unwrap();
+        assert_eq!(bias.weights(), &[0.4, 0.2, 0.2, 0.2]);
+        assert_eq!(bias.to_string(), "north=0.4,south=0.2,east=0.2,west=0.2");
+        assert_eq!("east=1".parse::<DirectionBias>().unwrap().weights(), &[0.0, 0.0, 1.0, 0.0]);
+
+        assert!("up=1".parse::<DirectionBias>().is_err());
+        assert!("north".parse::<DirectionBias>().is_err());
+        assert!("north=-1,south=2".parse::<DirectionBias>().is_err());
+        assert!("north=0,south=0".parse::<DirectionBias>().is_err());
+        assert!("north=inf".parse::<DirectionBias>().is_err());
+    }
+}
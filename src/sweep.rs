@@ -0,0 +1,171 @@
+use crate::cli::Args;
+use crate::limits::{self, Limit};
+use crate::node_id::NodeId;
+use crate::seeds::SubSeeds;
+use crate::simulation::{SimBuffers, SimulationEngine};
+use crate::world::World;
+use serde::{Deserialize, Serialize};
+use std::io::Write;
+
+/// Outcome of one run in a parameter sweep
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct SweepRow {
+    pub ants: usize,
+    pub seed: u64,
+    pub survivors: usize,
+    pub ticks: u64,
+    pub latency_ms: f64,
+    /// Resource limit that skipped or stopped this run (`ok` in the CSV otherwise)
+    pub limit: Option<Limit>,
+    /// Colonies down at the end of the run, in id order (not in the CSV)
+    #[serde(default)]
+    pub destroyed: Vec<NodeId>,
+}
+
+impl SweepRow {
+    pub const CSV_HEADER: &'static str = "ants,seed,survivors,ticks,latency_ms,status";
+
+    /// Write this row as one CSV line
+    pub fn write_csv<W: Write>(&self, out: &mut W) -> std::io::Result<()> {
+        writeln!(
+            out,
+            "{},{},{},{},{:.3},{}",
+            self.ants,
+            self.seed,
+            self.survivors,
+            self.ticks,
+            self.latency_ms,
+            self.limit.map_or_else(|| "ok".to_string(), |limit| limit.to_string())
+        )
+    }
+
+    /// Whether the run took place (it was not skipped for its memory estimate)
+    pub fn ran(&self) -> bool {
+        self.limit != Some(Limit::Memory)
+    }
+}
+
+/// Run the cartesian product of `ants` × `seeds` on clones of `world`.
+///
+/// `base` supplies every other engine option; its ant count and seed are
+/// overridden per run and events are always suppressed. Rows are streamed
+/// to `out` as CSV (header first) and also returned. Runs estimated to
+/// exceed `base.memory_limit` are skipped, and runs stopped by a limit keep
+/// their partial results; both are marked in the row's `limit`.
+pub fn run_sweep<W: Write>(
+    world: &World,
+    base: &Args,
+    ants: &[usize],
+    seeds: &[u64],
+    out: &mut W,
+) -> std::io::Result<Vec<SweepRow>> {
+    writeln!(out, "{}", SweepRow::CSV_HEADER)?;
+    let mut rows = Vec::with_capacity(ants.len() * seeds.len());
+    let mut args = base.clone();
+    args.suppress_events = true;
+    let fits = |n: usize| base.memory_limit.is_none_or(|max| limits::estimate_bytes(world.nodes.len(), n) <= max);
+    // One scratch arena for every run on this map
+    let largest = ants.iter().copied().filter(|&n| fits(n)).max().unwrap_or(0);
+    let mut buffers = SimBuffers::with_capacity(world.nodes.len(), largest);
+
+    for &n in ants {
+        for &seed in seeds {
+            args.ants = n;
+            args.seed = Some(seed);
+
+            if !fits(n) {
+                let row = SweepRow {
+                    ants: n,
+                    seed,
+                    survivors: world.count_survivors(),
+                    ticks: 0,
+                    latency_ms: 0.0,
+                    limit: Some(Limit::Memory),
+                    destroyed: Vec::new(),
+                };
+                row.write_csv(out)?;
+                rows.push(row);
+                continue;
+            }
+
+            let mut run_world = world.clone();
+            let seeds = SubSeeds::derive(seed);
+            let mut colony = run_world.place_colony(n, args.queen_count(), args.placement, &mut seeds.placement_rng());
+            let mut engine = SimulationEngine::new(&run_world, n)
+                .with_seeds(seeds)
+                .with_buffers(std::mem::take(&mut buffers));
+            let latency = engine.run_simulation(&mut run_world, &mut colony, &args, &mut seeds.movement_rng());
+
+            let row = SweepRow {
+                ants: n,
+                seed,
+                survivors: run_world.count_survivors(),
+                ticks: engine.ticks(),
+                latency_ms: latency.as_secs_f64() * 1000.0,
+                limit: engine.limit_exceeded(),
+                destroyed: (0..run_world.nodes.len())
+                    .map(NodeId::from_index)
+                    .filter(|&id| !run_world.is_alive(id))
+                    .collect(),
+            };
+            buffers = engine.into_buffers();
+            row.write_csv(out)?;
+            rows.push(row);
+        }
+    }
+
+    Ok(rows)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::world::parser::parse_world_from_str;
+
+    #[test]
+    fn test_sweep_covers_cartesian_product() {
+        let (names, nodes) = parse_world_from_str("A east=B\nB west=A\nC\n");
+        let world = World::new(names, nodes);
+        let base = Args::with_defaults(0, "unused");
+
+        let mut out = Vec::new();
+        let rows = run_sweep(&world, &base, &[1, 2], &[7, 8, 9], &mut out).unwrap();
+
+        assert_eq!(rows.len(), 6);
+        assert_eq!((rows[0].ants, rows[0].seed), (1, 7));
+        assert_eq!((rows[5].ants, rows[5].seed), (2, 9));
+        let csv = String::from_utf8(out).unwrap();
+        assert_eq!(csv.lines().count(), 7);
+        assert!(csv.starts_with(SweepRow::CSV_HEADER));
+    }
+
+    #[test]
+    fn test_sweep_is_deterministic_per_seed() {
+        let (names, nodes) = parse_world_from_str("A east=B\nB west=A south=C\nC north=B\n");
+        let world = World::new(names, nodes);
+        let base = Args::with_defaults(0, "unused");
+
+        let first = run_sweep(&world, &base, &[3], &[5], &mut std::io::sink()).unwrap();
+        let second = run_sweep(&world, &base, &[3], &[5], &mut std::io::sink()).unwrap();
+        assert_eq!(first[0].survivors, second[0].survivors);
+        assert_eq!(first[0].ticks, second[0].ticks);
+    }
+
+    #[test]
+    fn test_sweep_skips_runs_over_memory_limit() {
+        let (names, nodes) = parse_world_from_str("A east=B\nB west=A\n");
+        let world = World::new(names, nodes);
+        let mut base = Args::with_defaults(0, "unused");
+        base.memory_limit = Some(limits::estimate_bytes(2, 10));
+
+        let mut out = Vec::new();
+        let rows = run_sweep(&world, &base, &[10, 1_000_000_000], &[1], &mut out).unwrap();
+
+        assert_eq!(rows[0].limit, None);
+        assert_eq!(rows[1].limit, Some(Limit::Memory));
+        assert_eq!(rows[1].ticks, 0);
+        let csv = String::from_utf8(out).unwrap();
+        assert!(csv.lines().nth(1).unwrap().ends_with(",ok"));
+        assert!(csv.lines().nth(2).unwrap().ends_with(",memory"));
+    }
+}
@@ -1,2 +0,0 @@
-/// Sentinel value for "no tunnel"
-pub const INVALID_NODE: u32 = u32::MAX;
@@ -0,0 +1,316 @@
+use crate::node_id::NodeIndex;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::io::Write;
+
+/// One typed attribute column, indexed by node id (`None` = not set)
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Column {
+    U32(Vec<Option<u32>>),
+    F32(Vec<Option<f32>>),
+    Str(Vec<Option<String>>),
+}
+
+impl Column {
+    /// Number of node slots
+    pub fn len(&self) -> usize {
+        match self {
+            Column::U32(v) => v.len(),
+            Column::F32(v) => v.len(),
+            Column::Str(v) => v.len(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Widen to `f32`; `Str` stays as it is
+    fn to_f32(&self) -> Column {
+        match self {
+            Column::U32(v) => Column::F32(v.iter().map(|x| x.map(|x| x as f32)).collect()),
+            other => other.clone(),
+        }
+    }
+
+    /// Widen to `Str`
+    fn to_str(&self) -> Column {
+        let text = |i: usize| self.value_string(i);
+        Column::Str((0..self.len()).map(text).collect())
+    }
+
+    fn value_string(&self, i: usize) -> Option<String> {
+        match self {
+            Column::U32(v) => v[i].map(|x| x.to_string()),
+            Column::F32(v) => v[i].map(|x| x.to_string()),
+            Column::Str(v) => v[i].clone(),
+        }
+    }
+
+    /// Rank in the widening order `u32 < f32 < str`
+    fn rank(&self) -> u8 {
+        match self {
+            Column::U32(_) => 0,
+            Column::F32(_) => 1,
+            Column::Str(_) => 2,
+        }
+    }
+
+    fn widen_to(&self, rank: u8) -> Column {
+        match rank {
+            0 => self.clone(),
+            1 => self.to_f32(),
+            _ => self.to_str(),
+        }
+    }
+
+    /// Append `other`, widening both to the wider type
+    fn extend(&mut self, other: &Column) {
+        let rank = self.rank().max(other.rank());
+        let mut merged = self.widen_to(rank);
+        match (&mut merged, other.widen_to(rank)) {
+            (Column::U32(a), Column::U32(b)) => a.extend(b),
+            (Column::F32(a), Column::F32(b)) => a.extend(b),
+            (Column::Str(a), Column::Str(b)) => a.extend(b),
+            _ => unreachable!("columns widened to the same type"),
+        }
+        *self = merged;
+    }
+
+    /// Keep only the slots where `keep` is true
+    fn retain(&mut self, keep: &[bool]) {
+        fn filter<T>(v: &mut Vec<Option<T>>, keep: &[bool]) {
+            let mut i = 0;
+            v.retain(|_| {
+                i += 1;
+                keep[i - 1]
+            });
+        }
+        match self {
+            Column::U32(v) => filter(v, keep),
+            Column::F32(v) => filter(v, keep),
+            Column::Str(v) => filter(v, keep),
+        }
+    }
+
+    /// `len` unset slots of the same type
+    fn empty_like(&self, len: usize) -> Column {
+        match self {
+            Column::U32(_) => Column::U32(vec![None; len]),
+            Column::F32(_) => Column::F32(vec![None; len]),
+            Column::Str(_) => Column::Str(vec![None; len]),
+        }
+    }
+}
+
+/// Rust types that can be stored as an attribute column
+pub trait AttrValue: Sized {
+    fn column(col: &Column) -> Option<&[Option<Self>]>;
+    fn column_mut(col: &mut Column) -> Option<&mut [Option<Self>]>;
+    fn new_column(len: usize) -> Column;
+}
+
+macro_rules! attr_value {
+    ($ty:ty, $variant:ident) => {
+        impl AttrValue for $ty {
+            fn column(col: &Column) -> Option<&[Option<Self>]> {
+                match col {
+                    Column::$variant(v) => Some(v),
+                    _ => None,
+                }
+            }
+
+            fn column_mut(col: &mut Column) -> Option<&mut [Option<Self>]> {
+                match col {
+                    Column::$variant(v) => Some(v),
+                    _ => None,
+                }
+            }
+
+            fn new_column(len: usize) -> Column {
+                Column::$variant(vec![None; len])
+            }
+        }
+    };
+}
+
+attr_value!(u32, U32);
+attr_value!(f32, F32);
+attr_value!(String, Str);
+
+/// Per-node attributes by name (`hp`, `capacity`, `region`, ...), so new
+/// mechanics don't need a new `Node` field. Columns are kept in key order.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Attributes {
+    columns: BTreeMap<String, Column>,
+}
+
+impl Attributes {
+    pub fn is_empty(&self) -> bool {
+        self.columns.is_empty()
+    }
+
+    /// Attribute names, in order
+    pub fn keys(&self) -> impl Iterator<Item = &str> {
+        self.columns.keys().map(String::as_str)
+    }
+
+    /// Untyped access to a column
+    pub fn column(&self, key: &str) -> Option<&Column> {
+        self.columns.get(key)
+    }
+
+    /// Column `key` if it exists with type `T`
+    pub fn get<T: AttrValue>(&self, key: &str) -> Option<&[Option<T>]> {
+        T::column(self.columns.get(key)?)
+    }
+
+    /// Mutable column `key` if it exists with type `T`
+    pub fn get_mut<T: AttrValue>(&mut self, key: &str) -> Option<&mut [Option<T>]> {
+        T::column_mut(self.columns.get_mut(key)?)
+    }
+
+    /// Column `key` of type `T`, created unset for `len` nodes if missing.
+    /// `None` if `key` already holds another type.
+    pub fn insert<T: AttrValue>(&mut self, key: &str, len: usize) -> Option<&mut [Option<T>]> {
+        let column = self.columns.entry(key.to_string()).or_insert_with(|| T::new_column(len));
+        T::column_mut(column)
+    }
+
+    /// Build columns from raw `(node, key, value)` text for `len` nodes.
+    ///
+    /// A column is `u32` if every value parses as one, else `f32` if every
+    /// value does, else `str`. Later values for the same node win.
+    pub(crate) fn from_raw(len: usize, raw: &[(NodeIndex, &str, &str)]) -> Self {
+        let mut by_key: BTreeMap<&str, Vec<(NodeIndex, &str)>> = BTreeMap::new();
+        for &(node, key, value) in raw {
+            by_key.entry(key).or_default().push((node, value));
+        }
+        let columns = by_key
+            .into_iter()
+            .map(|(key, values)| {
+                let column = if values.iter().all(|(_, v)| v.parse::<u32>().is_ok()) {
+                    fill(len, &values, |v| v.parse::<u32>().ok(), Column::U32)
+                } else if values.iter().all(|(_, v)| v.parse::<f32>().is_ok()) {
+                    fill(len, &values, |v| v.parse::<f32>().ok(), Column::F32)
+                } else {
+                    fill(len, &values, |v| Some(v.to_string()), Column::Str)
+                };
+                (key.to_string(), column)
+            })
+            .collect();
+        Self { columns }
+    }
+
+    /// Write ` key=value` for every attribute set on node `i`
+    pub(crate) fn write_node<W: Write>(&self, i: usize, out: &mut W) -> std::io::Result<()> {
+        for (key, column) in &self.columns {
+            match column {
+                Column::U32(v) => {
+                    if let Some(x) = v[i] {
+                        write!(out, " {key}={x}")?;
+                    }
+                }
+                Column::F32(v) => {
+                    if let Some(x) = v[i] {
+                        write!(out, " {key}={x}")?;
+                    }
+                }
+                Column::Str(v) => {
+                    if let Some(x) = &v[i] {
+                        write!(out, " {key}={x}")?;
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Append the attributes of `other` (`other_len` nodes) after ours
+    /// (`len` nodes); a key holding different types is widened
+    pub(crate) fn append(&mut self, len: usize, other: &Attributes, other_len: usize) {
+        for (key, theirs) in &other.columns {
+            let ours = self.columns.entry(key.clone()).or_insert_with(|| theirs.empty_like(len));
+            ours.extend(theirs);
+        }
+        for (key, ours) in self.columns.iter_mut() {
+            if !other.columns.contains_key(key) {
+                let padding = ours.empty_like(other_len);
+                ours.extend(&padding);
+            }
+        }
+    }
+
+    /// Drop the node slots where `keep` is false, for compacted worlds
+    pub(crate) fn retain(&mut self, keep: &[bool]) {
+        for column in self.columns.values_mut() {
+            column.retain(keep);
+        }
+    }
+
+    /// These attributes repeated `copies` times, for replicated worlds
+    pub(crate) fn repeat(&self, copies: usize) -> Attributes {
+        let mut columns = BTreeMap::new();
+        for (key, column) in &self.columns {
+            let mut repeated = column.empty_like(0);
+            for _ in 0..copies {
+                repeated.extend(column);
+            }
+            columns.insert(key.clone(), repeated);
+        }
+        Attributes { columns }
+    }
+}
+
+/// Dense column of `len` slots from sparse `(node, text)` values
+fn fill<T: Clone>(
+    len: usize,
+    values: &[(NodeIndex, &str)],
+    parse: impl Fn(&str) -> Option<T>,
+    wrap: impl Fn(Vec<Option<T>>) -> Column,
+) -> Column {
+    let mut column = vec![None; len];
+    for &(node, value) in values {
+        column[node as usize] = parse(value);
+    }
+    wrap(column)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_infers_narrowest_type() {
+        let raw = [(0, "hp", "3"), (1, "hp", "5"), (0, "speed", "1.5"), (1, "speed", "2"), (1, "region", "north")];
+        let attrs = Attributes::from_raw(3, &raw);
+
+        assert_eq!(attrs.get::<u32>("hp"), Some(&[Some(3), Some(5), None][..]));
+        assert_eq!(attrs.get::<f32>("speed"), Some(&[Some(1.5), Some(2.0), None][..]));
+        assert_eq!(attrs.get::<String>("region").unwrap()[1].as_deref(), Some("north"));
+        assert!(attrs.get::<f32>("hp").is_none());
+        assert_eq!(attrs.keys().collect::<Vec<_>>(), ["hp", "region", "speed"]);
+    }
+
+    #[test]
+    fn test_insert_creates_or_reuses_typed_column() {
+        let mut attrs = Attributes::default();
+        attrs.insert::<f32>("pheromone", 2).unwrap()[1] = Some(0.5);
+
+        assert_eq!(attrs.get::<f32>("pheromone"), Some(&[None, Some(0.5)][..]));
+        assert_eq!(attrs.insert::<f32>("pheromone", 2).unwrap()[1], Some(0.5));
+        assert!(attrs.insert::<u32>("pheromone", 2).is_none());
+    }
+
+    #[test]
+    fn test_append_pads_and_widens() {
+        let mut a = Attributes::from_raw(2, &[(0, "hp", "3")]);
+        let b = Attributes::from_raw(1, &[(0, "hp", "2.5"), (0, "tag", "x")]);
+        a.append(2, &b, 1);
+
+        assert_eq!(a.get::<f32>("hp"), Some(&[Some(3.0), None, Some(2.5)][..]));
+        assert_eq!(a.get::<String>("tag"), Some(&[None, None, Some("x".to_string())][..]));
+    }
+}
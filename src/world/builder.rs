@@ -0,0 +1,194 @@
+use crate::direction::Direction;
+use crate::error::{ParseError, Result, SourceLocation};
+use crate::world::parser::{resolve, RawColony, FOOD_TAG, FORTIFIED_TAG, SPAWN_TAG};
+use crate::world::world::World;
+use std::collections::HashMap;
+
+/// Builds a [`World`] from code instead of map text.
+///
+/// The result is the world the equivalent map file would load to: colonies
+/// get ids in first-seen order, those passed to [`colony`](Self::colony) or
+/// leaving by a tunnel first and the ones only entered by a tunnel after
+/// them. Every call is checked as it is made; the first mistake is kept and
+/// returned by [`build`](Self::build).
+///
+/// ```
+/// use ants_code_challenge::direction::Direction::{East, North};
+/// use ants_code_challenge::world::World;
+///
+/// let world = World::builder().colony("A").tunnel("A", North, "B").two_way("B", East, "C").build().unwrap();
+/// assert_eq!(world.nodes().len(), 3);
+/// ```
+#[derive(Default)]
+pub struct WorldBuilder {
+    colonies: Vec<RawColony>,
+    index: HashMap<String, usize>,
+    error: Option<ParseError>,
+}
+
+/// Tunnels added by the builder have no place in a map file
+const NOWHERE: SourceLocation = SourceLocation { line: 0, column: 0 };
+
+impl World {
+    /// Start building a world in code; see [`WorldBuilder`]
+    pub fn builder() -> WorldBuilder {
+        WorldBuilder::default()
+    }
+}
+
+impl WorldBuilder {
+    /// Declare a colony, even one without tunnels. Declaring it again is a no-op.
+    pub fn colony(mut self, name: &str) -> Self {
+        self.declare(name);
+        self
+    }
+
+    /// One-way tunnel leaving `from` towards `direction`, declaring `from`.
+    ///
+    /// Repeating a tunnel is a no-op; giving `from` a second, different
+    /// tunnel in the same direction is an error.
+    pub fn tunnel(mut self, from: &str, direction: Direction, to: &str) -> Self {
+        if self.error.is_some() || !self.check_name(to) {
+            return self;
+        }
+        let Some(i) = self.declare(from) else {
+            return self;
+        };
+        let colony = &mut self.colonies[i];
+        match colony.edges.iter().find(|(dir, _, _)| *dir == direction) {
+            Some((_, first, _)) if first != to => {
+                self.error = Some(ParseError::ConflictingTunnel {
+                    colony: from.to_string(),
+                    direction,
+                    first: first.clone(),
+                    second: to.to_string(),
+                });
+            }
+            Some(_) => {}
+            None => colony.edges.push((direction, to.to_string(), NOWHERE)),
+        }
+        self
+    }
+
+    /// Tunnel from `a` to `b` plus the one back from `b` in the opposite direction
+    pub fn two_way(self, a: &str, direction: Direction, b: &str) -> Self {
+        self.tunnel(a, direction, b).tunnel(b, direction.opposite(), a)
+    }
+
+    /// Set attribute `key` of `colony`, as `key=value` on its map line would.
+    ///
+    /// `key` may not be a direction or `alias`, which mean something else
+    /// on a map line; setting a key twice keeps the later value.
+    pub fn attr(mut self, colony: &str, key: &str, value: &str) -> Self {
+        if self.error.is_some() {
+            return self;
+        }
+        if key == "alias" || key.parse::<Direction>().is_ok() || !is_token(key) || !is_token(value) {
+            self.error = Some(ParseError::invalid_line(format!("invalid attribute '{key}={value}' on {colony}")));
+            return self;
+        }
+        if let Some(i) = self.declare(colony) {
+            self.colonies[i].attrs.push((key.to_string(), value.to_string()));
+        }
+        self
+    }
+
+    /// Mark `colony` as a landing zone for ants (`spawn`)
+    pub fn spawn(self, colony: &str) -> Self {
+        self.attr(colony, SPAWN_TAG, "1")
+    }
+
+    /// Mark `colony` as fortified (`fortified`)
+    pub fn fortified(self, colony: &str) -> Self {
+        self.attr(colony, FORTIFIED_TAG, "1")
+    }
+
+    /// Put food on `colony` (`food`)
+    pub fn food(self, colony: &str) -> Self {
+        self.attr(colony, FOOD_TAG, "1")
+    }
+
+    /// The world, or the first mistake made while describing it
+    pub fn build(self) -> Result<World> {
+        if let Some(err) = self.error {
+            return Err(err);
+        }
+        let (names, nodes, attrs) = resolve(self.colonies, &Default::default(), None, None)?;
+        Ok(World::new(names, nodes).with_attributes(attrs))
+    }
+
+    /// Index of colony `name`, adding it if new; `None` once an error is kept
+    fn declare(&mut self, name: &str) -> Option<usize> {
+        if self.error.is_some() || !self.check_name(name) {
+            return None;
+        }
+        if let Some(&i) = self.index.get(name) {
+            return Some(i);
+        }
+        self.colonies.push(RawColony {
+            name: name.to_string(),
+            edges: Vec::new(),
+            aliases: Vec::new(),
+            attrs: Vec::new(),
+        });
+        self.index.insert(name.to_string(), self.colonies.len() - 1);
+        Some(self.colonies.len() - 1)
+    }
+
+    /// Whether `name` could be written on a map line, keeping an error if not
+    fn check_name(&mut self, name: &str) -> bool {
+        if is_token(name) && !name.starts_with('#') {
+            return true;
+        }
+        self.error = Some(ParseError::invalid_line(format!("invalid colony name '{name}'")));
+        false
+    }
+}
+
+/// Non-empty, one whitespace-free token without `=`
+fn is_token(s: &str) -> bool {
+    !s.is_empty() && !s.contains(|c: char| c.is_whitespace() || c == '=')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::direction::Direction::{East, North, South, West};
+    use crate::world::parser::load_world_from_str;
+
+    #[test]
+    fn test_builder_matches_the_parser() {
+        let built = World::builder()
+            .colony("A")
+            .tunnel("A", North, "B")
+            .two_way("A", East, "C")
+            .attr("A", "hp", "3")
+            .spawn("C")
+            .build()
+            .unwrap();
+        let parsed = load_world_from_str("A north=B east=C hp=3\nC west=A spawn\n", &Default::default()).unwrap();
+
+        let map = |world: &World| {
+            let mut out = Vec::new();
+            world.write_map(&mut out).unwrap();
+            String::from_utf8(out).unwrap()
+        };
+        assert_eq!(map(&built), map(&parsed));
+        assert_eq!(built.find("B"), parsed.find("B"));
+        assert_eq!(built.attr::<u32>("hp"), parsed.attr::<u32>("hp"));
+        assert_eq!(built.spawn_zones(), parsed.spawn_zones());
+    }
+
+    #[test]
+    fn test_builder_keeps_the_first_mistake() {
+        let err = World::builder().colony("bad name").colony("#x").build().unwrap_err();
+        assert!(err.to_string().contains("'bad name'"), "{err}");
+
+        let err = World::builder().tunnel("A", South, "B").tunnel("A", South, "C").build().unwrap_err();
+        assert!(matches!(err, ParseError::ConflictingTunnel { ref first, ref second, .. } if first == "B" && second == "C"));
+
+        let err = World::builder().attr("A", "west", "B").build().unwrap_err();
+        assert!(err.to_string().contains("west=B"), "{err}");
+        assert!(World::builder().tunnel("A", West, "B").tunnel("A", West, "B").build().is_ok());
+    }
+}
@@ -0,0 +1,128 @@
+use crate::node_id::{NodeId, NodeIndex};
+use crate::world::node_table::NodeTable;
+use crate::world::world::World;
+use serde::{Deserialize, Serialize};
+
+/// Id translation left by [`World::compact`]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IdMap {
+    /// New id of every old colony; `None` for the removed ones
+    new_ids: Vec<Option<NodeId>>,
+    /// Old id of every new colony
+    old_ids: Vec<NodeId>,
+}
+
+impl IdMap {
+    /// New id of old colony `old`, or `None` if it was removed
+    ///
+    /// # Panics
+    /// If `old` was not a colony of the world before compaction.
+    #[inline]
+    pub fn get(&self, old: NodeId) -> Option<NodeId> {
+        self.new_ids[old.index()]
+    }
+
+    /// Old id of new colony `new`
+    ///
+    /// # Panics
+    /// If `new` is not a colony of the compacted world.
+    #[inline]
+    pub fn old(&self, new: NodeId) -> NodeId {
+        self.old_ids[new.index()]
+    }
+
+    /// Colonies kept
+    pub fn len(&self) -> usize {
+        self.old_ids.len()
+    }
+
+    /// Whether every colony was removed
+    pub fn is_empty(&self) -> bool {
+        self.old_ids.is_empty()
+    }
+
+    /// Colonies removed
+    pub fn removed(&self) -> usize {
+        self.new_ids.len() - self.old_ids.len()
+    }
+}
+
+impl World {
+    /// Remove destroyed colonies and renumber the rest, keeping their order.
+    ///
+    /// Tunnels into removed colonies are dropped, names and attributes move
+    /// with their colony and the reverse edges are rebuilt, so a follow-up
+    /// run over a damaged world no longer carries dead slots in its
+    /// per-colony arrays. Ids from before the call (in ants, events or
+    /// portals) must be translated through the returned map.
+    pub fn compact(&mut self) -> IdMap {
+        let keep: Vec<bool> = self.nodes.iter().map(|n| n.is_alive()).collect();
+        let mut new_ids = Vec::with_capacity(self.nodes.len());
+        let mut old_ids = Vec::new();
+        for (i, &alive) in keep.iter().enumerate() {
+            new_ids.push(alive.then(|| NodeId::from_index(old_ids.len())));
+            if alive {
+                old_ids.push(NodeId::from_index(i));
+            }
+        }
+
+        let mut names = Vec::with_capacity(old_ids.len());
+        let mut nodes = Vec::with_capacity(old_ids.len());
+        for &old in &old_ids {
+            let mut node = self.nodes.get(old.index()).expect("kept colony");
+            names.push(self.names[node.name_idx as usize].clone());
+            node.name_idx = (names.len() - 1) as NodeIndex;
+            for slot in node.neighbors.iter_mut() {
+                *slot = slot.and_then(|n| new_ids[n.index()]);
+            }
+            nodes.push(node);
+        }
+        self.names = names;
+        self.nodes = NodeTable::from(nodes);
+        self.attrs.retain(&keep);
+        self.rebuild_in_edges();
+
+        IdMap { new_ids, old_ids }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::direction::Direction;
+    use crate::world::load_world_from_str;
+
+    #[test]
+    fn test_compact_renumbers_and_drops_dead_tunnels() {
+        let src = "A east=B south=C hp=1\nB west=A east=C hp=2\nC north=A west=B hp=3\n";
+        let mut world = load_world_from_str(src, &Default::default()).unwrap();
+        world.destroy(NodeId::new(1));
+
+        let map = world.compact();
+
+        assert_eq!((map.len(), map.removed()), (2, 1));
+        assert_eq!(map.get(NodeId::new(0)), Some(NodeId::new(0)));
+        assert_eq!(map.get(NodeId::new(1)), None);
+        assert_eq!(map.get(NodeId::new(2)), Some(NodeId::new(1)));
+        assert_eq!(map.old(NodeId::new(1)), NodeId::new(2));
+
+        assert_eq!(world.names(), ["A", "C"]);
+        let a = world.colony(NodeId::new(0)).unwrap();
+        assert_eq!(a.neighbor(Direction::South).map(|n| n.name()), Some("C"));
+        assert!(a.neighbor(Direction::East).is_none());
+        assert_eq!(world.attr::<u32>("hp").unwrap(), [Some(1), Some(3)]);
+        assert_eq!(world.in_neighbors(NodeId::new(1)), [NodeId::new(0)]);
+
+        let mut out = Vec::new();
+        world.write_map(&mut out).unwrap();
+        assert_eq!(String::from_utf8(out).unwrap(), "A south=C hp=1\nC north=A hp=3\n");
+    }
+
+    #[test]
+    fn test_compact_without_destruction_is_identity() {
+        let mut world = load_world_from_str("A east=B\nB west=A\n", &Default::default()).unwrap();
+        let map = world.compact();
+        assert_eq!(map.removed(), 0);
+        assert_eq!(map.get(NodeId::new(1)), Some(NodeId::new(1)));
+    }
+}
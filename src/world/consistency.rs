@@ -0,0 +1,183 @@
+use crate::direction::Direction;
+use crate::error::SourceLocation;
+use crate::node_id::NodeId;
+use crate::world::parser::TunnelSource;
+use crate::world::world::World;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+
+/// A tunnel as written in the map, resolved to names
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WrittenTunnel {
+    pub from: String,
+    pub direction: Direction,
+    pub to: String,
+    pub at: SourceLocation,
+}
+
+impl WrittenTunnel {
+    fn of(world: &World, tunnel: &TunnelSource) -> Self {
+        Self {
+            from: world.get_colony_name(tunnel.from).to_string(),
+            direction: tunnel.direction,
+            to: world.get_colony_name(tunnel.to).to_string(),
+            at: tunnel.at,
+        }
+    }
+}
+
+impl fmt::Display for WrittenTunnel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}={} ({})", self.from, self.direction.as_str(), self.to, self.at)
+    }
+}
+
+/// Tunnels that cannot all hold on a grid
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case", tag = "kind")]
+pub enum Contradiction {
+    /// The two tunnels put one colony both `direction` and opposite of another
+    Opposed { colony: String, other: String, direction: Direction, first: WrittenTunnel, second: WrittenTunnel },
+    /// `tunnel` leads to a colony whose way back (`back`) goes somewhere else
+    Mismatched { tunnel: WrittenTunnel, back: WrittenTunnel },
+    /// `tunnel` leads to a colony with no tunnel in the opposite direction
+    OneWay { tunnel: WrittenTunnel },
+}
+
+impl Contradiction {
+    /// Where the contradiction starts in the file
+    pub fn at(&self) -> SourceLocation {
+        match self {
+            Contradiction::Opposed { first: tunnel, .. }
+            | Contradiction::Mismatched { tunnel, .. }
+            | Contradiction::OneWay { tunnel } => tunnel.at,
+        }
+    }
+
+    pub fn is_one_way(&self) -> bool {
+        matches!(self, Contradiction::OneWay { .. })
+    }
+}
+
+impl fmt::Display for Contradiction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Contradiction::Opposed { colony, other, direction, first, second } => write!(
+                f,
+                "{other} is both {} and {} of {colony}: {first} and {second}",
+                direction.as_str(),
+                direction.opposite().as_str()
+            ),
+            Contradiction::Mismatched { tunnel, back } => write!(f, "{tunnel}, but the way back is {back}"),
+            Contradiction::OneWay { tunnel } => write!(
+                f,
+                "{tunnel} has no way back ({} {}=...)",
+                tunnel.to,
+                tunnel.direction.opposite().as_str()
+            ),
+        }
+    }
+}
+
+/// Check the explicit tunnels of `world` (from
+/// [`load_world_with_sources`](crate::world::parser::load_world_with_sources))
+/// for geometric consistency: if B is north of A then A must be south of
+/// B, and no colony may be both north and south (or east and west) of
+/// another. Only the tunnel that wins for each colony and direction counts.
+/// Contradictions come back in file order.
+pub fn check_directions(world: &World, tunnels: &[TunnelSource]) -> Vec<Contradiction> {
+    let slot = |id: NodeId, d: Direction| id.index() * 4 + d.index();
+    let mut winner: Vec<Option<&TunnelSource>> = vec![None; world.nodes.len() * 4];
+    for t in tunnels {
+        winner[slot(t.from, t.direction)] = Some(t);
+    }
+    let effective: Vec<&TunnelSource> = winner.iter().flatten().copied().collect();
+
+    let mut found = Vec::new();
+    // Where each colony lies relative to another, by the tunnels that say so
+    let mut claims: HashMap<(usize, usize), [Option<&TunnelSource>; 4]> = HashMap::new();
+    for &t in &effective {
+        let (a, b) = (t.from.index(), t.to.index());
+        claims.entry((a, b)).or_default()[t.direction.index()].get_or_insert(t);
+        claims.entry((b, a)).or_default()[t.direction.opposite().index()].get_or_insert(t);
+
+        match winner[slot(t.to, t.direction.opposite())] {
+            None => found.push(Contradiction::OneWay { tunnel: WrittenTunnel::of(world, t) }),
+            Some(back) if back.to != t.from => found.push(Contradiction::Mismatched {
+                tunnel: WrittenTunnel::of(world, t),
+                back: WrittenTunnel::of(world, back),
+            }),
+            Some(_) => {}
+        }
+    }
+    for (&(a, b), dirs) in &claims {
+        if a > b {
+            continue;
+        }
+        for direction in [Direction::North, Direction::East] {
+            if let (Some(x), Some(y)) = (dirs[direction.index()], dirs[direction.opposite().index()]) {
+                let (first, second) = if (x.at.line, x.at.column) <= (y.at.line, y.at.column) { (x, y) } else { (y, x) };
+                found.push(Contradiction::Opposed {
+                    colony: world.names[a].clone(),
+                    other: world.names[b].clone(),
+                    direction,
+                    first: WrittenTunnel::of(world, first),
+                    second: WrittenTunnel::of(world, second),
+                });
+            }
+        }
+    }
+    found.sort_by_key(|c| (c.at().line, c.at().column, !matches!(c, Contradiction::Opposed { .. })));
+    found
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::world::parser::load_world_with_sources_from_str;
+
+    fn check(src: &str) -> Vec<String> {
+        let (world, tunnels) = load_world_with_sources_from_str(src, &Default::default()).unwrap();
+        check_directions(&world, &tunnels).iter().map(|c| c.to_string()).collect()
+    }
+
+    #[test]
+    fn test_consistent_grid_has_no_contradictions() {
+        assert!(check("A east=B south=C\nB west=A\nC north=A\n").is_empty());
+    }
+
+    #[test]
+    fn test_mismatched_and_one_way_tunnels() {
+        assert_eq!(
+            check("A north=B\nB south=C\nC north=B east=D\n"),
+            [
+                "A north=B (line 1, column 3), but the way back is B south=C (line 2, column 3)",
+                "C east=D (line 3, column 11) has no way back (D west=...)",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_colony_on_both_sides() {
+        let found = check("A north=B south=B\nB south=A north=A\n");
+        assert_eq!(found[0], "B is both north and south of A: A north=B (line 1, column 3) and A south=B (line 1, column 11)");
+        assert_eq!(found.iter().filter(|c| c.contains("is both")).count(), 1);
+
+        // B north of A, and A north of B
+        let found = check("A north=B\nB north=A\n");
+        assert_eq!(
+            found,
+            [
+                "B is both north and south of A: A north=B (line 1, column 3) and B north=A (line 2, column 3)",
+                "A north=B (line 1, column 3) has no way back (B south=...)",
+                "B north=A (line 2, column 3) has no way back (A south=...)",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_later_tunnel_wins() {
+        assert!(check("A north=C\nB south=A\nA north=B\n").is_empty());
+    }
+}
@@ -0,0 +1,57 @@
+use crate::world::World;
+use std::collections::HashSet;
+
+/// What a damaged map (a previous run's final world) lost against the map
+/// it came from, matched by colony name
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Damage {
+    /// Colonies alive in the original but gone from the damaged map, in
+    /// original id order
+    pub lost: Vec<String>,
+    /// Colonies alive in the damaged map that the original never had alive,
+    /// in damaged id order; non-empty means the two maps do not match
+    pub unknown: Vec<String>,
+    /// Colonies alive in the original
+    pub original: usize,
+}
+
+impl Damage {
+    /// Diff `damaged` against `original`
+    pub fn between(original: &World, damaged: &World) -> Self {
+        let alive = |world: &World| -> HashSet<String> {
+            world.alive_nodes().map(|colony| colony.name().to_string()).collect()
+        };
+        let (before, after) = (alive(original), alive(damaged));
+        Self {
+            lost: original.alive_nodes().map(|c| c.name()).filter(|n| !after.contains(*n)).map(String::from).collect(),
+            unknown: damaged.alive_nodes().map(|c| c.name()).filter(|n| !before.contains(*n)).map(String::from).collect(),
+            original: before.len(),
+        }
+    }
+
+    /// Share of the original's alive colonies that were lost (0 for an empty original)
+    pub fn lost_fraction(&self) -> f64 {
+        match self.original {
+            0 => 0.0,
+            alive => self.lost.len() as f64 / alive as f64,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::world::{load_world_from_str, ParseOptions};
+
+    #[test]
+    fn test_between_lists_lost_and_unknown_colonies() {
+        let original = load_world_from_str("A east=B\nB west=A east=C\nC west=B\nD\n", &ParseOptions::default()).unwrap();
+        let options = ParseOptions { damaged: true, ..Default::default() };
+        let damaged = load_world_from_str("A east=B\nB west=A east=C\nE\n", &options).unwrap();
+
+        let damage = Damage::between(&original, &damaged);
+        assert_eq!(damage.lost, ["C", "D"]);
+        assert_eq!(damage.unknown, ["E"]);
+        assert_eq!(damage.lost_fraction(), 0.5);
+    }
+}
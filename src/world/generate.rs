@@ -0,0 +1,114 @@
+use crate::direction::Direction;
+use crate::error::{ParseError, Result};
+use crate::node_id::{NodeId, NodeIndex};
+use crate::world::attrs::Attributes;
+use crate::world::node::Node;
+use crate::world::world::World;
+
+/// Attribute holding a colony's column, see [`World::coords`]
+pub const X_TAG: &str = "x";
+/// Attribute holding a colony's row, see [`World::coords`]
+pub const Y_TAG: &str = "y";
+
+impl World {
+    /// A `width` x `height` lattice of colonies, with two-way tunnels
+    /// between grid neighbors (north = up, east = right).
+    ///
+    /// Colony `(x, y)` is named `C{x}_{y}`, gets id `y * width + x` and
+    /// stores its coordinates as the `x` and `y` attributes, which
+    /// [`World::coords`] reads back and map files keep. With `torus` the
+    /// edges wrap around: the east tunnel of the last column leads to the
+    /// first, and likewise for rows; a side one colony long does not wrap.
+    pub fn lattice(width: usize, height: usize, torus: bool) -> Result<World> {
+        let n = width.saturating_mul(height);
+        if !NodeId::can_address(n) || u32::try_from(width.max(height)).is_err() {
+            return Err(ParseError::TooManyColonies(n));
+        }
+        let mut names = Vec::with_capacity(n);
+        let mut nodes = Vec::with_capacity(n);
+        let id = |x: usize, y: usize| NodeId::from_index(y * width + x);
+        // Coordinate one step `d` away from `at` on a side `len` long, if any
+        let step = |at: usize, d: i64, len: usize| match (at as i64 + d, torus && len > 1) {
+            (to, _) if (0..len as i64).contains(&to) => Some(to as usize),
+            (-1, true) => Some(len - 1),
+            (_, true) => Some(0),
+            _ => None,
+        };
+        for y in 0..height {
+            for x in 0..width {
+                let mut node = Node::new(nodes.len() as NodeIndex);
+                for direction in Direction::iter() {
+                    let (dx, dy) = direction.delta();
+                    if let (Some(nx), Some(ny)) = (step(x, dx, width), step(y, dy, height)) {
+                        node.set_neighbor(direction.index(), id(nx, ny));
+                    }
+                }
+                names.push(format!("C{x}_{y}"));
+                nodes.push(node);
+            }
+        }
+
+        let mut attrs = Attributes::default();
+        for (key, coord) in [(X_TAG, 0), (Y_TAG, 1)] {
+            if let Some(column) = attrs.insert::<u32>(key, n) {
+                for (i, slot) in column.iter_mut().enumerate() {
+                    let (x, y) = (i % width, i / width);
+                    *slot = Some([x, y][coord] as u32);
+                }
+            }
+        }
+        Ok(World::new(names, nodes).with_attributes(attrs))
+    }
+
+    /// Column and row of colony `node`, from its `x` and `y` attributes (set
+    /// by [`World::lattice`] or in the map); `None` unless both are there
+    pub fn coords(&self, node: NodeId) -> Option<(u32, u32)> {
+        let x = self.attr::<u32>(X_TAG)?.get(node.index()).copied().flatten()?;
+        let y = self.attr::<u32>(Y_TAG)?.get(node.index()).copied().flatten()?;
+        Some((x, y))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::direction::Direction::{East, North, South, West};
+    use crate::world::parser::load_world_from_str;
+
+    fn neighbor<'a>(world: &'a World, name: &str, direction: Direction) -> Option<&'a str> {
+        let id = world.find(name)?;
+        world.nodes().neighbors(id.index())[direction.index()].map(|n| world.get_colony_name(n))
+    }
+
+    #[test]
+    fn test_lattice_links_grid_neighbors() {
+        let world = World::lattice(3, 2, false).unwrap();
+
+        assert_eq!(world.names().len(), 6);
+        assert_eq!(neighbor(&world, "C1_0", East), Some("C2_0"));
+        assert_eq!(neighbor(&world, "C1_0", South), Some("C1_1"));
+        assert_eq!(neighbor(&world, "C1_0", North), None);
+        assert_eq!(neighbor(&world, "C0_1", West), None);
+        assert_eq!(world.coords(world.find("C2_1").unwrap()), Some((2, 1)));
+    }
+
+    #[test]
+    fn test_torus_wraps_around() {
+        let world = World::lattice(3, 1, true).unwrap();
+
+        assert_eq!(neighbor(&world, "C2_0", East), Some("C0_0"));
+        assert_eq!(neighbor(&world, "C0_0", West), Some("C2_0"));
+        // One row: nothing to wrap to north or south
+        assert_eq!(neighbor(&world, "C1_0", North), None);
+    }
+
+    #[test]
+    fn test_coords_survive_the_map_file() {
+        let mut out = Vec::new();
+        World::lattice(2, 2, true).unwrap().write_map(&mut out).unwrap();
+        let world = load_world_from_str(std::str::from_utf8(&out).unwrap(), &Default::default()).unwrap();
+
+        assert_eq!(world.coords(world.find("C1_1").unwrap()), Some((1, 1)));
+        assert!(World::lattice(usize::MAX, 2, false).is_err());
+    }
+}
@@ -0,0 +1,201 @@
+use crate::ant::Ant;
+use crate::direction::Direction;
+use crate::node_id::NodeId;
+use crate::world::world::World;
+use std::collections::VecDeque;
+
+/// 2D placement of a lattice-shaped world (north = up, east = right)
+#[derive(Clone, Debug)]
+pub struct GridLayout {
+    pub width: usize,
+    pub height: usize,
+    /// Row-major node ids; `None` where no colony sits
+    cells: Vec<Option<NodeId>>,
+}
+
+impl GridLayout {
+    /// Detect whether the world is a lattice and compute its layout.
+    ///
+    /// Coordinates are propagated from the first colony along tunnels in both
+    /// directions. Returns `None` if the map is empty, disconnected, or any
+    /// tunnel contradicts the coordinates (two colonies on one cell, or a
+    /// tunnel spanning more than one step).
+    pub fn detect(world: &World) -> Option<Self> {
+        let n = world.nodes.len();
+        if n == 0 {
+            return None;
+        }
+
+        // Undirected adjacency with the step needed to follow each edge
+        let mut adjacency: Vec<Vec<(usize, (i64, i64))>> = vec![Vec::new(); n];
+        for (src, node) in world.nodes.iter().enumerate() {
+            for direction in Direction::iter() {
+                let Some(dst) = node.neighbors[direction.index()] else {
+                    continue;
+                };
+                adjacency[src].push((dst.index(), direction.delta()));
+                adjacency[dst.index()].push((src, direction.opposite().delta()));
+            }
+        }
+
+        let mut coords: Vec<Option<(i64, i64)>> = vec![None; n];
+        let mut queue = VecDeque::from([0usize]);
+        coords[0] = Some((0, 0));
+        while let Some(nid) = queue.pop_front() {
+            let (x, y) = coords[nid]?;
+            for &(dst, (dx, dy)) in &adjacency[nid] {
+                let expected = (x + dx, y + dy);
+                match coords[dst] {
+                    Some(actual) if actual != expected => return None,
+                    Some(_) => {}
+                    None => {
+                        coords[dst] = Some(expected);
+                        queue.push_back(dst);
+                    }
+                }
+            }
+        }
+
+        let coords: Vec<(i64, i64)> = coords.into_iter().collect::<Option<_>>()?;
+        let min_x = coords.iter().map(|c| c.0).min()?;
+        let max_x = coords.iter().map(|c| c.0).max()?;
+        let min_y = coords.iter().map(|c| c.1).min()?;
+        let max_y = coords.iter().map(|c| c.1).max()?;
+        let width = (max_x - min_x + 1) as usize;
+        let height = (max_y - min_y + 1) as usize;
+
+        let mut cells = vec![None; width.checked_mul(height)?];
+        for (nid, &(x, y)) in coords.iter().enumerate() {
+            let idx = (y - min_y) as usize * width + (x - min_x) as usize;
+            if cells[idx].is_some() {
+                return None;
+            }
+            cells[idx] = Some(NodeId::from_index(nid));
+        }
+
+        Some(Self { width, height, cells })
+    }
+
+    /// Layout of `world` from the coordinates of [`World::coords`] when
+    /// every colony has them (a torus among others, which
+    /// [`GridLayout::detect`] rejects), else as detected from its tunnels
+    pub fn of(world: &World) -> Option<Self> {
+        Self::from_coords(world).or_else(|| Self::detect(world))
+    }
+
+    /// Layout from [`World::coords`]; `None` if a colony has no coordinates
+    /// or two share a cell. The grid is trimmed to the colonies' bounds.
+    pub fn from_coords(world: &World) -> Option<Self> {
+        let coords: Vec<(u32, u32)> = world.colonies().map(|c| world.coords(c.id())).collect::<Option<_>>()?;
+        let min_x = coords.iter().map(|c| c.0).min()?;
+        let max_x = coords.iter().map(|c| c.0).max()?;
+        let min_y = coords.iter().map(|c| c.1).min()?;
+        let max_y = coords.iter().map(|c| c.1).max()?;
+        let width = (max_x - min_x) as usize + 1;
+        let height = (max_y - min_y) as usize + 1;
+
+        let mut cells = vec![None; width.checked_mul(height)?];
+        for (nid, &(x, y)) in coords.iter().enumerate() {
+            let idx = (y - min_y) as usize * width + (x - min_x) as usize;
+            if cells[idx].is_some() {
+                return None;
+            }
+            cells[idx] = Some(NodeId::from_index(nid));
+        }
+        Some(Self { width, height, cells })
+    }
+
+    /// Node id at a grid cell, if a colony sits there
+    pub fn node_at(&self, x: usize, y: usize) -> Option<NodeId> {
+        *self.cells.get(y * self.width + x)?
+    }
+
+    /// Render the grid, one character per cell:
+    /// - ` ` no colony
+    /// - `x` destroyed colony
+    /// - `.` alive colony without ants
+    /// - `1`..`9` alive ants standing there, `*` for ten or more
+    pub fn render(&self, world: &World, ants: &[Ant]) -> String {
+        let mut density = vec![0u32; world.nodes.len()];
+        for ant in ants.iter().filter(|a| a.is_alive()) {
+            density[ant.pos.index()] += 1;
+        }
+
+        let mut out = String::with_capacity((self.width + 1) * self.height);
+        for row in self.cells.chunks(self.width) {
+            for &cell in row {
+                let c = match cell {
+                    None => ' ',
+                    Some(nid) if !world.is_alive(nid) => 'x',
+                    Some(nid) => match density[nid.index()] {
+                        0 => '.',
+                        d @ 1..=9 => char::from_digit(d, 10).unwrap_or('*'),
+                        _ => '*',
+                    },
+                };
+                out.push(c);
+            }
+            let trimmed = out.trim_end_matches(' ').len();
+            out.truncate(trimmed);
+            out.push('\n');
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::world::parser::parse_world_from_str;
+
+    fn world_from(src: &str) -> World {
+        let (names, nodes) = parse_world_from_str(src);
+        World::new(names, nodes)
+    }
+
+    #[test]
+    fn test_detect_square_lattice() {
+        let world = world_from("A east=B south=C\nB west=A south=D\nC north=A east=D\nD\n");
+        let grid = GridLayout::detect(&world).expect("lattice");
+
+        assert_eq!((grid.width, grid.height), (2, 2));
+        assert_eq!(grid.node_at(0, 0), world.find("A"));
+        assert_eq!(grid.node_at(1, 1), world.find("D"));
+    }
+
+    #[test]
+    fn test_detect_rejects_inconsistent_tunnels() {
+        // Going east then east again must not land back on the start
+        let world = world_from("A east=B\nB east=A\n");
+        assert!(GridLayout::detect(&world).is_none());
+    }
+
+    #[test]
+    fn test_detect_rejects_disconnected_maps() {
+        let world = world_from("A east=B\nC\n");
+        assert!(GridLayout::detect(&world).is_none());
+    }
+
+    #[test]
+    fn test_torus_lays_out_from_coords() {
+        let world = World::lattice(3, 2, true).unwrap();
+        assert!(GridLayout::detect(&world).is_none());
+
+        let grid = GridLayout::of(&world).expect("coordinates");
+        assert_eq!((grid.width, grid.height), (3, 2));
+        assert_eq!(grid.node_at(2, 1), world.find("C2_1"));
+    }
+
+    #[test]
+    fn test_render_marks_state_and_density() {
+        let mut world = world_from("A east=B\nB east=C\nC south=D\nD\n");
+        let grid = GridLayout::detect(&world).expect("lattice");
+        let b = world.find("B").unwrap();
+        let c = world.find("C").unwrap();
+        let a = world.find("A").unwrap();
+        world.nodes.destroy(a.index());
+        let ants = vec![Ant::new(0, b), Ant::new(1, c), Ant::new(2, c)];
+
+        assert_eq!(grid.render(&world, &ants), "x12\n  .\n");
+    }
+}
@@ -0,0 +1,81 @@
+use crate::node_id::NodeId;
+use crate::world::node_table::NodeTable;
+
+/// Reverse adjacency in compressed sparse row form: for every colony, the
+/// colonies with a tunnel leading into it.
+///
+/// `sources[offsets[i]..offsets[i + 1]]` are the in-neighbors of colony `i`,
+/// in ascending id order and once per tunnel (a colony with two tunnels into
+/// `i` appears twice). Destroyed colonies are kept, as in [`Node::neighbors`](crate::world::Node::neighbors).
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct InEdges {
+    offsets: Vec<usize>,
+    sources: Vec<NodeId>,
+}
+
+impl InEdges {
+    /// Build the reverse edges of `nodes` with two passes over the tunnels
+    pub fn build(nodes: &NodeTable) -> Self {
+        let mut offsets = vec![0usize; nodes.len() + 1];
+        for i in 0..nodes.len() {
+            for target in nodes.neighbors(i).into_iter().flatten() {
+                offsets[target.index() + 1] += 1;
+            }
+        }
+        for i in 0..nodes.len() {
+            offsets[i + 1] += offsets[i];
+        }
+
+        let mut next = offsets.clone();
+        let placeholder = NodeId::from_index(0);
+        let mut sources = vec![placeholder; offsets[nodes.len()]];
+        for i in 0..nodes.len() {
+            for target in nodes.neighbors(i).into_iter().flatten() {
+                sources[next[target.index()]] = NodeId::from_index(i);
+                next[target.index()] += 1;
+            }
+        }
+        Self { offsets, sources }
+    }
+
+    /// Colonies with a tunnel into `node`
+    #[inline]
+    pub fn of(&self, node: NodeId) -> &[NodeId] {
+        let i = node.index();
+        &self.sources[self.offsets[i]..self.offsets[i + 1]]
+    }
+
+    /// Total number of tunnels
+    pub fn len(&self) -> usize {
+        self.sources.len()
+    }
+
+    /// Whether the map has no tunnels at all
+    pub fn is_empty(&self) -> bool {
+        self.sources.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::world::parser::parse_world_from_str;
+
+    #[test]
+    fn test_build_lists_sources_per_target() {
+        // A -> B, C -> B (twice), B -> A
+        let (_, nodes) = parse_world_from_str("A east=B\nB west=A\nC north=B south=B\n");
+        let edges = InEdges::build(&nodes.into());
+
+        assert_eq!(edges.len(), 4);
+        assert_eq!(edges.of(NodeId::new(0)), [NodeId::new(1)]);
+        assert_eq!(edges.of(NodeId::new(1)), [NodeId::new(0), NodeId::new(2), NodeId::new(2)]);
+        assert!(edges.of(NodeId::new(2)).is_empty());
+    }
+
+    #[test]
+    fn test_empty_world() {
+        let edges = InEdges::build(&NodeTable::default());
+        assert!(edges.is_empty());
+    }
+}
@@ -0,0 +1,121 @@
+use crate::direction::Direction;
+use crate::error::{ParseError, Result};
+use crate::node_id::{NodeId, NodeIndex};
+use crate::world::world::World;
+use std::collections::HashMap;
+
+impl World {
+    /// Append `other` to this world and connect the two with bridge tunnels.
+    ///
+    /// Colonies of `other` keep their names and order and are placed after
+    /// this world's colonies, so their ids are shifted by `self.nodes.len()`.
+    /// Each bridge `(a, dir, b)` is a one-way tunnel from colony `a` of this
+    /// world to colony `b` of `other`, replacing any existing tunnel in that
+    /// direction; list the reverse bridge as well for a two-way link. Fails
+    /// if a name exists in both worlds or a bridge names an unknown colony.
+    pub fn merge(mut self, other: &World, bridges: &[(&str, Direction, &str)]) -> Result<World> {
+        let offset = self.nodes.len();
+        let name_offset = self.names.len() as NodeIndex;
+        let index = |world: &World| -> HashMap<String, usize> {
+            world
+                .nodes
+                .iter()
+                .enumerate()
+                .map(|(i, nd)| (world.names[nd.name_idx as usize].clone(), i))
+                .collect()
+        };
+        let ours = index(&self);
+        let theirs = index(other);
+
+        if let Some(name) = other.names.iter().find(|name| ours.contains_key(*name)) {
+            return Err(ParseError::DuplicateColony(name.clone()));
+        }
+        let mut links = Vec::with_capacity(bridges.len());
+        for &(a, direction, b) in bridges {
+            let from = *ours.get(a).ok_or_else(|| ParseError::UnknownColony(a.to_string()))?;
+            let to = *theirs.get(b).ok_or_else(|| ParseError::UnknownColony(b.to_string()))?;
+            links.push((from, direction, to + offset));
+        }
+
+        self.attrs.append(offset, &other.attrs, other.nodes.len());
+        self.names.extend(other.names.iter().cloned());
+        self.nodes.extend(other.nodes.iter().map(|node| {
+            let mut copy = node;
+            copy.name_idx += name_offset;
+            for neighbor in copy.neighbors.iter_mut().flatten() {
+                *neighbor = NodeId::from_index(neighbor.index() + offset);
+            }
+            copy
+        }));
+        for (from, direction, to) in links {
+            self.nodes.set_neighbor(from, direction.index(), NodeId::from_index(to));
+        }
+        self.rebuild_in_edges();
+
+        Ok(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::direction::Direction;
+    use crate::error::ParseError;
+    use crate::node_id::NodeId;
+    use crate::world::parser::parse_world_from_str;
+    use crate::world::World;
+
+    fn world_from(src: &str) -> World {
+        let (names, nodes) = parse_world_from_str(src);
+        World::new(names, nodes)
+    }
+
+    #[test]
+    fn test_merge_remaps_ids_and_adds_bridges() {
+        let west = world_from("A east=B\nB west=A\n");
+        let east = world_from("C east=D\nD west=C\n");
+        let world = west
+            .merge(&east, &[("B", Direction::East, "C"), ("B", Direction::North, "D")])
+            .unwrap();
+
+        assert_eq!(world.names, ["A", "B", "C", "D"]);
+        let c = world.nodes.get(2).unwrap();
+        assert_eq!(c.get_neighbor(Direction::East.index()), Some(NodeId::new(3)));
+        let b = world.nodes.get(1).unwrap();
+        assert_eq!(b.get_neighbor(Direction::East.index()), Some(NodeId::new(2)));
+        assert_eq!(world.get_colony_name(b.get_neighbor(Direction::North.index()).unwrap()), "D");
+        // Bridges are one-way
+        assert!(c.get_neighbor(Direction::West.index()).is_none());
+        assert_eq!(world.in_neighbors(NodeId::new(3)), [NodeId::new(1), NodeId::new(2)]);
+    }
+
+    #[test]
+    fn test_merge_keeps_destroyed_colonies() {
+        let mut east = world_from("C east=D\nD\n");
+        east.nodes.destroy(1);
+        let world = world_from("A\n").merge(&east, &[]).unwrap();
+
+        assert_eq!(world.count_survivors(), 2);
+        assert!(!world.nodes.is_alive(2));
+    }
+
+    #[test]
+    fn test_merge_carries_attributes() {
+        use crate::world::parser::load_world_from_str;
+        let options = Default::default();
+        let west = load_world_from_str("A hp=3\nB\n", &options).unwrap();
+        let east = load_world_from_str("C hp=1 region=rim\n", &options).unwrap();
+        let world = west.merge(&east, &[]).unwrap();
+
+        assert_eq!(world.attr::<u32>("hp"), Some(&[Some(3), None, Some(1)][..]));
+        assert_eq!(world.attr::<String>("region").unwrap()[2].as_deref(), Some("rim"));
+    }
+
+    #[test]
+    fn test_merge_rejects_duplicate_and_unknown_names() {
+        let err = world_from("A\nB\n").merge(&world_from("B\n"), &[]).unwrap_err();
+        assert!(matches!(err, ParseError::DuplicateColony(name) if name == "B"));
+
+        let err = world_from("A\n").merge(&world_from("C\n"), &[("A", Direction::East, "Z")]).unwrap_err();
+        assert!(matches!(err, ParseError::UnknownColony(name) if name == "Z"));
+    }
+}
@@ -1,7 +1,42 @@
+pub mod attrs;
+pub mod builder;
+pub mod compact;
+pub mod consistency;
+pub mod damage;
+pub mod generate;
+pub mod grid;
+pub mod in_edges;
+pub mod merge;
 pub mod node;
+pub mod node_ref;
+pub mod node_table;
 pub mod parser;
+pub mod path;
+pub mod placement;
+pub mod query;
+pub mod scale;
+pub mod sinks;
+pub mod stats;
+pub mod view;
+#[allow(clippy::module_inception)]
 pub mod world;
 
+pub use attrs::{AttrValue, Attributes, Column};
+pub use builder::WorldBuilder;
+pub use compact::IdMap;
+pub use consistency::{check_directions, Contradiction, WrittenTunnel};
+pub use damage::Damage;
+pub use grid::GridLayout;
+pub use in_edges::InEdges;
 pub use node::Node;
-pub use parser::parse_world;
+pub use node_ref::NodeRef;
+pub use node_table::NodeTable;
+pub use parser::{
+    load_world, load_world_from_paths, load_world_from_str, load_world_from_str_with_warnings, load_world_with_sources,
+    load_world_with_warnings, map_files, parse_world, parse_world_with, ParseOptions, TunnelSource,
+};
+pub use placement::Placement;
+pub use query::{ColonyInfo, Reachability, Tunnel};
+pub use stats::WorldStats;
+pub use view::WorldView;
 pub use world::World;
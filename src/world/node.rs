@@ -1,39 +1,35 @@
-use crate::utils::INVALID_NODE;
+use crate::node_id::{NodeId, NodeIndex};
+use serde::{Deserialize, Serialize};
 
-/// Graph node: compact and cache-friendly
-#[derive(Clone, Debug)]
+/// Graph node: one row of a [`NodeTable`](crate::world::NodeTable)
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Node {
-    pub name_idx: u32,   // index into `names`
-    pub neighbors: [u32; 4], // neighbors by direction; INVALID_NODE if none
+    pub name_idx: NodeIndex, // index into `names`
+    pub neighbors: [Option<NodeId>; 4], // neighbors by direction; None if no tunnel
     pub alive: bool,     // colony alive
 }
 
 impl Node {
     /// Create a new node with the given name index
     #[inline]
-    pub fn new(name_idx: u32) -> Self {
+    pub fn new(name_idx: NodeIndex) -> Self {
         Self {
             name_idx,
-            neighbors: [INVALID_NODE; 4],
+            neighbors: [None; 4],
             alive: true,
         }
     }
 
     /// Set neighbor in a specific direction
     #[inline]
-    pub fn set_neighbor(&mut self, direction_idx: usize, neighbor_id: u32) {
-        self.neighbors[direction_idx] = neighbor_id;
+    pub fn set_neighbor(&mut self, direction_idx: usize, neighbor_id: NodeId) {
+        self.neighbors[direction_idx] = Some(neighbor_id);
     }
 
     /// Get neighbor in a specific direction
     #[inline]
-    pub fn get_neighbor(&self, direction_idx: usize) -> Option<u32> {
-        let neighbor = self.neighbors[direction_idx];
-        if neighbor == INVALID_NODE {
-            None
-        } else {
-            Some(neighbor)
-        }
+    pub fn get_neighbor(&self, direction_idx: usize) -> Option<NodeId> {
+        self.neighbors[direction_idx]
     }
 
     /// Destroy this colony
@@ -59,7 +55,7 @@ mod tests {
         
         assert_eq!(node.name_idx, 42);
         assert!(node.is_alive());
-        assert_eq!(node.neighbors, [INVALID_NODE; 4]);
+        assert_eq!(node.neighbors, [None; 4]);
     }
 
     #[test]
@@ -72,12 +68,12 @@ mod tests {
         }
         
         // Set some neighbors
-        node.set_neighbor(0, 10); // North
-        node.set_neighbor(2, 20); // East
+        node.set_neighbor(0, NodeId::new(10)); // North
+        node.set_neighbor(2, NodeId::new(20)); // East
         
-        assert_eq!(node.get_neighbor(0), Some(10));
+        assert_eq!(node.get_neighbor(0), Some(NodeId::new(10)));
         assert_eq!(node.get_neighbor(1), None); // South
-        assert_eq!(node.get_neighbor(2), Some(20));
+        assert_eq!(node.get_neighbor(2), Some(NodeId::new(20)));
         assert_eq!(node.get_neighbor(3), None); // West
     }
 
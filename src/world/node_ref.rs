@@ -0,0 +1,95 @@
+use crate::direction::Direction;
+use crate::node_id::NodeId;
+use crate::world::node::Node;
+use crate::world::World;
+use std::fmt;
+
+/// Read-only view of one colony, borrowed from its [`World`].
+///
+/// Handed out by [`World::colony`], [`World::colonies`] and
+/// [`World::alive_nodes`]; following tunnels yields more views of the same
+/// world, so ids can never be mixed up between worlds.
+#[derive(Clone, Copy)]
+pub struct NodeRef<'a> {
+    world: &'a World,
+    id: NodeId,
+}
+
+impl<'a> NodeRef<'a> {
+    /// `id` must belong to `world`
+    pub(crate) fn new(world: &'a World, id: NodeId) -> Self {
+        debug_assert!(id.index() < world.nodes.len());
+        Self { world, id }
+    }
+
+    #[inline]
+    pub fn id(&self) -> NodeId {
+        self.id
+    }
+
+    /// Colony name as written in the map
+    #[inline]
+    pub fn name(&self) -> &'a str {
+        self.world.get_colony_name(self.id)
+    }
+
+    #[inline]
+    pub fn is_alive(&self) -> bool {
+        self.world.nodes.is_alive(self.id.index())
+    }
+
+    /// The underlying node, read out of the table
+    #[inline]
+    pub fn node(&self) -> Node {
+        self.world.nodes.get(self.id.index()).expect("colony of this world")
+    }
+
+    /// Colony at the end of the tunnel leaving towards `direction`, if any
+    /// (destroyed or not)
+    pub fn neighbor(&self, direction: Direction) -> Option<NodeRef<'a>> {
+        let world = self.world;
+        self.world.nodes.neighbors(self.id.index())[direction.index()].map(|id| NodeRef::new(world, id))
+    }
+
+    /// Tunnels leaving this colony, in [`Direction::ALL`] order
+    pub fn neighbors(&self) -> impl Iterator<Item = (Direction, NodeRef<'a>)> + 'a {
+        let this = *self;
+        Direction::ALL.into_iter().filter_map(move |d| this.neighbor(d).map(|n| (d, n)))
+    }
+
+    /// Colonies with a tunnel into this one, as [`World::in_neighbors`]
+    pub fn in_neighbors(&self) -> impl Iterator<Item = NodeRef<'a>> + 'a {
+        let world = self.world;
+        world.in_neighbors(self.id).iter().map(move |&id| NodeRef::new(world, id))
+    }
+}
+
+impl fmt::Debug for NodeRef<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("NodeRef")
+            .field("id", &self.id)
+            .field("name", &self.name())
+            .field("alive", &self.is_alive())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::world::parser::parse_world_from_str;
+
+    #[test]
+    fn test_follow_tunnels_both_ways() {
+        let (names, nodes) = parse_world_from_str("A east=B\nB west=A south=C\nC\n");
+        let world = World::new(names, nodes);
+        let b = world.colony(NodeId::new(1)).unwrap();
+
+        assert_eq!(b.name(), "B");
+        let out: Vec<(Direction, &str)> = b.neighbors().map(|(d, n)| (d, n.name())).collect();
+        assert_eq!(out, [(Direction::South, "C"), (Direction::West, "A")]);
+        let c = b.neighbor(Direction::South).unwrap();
+        assert_eq!(c.in_neighbors().map(|n| n.id()).collect::<Vec<_>>(), [b.id()]);
+        assert!(b.neighbor(Direction::North).is_none());
+    }
+}
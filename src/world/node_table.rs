@@ -0,0 +1,228 @@
+use crate::node_id::{NodeId, NodeIndex};
+use crate::world::node::Node;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// Colony table in structure-of-arrays layout: one column per [`Node`]
+/// field, indexed by node id.
+///
+/// The movement and occupancy phases only ever look at tunnels and at
+/// whether a colony stands, so those live in their own dense columns: the
+/// tunnels of four colonies share a cache line, and the liveness of 64
+/// colonies fits in one word. Names are only needed for output.
+///
+/// Rows are read back as [`Node`] values, and the table serializes as a
+/// plain list of them, so the layout stays an implementation detail.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct NodeTable {
+    name_idx: Vec<NodeIndex>,
+    neighbors: Vec<[Option<NodeId>; 4]>,
+    /// One bit per colony, set while it stands
+    alive: Vec<u64>,
+    /// Set bits of `alive`, kept up to date by `push` and `destroy`
+    alive_count: usize,
+}
+
+impl NodeTable {
+    /// Empty table with room for `n` colonies
+    pub fn with_capacity(n: usize) -> Self {
+        Self {
+            name_idx: Vec::with_capacity(n),
+            neighbors: Vec::with_capacity(n),
+            alive: Vec::with_capacity(n.div_ceil(64)),
+            alive_count: 0,
+        }
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.name_idx.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.name_idx.is_empty()
+    }
+
+    /// Append `node` as the next colony id
+    pub fn push(&mut self, node: Node) {
+        let i = self.len();
+        if i.is_multiple_of(64) {
+            self.alive.push(0);
+        }
+        self.alive[i / 64] |= (node.alive as u64) << (i % 64);
+        self.alive_count += node.alive as usize;
+        self.name_idx.push(node.name_idx);
+        self.neighbors.push(node.neighbors);
+    }
+
+    /// Row `i`, if it exists
+    #[inline]
+    pub fn get(&self, i: usize) -> Option<Node> {
+        (i < self.len()).then(|| Node { name_idx: self.name_idx[i], neighbors: self.neighbors[i], alive: self.is_alive(i) })
+    }
+
+    /// Every row, in id order
+    pub fn iter(&self) -> impl ExactSizeIterator<Item = Node> + '_ {
+        (0..self.len()).map(|i| Node { name_idx: self.name_idx[i], neighbors: self.neighbors[i], alive: self.is_alive(i) })
+    }
+
+    /// Index into the names of colony `i`
+    #[inline]
+    pub fn name_idx(&self, i: usize) -> NodeIndex {
+        self.name_idx[i]
+    }
+
+    /// Tunnels of colony `i` by direction, as [`Node::neighbors`]
+    #[inline]
+    pub fn neighbors(&self, i: usize) -> [Option<NodeId>; 4] {
+        self.neighbors[i]
+    }
+
+    /// Tunnels of colony `i`, without a bounds check
+    ///
+    /// # Safety
+    /// `i` must be below [`NodeTable::len`]
+    #[inline(always)]
+    pub unsafe fn neighbors_unchecked(&self, i: usize) -> [Option<NodeId>; 4] {
+        *self.neighbors.get_unchecked(i)
+    }
+
+    /// Set the tunnel of colony `i` towards `direction_idx`
+    #[inline]
+    pub fn set_neighbor(&mut self, i: usize, direction_idx: usize, neighbor_id: NodeId) {
+        self.neighbors[i][direction_idx] = Some(neighbor_id);
+    }
+
+    /// Tunnel slots of colony `i`, for clearing them in place
+    #[inline]
+    pub(crate) fn neighbors_mut(&mut self, i: usize) -> &mut [Option<NodeId>; 4] {
+        &mut self.neighbors[i]
+    }
+
+    #[inline]
+    pub fn is_alive(&self, i: usize) -> bool {
+        self.alive[i / 64] >> (i % 64) & 1 != 0
+    }
+
+    /// Whether colony `i` stands, without a bounds check
+    ///
+    /// # Safety
+    /// `i` must be below [`NodeTable::len`]
+    #[inline(always)]
+    pub unsafe fn is_alive_unchecked(&self, i: usize) -> bool {
+        self.alive.get_unchecked(i / 64) >> (i % 64) & 1 != 0
+    }
+
+    /// Mark colony `i` destroyed; returns whether it was standing
+    #[inline]
+    pub fn destroy(&mut self, i: usize) -> bool {
+        let bit = 1u64 << (i % 64);
+        let word = &mut self.alive[i / 64];
+        let was_alive = *word & bit != 0;
+        *word &= !bit;
+        self.alive_count -= was_alive as usize;
+        was_alive
+    }
+
+    /// Mark colony `i` standing again; returns whether it was destroyed
+    #[inline]
+    pub fn revive(&mut self, i: usize) -> bool {
+        let bit = 1u64 << (i % 64);
+        let word = &mut self.alive[i / 64];
+        let was_dead = *word & bit == 0;
+        *word |= bit;
+        self.alive_count += was_dead as usize;
+        was_dead
+    }
+
+    /// Colonies still standing, in O(1)
+    #[inline]
+    pub fn alive_count(&self) -> usize {
+        self.alive_count
+    }
+
+    /// Colonies still standing, recounted with one popcount per 64 of them
+    pub fn recount_alive(&self) -> usize {
+        self.alive.iter().map(|w| w.count_ones() as usize).sum()
+    }
+}
+
+impl FromIterator<Node> for NodeTable {
+    fn from_iter<I: IntoIterator<Item = Node>>(iter: I) -> Self {
+        let iter = iter.into_iter();
+        let mut table = NodeTable::with_capacity(iter.size_hint().0);
+        iter.for_each(|node| table.push(node));
+        table
+    }
+}
+
+impl Extend<Node> for NodeTable {
+    fn extend<I: IntoIterator<Item = Node>>(&mut self, iter: I) {
+        iter.into_iter().for_each(|node| self.push(node));
+    }
+}
+
+impl From<Vec<Node>> for NodeTable {
+    fn from(nodes: Vec<Node>) -> Self {
+        nodes.into_iter().collect()
+    }
+}
+
+impl Serialize for NodeTable {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.iter())
+    }
+}
+
+impl<'de> Deserialize<'de> for NodeTable {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Vec::<Node>::deserialize(deserializer).map(NodeTable::from)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn node(name_idx: NodeIndex, alive: bool) -> Node {
+        Node { alive, ..Node::new(name_idx) }
+    }
+
+    #[test]
+    fn test_rows_round_trip_across_bitset_words() {
+        let nodes: Vec<Node> = (0..130).map(|i| node(i, i % 3 != 0)).collect();
+        let table = NodeTable::from(nodes.clone());
+
+        assert_eq!(table.len(), 130);
+        assert!(table.iter().eq(nodes.iter().copied()));
+        assert_eq!(table.alive_count(), nodes.iter().filter(|n| n.alive).count());
+        assert_eq!(table.recount_alive(), table.alive_count());
+        assert_eq!(table.get(129), Some(nodes[129]));
+        assert_eq!(table.get(130), None);
+    }
+
+    #[test]
+    fn test_destroy_and_tunnels() {
+        let mut table: NodeTable = (0..70).map(|i| node(i, true)).collect();
+        table.set_neighbor(65, 2, NodeId::new(1));
+
+        assert!(table.destroy(65));
+        assert!(!table.destroy(65));
+        assert!(!table.is_alive(65) && table.is_alive(64) && table.is_alive(66));
+        assert_eq!((table.alive_count(), table.recount_alive()), (69, 69));
+        assert_eq!(table.neighbors(65), [None, None, Some(NodeId::new(1)), None]);
+
+        assert!(table.revive(65));
+        assert!(!table.revive(65));
+        assert_eq!((table.alive_count(), table.recount_alive()), (70, 70));
+    }
+
+    #[test]
+    fn test_serializes_as_a_node_list() {
+        let nodes = vec![node(0, true), node(1, false)];
+        let json = serde_json::to_string(&NodeTable::from(nodes.clone())).unwrap();
+
+        assert_eq!(json, serde_json::to_string(&nodes).unwrap());
+        assert_eq!(serde_json::from_str::<NodeTable>(&json).unwrap(), NodeTable::from(nodes));
+    }
+}
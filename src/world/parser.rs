@@ -1,114 +1,549 @@
 use crate::direction::Direction;
-use crate::error::{ParseError, Result};
+use crate::error::{ParseError, Result, SourceLocation};
+use crate::node_id::{NodeId, NodeIndex};
+use crate::world::attrs::Attributes;
 use crate::world::node::Node;
+use crate::world::world::World;
 use std::collections::HashMap;
 use std::fs::File;
 use std::io::{BufRead, BufReader};
+use std::path::Path;
+
+/// Options controlling how colony names are resolved
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ParseOptions {
+    /// Treat names differing only in ASCII case as the same colony
+    pub case_insensitive: bool,
+    /// Drop `spawn` tags, so ants land on any colony
+    pub ignore_spawn_tags: bool,
+    /// Give every tunnel a way back: `A east=B` also adds `B west=A` unless
+    /// `B` already has a west tunnel
+    pub auto_mirror: bool,
+    /// Read the map as a previous run's final world: a colony named in a
+    /// tunnel but without a line of its own was destroyed, so it is kept
+    /// as a ruin instead of becoming a fresh colony
+    pub damaged: bool,
+    /// Skip what would be an error — a malformed token, a line that is not
+    /// UTF-8, an alias claimed twice — and read on; the loaders ending in
+    /// `_with_warnings` hand back what was skipped
+    pub lenient: bool,
+    /// Reject colony names (on their own line, in tunnels or as aliases)
+    /// that are empty or hold `=` or whitespace, as
+    /// [`ParseError::InvalidColonyName`]
+    pub validate_names: bool,
+    /// Reject colony names running past this many characters, the same
+    /// way; no limit when `None`
+    pub max_name_len: Option<usize>,
+}
+
+/// Attribute key of the `spawn` tag that marks landing zones
+pub const SPAWN_TAG: &str = "spawn";
+
+/// Attribute key of the `fortified` tag: the colony kills ants arriving alone
+pub const FORTIFIED_TAG: &str = "fortified";
+
+/// Attribute key of the `food` tag: the colony refills the energy of ants arriving there
+pub const FOOD_TAG: &str = "food";
+
+/// A tunnel as written in a map file, for checks that point back at the source
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TunnelSource {
+    pub from: NodeId,
+    pub direction: Direction,
+    pub to: NodeId,
+    /// Where the `direction=to` token is
+    pub at: SourceLocation,
+}
+
+/// One colony line before name resolution
+pub(crate) struct RawColony {
+    pub(crate) name: String,
+    pub(crate) edges: Vec<(Direction, String, SourceLocation)>,
+    pub(crate) aliases: Vec<String>,
+    /// `key=value` tokens whose key is not a direction
+    pub(crate) attrs: Vec<(String, String)>,
+}
 
 /// Parse a world from a file path
 pub fn parse_world(path: &str) -> Result<(Vec<String>, Vec<Node>)> {
-    let file = File::open(path)?;
-    let reader = BufReader::with_capacity(64 * 1024, file);
+    parse_world_with(path, &ParseOptions::default())
+}
 
-    let mut names: Vec<String> = Vec::with_capacity(1024);
-    let mut name_to_id: HashMap<String, u32> = HashMap::with_capacity(1024);
-    let mut edges: Vec<(u32, Direction, String)> = Vec::with_capacity(4096);
+/// Parse a world from a file path with explicit options
+pub fn parse_world_with(path: &str, options: &ParseOptions) -> Result<(Vec<String>, Vec<Node>)> {
+    let (names, nodes, _) = parse_map_file(path, options, None)?;
+    Ok((names, nodes))
+}
 
-    for line in reader.lines() {
-        let line = line?;
-        let line = line.trim();
-        if line.is_empty() {
-            continue;
-        }
+/// Load a world, with its per-colony attributes, from a file path or a
+/// directory of map fragments (see [`load_world_from_paths`])
+pub fn load_world(path: &str, options: &ParseOptions) -> Result<World> {
+    load_world_from_paths(&[path], options)
+}
 
-        let mut parts = line.split_whitespace();
-        let colony = parts
-            .next()
-            .ok_or_else(|| ParseError::InvalidLine("missing colony name".to_string()))?;
+/// Load one world from map fragments spread over several files, as if they
+/// were concatenated in order. A directory stands for its map files, sorted
+/// by name (hidden files and subdirectories are skipped).
+///
+/// Names resolve across fragments, so a tunnel may lead into a colony
+/// declared in another file. Unless `paths` is a single file, errors
+/// within a fragment come back as [`ParseError::InFile`] naming it.
+pub fn load_world_from_paths<S: AsRef<str>>(paths: &[S], options: &ParseOptions) -> Result<World> {
+    load_world_with_warnings(paths, options).map(|(world, _)| world)
+}
 
-        let src_id = *name_to_id.entry(colony.to_string()).or_insert_with(|| {
-            let id = names.len() as u32;
-            names.push(colony.to_string());
-            id
-        });
+/// [`load_world_from_paths`], also returning what a
+/// [`lenient`](ParseOptions::lenient) parse skipped, in map order (always
+/// empty otherwise). Each warning is the error a strict parse would have
+/// stopped at, with its location and fragment.
+pub fn load_world_with_warnings<S: AsRef<str>>(
+    paths: &[S],
+    options: &ParseOptions,
+) -> Result<(World, Vec<ParseError>)> {
+    let files = map_files(paths)?;
+    // A lone map file keeps its errors as they are
+    let bare = paths.len() == 1 && files == [paths[0].as_ref()];
+    let mut colonies = Vec::with_capacity(1024);
+    let mut warnings = Vec::new();
+    for file in &files {
+        let seen = warnings.len();
+        let skipped = options.lenient.then_some(&mut warnings);
+        read_fragment(file, options, &mut colonies, skipped).map_err(|err| if bare { err } else { err.in_file(file) })?;
+        if !bare {
+            let tagged: Vec<ParseError> = warnings.drain(seen..).map(|w| w.in_file(file)).collect();
+            warnings.extend(tagged);
+        }
+    }
+    let (names, nodes, attrs) = resolve(colonies, options, None, options.lenient.then_some(&mut warnings))?;
+    Ok((World::new(names, nodes).with_attributes(attrs), warnings))
+}
 
-        for kv in parts {
-            if let Some(eq) = kv.find('=') {
-                let dir_s = &kv[..eq];
-                let dst_s = &kv[eq + 1..];
-                let dir: Direction = dir_s.parse()?;
-                edges.push((src_id, dir, dst_s.to_string()));
+/// The map files behind `paths`: files as given, directories expanded to
+/// the visible files they hold, in name order
+pub fn map_files<S: AsRef<str>>(paths: &[S]) -> Result<Vec<String>> {
+    let mut files = Vec::with_capacity(paths.len());
+    for path in paths.iter().map(AsRef::as_ref) {
+        if !Path::new(path).is_dir() {
+            files.push(path.to_string());
+            continue;
+        }
+        let mut fragments = Vec::new();
+        for entry in std::fs::read_dir(path)? {
+            let entry = entry?;
+            let hidden = entry.file_name().to_string_lossy().starts_with('.');
+            if !hidden && !entry.file_type()?.is_dir() {
+                fragments.push(entry.path().to_string_lossy().into_owned());
             }
         }
+        if fragments.is_empty() {
+            return Err(ParseError::NoMapFiles(path.to_string()));
+        }
+        fragments.sort();
+        files.extend(fragments);
     }
+    Ok(files)
+}
+
+/// Load a world from a file path along with every explicit tunnel and its
+/// location, in file order (tunnels added by `auto_mirror` are not listed)
+pub fn load_world_with_sources(path: &str, options: &ParseOptions) -> Result<(World, Vec<TunnelSource>)> {
+    let mut tunnels = Vec::new();
+    let (names, nodes, attrs) = parse_map_file(path, options, Some(&mut tunnels))?;
+    Ok((World::new(names, nodes).with_attributes(attrs), tunnels))
+}
+
+/// Load a world, with its per-colony attributes, from an in-memory string
+pub fn load_world_from_str(src: &str, options: &ParseOptions) -> Result<World> {
+    load_world_from_str_with_warnings(src, options).map(|(world, _)| world)
+}
+
+/// [`load_world_with_warnings`] for an in-memory string
+pub fn load_world_from_str_with_warnings(src: &str, options: &ParseOptions) -> Result<(World, Vec<ParseError>)> {
+    let mut warnings = Vec::new();
+    let (names, nodes, attrs) = parse_map_str(src, options, None, options.lenient.then_some(&mut warnings))?;
+    Ok((World::new(names, nodes).with_attributes(attrs), warnings))
+}
+
+/// [`load_world_with_sources`] for an in-memory string
+pub fn load_world_with_sources_from_str(src: &str, options: &ParseOptions) -> Result<(World, Vec<TunnelSource>)> {
+    let mut tunnels = Vec::new();
+    let mut skipped = Vec::new();
+    let (names, nodes, attrs) = parse_map_str(src, options, Some(&mut tunnels), options.lenient.then_some(&mut skipped))?;
+    Ok((World::new(names, nodes).with_attributes(attrs), tunnels))
+}
+
+fn parse_map_file(
+    path: &str,
+    options: &ParseOptions,
+    sources: Option<&mut Vec<TunnelSource>>,
+) -> Result<(Vec<String>, Vec<Node>, Attributes)> {
+    let mut colonies = Vec::with_capacity(1024);
+    // Callers that want the warnings go through `load_world_with_warnings`
+    let mut skipped = Vec::new();
+    read_fragment(path, options, &mut colonies, options.lenient.then_some(&mut skipped))?;
+    resolve(colonies, options, sources, options.lenient.then_some(&mut skipped))
+}
 
-    // Ensure ids exist for destinations not seen as sources
-    for (_, _, dst) in &edges {
-        name_to_id.entry(dst.clone()).or_insert_with(|| {
-            let id = names.len() as u32;
-            names.push(dst.clone());
-            id
-        });
+/// Keep `err` in `skipped` and carry on when parsing leniently, else fail with it
+fn tolerate(skipped: &mut Option<&mut Vec<ParseError>>, err: ParseError) -> Result<()> {
+    match skipped {
+        Some(list) => {
+            list.push(err);
+            Ok(())
+        }
+        None => Err(err),
     }
+}
 
-    let mut nodes: Vec<Node> = (0..names.len()).map(|i| Node::new(i as u32)).collect();
+/// Append the colony lines of the map file at `path` to `colonies`; with
+/// `skipped`, lines that are not UTF-8 are left out and noted there
+fn read_fragment(
+    path: &str,
+    options: &ParseOptions,
+    colonies: &mut Vec<RawColony>,
+    mut skipped: Option<&mut Vec<ParseError>>,
+) -> Result<()> {
+    let reader = open_map(path)?;
+    let mut joiner = LineJoiner::default();
+    for (i, line) in reader.lines().enumerate() {
+        let line = match line {
+            Ok(line) => line,
+            Err(err) if err.kind() == std::io::ErrorKind::InvalidData => {
+                let at = SourceLocation { line: i + 1, column: 1 };
+                tolerate(&mut skipped, ParseError::invalid_line("not valid UTF-8").at(at))?;
+                continue;
+            }
+            Err(err) => return Err(err.into()),
+        };
+        if let Some(logical) = joiner.push(i + 1, &line) {
+            colonies.extend(parse_line(&logical, options, skipped.as_deref_mut())?);
+        }
+    }
+    if let Some(logical) = joiner.finish() {
+        colonies.extend(parse_line(&logical, options, skipped)?);
+    }
+    Ok(())
+}
 
-    for (src, dir, dst_name) in &edges {
-        if let Some(&dst) = name_to_id.get(dst_name) {
-            nodes[*src as usize].set_neighbor(dir.index(), dst);
+/// Open a map file, decompressing `.gz` / `.zst` by extension
+fn open_map(path: &str) -> Result<Box<dyn BufRead>> {
+    let file = File::open(path)?;
+    if path.ends_with(".gz") {
+        #[cfg(feature = "gzip")]
+        {
+            let decoder = flate2::read::MultiGzDecoder::new(BufReader::new(file));
+            return Ok(Box::new(BufReader::with_capacity(64 * 1024, decoder)));
+        }
+        #[cfg(not(feature = "gzip"))]
+        return Err(ParseError::UnsupportedFormat("gzip (build with --features gzip)".to_string()));
+    }
+    if path.ends_with(".zst") {
+        #[cfg(feature = "zstd")]
+        {
+            let decoder = zstd::stream::read::Decoder::new(file)?;
+            return Ok(Box::new(BufReader::with_capacity(64 * 1024, decoder)));
         }
+        #[cfg(not(feature = "zstd"))]
+        return Err(ParseError::UnsupportedFormat("zstd (build with --features zstd)".to_string()));
     }
+    Ok(Box::new(BufReader::with_capacity(64 * 1024, file)))
+}
 
-    Ok((names, nodes))
+/// Text of 1-based line `line` of a map file, for error rendering
+pub fn source_line(path: &str, line: usize) -> Result<Option<String>> {
+    let reader = open_map(path)?;
+    Ok(reader.lines().nth(line.saturating_sub(1)).transpose()?)
 }
 
 /// Parse a world directly from an in-memory string for testing
 pub fn parse_world_from_str(src: &str) -> (Vec<String>, Vec<Node>) {
-    let mut names: Vec<String> = Vec::new();
-    let mut name_to_id: HashMap<String, u32> = HashMap::new();
-    let mut edges: Vec<(u32, Direction, String)> = Vec::new();
+    parse_world_from_str_with(src, &ParseOptions::default()).expect("invalid map")
+}
 
-    for raw in src.lines() {
-        let line = raw.trim();
-        if line.is_empty() {
-            continue;
+/// Parse a world from an in-memory string with explicit options
+pub fn parse_world_from_str_with(src: &str, options: &ParseOptions) -> Result<(Vec<String>, Vec<Node>)> {
+    let mut skipped = Vec::new();
+    let (names, nodes, _) = parse_map_str(src, options, None, options.lenient.then_some(&mut skipped))?;
+    Ok((names, nodes))
+}
+
+fn parse_map_str(
+    src: &str,
+    options: &ParseOptions,
+    sources: Option<&mut Vec<TunnelSource>>,
+    mut skipped: Option<&mut Vec<ParseError>>,
+) -> Result<(Vec<String>, Vec<Node>, Attributes)> {
+    let mut colonies = Vec::new();
+    let mut joiner = LineJoiner::default();
+    for (i, line) in src.lines().enumerate() {
+        if let Some(logical) = joiner.push(i + 1, line) {
+            colonies.extend(parse_line(&logical, options, skipped.as_deref_mut())?);
+        }
+    }
+    if let Some(logical) = joiner.finish() {
+        colonies.extend(parse_line(&logical, options, skipped.as_deref_mut())?);
+    }
+    resolve(colonies, options, sources, skipped)
+}
+
+/// Drop a `#` comment: everything from the first token starting with `#`
+fn strip_comment(line: &str) -> &str {
+    let mut prev_space = true;
+    for (i, c) in line.char_indices() {
+        if c == '#' && prev_space {
+            return &line[..i];
         }
-        let mut parts = line.split_whitespace();
-        let colony = parts.next().expect("missing colony name");
-
-        let src_id = *name_to_id.entry(colony.to_string()).or_insert_with(|| {
-            let id = names.len() as u32;
-            names.push(colony.to_string());
-            id
-        });
-
-        for kv in parts {
-            if let Some(eq) = kv.find('=') {
-                let dir_s = &kv[..eq];
-                let dst_s = &kv[eq + 1..];
-                let dir = dir_s.parse().expect("invalid direction");
-                edges.push((src_id, dir, dst_s.to_string()));
+        prev_space = c.is_whitespace();
+    }
+    line
+}
+
+/// One logical colony line: the pieces of physical lines joined by a
+/// trailing `\`, each tagged with its 1-based line number
+type LogicalLine = Vec<(usize, String)>;
+
+/// Joins physical lines ending in `\` into logical lines
+#[derive(Default)]
+struct LineJoiner {
+    pending: LogicalLine,
+}
+
+impl LineJoiner {
+    /// Feed one physical line; returns a logical line once it is complete
+    fn push(&mut self, line_no: usize, line: &str) -> Option<LogicalLine> {
+        let text = strip_comment(line).trim_end();
+        match text.strip_suffix('\\') {
+            Some(head) => {
+                self.pending.push((line_no, head.to_string()));
+                None
+            }
+            None => {
+                self.pending.push((line_no, text.to_string()));
+                Some(std::mem::take(&mut self.pending))
             }
         }
     }
 
-    for (_, _, dst) in &edges {
-        name_to_id.entry(dst.clone()).or_insert_with(|| {
-            let id = names.len() as u32;
-            names.push(dst.clone());
-            id
-        });
+    /// Flush a dangling continuation at end of input
+    fn finish(&mut self) -> Option<LogicalLine> {
+        (!self.pending.is_empty()).then(|| std::mem::take(&mut self.pending))
+    }
+}
+
+/// Why `name` breaks the `validate_names` / `max_name_len` rules of
+/// `options`, if it does
+fn name_problem(name: &str, options: &ParseOptions) -> Option<String> {
+    if options.validate_names {
+        if name.is_empty() {
+            return Some("empty".to_string());
+        } else if name.contains('=') {
+            return Some("contains '='".to_string());
+        } else if name.contains(char::is_whitespace) {
+            return Some("contains whitespace".to_string());
+        }
     }
+    let max = options.max_name_len?;
+    (name.chars().count() > max).then(|| format!("longer than {max} characters"))
+}
+
+/// Whether `name`, found at `at`, may be used; a bad one is an error, or
+/// is noted in `skipped` when parsing leniently
+fn check_name(
+    name: &str,
+    at: SourceLocation,
+    options: &ParseOptions,
+    skipped: &mut Option<&mut Vec<ParseError>>,
+) -> Result<bool> {
+    let Some(reason) = name_problem(name, options) else {
+        return Ok(true);
+    };
+    tolerate(skipped, ParseError::InvalidColonyName { name: name.to_string(), reason, location: Some(at) })?;
+    Ok(false)
+}
 
-    let mut nodes: Vec<Node> = (0..names.len()).map(|i| Node::new(i as u32)).collect();
-    for (src, dir, dst_name) in &edges {
-        let dst = *name_to_id.get(dst_name).unwrap();
-        nodes[*src as usize].set_neighbor(dir.index(), dst);
+/// Split one logical map line into its colony name, tunnels and aliases;
+/// with `skipped`, malformed tokens (and lines with a malformed name) are
+/// left out and noted there
+fn parse_line(
+    line: &LogicalLine,
+    options: &ParseOptions,
+    mut skipped: Option<&mut Vec<ParseError>>,
+) -> Result<Option<RawColony>> {
+    let mut tokens = line.iter().flat_map(|(line_no, text)| {
+        text.split_whitespace().map(move |t| {
+            // `t` borrows from `text`, so its offset is the token's byte column
+            let offset = t.as_ptr() as usize - text.as_ptr() as usize;
+            let column = text[..offset].chars().count() + 1;
+            (SourceLocation { line: *line_no, column }, t)
+        })
+    });
+    let Some((at, name)) = tokens.next() else {
+        return Ok(None);
+    };
+    if !check_name(name, at, options, &mut skipped)? {
+        return Ok(None);
     }
+    let mut colony = RawColony {
+        name: name.to_string(),
+        edges: Vec::new(),
+        aliases: Vec::new(),
+        attrs: Vec::new(),
+    };
 
-    (names, nodes)
+    for (at, kv) in tokens {
+        if kv == SPAWN_TAG || kv == FORTIFIED_TAG || kv == FOOD_TAG {
+            // A bare `spawn` / `fortified` / `food` is short for `spawn=1` etc.
+            colony.attrs.push((kv.to_string(), "1".to_string()));
+        } else if let Some(eq) = kv.find('=') {
+            let key = &kv[..eq];
+            let value = &kv[eq + 1..];
+            if key.is_empty() {
+                tolerate(&mut skipped, ParseError::invalid_line(format!("missing key in '{kv}'")).at(at))?;
+                continue;
+            }
+            let value_at = SourceLocation { column: at.column + key.chars().count() + 1, ..at };
+            if key == "alias" {
+                if check_name(value, value_at, options, &mut skipped)? {
+                    colony.aliases.push(value.to_string());
+                }
+            } else if let Ok(dir) = key.parse::<Direction>() {
+                if check_name(value, value_at, options, &mut skipped)? {
+                    colony.edges.push((dir, value.to_string(), at));
+                }
+            } else {
+                colony.attrs.push((key.to_string(), value.to_string()));
+            }
+        }
+    }
+    Ok(Some(colony))
 }
 
+/// Assign ids and build nodes, merging aliases (and case variants if enabled).
+///
+/// Ids follow first-seen order: colony lines first, then tunnel destinations
+/// that never appear as a line. A colony keeps the spelling it was first seen
+/// with. Repeated lines for one colony merge, later tunnels and attributes
+/// winning. When `sources` is given, every explicit tunnel is appended to it.
+/// With `skipped`, conflicting aliases are dropped and noted there.
+pub(crate) fn resolve(
+    colonies: Vec<RawColony>,
+    options: &ParseOptions,
+    mut sources: Option<&mut Vec<TunnelSource>>,
+    mut skipped: Option<&mut Vec<ParseError>>,
+) -> Result<(Vec<String>, Vec<Node>, Attributes)> {
+    let key = |name: &str| -> String {
+        if options.case_insensitive {
+            name.to_ascii_lowercase()
+        } else {
+            name.to_string()
+        }
+    };
+
+    // alias key -> (owner key, owner spelling)
+    let mut aliases: HashMap<String, (String, String)> = HashMap::new();
+    for colony in &colonies {
+        let owner = key(&colony.name);
+        for alias in &colony.aliases {
+            let alias_key = key(alias);
+            if alias_key == owner {
+                continue;
+            }
+            if let Some((other, other_name)) = aliases.get(&alias_key) {
+                if *other != owner {
+                    let err = ParseError::ConflictingAlias {
+                        alias: alias.clone(),
+                        first: other_name.clone(),
+                        second: colony.name.clone(),
+                    };
+                    // The first claim stands
+                    tolerate(&mut skipped, err)?;
+                    continue;
+                }
+            }
+            aliases.insert(alias_key, (owner.clone(), colony.name.clone()));
+        }
+    }
+    // An owner that is itself an alias of someone else would make a chain
+    for colony in colonies.iter().filter(|c| !c.aliases.is_empty()) {
+        if let Some((_, outer_name)) = aliases.get(&key(&colony.name)) {
+            let err = ParseError::ConflictingAlias {
+                alias: colony.name.clone(),
+                first: outer_name.clone(),
+                second: colony.name.clone(),
+            };
+            // The colony stays an alias and its own aliases go
+            tolerate(&mut skipped, err)?;
+            let owner = key(&colony.name);
+            aliases.retain(|_, (o, _)| *o != owner);
+        }
+    }
+    let canonical = |name: &str| -> String {
+        let k = key(name);
+        match aliases.get(&k) {
+            Some((owner, _)) => owner.clone(),
+            None => k,
+        }
+    };
+
+    let mut names: Vec<String> = Vec::with_capacity(colonies.len());
+    let mut name_to_id: HashMap<String, NodeIndex> = HashMap::with_capacity(colonies.len());
+    let mut intern = |names: &mut Vec<String>, name: &str| -> NodeIndex {
+        *name_to_id.entry(canonical(name)).or_insert_with(|| {
+            names.push(name.to_string());
+            (names.len() - 1) as NodeIndex
+        })
+    };
+
+    let mut edges: Vec<(NodeIndex, Direction, &str, SourceLocation)> = Vec::with_capacity(colonies.len() * 2);
+    let mut raw_attrs: Vec<(NodeIndex, &str, &str)> = Vec::new();
+    for colony in &colonies {
+        let src = intern(&mut names, &colony.name);
+        for (dir, dst, at) in &colony.edges {
+            edges.push((src, *dir, dst, *at));
+        }
+        for (key, value) in &colony.attrs {
+            if options.ignore_spawn_tags && key == SPAWN_TAG {
+                continue;
+            }
+            raw_attrs.push((src, key, value));
+        }
+    }
+    // Colonies with a line of their own come first; the rest are only named in tunnels
+    let declared = names.len();
+    let mut resolved: Vec<(NodeIndex, Direction, NodeIndex)> = Vec::with_capacity(edges.len());
+    for (src, dir, dst, at) in edges {
+        let dst = intern(&mut names, dst);
+        resolved.push((src, dir, dst));
+        if let Some(sources) = sources.as_deref_mut() {
+            sources.push(TunnelSource { from: NodeId::new(src), direction: dir, to: NodeId::new(dst), at });
+        }
+    }
+    if !NodeId::can_address(names.len()) {
+        return Err(ParseError::TooManyColonies(names.len()));
+    }
+
+    let mut nodes: Vec<Node> = (0..names.len()).map(|i| Node::new(i as NodeIndex)).collect();
+    for &(src, dir, dst) in &resolved {
+        nodes[src as usize].set_neighbor(dir.index(), NodeId::new(dst));
+    }
+    if options.auto_mirror {
+        // After every explicit tunnel is in, so those always win
+        for &(src, dir, dst) in &resolved {
+            let back = dir.opposite().index();
+            if nodes[dst as usize].get_neighbor(back).is_none() {
+                nodes[dst as usize].set_neighbor(back, NodeId::new(src));
+            }
+        }
+    }
+    if options.damaged {
+        for node in &mut nodes[declared..] {
+            node.destroy();
+        }
+    }
+    let attrs = Attributes::from_raw(names.len(), &raw_attrs);
+
+    Ok((names, nodes, attrs))
+}
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -145,8 +580,207 @@ mod tests {
         let c_idx = names.iter().position(|n| n == "C").unwrap();
         let d_idx = names.iter().position(|n| n == "D").unwrap();
         
-        assert_eq!(nodes[a_idx].get_neighbor(Direction::North.index()), Some(b_idx as u32));
-        assert_eq!(nodes[a_idx].get_neighbor(Direction::East.index()), Some(c_idx as u32));
-        assert_eq!(nodes[a_idx].get_neighbor(Direction::West.index()), Some(d_idx as u32));
+        assert_eq!(nodes[a_idx].get_neighbor(Direction::North.index()), Some(NodeId::from_index(b_idx)));
+        assert_eq!(nodes[a_idx].get_neighbor(Direction::East.index()), Some(NodeId::from_index(c_idx)));
+        assert_eq!(nodes[a_idx].get_neighbor(Direction::West.index()), Some(NodeId::from_index(d_idx)));
+    }
+
+    #[test]
+    fn test_comments_are_ignored() {
+        let src = "# header comment\nA north=B # trailing\n   # indented\nB south=A\n";
+        let (names, nodes) = parse_world_from_str(src);
+
+        assert_eq!(names, ["A", "B"]);
+        assert_eq!(nodes[0].get_neighbor(Direction::North.index()), Some(NodeId::new(1)));
+    }
+
+    #[test]
+    fn test_hash_inside_name_is_not_a_comment() {
+        let (names, _) = parse_world_from_str("A#1 north=B#2\n");
+        assert_eq!(names, ["A#1", "B#2"]);
+    }
+
+    #[test]
+    fn test_line_continuation_joins_lines() {
+        let src = "A north=B \\\n  east=C \\\n  west=D\nB\n";
+        let (names, nodes) = parse_world_from_str(src);
+
+        assert_eq!(names, ["A", "B", "C", "D"]);
+        assert_eq!(nodes[0].get_neighbor(Direction::West.index()), Some(NodeId::new(3)));
+    }
+
+    #[test]
+    fn test_continuation_with_comment_and_dangling_end() {
+        let src = "A north=B \\ # more below\n  south=C \\";
+        let (_, nodes) = parse_world_from_str(src);
+        assert_eq!(nodes[0].get_neighbor(Direction::South.index()), Some(NodeId::new(2)));
+    }
+
+    #[test]
+    fn test_error_reports_physical_line_of_continuation() {
+        let src = "# comment\nA north=B \\\n  =C\n";
+        let err = parse_world_from_str_with(src, &ParseOptions::default()).unwrap_err();
+
+        assert_eq!(err.location(), Some(SourceLocation { line: 3, column: 3 }));
+        assert_eq!(err.to_string(), "line 3, column 3: Invalid line: missing key in '=C'");
+    }
+
+    #[test]
+    fn test_non_direction_keys_become_attributes() {
+        let src = "A north=B hp=3 region=rim\nB hp=7 capacity=2.5\nA hp=4\n";
+        let world = load_world_from_str(src, &ParseOptions::default()).unwrap();
+
+        // `A hp=4` overrides the earlier value; B is not a tunnel target named `3`
+        assert_eq!(world.names, ["A", "B"]);
+        assert_eq!(world.attr::<u32>("hp"), Some(&[Some(4), Some(7)][..]));
+        assert_eq!(world.attr::<f32>("capacity"), Some(&[None, Some(2.5)][..]));
+        assert_eq!(world.attr::<String>("region").unwrap()[0].as_deref(), Some("rim"));
+        assert!(world.attr::<u32>("missing").is_none());
+    }
+
+    #[test]
+    fn test_spawn_tag_is_an_attribute() {
+        let src = "A spawn north=B\nB spawn=0\nC\n";
+        let world = load_world_from_str(src, &ParseOptions::default()).unwrap();
+        assert_eq!(world.attr::<u32>(SPAWN_TAG), Some(&[Some(1), Some(0), None][..]));
+
+        let options = ParseOptions { ignore_spawn_tags: true, ..Default::default() };
+        assert!(load_world_from_str(src, &options).unwrap().attr::<u32>(SPAWN_TAG).is_none());
+    }
+
+    #[test]
+    fn test_fortified_tag_is_an_attribute() {
+        let world = load_world_from_str("A fortified spawn\nB fortified=0\nC food\n", &ParseOptions::default()).unwrap();
+        assert_eq!(world.attr::<u32>(FORTIFIED_TAG), Some(&[Some(1), Some(0), None][..]));
+        assert_eq!(world.attr::<u32>(SPAWN_TAG), Some(&[Some(1), None, None][..]));
+        assert_eq!(world.attr::<u32>(FOOD_TAG), Some(&[None, None, Some(1)][..]));
+    }
+
+    #[test]
+    fn test_auto_mirror_adds_missing_way_back() {
+        let options = ParseOptions { auto_mirror: true, ..Default::default() };
+        let (names, nodes) = parse_world_from_str_with("A east=B north=C\nB\nC south=B\n", &options).unwrap();
+        let id = |name: &str| NodeId::from_index(names.iter().position(|n| n == name).unwrap());
+        let back = |of: &str, d: Direction| nodes[id(of).index()].get_neighbor(d.index());
+        assert_eq!(back("B", Direction::West), Some(id("A")));
+        // C already goes south to B, so A's north tunnel gets no way back
+        assert_eq!(back("C", Direction::South), Some(id("B")));
+        assert_eq!(back("B", Direction::North), Some(id("C")));
+
+        let (_, plain) = parse_world_from_str_with("A east=B\nB\n", &ParseOptions::default()).unwrap();
+        assert!(plain[1].get_neighbor(Direction::West.index()).is_none());
+    }
+
+    #[test]
+    fn test_damaged_map_keeps_undeclared_colonies_as_ruins() {
+        let src = "A east=B\nC west=A\n";
+        let world = load_world_from_str(src, &ParseOptions::default()).unwrap();
+        assert_eq!(world.count_survivors(), 3);
+
+        let options = ParseOptions { damaged: true, ..Default::default() };
+        let world = load_world_from_str(src, &options).unwrap();
+        assert_eq!(world.names(), ["A", "C", "B"]);
+        assert_eq!(world.count_survivors(), 2);
+        assert!(!world.is_alive(NodeId::new(2)));
+    }
+
+    #[test]
+    fn test_case_sensitive_by_default() {
+        let (names, _) = parse_world_from_str("Fizz north=buzz\nfizz south=Buzz\n");
+        assert_eq!(names.len(), 4);
+    }
+
+    #[test]
+    fn test_case_insensitive_merges_spellings() {
+        let options = ParseOptions { case_insensitive: true, ..Default::default() };
+        let (names, nodes) = parse_world_from_str_with("Fizz north=buzz\nfizz south=Buzz\n", &options).unwrap();
+
+        // First spelling wins; both lines merge into one colony
+        assert_eq!(names, ["Fizz", "buzz"]);
+        assert_eq!(nodes[0].get_neighbor(Direction::North.index()), Some(NodeId::new(1)));
+        assert_eq!(nodes[0].get_neighbor(Direction::South.index()), Some(NodeId::new(1)));
+    }
+
+    #[test]
+    fn test_alias_resolves_references() {
+        let src = "Fizz alias=Fzz north=Buzz\nBuzz south=Fzz\nFzz west=Bla\n";
+        let (names, nodes) = parse_world_from_str(src);
+
+        assert_eq!(names, ["Fizz", "Buzz", "Bla"]);
+        assert_eq!(nodes[1].get_neighbor(Direction::South.index()), Some(NodeId::new(0)));
+        assert_eq!(nodes[0].get_neighbor(Direction::West.index()), Some(NodeId::new(2)));
+    }
+
+    #[test]
+    fn test_alias_claimed_twice_is_a_conflict() {
+        let src = "A alias=X\nB alias=X\n";
+        let err = parse_world_from_str_with(src, &ParseOptions::default()).unwrap_err();
+        assert!(matches!(
+            err,
+            ParseError::ConflictingAlias { ref alias, ref first, ref second }
+                if alias == "X" && first == "A" && second == "B"
+        ));
+    }
+
+    #[test]
+    fn test_lenient_skips_what_strict_stops_at() {
+        let src = "A east=B =C\nB alias=X\nC alias=X west=B\nD east=X\n";
+        assert!(load_world_from_str(src, &ParseOptions::default()).is_err());
+
+        let options = ParseOptions { lenient: true, ..Default::default() };
+        let (world, warnings) = load_world_from_str_with_warnings(src, &options).unwrap();
+        // The bad token goes, the rest of its line stays; the first alias claim stands
+        assert_eq!(world.names(), ["A", "B", "C", "D"]);
+        assert_eq!(world.nodes().neighbors(3)[Direction::East.index()], world.find("B"));
+        assert_eq!(warnings.len(), 2);
+        assert_eq!(warnings[0].location(), Some(SourceLocation { line: 1, column: 10 }));
+        assert!(matches!(warnings[1], ParseError::ConflictingAlias { ref second, .. } if second == "C"));
+    }
+
+    #[test]
+    fn test_lenient_skips_lines_that_are_not_utf8() {
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        std::io::Write::write_all(&mut file, b"A east=B\n\xff\xfe\nB west=A\n").unwrap();
+        let path = file.path().to_str().unwrap();
+        let options = ParseOptions { lenient: true, ..Default::default() };
+
+        let (world, warnings) = load_world_with_warnings(&[path], &options).unwrap();
+        assert_eq!(world.names(), ["A", "B"]);
+        assert_eq!(warnings[0].location().map(|at| at.line), Some(2));
+        assert!(load_world(path, &ParseOptions::default()).is_err());
+    }
+
+    #[test]
+    fn test_name_rules_are_opt_in() {
+        let src = "A east=B=C\nLongName west=A\n";
+        assert!(load_world_from_str(src, &ParseOptions::default()).is_ok());
+
+        let options = ParseOptions { validate_names: true, ..Default::default() };
+        let err = load_world_from_str(src, &options).unwrap_err();
+        assert_eq!(err.to_string(), "line 1, column 8: Invalid colony name 'B=C': contains '='");
+        // A length limit alone leaves '=' be
+        let options = ParseOptions { max_name_len: Some(8), ..Default::default() };
+        assert!(load_world_from_str(src, &options).is_ok());
+
+        let options = ParseOptions { validate_names: true, max_name_len: Some(4), lenient: true, ..Default::default() };
+        let (world, warnings) = load_world_from_str_with_warnings(src, &options).unwrap();
+        // The bad tunnel and the whole line of the long name are left out
+        assert_eq!(world.names(), ["A"]);
+        let reasons: Vec<_> = warnings.iter().map(ToString::to_string).collect();
+        assert_eq!(reasons[1], "line 2, column 1: Invalid colony name 'LongName': longer than 4 characters");
+    }
+
+    #[test]
+    fn test_alias_conflict_respects_case_option() {
+        let src = "A alias=x\nB alias=X\n";
+        assert!(parse_world_from_str_with(src, &ParseOptions::default()).is_ok());
+        let options = ParseOptions { case_insensitive: true, ..Default::default() };
+        assert!(parse_world_from_str_with(src, &options).is_err());
+    }
+
+    #[test]
+    fn test_alias_chain_is_a_conflict() {
+        let src = "A alias=B\nB alias=C\n";
+        assert!(parse_world_from_str_with(src, &ParseOptions::default()).is_err());
     }
 }
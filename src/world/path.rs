@@ -0,0 +1,80 @@
+use crate::node_id::NodeId;
+use crate::world::world::World;
+use std::collections::VecDeque;
+
+impl World {
+    /// Fewest-tunnel route from `from` to `to`, both ends included, found
+    /// breadth-first along tunnel directions through alive colonies.
+    ///
+    /// `Some(vec![from])` when the two are the same alive colony; `None`
+    /// when either end is destroyed or `to` cannot be reached. Among routes
+    /// of equal length the one whose steps come first in direction order
+    /// (north, south, east, west) wins.
+    pub fn shortest_path(&self, from: NodeId, to: NodeId) -> Option<Vec<NodeId>> {
+        if !self.is_alive(from) || !self.is_alive(to) {
+            return None;
+        }
+        // Colony each one was first reached from; `from` points to itself
+        let mut came_from: Vec<Option<NodeId>> = vec![None; self.nodes.len()];
+        came_from[from.index()] = Some(from);
+        let mut queue = VecDeque::from([from]);
+        while let Some(node) = queue.pop_front() {
+            if node == to {
+                let mut path = vec![to];
+                let mut at = to;
+                while at != from {
+                    at = came_from[at.index()]?;
+                    path.push(at);
+                }
+                path.reverse();
+                return Some(path);
+            }
+            for n in self.nodes.neighbors(node.index()).into_iter().flatten() {
+                if came_from[n.index()].is_none() && self.is_alive(n) {
+                    came_from[n.index()] = Some(node);
+                    queue.push_back(n);
+                }
+            }
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::world::parser::parse_world_from_str;
+
+    fn world(src: &str) -> World {
+        let (names, nodes) = parse_world_from_str(src);
+        World::new(names, nodes)
+    }
+
+    fn names(world: &World, path: Option<Vec<NodeId>>) -> Option<Vec<&str>> {
+        path.map(|p| p.into_iter().map(|id| world.get_colony_name(id)).collect())
+    }
+
+    #[test]
+    fn test_shortest_path_takes_the_fewest_tunnels() {
+        // A -> B -> C -> D, plus a shortcut A -> C
+        let w = world("A east=B south=C\nB east=C\nC east=D\nD\n");
+        let (a, b, d) = (w.find("A").unwrap(), w.find("B").unwrap(), w.find("D").unwrap());
+
+        assert_eq!(names(&w, w.shortest_path(a, d)), Some(vec!["A", "C", "D"]));
+        assert_eq!(names(&w, w.shortest_path(b, b)), Some(vec!["B"]));
+        // Tunnels are one-way
+        assert_eq!(w.shortest_path(d, a), None);
+    }
+
+    #[test]
+    fn test_shortest_path_avoids_destroyed_colonies() {
+        let mut w = world("A east=B south=C\nB east=D\nC east=E\nE east=D\nD\n");
+        let (a, c, d) = (w.find("A").unwrap(), w.find("C").unwrap(), w.find("D").unwrap());
+
+        w.destroy(w.find("B").unwrap());
+        assert_eq!(names(&w, w.shortest_path(a, d)), Some(vec!["A", "C", "E", "D"]));
+        w.destroy(c);
+        assert_eq!(w.shortest_path(a, d), None);
+        assert_eq!(w.shortest_path(c, c), None);
+    }
+}
@@ -0,0 +1,242 @@
+use crate::node_id::NodeId;
+use crate::world::world::World;
+use std::collections::VecDeque;
+use std::fmt;
+use std::str::FromStr;
+
+/// How initial ants are spread over the landing zones (`--placement`)
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Placement {
+    /// Every zone equally likely: `uniform`
+    #[default]
+    Uniform,
+    /// Zones drawn in proportion to their alive exits: `weighted-by-degree`
+    WeightedByDegree,
+    /// Ants packed into `k` random neighborhoods: `cluster:K`
+    Cluster { k: usize },
+    /// Ants spread out, keeping apart while there is room: `poisson`
+    Poisson,
+}
+
+impl FromStr for Placement {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.split_once(':') {
+            None if s == "uniform" => Ok(Placement::Uniform),
+            None if s == "weighted-by-degree" => Ok(Placement::WeightedByDegree),
+            None if s == "poisson" => Ok(Placement::Poisson),
+            Some(("cluster", k)) => match k.trim().parse::<usize>() {
+                Ok(0) => Err("cluster count must be positive".to_string()),
+                Ok(k) => Ok(Placement::Cluster { k }),
+                Err(e) => Err(format!("invalid number '{k}': {e}")),
+            },
+            _ => Err(format!(
+                "unknown placement '{s}' (expected uniform, weighted-by-degree, cluster:K or poisson)"
+            )),
+        }
+    }
+}
+
+impl fmt::Display for Placement {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Placement::Uniform => write!(f, "uniform"),
+            Placement::WeightedByDegree => write!(f, "weighted-by-degree"),
+            Placement::Cluster { k } => write!(f, "cluster:{k}"),
+            Placement::Poisson => write!(f, "poisson"),
+        }
+    }
+}
+
+impl World {
+    /// Starting colonies for `count` ants under `placement`, drawn from
+    /// [`World::spawn_zones`]; empty when no colony is standing
+    pub fn place(&self, count: usize, placement: Placement, rng: &mut fastrand::Rng) -> Vec<NodeId> {
+        let zones = self.spawn_zones();
+        if zones.is_empty() {
+            return Vec::new();
+        }
+        match placement {
+            Placement::Uniform => (0..count).map(|_| zones[rng.usize(..zones.len())]).collect(),
+            Placement::WeightedByDegree => self.place_by_degree(&zones, count, rng),
+            Placement::Cluster { k } => self.place_clustered(&zones, count, k, rng),
+            Placement::Poisson => self.place_spread(&zones, count, rng),
+        }
+    }
+
+    /// Alive exits of colony `id`
+    fn alive_degree(&self, id: NodeId) -> u64 {
+        self.nodes.neighbors(id.index()).into_iter().flatten().filter(|&n| self.is_alive(n)).count() as u64
+    }
+
+    /// Zones drawn in proportion to their alive exits; uniform if none has one
+    fn place_by_degree(&self, zones: &[NodeId], count: usize, rng: &mut fastrand::Rng) -> Vec<NodeId> {
+        let mut cumulative = Vec::with_capacity(zones.len());
+        let mut total = 0u64;
+        for &z in zones {
+            total += self.alive_degree(z);
+            cumulative.push(total);
+        }
+        if total == 0 {
+            return self.place(count, Placement::Uniform, rng);
+        }
+        (0..count)
+            .map(|_| {
+                let r = rng.u64(..total);
+                zones[cumulative.partition_point(|&c| c <= r)]
+            })
+            .collect()
+    }
+
+    /// Pick `k` distinct centers, grow a neighborhood of about `count / k`
+    /// zones around each by breadth-first search over alive tunnels (either
+    /// way), then deal the ants round-robin to the clusters, each landing on
+    /// a random zone of its cluster's neighborhood
+    fn place_clustered(&self, zones: &[NodeId], count: usize, k: usize, rng: &mut fastrand::Rng) -> Vec<NodeId> {
+        let mut pool = zones.to_vec();
+        let k = k.min(pool.len());
+        for i in 0..k {
+            let j = rng.usize(i..pool.len());
+            pool.swap(i, j);
+        }
+        let target = count.div_ceil(k).max(1);
+
+        let mut is_zone = vec![false; self.nodes.len()];
+        for z in zones {
+            is_zone[z.index()] = true;
+        }
+        let mut seen = vec![false; self.nodes.len()];
+        let hoods: Vec<Vec<NodeId>> = pool[..k]
+            .iter()
+            .map(|&center| {
+                seen.iter_mut().for_each(|s| *s = false);
+                let mut hood = Vec::with_capacity(target);
+                let mut queue = VecDeque::from([center]);
+                seen[center.index()] = true;
+                while let Some(node) = queue.pop_front() {
+                    if is_zone[node.index()] {
+                        hood.push(node);
+                        if hood.len() == target {
+                            break;
+                        }
+                    }
+                    let out = self.nodes.neighbors(node.index()).into_iter().flatten();
+                    for next in out.chain(self.in_neighbors(node).iter().copied()) {
+                        if !seen[next.index()] && self.is_alive(next) {
+                            seen[next.index()] = true;
+                            queue.push_back(next);
+                        }
+                    }
+                }
+                hood
+            })
+            .collect();
+
+        (0..count)
+            .map(|i| {
+                let hood = &hoods[i % k];
+                hood[rng.usize(..hood.len())]
+            })
+            .collect()
+    }
+
+    /// Random zones that no placed ant stands on or next to (either way)
+    /// while any are left, then free zones, then any zone
+    fn place_spread(&self, zones: &[NodeId], count: usize, rng: &mut fastrand::Rng) -> Vec<NodeId> {
+        let mut order = zones.to_vec();
+        rng.shuffle(&mut order);
+        let mut taken = vec![false; self.nodes.len()];
+        let mut crowded = vec![false; self.nodes.len()];
+        let mut positions = Vec::with_capacity(count);
+
+        for &z in &order {
+            if positions.len() == count {
+                return positions;
+            }
+            if crowded[z.index()] {
+                continue;
+            }
+            positions.push(z);
+            taken[z.index()] = true;
+            crowded[z.index()] = true;
+            let out = self.nodes.neighbors(z.index()).into_iter().flatten();
+            for n in out.chain(self.in_neighbors(z).iter().copied()) {
+                crowded[n.index()] = true;
+            }
+        }
+        for &z in &order {
+            if positions.len() == count {
+                return positions;
+            }
+            if !taken[z.index()] {
+                positions.push(z);
+                taken[z.index()] = true;
+            }
+        }
+        while positions.len() < count {
+            positions.push(zones[rng.usize(..zones.len())]);
+        }
+        positions
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::world::parser::parse_world_from_str;
+
+    fn world(src: &str) -> World {
+        let (names, nodes) = parse_world_from_str(src);
+        World::new(names, nodes)
+    }
+
+    #[test]
+    fn test_parse_and_display_round_trip() {
+        for s in ["uniform", "weighted-by-degree", "cluster:3", "poisson"] {
+            assert_eq!(s.parse::<Placement>().unwrap().to_string(), s);
+        }
+        assert!("cluster:0".parse::<Placement>().is_err());
+        assert!("cluster".parse::<Placement>().is_err());
+        assert!("gaussian".parse::<Placement>().is_err());
+    }
+
+    #[test]
+    fn test_degree_weighting_skips_dead_ends() {
+        // B has three exits, A one, C and D none
+        let world = world("A east=B\nB west=A south=C east=D\nC\nD\n");
+        let mut rng = fastrand::Rng::with_seed(4);
+        let spots = world.place(300, Placement::WeightedByDegree, &mut rng);
+
+        let on = |i| spots.iter().filter(|p| p.index() == i).count();
+        assert_eq!(on(2) + on(3), 0);
+        assert!(on(1) > on(0) * 2, "{} vs {}", on(1), on(0));
+    }
+
+    #[test]
+    fn test_clusters_stay_near_their_center() {
+        // Two islands of three; one cluster of 3 ants fills one island
+        let world = world("A east=B\nB east=C\nC\nX east=Y\nY east=Z\nZ\n");
+        let mut rng = fastrand::Rng::with_seed(1);
+        let spots = world.place(3, Placement::Cluster { k: 1 }, &mut rng);
+
+        let island = spots[0].index() / 3;
+        assert!(spots.iter().all(|p| p.index() / 3 == island), "{spots:?}");
+    }
+
+    #[test]
+    fn test_poisson_keeps_ants_apart_while_it_can() {
+        // A row of 6: any two of the first pass are at least two hops apart
+        let world = world("A east=B\nB east=C\nC east=D\nD east=E\nE east=F\nF\n");
+        for seed in 0..20 {
+            let spots = world.place(2, Placement::Poisson, &mut fastrand::Rng::with_seed(seed));
+            assert!(spots[0].index().abs_diff(spots[1].index()) >= 2, "{spots:?}");
+        }
+
+        // Then every colony once before any doubles up
+        let mut rng = fastrand::Rng::with_seed(9);
+        let mut spots: Vec<usize> = world.place(6, Placement::Poisson, &mut rng).iter().map(|p| p.index()).collect();
+        spots.sort_unstable();
+        assert_eq!(spots, [0, 1, 2, 3, 4, 5]);
+    }
+}
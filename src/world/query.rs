@@ -0,0 +1,154 @@
+use crate::direction::Direction;
+use crate::node_id::NodeId;
+use crate::world::world::World;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// One outgoing tunnel of a queried colony
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Tunnel {
+    pub direction: Direction,
+    pub colony: String,
+}
+
+/// Whether a queried colony can be reached from another one
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Reachability {
+    pub from: String,
+    /// Fewest tunnels from `from`, following their direction; `None` when unreachable
+    pub hops: Option<u32>,
+}
+
+impl Reachability {
+    pub fn reachable(&self) -> bool {
+        self.hops.is_some()
+    }
+}
+
+/// What the `query` subcommand reports about one colony
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ColonyInfo {
+    pub colony: String,
+    pub id: NodeId,
+    /// Outgoing tunnels to alive colonies, in direction order
+    pub neighbors: Vec<Tunnel>,
+    /// Alive colonies with a tunnel into this one, in id order (once per tunnel)
+    pub in_neighbors: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub reachable: Option<Reachability>,
+}
+
+impl ColonyInfo {
+    /// Tunnels in and out of colony `id`, skipping destroyed colonies
+    pub fn of(world: &World, id: NodeId) -> Self {
+        let tunnels = world.nodes.neighbors(id.index());
+        let neighbors = Direction::iter()
+            .filter_map(|direction| {
+                let n = tunnels[direction.index()].filter(|&n| world.is_alive(n))?;
+                Some(Tunnel { direction, colony: world.get_colony_name(n).to_string() })
+            })
+            .collect();
+        let in_neighbors = world
+            .in_neighbors(id)
+            .iter()
+            .filter(|&&src| world.is_alive(src))
+            .map(|&src| world.get_colony_name(src).to_string())
+            .collect();
+        Self { colony: world.get_colony_name(id).to_string(), id, neighbors, in_neighbors, reachable: None }
+    }
+
+    /// Also answer whether this colony can be reached from `from`
+    pub fn with_reachability(mut self, world: &World, from: NodeId) -> Self {
+        self.reachable = Some(Reachability {
+            from: world.get_colony_name(from).to_string(),
+            hops: hops_between(world, from, self.id),
+        });
+        self
+    }
+
+    pub fn out_degree(&self) -> usize {
+        self.neighbors.len()
+    }
+
+    pub fn in_degree(&self) -> usize {
+        self.in_neighbors.len()
+    }
+}
+
+/// Fewest tunnels from `from` to `to` through alive colonies, see [`World::shortest_path`]
+pub fn hops_between(world: &World, from: NodeId, to: NodeId) -> Option<u32> {
+    world.shortest_path(from, to).map(|path| path.len() as u32 - 1)
+}
+
+impl fmt::Display for ColonyInfo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "colony:       {} (id {})", self.colony, self.id)?;
+        write!(f, "neighbors:   ")?;
+        if self.neighbors.is_empty() {
+            write!(f, " none")?;
+        }
+        for tunnel in &self.neighbors {
+            write!(f, " {}={}", tunnel.direction.as_str(), tunnel.colony)?;
+        }
+        writeln!(f)?;
+        write!(f, "reached from:")?;
+        if self.in_neighbors.is_empty() {
+            write!(f, " none")?;
+        }
+        for name in &self.in_neighbors {
+            write!(f, " {name}")?;
+        }
+        writeln!(f)?;
+        write!(f, "degree:       out {}, in {}", self.out_degree(), self.in_degree())?;
+        if let Some(reach) = &self.reachable {
+            writeln!(f)?;
+            match reach.hops {
+                Some(hops) => write!(f, "reachable:    from {} in {} hops", reach.from, hops)?,
+                None => write!(f, "reachable:    not from {}", reach.from)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::world::parser::parse_world_from_str;
+
+    fn world(src: &str) -> World {
+        let (names, nodes) = parse_world_from_str(src);
+        World::new(names, nodes)
+    }
+
+    #[test]
+    fn test_colony_info_lists_tunnels_both_ways() {
+        // A -> B -> C, C -> B, D -> B
+        let w = world("A east=B\nB east=C\nC west=B\nD north=B\n");
+        let b = w.find("B").unwrap();
+        let info = ColonyInfo::of(&w, b);
+
+        assert_eq!(info.neighbors, [Tunnel { direction: Direction::East, colony: "C".to_string() }]);
+        assert_eq!(info.in_neighbors, ["A", "C", "D"]);
+        assert_eq!((info.out_degree(), info.in_degree()), (1, 3));
+        assert_eq!(
+            info.to_string(),
+            "colony:       B (id 1)\nneighbors:    east=C\nreached from: A C D\ndegree:       out 1, in 3"
+        );
+    }
+
+    #[test]
+    fn test_reachability_follows_tunnel_direction() {
+        let mut w = world("A east=B\nB east=C\nC\nD west=A\n");
+        let (a, c) = (w.find("A").unwrap(), w.find("C").unwrap());
+
+        let info = ColonyInfo::of(&w, c).with_reachability(&w, a);
+        assert_eq!(info.reachable, Some(Reachability { from: "A".to_string(), hops: Some(2) }));
+        assert_eq!(hops_between(&w, c, a), None);
+        assert_eq!(hops_between(&w, a, a), Some(0));
+
+        w.destroy(w.find("B").unwrap());
+        assert_eq!(hops_between(&w, a, c), None);
+        assert!(ColonyInfo::of(&w, c).in_neighbors.is_empty());
+    }
+}
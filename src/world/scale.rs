@@ -0,0 +1,89 @@
+use crate::direction::Direction;
+use crate::node_id::{NodeId, NodeIndex};
+use crate::world::node::Node;
+use crate::world::world::World;
+
+impl World {
+    /// Replicate this world `copies` times into one larger world.
+    ///
+    /// Colony `X` of copy `k` is named `X_k`, so the result is deterministic
+    /// and names stay unique. With `bridge`, consecutive copies are joined by
+    /// a two-way tunnel from the first colony without an east exit in copy
+    /// `k` to the first colony without a west exit in copy `k + 1` (no bridge
+    /// is added if the base map has no such pair).
+    pub fn replicate(&self, copies: usize, bridge: bool) -> World {
+        let n = self.nodes.len();
+        let mut names = Vec::with_capacity(n * copies);
+        let mut nodes = Vec::with_capacity(n * copies);
+
+        for k in 0..copies {
+            let offset = k * n;
+            for node in self.nodes.iter() {
+                let mut copy = Node::new(names.len() as NodeIndex);
+                for (slot, &neighbor) in node.neighbors.iter().enumerate() {
+                    if let Some(neighbor) = neighbor {
+                        copy.set_neighbor(slot, NodeId::from_index(neighbor.index() + offset));
+                    }
+                }
+                copy.alive = node.alive;
+                names.push(format!("{}_{}", self.names[node.name_idx as usize], k));
+                nodes.push(copy);
+            }
+        }
+
+        let east = Direction::East.index();
+        let west = Direction::West.index();
+        let exit = self.nodes.iter().position(|nd| nd.get_neighbor(east).is_none());
+        let entry = self.nodes.iter().position(|nd| nd.get_neighbor(west).is_none());
+        if let (true, Some(exit), Some(entry)) = (bridge, exit, entry) {
+            for k in 1..copies {
+                let from = (k - 1) * n + exit;
+                let to = k * n + entry;
+                nodes[from].set_neighbor(east, NodeId::from_index(to));
+                nodes[to].set_neighbor(west, NodeId::from_index(from));
+            }
+        }
+
+        World::new(names, nodes).with_attributes(self.attrs.repeat(copies))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::direction::Direction;
+    use crate::node_id::NodeId;
+    use crate::world::parser::parse_world_from_str;
+    use crate::world::World;
+
+    fn world_from(src: &str) -> World {
+        let (names, nodes) = parse_world_from_str(src);
+        World::new(names, nodes)
+    }
+
+    #[test]
+    fn test_replicate_suffixes_names_and_remaps_ids() {
+        let world = world_from("A north=B\nB south=A\n").replicate(3, false);
+
+        assert_eq!(world.names, ["A_0", "B_0", "A_1", "B_1", "A_2", "B_2"]);
+        let a2 = world.nodes.get(4).unwrap();
+        assert_eq!(world.get_colony_name(a2.get_neighbor(Direction::North.index()).unwrap()), "B_2");
+        assert!(a2.get_neighbor(Direction::East.index()).is_none());
+    }
+
+    #[test]
+    fn test_replicate_with_bridges_links_consecutive_copies() {
+        let world = world_from("A east=B\nB west=A\n").replicate(2, true);
+
+        // B_0 has no east exit, A_1 has no west exit
+        let b0 = world.nodes.get(1).unwrap();
+        let a1 = world.nodes.get(2).unwrap();
+        assert_eq!(b0.get_neighbor(Direction::East.index()), Some(NodeId::new(2)));
+        assert_eq!(a1.get_neighbor(Direction::West.index()), Some(NodeId::new(1)));
+    }
+
+    #[test]
+    fn test_replicate_zero_copies_is_empty() {
+        let world = world_from("A\n").replicate(0, true);
+        assert!(world.nodes.is_empty());
+    }
+}
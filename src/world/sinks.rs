@@ -0,0 +1,165 @@
+use crate::ant::Ant;
+use crate::node_id::{NodeId, NodeIndex};
+use crate::world::world::World;
+
+impl World {
+    /// Strongly connected components of alive colonies that can be entered
+    /// from elsewhere but never left: an ant that walks in stays inside for
+    /// the rest of the run. Components are listed in discovery order.
+    ///
+    /// A closed component nobody can enter (e.g. an isolated island or a map
+    /// that is strongly connected as a whole) is not a sink.
+    pub fn sink_components(&self) -> Vec<Vec<NodeId>> {
+        let comp = self.strong_components();
+        let count = comp.iter().flatten().map(|&c| c + 1).max().unwrap_or(0);
+        let mut has_exit = vec![false; count];
+        let mut has_entry = vec![false; count];
+        for (i, node) in self.nodes.iter().enumerate() {
+            let Some(ci) = comp[i] else { continue };
+            for nb in node.neighbors.into_iter().flatten() {
+                match comp[nb.index()] {
+                    Some(cj) if cj != ci => {
+                        has_exit[ci] = true;
+                        has_entry[cj] = true;
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        let mut sinks: Vec<Vec<NodeId>> = vec![Vec::new(); count];
+        for (i, c) in comp.iter().enumerate() {
+            if let Some(c) = *c {
+                if has_entry[c] && !has_exit[c] {
+                    sinks[c].push(NodeId::from_index(i));
+                }
+            }
+        }
+        sinks.retain(|s| !s.is_empty());
+        sinks
+    }
+
+    /// How many of `ants` stand inside one of `sinks`
+    pub fn ants_in_sinks(&self, sinks: &[Vec<NodeId>], ants: &[Ant]) -> usize {
+        let mut in_sink = vec![false; self.nodes.len()];
+        for id in sinks.iter().flatten() {
+            in_sink[id.index()] = true;
+        }
+        ants.iter().filter(|ant| in_sink[ant.pos.index()]).count()
+    }
+
+    /// Component index per colony (`None` for destroyed ones), using an
+    /// iterative Tarjan so huge maps cannot overflow the stack
+    fn strong_components(&self) -> Vec<Option<usize>> {
+        const UNSEEN: NodeIndex = NodeIndex::MAX;
+        let n = self.nodes.len();
+        let mut index = vec![UNSEEN; n];
+        let mut low: Vec<NodeIndex> = vec![0; n];
+        let mut on_stack = vec![false; n];
+        let mut stack: Vec<usize> = Vec::new();
+        let mut comp: Vec<Option<usize>> = vec![None; n];
+        let mut next_index: NodeIndex = 0;
+        let mut next_comp = 0usize;
+        // (node, next neighbor slot to visit)
+        let mut call: Vec<(usize, usize)> = Vec::new();
+
+        for root in 0..n {
+            if index[root] != UNSEEN || !self.nodes.is_alive(root) {
+                continue;
+            }
+            call.push((root, 0));
+            index[root] = next_index;
+            low[root] = next_index;
+            next_index += 1;
+            stack.push(root);
+            on_stack[root] = true;
+
+            while let Some(&mut (v, ref mut slot)) = call.last_mut() {
+                if *slot < 4 {
+                    let neighbor = self.nodes.neighbors(v)[*slot];
+                    *slot += 1;
+                    let Some(w) = neighbor.map(|w| w.index()) else { continue };
+                    if !self.nodes.is_alive(w) {
+                        continue;
+                    }
+                    if index[w] == UNSEEN {
+                        index[w] = next_index;
+                        low[w] = next_index;
+                        next_index += 1;
+                        stack.push(w);
+                        on_stack[w] = true;
+                        call.push((w, 0));
+                    } else if on_stack[w] {
+                        low[v] = low[v].min(index[w]);
+                    }
+                    continue;
+                }
+
+                call.pop();
+                if let Some(&(parent, _)) = call.last() {
+                    low[parent] = low[parent].min(low[v]);
+                }
+                if low[v] == index[v] {
+                    while let Some(w) = stack.pop() {
+                        on_stack[w] = false;
+                        comp[w] = Some(next_comp);
+                        if w == v {
+                            break;
+                        }
+                    }
+                    next_comp += 1;
+                }
+            }
+        }
+        comp
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::node_id::NodeId;
+    use crate::world::parser::parse_world_from_str;
+    use crate::world::World;
+
+    fn world_from(src: &str) -> World {
+        let (names, nodes) = parse_world_from_str(src);
+        World::new(names, nodes)
+    }
+
+    fn names(world: &World, sinks: &[Vec<NodeId>]) -> Vec<Vec<String>> {
+        sinks
+            .iter()
+            .map(|s| {
+                let mut v: Vec<String> = s.iter().map(|&id| world.get_colony_name(id).to_string()).collect();
+                v.sort();
+                v
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_detects_one_way_trap_and_dead_end() {
+        // A <-> B -> C <-> D is a trap; B -> E is a dead end
+        let world = world_from("A east=B\nB west=A east=C south=E\nC east=D\nD west=C\nE\n");
+        let mut sinks = names(&world, &world.sink_components());
+        sinks.sort();
+
+        assert_eq!(sinks, [vec!["C", "D"], vec!["E"]]);
+    }
+
+    #[test]
+    fn test_closed_components_are_not_sinks() {
+        let world = world_from("A east=B\nB west=A\nC\n");
+        assert!(world.sink_components().is_empty());
+    }
+
+    #[test]
+    fn test_destroyed_exit_creates_sink() {
+        // C's only exit is B; once B falls, A -> C leads into a trap
+        let mut world = world_from("A east=C\nC east=D west=B\nD west=C\nB\n");
+        assert_eq!(names(&world, &world.sink_components()), [vec!["B"]]);
+
+        world.nodes.destroy(3);
+        assert_eq!(names(&world, &world.sink_components()), [vec!["C", "D"]]);
+    }
+}
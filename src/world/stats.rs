@@ -0,0 +1,144 @@
+use crate::world::world::World;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Structural summary of a map, computed without running a simulation
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct WorldStats {
+    pub colonies: usize,
+    /// Directed tunnels
+    pub edges: usize,
+    /// `degree_histogram[d]` = colonies with `d` outgoing tunnels
+    pub degree_histogram: [usize; 5],
+    /// Colonies without outgoing tunnels (ants landing there are trapped)
+    pub dead_ends: usize,
+    /// Share of tunnels `a -> b` for which some tunnel `b -> a` exists
+    pub reciprocity: f64,
+    /// Colonies in the largest weakly connected component
+    pub largest_component: usize,
+    pub components: usize,
+}
+
+impl WorldStats {
+    /// Compute statistics over alive colonies and the tunnels between them
+    pub fn of(world: &World) -> Self {
+        let n = world.nodes.len();
+        let alive = |i: usize| world.nodes.is_alive(i);
+        let out = |i: usize| {
+            world
+                .nodes
+                .neighbors(i)
+                .into_iter()
+                .flatten()
+                .map(|nb| nb.index())
+                .filter(move |&j| alive(j))
+        };
+
+        let mut colonies = 0;
+        let mut edges = 0;
+        let mut reciprocal = 0;
+        let mut degree_histogram = [0usize; 5];
+        let mut parent: Vec<usize> = (0..n).collect();
+        for i in (0..n).filter(|&i| alive(i)) {
+            colonies += 1;
+            let mut degree = 0;
+            for j in out(i) {
+                degree += 1;
+                reciprocal += out(j).any(|k| k == i) as usize;
+                union(&mut parent, i, j);
+            }
+            edges += degree;
+            degree_histogram[degree] += 1;
+        }
+
+        let mut sizes = vec![0usize; n];
+        for i in (0..n).filter(|&i| alive(i)) {
+            sizes[find(&mut parent, i)] += 1;
+        }
+
+        Self {
+            colonies,
+            edges,
+            degree_histogram,
+            dead_ends: degree_histogram[0],
+            reciprocity: if edges == 0 { 0.0 } else { reciprocal as f64 / edges as f64 },
+            largest_component: sizes.iter().copied().max().unwrap_or(0),
+            components: sizes.iter().filter(|&&s| s > 0).count(),
+        }
+    }
+}
+
+/// Union-find root with path halving
+fn find(parent: &mut [usize], mut i: usize) -> usize {
+    while parent[i] != i {
+        parent[i] = parent[parent[i]];
+        i = parent[i];
+    }
+    i
+}
+
+fn union(parent: &mut [usize], a: usize, b: usize) {
+    let (ra, rb) = (find(parent, a), find(parent, b));
+    if ra != rb {
+        parent[ra] = rb;
+    }
+}
+
+impl fmt::Display for WorldStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "colonies:          {}", self.colonies)?;
+        writeln!(f, "tunnels:           {}", self.edges)?;
+        write!(f, "out-degree:       ")?;
+        for (degree, count) in self.degree_histogram.iter().enumerate() {
+            write!(f, " {degree}:{count}")?;
+        }
+        writeln!(f)?;
+        writeln!(f, "dead ends:         {}", self.dead_ends)?;
+        writeln!(f, "reciprocity:       {:.3}", self.reciprocity)?;
+        write!(
+            f,
+            "largest component: {} of {} ({} components)",
+            self.largest_component, self.colonies, self.components
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::world::parser::parse_world_from_str;
+
+    fn stats(src: &str) -> WorldStats {
+        let (names, nodes) = parse_world_from_str(src);
+        WorldStats::of(&World::new(names, nodes))
+    }
+
+    #[test]
+    fn test_counts_degrees_and_reciprocity() {
+        // A <-> B, B -> C, D isolated
+        let s = stats("A east=B\nB west=A south=C\nC\nD\n");
+
+        assert_eq!((s.colonies, s.edges), (4, 3));
+        assert_eq!(s.degree_histogram, [2, 1, 1, 0, 0]);
+        assert_eq!(s.dead_ends, 2);
+        assert!((s.reciprocity - 2.0 / 3.0).abs() < 1e-9);
+        assert_eq!((s.largest_component, s.components), (3, 2));
+    }
+
+    #[test]
+    fn test_destroyed_colonies_are_ignored() {
+        let (names, nodes) = parse_world_from_str("A east=B\nB west=A\n");
+        let mut world = World::new(names, nodes);
+        world.nodes.destroy(1);
+        let s = WorldStats::of(&world);
+
+        assert_eq!((s.colonies, s.edges, s.dead_ends), (1, 0, 1));
+        assert_eq!(s.reciprocity, 0.0);
+    }
+
+    #[test]
+    fn test_empty_world() {
+        let s = WorldStats::of(&World::new(Vec::new(), Vec::new()));
+        assert_eq!((s.colonies, s.largest_component, s.components), (0, 0, 0));
+    }
+}
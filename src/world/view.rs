@@ -0,0 +1,118 @@
+use crate::node_id::NodeId;
+use crate::world::node_ref::NodeRef;
+use crate::world::World;
+
+/// Read-only snapshot of a [`World`] as the engine hands it out mid-run.
+///
+/// Hooks, move strategies and stop conditions get one instead of the world
+/// itself: it answers alive checks, tunnel and name lookups, and cannot be
+/// used to change the map or held past the call. It is `Copy`, so pass it
+/// around by value.
+#[derive(Clone, Copy)]
+pub struct WorldView<'a> {
+    world: &'a World,
+}
+
+impl<'a> WorldView<'a> {
+    pub fn new(world: &'a World) -> Self {
+        Self { world }
+    }
+
+    /// The world behind the view, for crate code that still takes `&World`
+    #[inline]
+    pub(crate) fn world(&self) -> &'a World {
+        self.world
+    }
+
+    /// Number of colonies, destroyed ones included; ids run `0..len()`
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.world.nodes.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Whether colony `id` still stands; `false` for ids outside the world
+    #[inline]
+    pub fn is_alive(&self, id: NodeId) -> bool {
+        id.index() < self.len() && self.world.is_alive(id)
+    }
+
+    /// Colonies still standing
+    pub fn alive_count(&self) -> usize {
+        self.world.alive_count()
+    }
+
+    /// Colony `id`, if it belongs to the world
+    pub fn colony(&self, id: NodeId) -> Option<NodeRef<'a>> {
+        self.world.colony(id)
+    }
+
+    /// Colonies still standing, in id order
+    pub fn alive_colonies(&self) -> impl Iterator<Item = NodeRef<'a>> + 'a {
+        self.world.alive_nodes()
+    }
+
+    /// Tunnels leaving colony `id`, indexed by [`Direction::index`](crate::Direction::index),
+    /// whether they lead to a standing colony or not
+    #[inline]
+    pub fn neighbors(&self, id: NodeId) -> [Option<NodeId>; 4] {
+        self.world.nodes.neighbors(id.index())
+    }
+
+    /// Standing colonies one tunnel away from `id`, in direction order
+    pub fn alive_neighbors(&self, id: NodeId) -> impl Iterator<Item = NodeId> + 'a {
+        let world = self.world;
+        self.neighbors(id).into_iter().flatten().filter(move |&n| world.is_alive(n))
+    }
+
+    /// Colonies with a tunnel into `id`, as [`World::in_neighbors`]
+    pub fn in_neighbors(&self, id: NodeId) -> &'a [NodeId] {
+        self.world.in_neighbors(id)
+    }
+
+    /// Name of colony `id` as written in the map
+    #[inline]
+    pub fn name(&self, id: NodeId) -> &'a str {
+        self.world.get_colony_name(id)
+    }
+
+    /// Id of the colony called `name`
+    pub fn find(&self, name: &str) -> Option<NodeId> {
+        self.world.find(name)
+    }
+}
+
+impl<'a> From<&'a World> for WorldView<'a> {
+    fn from(world: &'a World) -> Self {
+        Self::new(world)
+    }
+}
+
+impl std::fmt::Debug for WorldView<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("WorldView").field("colonies", &self.len()).field("alive", &self.alive_count()).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::world::parser::parse_world_from_str;
+
+    #[test]
+    fn test_view_follows_the_world() {
+        let (names, nodes) = parse_world_from_str("A east=B south=C\nB west=A\nC north=A\n");
+        let mut world = World::new(names, nodes);
+        world.destroy(NodeId::new(2));
+        let view = WorldView::new(&world);
+
+        let a = view.find("A").unwrap();
+        assert_eq!(view.alive_neighbors(a).map(|n| view.name(n)).collect::<Vec<_>>(), ["B"]);
+        assert_eq!(view.alive_count(), 2);
+        assert!(!view.is_alive(NodeId::new(2)) && !view.is_alive(NodeId::new(7)));
+    }
+}
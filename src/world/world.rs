@@ -1,97 +1,349 @@
 use crate::ant::Ant;
+use crate::cli::SortOrder;
 use crate::direction::Direction;
-use crate::utils::INVALID_NODE;
+use crate::node_id::{NodeId, NodeIndex};
+use crate::world::attrs::{AttrValue, Attributes};
+use crate::world::in_edges::InEdges;
 use crate::world::node::Node;
+use crate::world::node_ref::NodeRef;
+use crate::world::node_table::NodeTable;
+use crate::strategy::DirectionBias;
+use crate::world::placement::Placement;
+use crate::world::parser::SPAWN_TAG;
+use serde::{Deserialize, Serialize};
+use std::io::Write;
 
-/// Final world: names + nodes (no hashmaps kept at runtime)
-#[derive(Clone, Debug)]
+/// Final world: names + nodes (no hashmaps kept at runtime).
+///
+/// The node table is private: every tunnel is checked to stay inside it
+/// when the world is built, which the unchecked lookups of the movement
+/// hot path rely on. Read it through [`World::nodes`], [`World::colony`]
+/// and [`World::alive_nodes`], and change it only through methods such as
+/// [`World::destroy`] that keep the tunnels intact.
+///
+/// Serializes as `names`, `nodes` and `attrs`; deserializing checks that
+/// every tunnel, name index and attribute column fits the node table and
+/// rebuilds the reverse edges.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(try_from = "WorldData")]
 pub struct World {
-    pub names: Vec<String>,
-    pub nodes: Vec<Node>,
+    pub(crate) names: Vec<String>,
+    pub(crate) nodes: NodeTable,
+    /// Per-colony attributes (`hp=3` tokens in the map), indexed by node id
+    pub attrs: Attributes,
+    /// Reverse tunnels, built from `nodes` on construction
+    #[serde(skip_serializing)]
+    in_edges: InEdges,
+    /// Whether tunnels into destroyed colonies are cleared, see [`World::prune_dead_edges`]
+    #[serde(skip_serializing)]
+    prune_dead_edges: bool,
+}
+
+/// Serialized form of a [`World`], checked before it becomes one
+#[derive(Deserialize)]
+struct WorldData {
+    names: Vec<String>,
+    nodes: Vec<Node>,
+    #[serde(default)]
+    attrs: Attributes,
+}
+
+impl TryFrom<WorldData> for World {
+    type Error = String;
+
+    fn try_from(data: WorldData) -> Result<Self, Self::Error> {
+        let n = data.nodes.len();
+        check_tables(&data.names, &data.nodes)?;
+        if let Some(key) = data.attrs.keys().find(|&key| data.attrs.column(key).is_some_and(|c| c.len() != n)) {
+            return Err(format!("attribute column '{key}' does not have {n} entries"));
+        }
+        Ok(World::new(data.names, data.nodes).with_attributes(data.attrs))
+    }
+}
+
+/// Every name index and tunnel of `nodes` must point inside the tables
+fn check_tables(names: &[String], nodes: &[Node]) -> Result<(), String> {
+    let n = nodes.len();
+    for (i, node) in nodes.iter().enumerate() {
+        if node.name_idx as usize >= names.len() {
+            return Err(format!("colony {i} names entry {} of {}", node.name_idx, names.len()));
+        }
+        if let Some(bad) = node.neighbors.into_iter().flatten().find(|nb| nb.index() >= n) {
+            return Err(format!("colony {i} has a tunnel to {bad}, past the {n} colonies"));
+        }
+    }
+    Ok(())
 }
 
 impl World {
     /// Create a new world from names and nodes
+    ///
+    /// # Panics
+    /// If a node names an entry past `names` or has a tunnel past `nodes`.
     pub fn new(names: Vec<String>, nodes: Vec<Node>) -> Self {
-        Self { names, nodes }
+        if let Err(err) = check_tables(&names, &nodes) {
+            panic!("invalid world: {err}");
+        }
+        let nodes = NodeTable::from(nodes);
+        let in_edges = InEdges::build(&nodes);
+        Self { names, nodes, attrs: Attributes::default(), in_edges, prune_dead_edges: false }
+    }
+
+    /// Colonies with a tunnel into `node`, in id order, destroyed ones included
+    #[inline]
+    pub fn in_neighbors(&self, node: NodeId) -> &[NodeId] {
+        self.in_edges.of(node)
+    }
+
+    /// Recompute [`World::in_neighbors`] after adding or removing tunnels
+    pub(crate) fn rebuild_in_edges(&mut self) {
+        self.in_edges = InEdges::build(&self.nodes);
+    }
+
+    /// Attach per-colony attributes (one slot per node)
+    pub fn with_attributes(mut self, attrs: Attributes) -> Self {
+        self.attrs = attrs;
+        self
+    }
+
+    /// Attribute column `key` by node index, if present with type `T`
+    pub fn attr<T: AttrValue>(&self, key: &str) -> Option<&[Option<T>]> {
+        self.attrs.get(key)
+    }
+
+    /// Mutable attribute column `key`, if present with type `T`
+    pub fn attr_mut<T: AttrValue>(&mut self, key: &str) -> Option<&mut [Option<T>]> {
+        self.attrs.get_mut(key)
+    }
+
+    /// Attribute column `key` of type `T`, created unset if missing;
+    /// `None` if it exists with another type
+    pub fn add_attr<T: AttrValue>(&mut self, key: &str) -> Option<&mut [Option<T>]> {
+        let len = self.nodes.len();
+        self.attrs.insert(key, len)
     }
 
     /// Get a node by id (unsafe for performance)
     /// 
     /// # Safety
-    /// The caller must ensure that `idx` is a valid node index
+    /// The caller must ensure that `id` belongs to this world
     #[inline(always)]
-    pub unsafe fn node_unchecked(&self, idx: u32) -> &Node {
-        self.nodes.get_unchecked(idx as usize)
+    pub unsafe fn node_unchecked(&self, id: NodeId) -> Node {
+        let i = id.index();
+        Node {
+            name_idx: self.nodes.name_idx(i),
+            neighbors: self.nodes.neighbors_unchecked(i),
+            alive: self.nodes.is_alive_unchecked(i),
+        }
     }
 
-    /// Get a mutable node by id
+    /// Get a node by id
     #[inline]
-    pub fn node_mut(&mut self, idx: u32) -> Option<&mut Node> {
-        self.nodes.get_mut(idx as usize)
+    pub fn node(&self, id: NodeId) -> Option<Node> {
+        self.nodes.get(id.index())
     }
 
-    /// Get a node by id
+    /// All nodes, indexed by id
     #[inline]
-    pub fn node(&self, idx: u32) -> Option<&Node> {
-        self.nodes.get(idx as usize)
+    pub fn nodes(&self) -> &NodeTable {
+        &self.nodes
+    }
+
+    /// Colony names, indexed by [`Node::name_idx`]
+    #[inline]
+    pub fn names(&self) -> &[String] {
+        &self.names
+    }
+
+    /// Read-only view of colony `id`, if it exists
+    pub fn colony(&self, id: NodeId) -> Option<NodeRef<'_>> {
+        (id.index() < self.nodes.len()).then(|| NodeRef::new(self, id))
+    }
+
+    /// Every colony, destroyed ones included, in id order
+    pub fn colonies(&self) -> impl Iterator<Item = NodeRef<'_>> + '_ {
+        (0..self.nodes.len()).map(|i| NodeRef::new(self, NodeId::from_index(i)))
+    }
+
+    /// Colonies still standing, in id order
+    pub fn alive_nodes(&self) -> impl Iterator<Item = NodeRef<'_>> + '_ {
+        self.colonies().filter(|c| c.is_alive())
+    }
+
+    /// Destroy colony `id`; returns whether it was still standing
+    ///
+    /// # Panics
+    /// If `id` does not belong to this world.
+    pub fn destroy(&mut self, id: NodeId) -> bool {
+        let was_alive = self.nodes.destroy(id.index());
+        if was_alive && self.prune_dead_edges {
+            self.unlink(id);
+        }
+        was_alive
+    }
+
+    /// Rebuild the destroyed colony `id` from its neighbor `from`: it stands
+    /// again, with tunnels both ways between the two. A missing one is laid
+    /// opposite the other, if that slot of its colony is free. Under
+    /// [`World::prune_dead_edges`] the rebuilt colony's tunnels into ruins
+    /// are cleared. Returns whether `id` was destroyed.
+    ///
+    /// # Panics
+    /// If `id` or `from` does not belong to this world.
+    pub fn rebuild(&mut self, id: NodeId, from: NodeId) -> bool {
+        if !self.nodes.revive(id.index()) {
+            return false;
+        }
+        let slot_to = |nodes: &NodeTable, a: NodeId, b: NodeId| nodes.neighbors(a.index()).iter().position(|&n| n == Some(b));
+        let added = match (slot_to(&self.nodes, from, id), slot_to(&self.nodes, id, from)) {
+            (Some(out), None) => self.lay_tunnel(id, Direction::ALL[out].opposite(), from),
+            (None, Some(back)) => self.lay_tunnel(from, Direction::ALL[back].opposite(), id),
+            _ => false,
+        };
+        if self.prune_dead_edges {
+            let nodes = &mut self.nodes;
+            for d in 0..4 {
+                if nodes.neighbors(id.index())[d].is_some_and(|n| !nodes.is_alive(n.index())) {
+                    nodes.neighbors_mut(id.index())[d] = None;
+                }
+            }
+        }
+        if added {
+            self.rebuild_in_edges();
+        }
+        true
+    }
+
+    /// Lay a tunnel from `src` towards `direction` into `dst` if that slot is
+    /// free; returns whether [`World::in_neighbors`] now misses it
+    fn lay_tunnel(&mut self, src: NodeId, direction: Direction, dst: NodeId) -> bool {
+        if self.nodes.neighbors(src.index())[direction.index()].is_some() {
+            return false;
+        }
+        self.nodes.set_neighbor(src.index(), direction.index(), dst);
+        // Tunnels cleared by pruning keep their source listed
+        !self.in_edges.of(dst).contains(&src)
+    }
+
+    /// Clear every tunnel into a destroyed colony, now and whenever
+    /// [`World::destroy`] takes one down later, so that each remaining
+    /// tunnel leads to an alive colony: exports never show edges into
+    /// ruins and movement skips its liveness checks.
+    ///
+    /// [`World::in_neighbors`] still lists the old sources of dead colonies.
+    pub fn prune_dead_edges(&mut self) {
+        self.prune_dead_edges = true;
+        for i in 0..self.nodes.len() {
+            if !self.nodes.is_alive(i) {
+                self.unlink(NodeId::from_index(i));
+            }
+        }
+    }
+
+    /// Whether [`World::prune_dead_edges`] is in effect
+    pub fn prunes_dead_edges(&self) -> bool {
+        self.prune_dead_edges
+    }
+
+    /// Clear the tunnels of every colony that leads into `id`
+    fn unlink(&mut self, id: NodeId) {
+        for src in self.in_edges.of(id) {
+            for slot in self.nodes.neighbors_mut(src.index()) {
+                if *slot == Some(id) {
+                    *slot = None;
+                }
+            }
+        }
+    }
+
+    /// Check whether the colony `id` is still standing
+    #[inline]
+    pub fn is_alive(&self, id: NodeId) -> bool {
+        self.nodes.is_alive(id.index())
     }
 
     /// Place ants uniformly at alive nodes
     pub fn create_ants(&self, count: usize, rng: &mut fastrand::Rng) -> Vec<Ant> {
-        let alive_nodes: Vec<u32> = self
-            .nodes
-            .iter()
-            .enumerate()
-            .filter_map(|(i, nd)| nd.is_alive().then_some(i as u32))
-            .collect();
+        self.create_colony(count, 0, rng)
+    }
 
-        (0..count)
-            .map(|i| {
-                let pos = alive_nodes[rng.usize(..alive_nodes.len())];
-                Ant::new(i as u32, pos)
-            })
+    /// Place ants uniformly at alive nodes, the first `queens` of them being queens.
+    ///
+    /// Only [`World::spawn_zones`] are used when the map tags any.
+    pub fn create_colony(&self, count: usize, queens: usize, rng: &mut fastrand::Rng) -> Vec<Ant> {
+        self.place_colony(count, queens, Placement::Uniform, rng)
+    }
+
+    /// Like [`World::create_colony`], spreading the ants as `placement` says.
+    ///
+    /// Positions are random, so taking the lowest ids as queens is as good as
+    /// picking them at random and leaves the RNG stream untouched.
+    pub fn place_colony(&self, count: usize, queens: usize, placement: Placement, rng: &mut fastrand::Rng) -> Vec<Ant> {
+        debug_assert!(count < u32::MAX as usize, "ant ids are u32");
+        self.place(count, placement, rng)
+            .into_iter()
+            .enumerate()
+            .map(|(i, pos)| if i < queens { Ant::queen(i as u32, pos) } else { Ant::new(i as u32, pos) })
             .collect()
     }
 
+    /// Alive colonies where ants may land: those tagged `spawn` (non-zero)
+    /// in the map, or every alive colony when none is
+    pub fn spawn_zones(&self) -> Vec<NodeId> {
+        let tags = self.attr::<u32>(SPAWN_TAG);
+        let tagged = |i: usize| tags.is_some_and(|t| t[i].is_some_and(|v| v != 0));
+        let alive = |i: &usize| self.nodes.is_alive(*i);
+        let mut zones: Vec<NodeId> = (0..self.nodes.len()).filter(alive).filter(|&i| tagged(i)).map(NodeId::from_index).collect();
+        if zones.is_empty() {
+            zones = (0..self.nodes.len()).filter(alive).map(NodeId::from_index).collect();
+        }
+        zones
+    }
+
     /// Choose next position for an ant, returns (new_position, is_trapped)
     /// 
     /// # Safety invariants:
     /// - `ant_pos` is always a valid node index (< world.nodes.len())
     /// - `ant_pos` points to an alive colony (callers guarantee this)
-    /// - All neighbor indices in nodes[ant_pos].neighbors are either INVALID_NODE or valid node indices
+    /// - All neighbor ids in the tunnels of `ant_pos` belong to this world (parser guarantees this)
     #[inline(always)]
-    pub fn choose_next_position(&self, ant_pos: u32, rng: &mut fastrand::Rng) -> (u32, bool) {
-        let node = unsafe { self.node_unchecked(ant_pos) };
-        debug_assert!(node.is_alive());
+    pub(crate) fn choose_next_position(&self, ant_pos: NodeId, rng: &mut fastrand::Rng) -> (NodeId, bool) {
+        debug_assert!(self.is_alive(ant_pos));
+        let neighbors = unsafe { self.nodes.neighbors_unchecked(ant_pos.index()) };
 
         // Manual unroll like the original - this is the performance critical path
-        let mut opts = [INVALID_NODE; 4];
+        let mut opts = [ant_pos; 4];
         let mut k = 0usize;
 
-        let n0 = node.neighbors[0];
-        let n1 = node.neighbors[1];
-        let n2 = node.neighbors[2];
-        let n3 = node.neighbors[3];
+        if self.prune_dead_edges {
+            // Every remaining tunnel leads to an alive colony
+            for n in neighbors.into_iter().flatten() {
+                opts[k] = n;
+                k += 1;
+            }
+            return if k == 0 { (ant_pos, true) } else { (opts[rng.usize(..k)], false) };
+        }
+
+        let [n0, n1, n2, n3] = neighbors;
 
         // Manually unrolled for maximum performance
-        if n0 != INVALID_NODE {
-            let alive = unsafe { self.node_unchecked(n0) }.is_alive();
+        if let Some(n0) = n0 {
+            let alive = unsafe { self.nodes.is_alive_unchecked(n0.index()) };
             opts[k] = n0;
             k += alive as usize;
         }
-        if n1 != INVALID_NODE {
-            let alive = unsafe { self.node_unchecked(n1) }.is_alive();
+        if let Some(n1) = n1 {
+            let alive = unsafe { self.nodes.is_alive_unchecked(n1.index()) };
             opts[k] = n1;
             k += alive as usize;
         }
-        if n2 != INVALID_NODE {
-            let alive = unsafe { self.node_unchecked(n2) }.is_alive();
+        if let Some(n2) = n2 {
+            let alive = unsafe { self.nodes.is_alive_unchecked(n2.index()) };
             opts[k] = n2;
             k += alive as usize;
         }
-        if n3 != INVALID_NODE {
-            let alive = unsafe { self.node_unchecked(n3) }.is_alive();
+        if let Some(n3) = n3 {
+            let alive = unsafe { self.nodes.is_alive_unchecked(n3.index()) };
             opts[k] = n3;
             k += alive as usize;
         }
@@ -103,38 +355,138 @@ impl World {
         }
     }
 
-    /// Print the remaining world in the same input format
-    pub fn print_world(&self) {
-        let mut line = String::with_capacity(128);
-        for node in &self.nodes {
-            if !node.is_alive() {
-                continue;
+    /// One random step: [`World::choose_biased_position`] under a `--bias`,
+    /// else [`World::choose_next_position`]
+    #[inline(always)]
+    pub(crate) fn random_step(
+        &self,
+        ant_pos: NodeId,
+        bias: Option<&DirectionBias>,
+        rng: &mut fastrand::Rng,
+    ) -> (NodeId, bool) {
+        match bias {
+            Some(bias) => self.choose_biased_position(ant_pos, bias, rng),
+            None => self.choose_next_position(ant_pos, rng),
+        }
+    }
+
+    /// Like [`World::choose_next_position`], picking among the alive exits
+    /// in proportion to their `bias` weight. Exits that all weigh 0 are
+    /// picked uniformly; an ant is trapped only when no exit is alive.
+    #[inline]
+    pub(crate) fn choose_biased_position(
+        &self,
+        ant_pos: NodeId,
+        bias: &DirectionBias,
+        rng: &mut fastrand::Rng,
+    ) -> (NodeId, bool) {
+        debug_assert!(self.is_alive(ant_pos));
+
+        let mut opts = [(ant_pos, 0.0); 4];
+        let mut k = 0usize;
+        let mut total = 0.0;
+        for (i, n) in self.nodes.neighbors(ant_pos.index()).into_iter().enumerate() {
+            if let Some(n) = n.filter(|&n| self.is_alive(n)) {
+                let w = bias.weights()[i];
+                opts[k] = (n, w);
+                total += w;
+                k += 1;
             }
-            line.clear();
-            line.push_str(&self.names[node.name_idx as usize]);
-            
-            for &direction in &Direction::ALL {
-                let neighbor_id = node.neighbors[direction.index()];
-                if neighbor_id != INVALID_NODE && self.nodes[neighbor_id as usize].is_alive() {
-                    line.push(' ');
-                    line.push_str(direction.as_str());
-                    line.push('=');
-                    line.push_str(&self.names[self.nodes[neighbor_id as usize].name_idx as usize]);
+        }
+
+        if k == 0 {
+            return (ant_pos, true); // trapped
+        }
+        if total <= 0.0 {
+            return (opts[rng.usize(..k)].0, false);
+        }
+        let mut r = rng.f64() * total;
+        let mut pick = ant_pos;
+        for &(n, w) in &opts[..k] {
+            if w > 0.0 {
+                // Last positive exit, in case rounding leaves `r` past the end
+                pick = n;
+                if r < w {
+                    break;
                 }
+                r -= w;
             }
-            // Commented out to match original behavior
-            // println!("{}", line);
         }
+        (pick, false)
     }
 
-    /// Count surviving colonies
+    /// Print the remaining world to stdout in the input format, in `order`,
+    /// through one buffered lock on stdout
+    pub fn print_world(&self, order: SortOrder) -> std::io::Result<()> {
+        let mut out = std::io::BufWriter::new(std::io::stdout().lock());
+        self.write_sorted(&mut out, order)?;
+        out.flush()
+    }
+
+    /// Write alive colonies in the map input format, one line per colony.
+    ///
+    /// Each line goes to `out` as soon as its colony is reached in a single
+    /// pass over the nodes; nothing is buffered here, so memory use does not
+    /// grow with the map. Wrap `out` in a `BufWriter` for unbuffered sinks.
+    pub fn write_map<W: Write>(&self, out: &mut W) -> std::io::Result<()> {
+        for i in (0..self.nodes.len()).filter(|&i| self.nodes.is_alive(i)) {
+            self.write_colony(i, out)?;
+        }
+        Ok(())
+    }
+
+    /// Like [`World::write_map`], but in a stable `order` independent of input order.
+    ///
+    /// Id order is input order and streams like `write_map`; name order has
+    /// to sort first and keeps one `u32` per alive colony.
+    pub fn write_sorted<W: Write>(&self, out: &mut W, order: SortOrder) -> std::io::Result<()> {
+        if order == SortOrder::Id {
+            return self.write_map(out);
+        }
+        let mut alive: Vec<NodeIndex> = (0..self.nodes.len() as NodeIndex)
+            .filter(|&i| self.nodes.is_alive(i as usize))
+            .collect();
+        alive.sort_unstable_by_key(|&i| &self.names[self.nodes.name_idx(i as usize) as usize]);
+        for i in alive {
+            self.write_colony(i as usize, out)?;
+        }
+        Ok(())
+    }
+
+    /// One colony line with its tunnels to alive neighbors, then its attributes
+    fn write_colony<W: Write>(&self, i: usize, out: &mut W) -> std::io::Result<()> {
+        out.write_all(self.names[self.nodes.name_idx(i) as usize].as_bytes())?;
+        for &direction in &Direction::ALL {
+            if let Some(neighbor_id) = self.nodes.neighbors(i)[direction.index()] {
+                if self.is_alive(neighbor_id) {
+                    write!(out, " {}={}", direction.as_str(), self.get_colony_name(neighbor_id))?;
+                }
+            }
+        }
+        self.attrs.write_node(i, out)?;
+        out.write_all(b"\n")
+    }
+
+    /// Colonies still standing, kept as a counter so this is O(1)
+    #[inline]
+    pub fn alive_count(&self) -> usize {
+        self.nodes.alive_count()
+    }
+
+    /// Count surviving colonies, as [`World::alive_count`]
+    #[inline]
     pub fn count_survivors(&self) -> usize {
-        self.nodes.iter().filter(|n| n.is_alive()).count()
+        self.alive_count()
+    }
+
+    /// Id of the colony named `name` (exact match, linear scan)
+    pub fn find(&self, name: &str) -> Option<NodeId> {
+        (0..self.nodes.len()).find(|&i| self.names[self.nodes.name_idx(i) as usize] == name).map(NodeId::from_index)
     }
 
     /// Get the name of a colony by node id
-    pub fn get_colony_name(&self, node_id: u32) -> &str {
-        &self.names[self.nodes[node_id as usize].name_idx as usize]
+    pub fn get_colony_name(&self, node_id: NodeId) -> &str {
+        &self.names[self.nodes.name_idx(node_id.index()) as usize]
     }
 }
 
@@ -144,12 +496,13 @@ mod tests {
     use crate::world::parser::parse_world_from_str;
 
     /// Helper function to find a node id by name
-    fn id_of(world: &World, name: &str) -> u32 {
-        world
+    fn id_of(world: &World, name: &str) -> NodeId {
+        let idx = world
             .names
             .iter()
             .position(|n| n == name)
-            .expect("name not found") as u32
+            .expect("name not found");
+        NodeId::from_index(idx)
     }
 
     #[test]
@@ -188,6 +541,27 @@ mod tests {
         assert!(!is_trapped);
     }
 
+    #[test]
+    fn test_choose_biased_position() {
+        let (names, nodes) = parse_world_from_str("A north=N east=E west=W\nN\nE\nW\n");
+        let mut world = World::new(names, nodes);
+        let (a, n, e, w) = (id_of(&world, "A"), id_of(&world, "N"), id_of(&world, "E"), id_of(&world, "W"));
+        let bias: DirectionBias = "north=3,east=1".parse().unwrap();
+        let mut rng = fastrand::Rng::with_seed(7);
+
+        let picks: Vec<NodeId> = (0..4000).map(|_| world.choose_biased_position(a, &bias, &mut rng).0).collect();
+        let count = |id| picks.iter().filter(|&&p| p == id).count();
+        assert_eq!(count(w), 0);
+        assert!((2800..3200).contains(&count(n)), "{}", count(n));
+
+        // Only zero-weight exits left: uniform among them, not trapped
+        world.nodes.destroy(n.index());
+        world.nodes.destroy(e.index());
+        assert_eq!(world.choose_biased_position(a, &bias, &mut rng), (w, false));
+        world.nodes.destroy(w.index());
+        assert_eq!(world.choose_biased_position(a, &bias, &mut rng), (a, true));
+    }
+
     #[test]
     fn test_create_ants() {
         let (names, nodes) = parse_world_from_str("A north=B\nB south=A\n");
@@ -205,6 +579,18 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_create_colony_with_queens() {
+        let (names, nodes) = parse_world_from_str("A north=B\nB south=A\n");
+        let world = World::new(names, nodes);
+
+        let mut rng = fastrand::Rng::with_seed(123);
+        let ants = world.create_colony(5, 2, &mut rng);
+
+        let queens: Vec<u32> = ants.iter().filter(|a| a.is_queen()).map(|a| a.id).collect();
+        assert_eq!(queens, vec![0, 1]);
+    }
+
     #[test]
     fn test_count_survivors() {
         let (names, nodes) = parse_world_from_str("A north=B\nB south=A\nC\n");
@@ -215,11 +601,114 @@ mod tests {
         // Destroy one colony
         let mut world = world;
         let c_id = id_of(&world, "C");
-        world.nodes[c_id as usize].destroy();
+        world.nodes.destroy(c_id.index());
         
         assert_eq!(world.count_survivors(), 2);
     }
 
+    #[test]
+    fn test_alive_count_follows_destruction() {
+        let (names, nodes) = parse_world_from_str("A north=B\nB south=A\nC\n");
+        let mut world = World::new(names, nodes);
+        assert_eq!(world.alive_count(), 3);
+
+        let b = id_of(&world, "B");
+        assert!(world.destroy(b));
+        assert!(!world.destroy(b));
+        assert_eq!(world.alive_count(), 2);
+        assert_eq!(world.nodes().recount_alive(), 2);
+        // Compaction keeps the count of the colonies it keeps
+        world.compact();
+        assert_eq!((world.nodes().len(), world.alive_count()), (2, 2));
+    }
+
+    #[test]
+    fn test_write_map_skips_destroyed_colonies() {
+        let (names, nodes) = parse_world_from_str("A north=B west=C\nB south=A\nC east=A\n");
+        let mut world = World::new(names, nodes);
+        let c_id = id_of(&world, "C");
+        world.nodes.destroy(c_id.index());
+
+        let mut out = Vec::new();
+        world.write_map(&mut out).unwrap();
+        assert_eq!(String::from_utf8(out).unwrap(), "A north=B\nB south=A\n");
+    }
+
+    #[test]
+    fn test_write_sorted_by_name_and_id() {
+        let (names, nodes) = parse_world_from_str("Zed west=Ant\nMid\nAnt east=Zed\n");
+        let world = World::new(names, nodes);
+
+        let mut by_name = Vec::new();
+        world.write_sorted(&mut by_name, SortOrder::Name).unwrap();
+        assert_eq!(String::from_utf8(by_name).unwrap(), "Ant east=Zed\nMid\nZed west=Ant\n");
+
+        let mut by_id = Vec::new();
+        world.write_sorted(&mut by_id, SortOrder::Id).unwrap();
+        assert_eq!(String::from_utf8(by_id).unwrap(), "Zed west=Ant\nMid\nAnt east=Zed\n");
+    }
+
+    /// Sink that keeps only counters, optionally failing once `budget` bytes are spent
+    struct CountingWriter {
+        bytes: usize,
+        writes: usize,
+        largest_write: usize,
+        budget: usize,
+    }
+
+    impl CountingWriter {
+        fn new(budget: usize) -> Self {
+            Self { bytes: 0, writes: 0, largest_write: 0, budget }
+        }
+    }
+
+    impl Write for CountingWriter {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            if self.bytes + buf.len() > self.budget {
+                return Err(std::io::Error::other("budget exhausted"));
+            }
+            self.bytes += buf.len();
+            self.writes += 1;
+            self.largest_write = self.largest_write.max(buf.len());
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    fn large_world() -> World {
+        let (names, nodes) = parse_world_from_str("A east=B south=C\nB west=A\nC north=A\n");
+        World::new(names, nodes).replicate(20_000, true)
+    }
+
+    #[test]
+    fn test_write_map_streams_small_pieces() {
+        let world = large_world();
+        let mut whole = Vec::new();
+        world.write_map(&mut whole).unwrap();
+        let longest_line = whole.split(|&b| b == b'\n').map(<[u8]>::len).max().unwrap();
+
+        let mut out = CountingWriter::new(usize::MAX);
+        world.write_map(&mut out).unwrap();
+        assert_eq!(out.bytes, whole.len());
+        // No write holds more than one line, however large the world
+        assert!(out.largest_write <= longest_line);
+        assert!(out.writes >= world.count_survivors());
+    }
+
+    #[test]
+    fn test_write_map_stops_at_first_failed_write() {
+        let world = large_world();
+        let mut out = CountingWriter::new(1024);
+
+        assert!(world.write_map(&mut out).is_err());
+        // Output was handed over as it was produced, not assembled up front
+        assert!(out.bytes <= 1024 && out.bytes > 0);
+        assert!(out.writes < world.count_survivors());
+    }
+
     #[test]
     fn test_get_colony_name() {
         let (names, nodes) = parse_world_from_str("Colony1 north=Colony2\n");
@@ -239,7 +728,84 @@ mod tests {
         assert!(node.is_alive());
         
         let mut world = world;
-        let node_mut = world.node_mut(a_id);
-        assert!(node_mut.is_some());
+        assert!(world.destroy(a_id));
+        assert!(!world.destroy(a_id));
+        assert!(!world.is_alive(a_id));
+    }
+
+    #[test]
+    fn test_alive_nodes_skip_destroyed() {
+        let (names, nodes) = parse_world_from_str("A east=B
+B
+C
+");
+        let mut world = World::new(names, nodes);
+        world.destroy(id_of(&world, "B"));
+
+        let alive: Vec<&str> = world.alive_nodes().map(|c| c.name()).collect();
+        assert_eq!(alive, ["A", "C"]);
+        assert_eq!(world.colonies().count(), 3);
+        assert!(world.colony(NodeId::from_index(3)).is_none());
+    }
+
+    #[test]
+    #[should_panic(expected = "tunnel to")]
+    fn test_new_rejects_tunnels_past_the_node_table() {
+        let mut node = Node::new(0);
+        node.set_neighbor(0, NodeId::new(7));
+        World::new(vec!["A".to_string()], vec![node]);
+    }
+
+    #[test]
+    fn test_in_neighbors() {
+        let (names, nodes) = parse_world_from_str("A east=B\nB west=A south=C\nC\n");
+        let mut world = World::new(names, nodes);
+        let (a, b, c) = (id_of(&world, "A"), id_of(&world, "B"), id_of(&world, "C"));
+
+        assert_eq!(world.in_neighbors(b), [a]);
+        assert_eq!(world.in_neighbors(c), [b]);
+        // Destroyed colonies still count; callers filter by liveness
+        world.nodes.destroy(b.index());
+        assert_eq!(world.in_neighbors(a), [b]);
+
+        world.nodes.set_neighbor(c.index(), Direction::North.index(), b);
+        world.rebuild_in_edges();
+        assert_eq!(world.in_neighbors(b), [a, c]);
+    }
+
+    #[test]
+    fn test_prune_dead_edges() {
+        let (names, nodes) = parse_world_from_str("A east=B\nB west=A south=C\nC north=B west=A\n");
+        let mut world = World::new(names, nodes);
+        let (a, b, c) = (id_of(&world, "A"), id_of(&world, "B"), id_of(&world, "C"));
+
+        world.destroy(c);
+        world.prune_dead_edges();
+        assert_eq!(world.nodes.neighbors(b.index()), [None, None, None, Some(a)]);
+        // A's tunnels are only cleared once it falls
+        world.destroy(a);
+        assert_eq!(world.nodes.neighbors(b.index()), [None; 4]);
+        assert_eq!(world.choose_next_position(b, &mut fastrand::Rng::with_seed(1)), (b, true));
+        // The dead keep their own tunnels
+        assert_eq!(world.nodes.neighbors(c.index())[Direction::North.index()], Some(b));
+    }
+
+    #[test]
+    fn test_rebuild_restores_tunnels_both_ways() {
+        let (names, nodes) = parse_world_from_str("A east=B\nB west=A south=C\nC north=B west=A\n");
+        let mut world = World::new(names, nodes);
+        let (a, b, c) = (id_of(&world, "A"), id_of(&world, "B"), id_of(&world, "C"));
+        world.prune_dead_edges();
+        world.destroy(b);
+        world.destroy(c);
+
+        // A only had C's tunnel into it: one back is laid west -> east
+        assert!(world.rebuild(c, a));
+        assert!(!world.rebuild(c, a));
+        assert_eq!(world.alive_count(), 2);
+        assert_eq!(world.nodes.neighbors(a.index()), [None, None, Some(c), None]);
+        // C's tunnel into B, still in ruins, is gone
+        assert_eq!(world.nodes.neighbors(c.index()), [None, None, None, Some(a)]);
+        assert_eq!(world.in_neighbors(c), [a]);
     }
 }
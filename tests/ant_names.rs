@@ -0,0 +1,72 @@
+use assert_cmd::Command;
+use predicates::str::contains;
+use std::io::Write;
+
+const BIN: &str = "ants_code_challenge";
+
+/// One colony: both ants land on it and fight at t=0
+fn single_colony() -> tempfile::NamedTempFile {
+    let mut file = tempfile::NamedTempFile::new().unwrap();
+    writeln!(file, "Solo").unwrap();
+    file
+}
+
+#[test]
+fn generated_names_appear_in_events_and_report() {
+    let map = single_colony();
+    let report = tempfile::NamedTempFile::new().unwrap();
+    Command::cargo_bin(BIN)
+        .unwrap()
+        .args(["-n", "2", "-m", map.path().to_str().unwrap(), "--generate-ant-names"])
+        .args(["--report", report.path().to_str().unwrap()])
+        .env("NO_COLOR", "1")
+        .assert()
+        .success()
+        .stdout(contains("Solo has been destroyed by ant-red-0 and ant-blue-1"));
+
+    let json: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(report.path()).unwrap()).unwrap();
+    let entry = &json["timeline"][0];
+    assert_eq!(entry["ants"], serde_json::json!([0, 1]));
+    assert_eq!(entry["ant_names"], serde_json::json!(["ant-red-0", "ant-blue-1"]));
+}
+
+#[test]
+fn names_file_is_used_and_falls_back_to_ids() {
+    let map = single_colony();
+    let mut names = tempfile::NamedTempFile::new().unwrap();
+    writeln!(names, "Ada").unwrap();
+    Command::cargo_bin(BIN)
+        .unwrap()
+        .args(["-n", "2", "-m", map.path().to_str().unwrap()])
+        .args(["--ant-names", names.path().to_str().unwrap()])
+        .env("NO_COLOR", "1")
+        .assert()
+        .success()
+        .stdout(contains("destroyed by Ada and ant 1"));
+}
+
+#[test]
+fn unnamed_runs_keep_ids() {
+    let map = single_colony();
+    let report = tempfile::NamedTempFile::new().unwrap();
+    Command::cargo_bin(BIN)
+        .unwrap()
+        .args(["-n", "2", "-m", map.path().to_str().unwrap()])
+        .args(["--report", report.path().to_str().unwrap()])
+        .env("NO_COLOR", "1")
+        .assert()
+        .success()
+        .stdout(contains("destroyed by ant 0 and ant 1"));
+
+    let report = std::fs::read_to_string(report.path()).unwrap();
+    assert!(!report.contains("ant_names"));
+}
+
+#[test]
+fn names_sources_conflict() {
+    Command::cargo_bin(BIN)
+        .unwrap()
+        .args(["-n", "2", "-m", "x", "--ant-names", "n.txt", "--generate-ant-names"])
+        .assert()
+        .code(3);
+}
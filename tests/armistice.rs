@@ -0,0 +1,54 @@
+use ants_code_challenge::prelude::*;
+use ants_code_challenge::simulation::DestructionCause;
+
+mod common;
+
+/// Two fights are due on tick 1: at C (ants 0, 1) and at F (ants 2, 3)
+fn run(scheduling: &str, extra: &[&str]) -> (World, Vec<Ant>, SimulationEngine) {
+    common::run("A east=C\nB west=C\nC\nD east=F\nE west=F\nF\n", &["A", "B", "D", "E"], scheduling, extra)
+}
+
+#[test]
+fn without_cap_both_colonies_fall_on_the_same_tick() {
+    for scheduling in ["simultaneous", "sequential"] {
+        let (_, _, engine) = run(scheduling, &[]);
+        let ticks: Vec<u64> = engine.events().iter().map(|e| e.tick).collect();
+        assert_eq!(ticks, [1, 1], "{scheduling}");
+    }
+}
+
+#[test]
+fn cap_averts_the_second_fight_and_ants_bounce_back() {
+    for scheduling in ["simultaneous", "sequential"] {
+        let (world, ants, engine) = run(scheduling, &["--max-destructions-per-tick", "1"]);
+        let f = world.find("F").unwrap();
+        // Ant order within a pair depends on the scheduler, so normalize it
+        let summary: Vec<(u64, NodeId, DestructionCause)> = engine
+            .events()
+            .iter()
+            .map(|e| {
+                let cause = match e.cause {
+                    DestructionCause::Fight { ant1, ant2 } => DestructionCause::Fight {
+                        ant1: ant1.min(ant2),
+                        ant2: ant1.max(ant2),
+                    },
+                    other => other,
+                };
+                (e.tick, e.node_id, cause)
+            })
+            .collect();
+
+        assert_eq!(
+            summary,
+            [
+                (1, world.find("C").unwrap(), DestructionCause::Fight { ant1: 0, ant2: 1 }),
+                (1, f, DestructionCause::Averted { ant1: 2, ant2: 3 }),
+                (2, f, DestructionCause::Fight { ant1: 2, ant2: 3 }),
+            ],
+            "{scheduling}"
+        );
+        // The spared fight only cost a tick: every ant is dead in the end
+        assert!(ants.iter().all(|a| !a.is_alive() || a.is_trapped()), "{scheduling}");
+        assert_eq!(world.count_survivors(), 4, "{scheduling}");
+    }
+}
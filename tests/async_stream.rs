@@ -0,0 +1,106 @@
+#![cfg(feature = "async")]
+
+use ants_code_challenge::prelude::*;
+use ants_code_challenge::simulation::r#async::{run_stream, RunStream, SimulationEvent};
+use ants_code_challenge::simulation::TickDelta;
+use ants_code_challenge::world::parser::parse_world_from_str;
+use clap::Parser;
+use futures_core::Stream;
+use std::pin::Pin;
+use std::task::{Context, Poll, Waker};
+
+const ROW: &str = "A east=B\nB west=A east=C\nC west=B east=D\nD west=C east=E\nE west=D\n";
+
+fn setup() -> (World, Vec<Ant>, Args) {
+    let (names, nodes) = parse_world_from_str(ROW);
+    let world = World::new(names, nodes);
+    let ants = (0..5).map(|i| Ant::new(i, NodeId::from_index(i as usize))).collect();
+    let args = Args::parse_from(["ants", "-n", "5", "-m", "unused", "--suppress-events", "--max-moves", "40"]);
+    (world, ants, args)
+}
+
+/// Every item of `stream`, and how many times it returned `Pending`
+fn drain(stream: &mut RunStream) -> (Vec<SimulationEvent>, usize) {
+    let mut cx = Context::from_waker(Waker::noop());
+    let (mut events, mut pending) = (Vec::new(), 0);
+    loop {
+        match Pin::new(&mut *stream).poll_next(&mut cx) {
+            Poll::Ready(Some(event)) => events.push(event),
+            Poll::Ready(None) => return (events, pending),
+            Poll::Pending => pending += 1,
+        }
+    }
+}
+
+#[test]
+fn stream_matches_a_blocking_run() {
+    let (mut world, mut ants, args) = setup();
+    let mut engine = SimulationEngine::new(&world, ants.len());
+    engine.run_simulation(&mut world, &mut ants, &args, &mut fastrand::Rng::with_seed(4));
+
+    let (w, a, args) = setup();
+    let mut stream = run_stream(SimulationEngine::new(&w, a.len()), w, a, args, fastrand::Rng::with_seed(4), 1);
+    let (events, _) = drain(&mut stream);
+
+    let destructions: Vec<_> = events
+        .iter()
+        .filter_map(|e| match e {
+            SimulationEvent::Destruction(event) => Some(*event),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(destructions, engine.events());
+    let ticks = events.iter().filter(|e| matches!(e, SimulationEvent::Tick { .. })).count();
+    assert_eq!(ticks as u64, engine.ticks());
+    assert_eq!(
+        events.last(),
+        Some(&SimulationEvent::Finished { ticks: engine.ticks(), survivors: world.count_survivors(), interrupted: false })
+    );
+    assert_eq!(stream.world().count_survivors(), world.count_survivors());
+}
+
+#[test]
+fn stream_yields_every_k_ticks() {
+    let (world, ants, args) = setup();
+    let mut stream = run_stream(SimulationEngine::new(&world, ants.len()), world, ants, args, fastrand::Rng::with_seed(4), 3);
+    let (_, pending) = drain(&mut stream);
+
+    assert!(stream.engine().ticks() >= 3);
+    assert_eq!(pending as u64, stream.engine().ticks() / 3);
+}
+
+#[test]
+fn stream_is_send_and_events_serialize() {
+    fn assert_send<T: Send>() {}
+    assert_send::<RunStream>();
+
+    let event = SimulationEvent::Tick { tick: 2, ants_alive: 3 };
+    let json = serde_json::to_string(&event).unwrap();
+    assert_eq!(json, r#"{"type":"tick","tick":2,"ants_alive":3}"#);
+    assert_eq!(serde_json::from_str::<SimulationEvent>(&json).unwrap(), event);
+}
+
+#[test]
+fn deltas_follow_each_tick_when_asked() {
+    let (world, ants, args) = setup();
+    let stream = run_stream(SimulationEngine::new(&world, ants.len()), world, ants, args, fastrand::Rng::with_seed(4), 1);
+    let (events, _) = drain(&mut stream.with_deltas());
+
+    // One delta for t=0, then one right before every tick item
+    let deltas: Vec<&TickDelta> = events
+        .iter()
+        .filter_map(|e| match e {
+            SimulationEvent::Delta(delta) => Some(delta),
+            _ => None,
+        })
+        .collect();
+    let ticks = events.iter().filter(|e| matches!(e, SimulationEvent::Tick { .. })).count();
+    assert_eq!(deltas.len(), ticks + 1);
+    assert_eq!(deltas.iter().map(|d| d.tick).collect::<Vec<_>>(), (0..=ticks as u64).collect::<Vec<_>>());
+    let fallen = deltas.iter().map(|d| d.destroyed.len()).sum::<usize>();
+    let fights = events.iter().filter(|e| matches!(e, SimulationEvent::Destruction(ev) if ev.cause.destroyed())).count();
+    assert_eq!(fallen, fights);
+
+    let json = serde_json::to_string(deltas[0]).unwrap();
+    assert!(json.starts_with(r#"{"tick":0,"destroyed":["#));
+}
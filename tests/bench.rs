@@ -0,0 +1,58 @@
+use assert_cmd::Command;
+
+const BIN: &str = "ants_code_challenge";
+const MAP: &str = "files/hiveum_map_small.txt";
+
+fn bench(extra: &[&str]) -> assert_cmd::assert::Assert {
+    Command::cargo_bin(BIN)
+        .unwrap()
+        .args(["-n", "20", "-m", MAP, "--seed", "42", "--bench"])
+        .args(extra)
+        .assert()
+}
+
+#[test]
+fn bench_prints_a_single_plain_line() {
+    let out = bench(&["--repeat", "3"]).success().get_output().stdout.clone();
+    let out = String::from_utf8(out).unwrap();
+
+    assert_eq!(out.lines().count(), 1, "{out}");
+    assert!(!out.contains('\x1b'), "{out}");
+    let fields: Vec<&str> = out.split_whitespace().collect();
+    assert_eq!(fields[..4], ["bench", "ants=20", "colonies=28", "repeat=3"]);
+    let median: f64 = fields[4].strip_prefix("median_ms=").unwrap().parse().unwrap();
+    assert!(median >= 0.0);
+    assert!(fields[7].starts_with("survivors="));
+}
+
+#[test]
+fn bench_survivors_match_a_normal_run() {
+    let plain = Command::cargo_bin(BIN)
+        .unwrap()
+        .args(["-n", "20", "-m", MAP, "--seed", "42", "--suppress-events"])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let plain = String::from_utf8(plain).unwrap();
+    let survivors = plain.split_whitespace().find(|t| t.contains("survivors=")).unwrap();
+
+    let out = bench(&[]).success().get_output().stdout.clone();
+    assert!(String::from_utf8(out).unwrap().trim_end().ends_with(survivors));
+}
+
+#[test]
+fn repeat_needs_bench() {
+    Command::cargo_bin(BIN)
+        .unwrap()
+        .args(["-n", "20", "-m", MAP, "--repeat", "3"])
+        .assert()
+        .code(3);
+}
+
+#[cfg(not(feature = "pin"))]
+#[test]
+fn pinning_needs_the_pin_feature() {
+    bench(&["--pin-cpu", "0"]).failure().stderr(predicates::str::contains("--features pin"));
+}
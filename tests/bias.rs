@@ -0,0 +1,39 @@
+use assert_cmd::Command;
+
+mod common;
+use common::Run;
+
+/// A ring of 20 colonies joined east/west; one ant starts on R0 and a
+/// trapped ant on the island Z keeps the run going
+fn final_position(extra: &[&str]) -> usize {
+    let mut map = String::new();
+    for i in 0..20 {
+        map.push_str(&format!("R{i} east=R{} west=R{}\n", (i + 1) % 20, (i + 19) % 20));
+    }
+    map.push_str("Z\n");
+    let (_, ants, _) = Run::on(&map).at(&["R0", "Z"]).flags(&["--max-moves", "10"]).flags(extra).seed(9).run();
+    assert_eq!(ants[0].moves, 10);
+    ants[0].pos.index()
+}
+
+#[test]
+fn full_bias_walks_in_one_direction() {
+    for scheduling in ["simultaneous", "sequential"] {
+        assert_eq!(final_position(&["--bias", "east=1", "--scheduling", scheduling]), 10);
+        assert_eq!(final_position(&["--bias", "west=1,north=5", "--scheduling", scheduling]), 10);
+    }
+    // Homing ants take their random steps with the bias too
+    assert_eq!(final_position(&["--bias", "east=1", "--strategy", "homing:20"]), 10);
+}
+
+#[test]
+fn cli_rejects_bad_weights() {
+    for bias in ["east=-1", "up=1", "north=0", "east"] {
+        Command::cargo_bin("ants_code_challenge")
+            .unwrap()
+            .args(["-n", "1", "-m", "unused", "--bias", bias])
+            .assert()
+            .failure()
+            .code(3);
+    }
+}
@@ -0,0 +1,29 @@
+mod common;
+use common::Run;
+
+const LINE: &str = "A east=B\nB east=C\nC east=D\nD\n";
+
+#[test]
+fn zero_radius_only_destroys_the_collision_site() {
+    let (world, _, _) = Run::on(LINE).at(&["A", "A"]).flags(&["--blast-radius", "0"]).run();
+    assert!(!world.is_alive(world.find("A").unwrap()));
+    assert_eq!(world.count_survivors(), 3);
+}
+
+#[test]
+fn blast_destroys_colonies_within_radius() {
+    let (world, _, _) = Run::on(LINE).at(&["A", "A"]).flags(&["--blast-radius", "2"]).run();
+    for name in ["A", "B", "C"] {
+        assert!(!world.is_alive(world.find(name).unwrap()), "{name}");
+    }
+    assert!(world.is_alive(world.find("D").unwrap()));
+}
+
+#[test]
+fn blast_kills_ants_standing_on_collateral_colonies() {
+    // The lone ant on the isolated colony B is trapped; the blast from A kills it.
+    let (world, ants, _) = Run::on("A east=B\nB\n").at(&["A", "A", "B"]).flags(&["--blast-radius", "1"]).run();
+
+    assert_eq!(world.count_survivors(), 0);
+    assert!(ants.iter().all(|a| !a.is_alive()));
+}
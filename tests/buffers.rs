@@ -0,0 +1,54 @@
+use ants_code_challenge::prelude::*;
+use ants_code_challenge::seeds::SubSeeds;
+use ants_code_challenge::simulation::{DestructionEvent, SimBuffers};
+use ants_code_challenge::world::parse_world;
+use clap::Parser;
+
+/// Run one seeded simulation, returning its outcome and the arena it used
+fn run(map: &str, seed: u64, scheduling: &str, buffers: SimBuffers) -> (usize, Vec<DestructionEvent>, SimBuffers) {
+    let args = Args::parse_from([
+        "ants", "-n", "300", "-m", map, "--scheduling", scheduling, "--blast-radius", "1", "--suppress-events",
+    ]);
+    let (names, nodes) = parse_world(map).unwrap();
+    let mut world = World::new(names, nodes);
+    let seeds = SubSeeds::derive(seed);
+    let mut ants = world.create_colony(args.ants, 0, &mut seeds.placement_rng());
+    let mut engine = SimulationEngine::new(&world, args.ants)
+        .with_seeds(seeds)
+        .with_buffers(buffers);
+    engine.run_simulation(&mut world, &mut ants, &args, &mut seeds.movement_rng());
+    let events = engine.events().to_vec();
+    (world.count_survivors(), events, engine.into_buffers())
+}
+
+#[test]
+fn reused_buffers_do_not_change_outcomes() {
+    let mut shared = SimBuffers::new();
+    for scheduling in ["simultaneous", "sequential"] {
+        // Alternate map sizes so stale state from a larger run would show up
+        for (map, seed) in [
+            ("files/hiveum_map_medium.txt", 1),
+            ("files/hiveum_map_small.txt", 2),
+            ("files/hiveum_map_medium.txt", 3),
+        ] {
+            let (fresh_survivors, fresh_events, _) = run(map, seed, scheduling, SimBuffers::new());
+            let (survivors, events, buffers) = run(map, seed, scheduling, shared);
+            shared = buffers;
+
+            assert_eq!(survivors, fresh_survivors, "{scheduling} {map} seed {seed}");
+            assert_eq!(events, fresh_events, "{scheduling} {map} seed {seed}");
+        }
+    }
+}
+
+#[test]
+fn buffers_keep_their_allocation_between_runs() {
+    let (names, nodes) = parse_world("files/hiveum_map_medium.txt").unwrap();
+    let colonies = World::new(names, nodes).nodes().len();
+
+    let (_, _, buffers) = run("files/hiveum_map_medium.txt", 1, "simultaneous", SimBuffers::with_capacity(colonies, 300));
+    assert_eq!(buffers.node_capacity(), colonies);
+
+    let (_, _, buffers) = run("files/hiveum_map_small.txt", 1, "simultaneous", buffers);
+    assert_eq!(buffers.node_capacity(), colonies);
+}
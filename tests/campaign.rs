@@ -0,0 +1,80 @@
+use ants_code_challenge::campaign::{run_campaign, WaveSpec};
+use ants_code_challenge::prelude::*;
+use ants_code_challenge::seeds::SubSeeds;
+use ants_code_challenge::world::load_world;
+use assert_cmd::Command;
+
+const MAP: &str = "files/hiveum_map_small.txt";
+
+fn waves(specs: &[&str]) -> Vec<WaveSpec> {
+    specs.iter().map(|s| s.parse().unwrap()).collect()
+}
+
+#[test]
+fn first_wave_matches_a_plain_run() {
+    let mut args = Args::with_defaults(10, MAP);
+    args.suppress_events = true;
+    let seeds = SubSeeds::derive(5);
+    let mut plain = load_world(MAP, &Default::default()).unwrap();
+    let mut ants = plain.place_colony(args.ants, args.queen_count(), args.placement, &mut seeds.placement_rng());
+    SimulationEngine::new(&plain, ants.len())
+        .with_seeds(seeds)
+        .run_simulation(&mut plain, &mut ants, &args, &mut seeds.movement_rng());
+
+    let mut world = load_world(MAP, &Default::default()).unwrap();
+    let summaries = run_campaign(&mut world, &args, &waves(&["10"]), 5, false, |_| {});
+    assert_eq!(summaries[0].survivors, plain.count_survivors());
+    assert_eq!(summaries[0].seed, 5);
+}
+
+#[test]
+fn waves_build_on_earlier_damage() {
+    let mut world = load_world(MAP, &Default::default()).unwrap();
+    let colonies = world.count_survivors();
+    let mut seen = 0;
+    let summaries = run_campaign(
+        &mut world,
+        &Args::with_defaults(0, MAP),
+        &waves(&["10", "10,max-moves=50", "10,placement=poisson"]),
+        9,
+        false,
+        |_| seen += 1,
+    );
+    assert_eq!(seen, 3);
+    let destroyed: usize = summaries.iter().map(|s| s.destroyed).sum();
+    assert_eq!(colonies - destroyed, world.count_survivors());
+    assert!(summaries.windows(2).all(|w| w[1].survivors <= w[0].survivors));
+    assert_eq!(summaries.iter().map(|s| s.seed).collect::<Vec<_>>(), [9, 10, 11]);
+}
+
+#[test]
+fn compacting_between_waves_keeps_the_same_survivor_count() {
+    let mut world = load_world(MAP, &Default::default()).unwrap();
+    let summaries = run_campaign(&mut world, &Args::with_defaults(0, MAP), &waves(&["12", "12"]), 3, true, |_| {});
+    assert_eq!(world.nodes().len(), summaries[1].survivors);
+}
+
+#[test]
+fn cli_prints_one_line_per_wave() {
+    let out = Command::cargo_bin("ants_code_challenge")
+        .unwrap()
+        .args(["campaign", "-m", MAP, "--seed", "1", "--wave", "8", "--wave", "8,max-moves=100"])
+        .output()
+        .unwrap();
+    assert!(out.status.success());
+    let stdout = String::from_utf8(out.stdout).unwrap();
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(lines.len(), 2, "{stdout}");
+    assert!(lines[0].starts_with("wave 1: ants=8 "));
+    assert!(lines[1].starts_with("wave 2: ants=8 "));
+}
+
+#[test]
+fn cli_rejects_bad_wave_specs() {
+    Command::cargo_bin("ants_code_challenge")
+        .unwrap()
+        .args(["campaign", "-m", MAP, "--wave", "8,speed=2"])
+        .assert()
+        .code(3)
+        .stderr(predicates::str::contains("unknown wave option 'speed=2'"));
+}
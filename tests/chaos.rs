@@ -0,0 +1,61 @@
+use ants_code_challenge::prelude::*;
+use ants_code_challenge::seeds::SubSeeds;
+use ants_code_challenge::simulation::DestructionCause;
+use assert_cmd::prelude::*;
+use predicates::prelude::*;
+use std::process::Command;
+
+mod common;
+use common::Run;
+
+/// A two-way ring of four colonies
+const RING: &str = "A east=B south=D\nB west=A south=C\nC north=B west=D\nD east=C north=A\n";
+
+/// One ant walking the ring, alone, so only meteors bring colonies down
+fn run(seed: u64, configure: impl FnOnce(&mut Args)) -> (World, SimulationEngine, Vec<Ant>) {
+    let (world, ants, engine) = Run::on(RING)
+        .at(&["A"])
+        .flags(&["--max-moves", "10", "--max-ticks", "3"])
+        .seed(seed)
+        .args(configure)
+        .engine(|engine| engine.with_seeds(SubSeeds::derive(seed)))
+        .run();
+    (world, engine, ants)
+}
+
+#[test]
+fn a_sure_chaos_strikes_while_the_run_lasts() {
+    let (world, engine, _) = run(7, |args| args.chaos = Some(1.0));
+
+    assert!(engine.meteors() >= 1);
+    assert_eq!(world.alive_count(), 4 - engine.meteors());
+    assert!(engine.events().iter().all(|e| e.cause == DestructionCause::Meteor));
+}
+
+#[test]
+fn no_chaos_leaves_a_lone_ant_harmless() {
+    let (world, engine, _) = run(7, |args| args.chaos = Some(0.0));
+
+    assert_eq!(engine.meteors(), 0);
+    assert_eq!(world.alive_count(), 4);
+}
+
+#[test]
+fn meteors_replay_from_the_seed() {
+    let strikes = |seed| {
+        let (_, engine, _) = run(seed, |args| args.chaos = Some(0.5));
+        engine.events().to_vec()
+    };
+
+    assert_eq!(strikes(11), strikes(11));
+}
+
+#[test]
+fn chaos_is_a_probability() {
+    Command::cargo_bin("ants_code_challenge")
+        .unwrap()
+        .args(["-n", "1", "-m", "unused.txt", "--chaos", "1.5"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("outside 0.0..=1.0"));
+}
@@ -0,0 +1,63 @@
+use assert_cmd::prelude::*;
+use predicates::prelude::*;
+use std::io::Write;
+use std::process::Command;
+use tempfile::NamedTempFile;
+
+const BIN: &str = "ants_code_challenge";
+
+fn map(src: &str) -> NamedTempFile {
+    let mut f = NamedTempFile::new().unwrap();
+    write!(f, "{src}").unwrap();
+    f
+}
+
+#[test]
+fn consistent_map_passes() {
+    let f = map("A east=B south=C\nB west=A\nC north=A\n");
+    Command::cargo_bin(BIN)
+        .unwrap()
+        .args(["check-directions", "-m", f.path().to_str().unwrap()])
+        .assert()
+        .success()
+        .stdout(predicate::str::ends_with(": 4 tunnels, no contradictions\n"));
+}
+
+#[test]
+fn contradictions_are_listed_with_lines_and_fail() {
+    // C is both east and west of A; B has no way back
+    let f = map("A east=C north=B\nC east=A\n");
+    let path = f.path().to_str().unwrap();
+    Command::cargo_bin(BIN)
+        .unwrap()
+        .args(["check-directions", "-m", path])
+        .assert()
+        .code(1)
+        .stdout(
+            [
+                "C is both east and west of A: A east=C (line 1, column 3) and C east=A (line 2, column 3)",
+                "A east=C (line 1, column 3) has no way back (C west=...)",
+                "A north=B (line 1, column 10) has no way back (B south=...)",
+                "C east=A (line 2, column 3) has no way back (A west=...)",
+            ]
+            .map(|line| format!("{path}: {line}\n"))
+            .concat(),
+        );
+}
+
+#[test]
+fn allow_one_way_and_json() {
+    let f = map("A north=B\nC east=D\nD west=E\n");
+    let output = Command::cargo_bin(BIN)
+        .unwrap()
+        .args(["check-directions", "-m", f.path().to_str().unwrap(), "--allow-one-way", "--json"])
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(1));
+    let found: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let found = found.as_array().unwrap();
+    assert_eq!(found.len(), 1);
+    assert_eq!(found[0]["kind"], "mismatched");
+    assert_eq!(found[0]["tunnel"]["at"], serde_json::json!({ "line": 2, "column": 3 }));
+    assert_eq!(found[0]["back"]["to"], "E");
+}
@@ -0,0 +1,108 @@
+use assert_cmd::prelude::*;
+use ants_code_challenge::prelude::{Ant, Args, NodeId, SimulationEngine, World};
+use ants_code_challenge::report::Report;
+use ants_code_challenge::seeds::SubSeeds;
+use ants_code_challenge::simulation::{DestructionCause, DestructionEvent};
+use ants_code_challenge::world::parse_world;
+use clap::Parser;
+use std::process::Command;
+use tempfile::NamedTempFile;
+
+mod common;
+use common::Run;
+
+const BIN: &str = "ants_code_challenge";
+
+/// Ants 0 and 1 meet at the dead end C on tick 1
+fn run_meeting(scheduling: &str, prob: &str) -> (World, Vec<Ant>, SimulationEngine) {
+    Run::on("A east=C\nB west=C\nC\n")
+        .at(&["A", "B"])
+        .scheduling(scheduling)
+        .flags(&["--collision-prob", prob])
+        .engine(|engine| engine.with_seeds(SubSeeds::derive(1)))
+        .run()
+}
+
+/// Seeded run on the medium map, returning survivors and events
+fn run_medium(extra: &[&str]) -> (usize, Vec<DestructionEvent>, usize) {
+    let mut argv = vec!["ants", "-n", "500", "-m", "files/hiveum_map_medium.txt", "--suppress-events"];
+    argv.extend_from_slice(extra);
+    let args = Args::parse_from(argv);
+    let (names, nodes) = parse_world(&args.map[0]).unwrap();
+    let mut world = World::new(names, nodes);
+    let seeds = SubSeeds::derive(7);
+    let mut ants = world.create_colony(args.ants, 0, &mut seeds.placement_rng());
+    let mut engine = SimulationEngine::new(&world, args.ants).with_seeds(seeds);
+    engine.run_simulation(&mut world, &mut ants, &args, &mut seeds.movement_rng());
+    (world.count_survivors(), engine.events().to_vec(), engine.near_misses())
+}
+
+#[test]
+fn zero_probability_turns_every_meeting_into_a_near_miss() {
+    for scheduling in ["simultaneous", "sequential"] {
+        let (world, ants, engine) = run_meeting(scheduling, "0");
+
+        assert_eq!(world.count_survivors(), 3, "{scheduling}");
+        assert!(ants.iter().all(|a| a.is_alive()), "{scheduling}");
+        assert!(ants.iter().all(|a| a.pos == NodeId::new(2)), "{scheduling}");
+        assert!(engine.near_misses() >= 1, "{scheduling}");
+        assert!(engine
+            .events()
+            .iter()
+            .all(|e| matches!(e.cause, DestructionCause::NearMiss { .. }) && e.node_id == NodeId::new(2)));
+    }
+}
+
+#[test]
+fn certain_collisions_match_the_default_model() {
+    let baseline = run_medium(&[]);
+    let (survivors, events, near_misses) = run_medium(&["--collision-prob", "1"]);
+
+    assert_eq!((survivors, events), (baseline.0, baseline.1));
+    assert_eq!(near_misses, 0);
+}
+
+#[test]
+fn near_misses_are_seeded_and_spare_colonies() {
+    let baseline = run_medium(&[]);
+    let first = run_medium(&["--collision-prob", "0.5"]);
+    let second = run_medium(&["--collision-prob", "0.5"]);
+
+    assert_eq!(first, second);
+    assert!(first.2 > 0);
+    assert!(first.0 > baseline.0);
+}
+
+#[test]
+fn report_counts_near_misses() -> Result<(), Box<dyn std::error::Error>> {
+    let report = NamedTempFile::new()?;
+    Command::cargo_bin(BIN)?
+        .args([
+            "-n", "500",
+            "-m", "files/hiveum_map_medium.txt",
+            "--seed", "7",
+            "--collision-prob", "0.5",
+            "--suppress-events",
+            "--report", report.path().to_str().unwrap(),
+        ])
+        .assert()
+        .success();
+
+    let report = Report::read_json(report.path().to_str().unwrap())?;
+    let logged = report
+        .timeline
+        .iter()
+        .filter(|e| matches!(e.cause, ants_code_challenge::report::TimelineCause::NearMiss { .. }))
+        .count();
+    assert!(report.near_misses.is_some_and(|n| n > 0 && n == logged));
+    Ok(())
+}
+
+#[test]
+fn rejects_probabilities_outside_unit_interval() -> Result<(), Box<dyn std::error::Error>> {
+    Command::cargo_bin(BIN)?
+        .args(["-n", "2", "-m", "files/hiveum_map_small.txt", "--collision-prob", "1.5"])
+        .assert()
+        .failure();
+    Ok(())
+}
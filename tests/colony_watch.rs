@@ -0,0 +1,74 @@
+use ants_code_challenge::prelude::*;
+use ants_code_challenge::simulation::{DestructionEvent, ScriptedStrategy, SimulationHooks};
+use ants_code_challenge::world::WorldView;
+use ants_code_challenge::Direction::{East, West};
+use assert_cmd::prelude::*;
+use predicates::prelude::*;
+use std::process::Command;
+use std::sync::{Arc, Mutex};
+
+mod common;
+use common::Run;
+
+#[derive(Default)]
+struct Seen {
+    events: Vec<DestructionEvent>,
+    arrivals: Vec<(u64, NodeId, u32)>,
+}
+
+#[derive(Clone, Default)]
+struct Recorder(Arc<Mutex<Seen>>);
+
+impl SimulationHooks for Recorder {
+    fn on_destruction(&mut self, event: &DestructionEvent, _world: WorldView<'_>) {
+        self.0.lock().unwrap().events.push(*event);
+    }
+
+    fn on_arrivals(&mut self, tick: u64, colony: NodeId, ants: u32, _world: WorldView<'_>) {
+        self.0.lock().unwrap().arrivals.push((tick, colony, ants));
+    }
+}
+
+/// A row of four colonies; ants 0 and 1 meet on B on tick 1, ant 2 walks to C
+const ROW: &str = "A east=B\nB west=A east=C\nC west=B east=D\nD west=C\n";
+
+fn run(watched: &[&str]) -> (World, SimulationEngine, Seen) {
+    let strategy = ScriptedStrategy::new().ant(0, [East]).ant(1, [West]).ant(2, [West]);
+    let recorder = Recorder::default();
+    let (world, _, engine) = Run::on(ROW)
+        .at(&["A", "C", "D"])
+        .flags(&["--max-moves", "1"])
+        .engine(|engine| engine.with_move_strategy(strategy).with_hooks(recorder.clone().watch(watched.iter().copied())))
+        .run();
+    let seen = std::mem::take(&mut *recorder.0.lock().unwrap());
+    (world, engine, seen)
+}
+
+#[test]
+fn a_watch_sees_its_colonies_only() {
+    let (world, engine, seen) = run(&["B", "C"]);
+
+    assert_eq!(engine.events().len(), 1);
+    assert_eq!(seen.events, engine.events());
+    // Ants 0 and 1 died arriving on B; only ant 2 made it
+    assert_eq!(seen.arrivals, [(1, world.find("C").unwrap(), 1)]);
+}
+
+#[test]
+fn quiet_colonies_report_nothing() {
+    let (world, _, seen) = run(&["A", "Nowhere"]);
+
+    assert!(seen.events.is_empty());
+    assert!(seen.arrivals.is_empty());
+    assert_eq!(world.alive_count(), 3);
+}
+
+#[test]
+fn watching_an_unknown_colony_fails() {
+    Command::cargo_bin("ants_code_challenge")
+        .unwrap()
+        .args(["-n", "1", "-m", "files/hiveum_map_small.txt", "--watch", "Kara,Nowhere"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("no colony named 'Nowhere' to watch"));
+}
@@ -0,0 +1,111 @@
+//! Fixtures shared by the integration tests that run a small map to the end
+#![allow(dead_code)]
+
+use ants_code_challenge::prelude::*;
+use ants_code_challenge::world::load_world_from_str;
+use clap::Parser;
+use std::time::Duration;
+
+/// One ant per `starts` colony, in order, run to the end under
+/// `scheduling` with the `extra` command-line flags
+pub fn run(map: &str, starts: &[&str], scheduling: &str, extra: &[&str]) -> (World, Vec<Ant>, SimulationEngine) {
+    Run::on(map).at(starts).scheduling(scheduling).flags(extra).run()
+}
+
+/// Places the ants of a [`Run`], with its RNG at hand
+type Placement<'a> = Box<dyn FnOnce(&World, &mut fastrand::Rng) -> Vec<Ant> + 'a>;
+
+/// A run of a map given inline, set up step by step: who stands where, the
+/// command-line flags, the seed (1 unless set) and the engine's extras.
+/// Events are suppressed and `-n` follows the ants placed.
+pub struct Run<'a> {
+    world: World,
+    ants: Placement<'a>,
+    flags: Vec<&'a str>,
+    seed: u64,
+    configure: Box<dyn FnOnce(&mut Args) + 'a>,
+    engine: Box<dyn FnOnce(SimulationEngine) -> SimulationEngine + 'a>,
+}
+
+impl<'a> Run<'a> {
+    pub fn on(map: &str) -> Self {
+        Self {
+            world: load_world_from_str(map, &Default::default()).unwrap(),
+            ants: Box::new(|_, _| Vec::new()),
+            flags: Vec::new(),
+            seed: 1,
+            configure: Box::new(|_| {}),
+            engine: Box::new(|engine| engine),
+        }
+    }
+
+    /// Change the world before anyone is placed on it
+    pub fn prepare(mut self, prepare: impl FnOnce(&mut World)) -> Self {
+        prepare(&mut self.world);
+        self
+    }
+
+    /// One worker per `starts` colony, ids in order
+    pub fn at(self, starts: &'a [&'a str]) -> Self {
+        self.ants(move |world, _| {
+            starts.iter().enumerate().map(|(i, name)| Ant::new(i as u32, world.find(name).unwrap())).collect()
+        })
+    }
+
+    /// One worker per colony id in `starts`, ids in order
+    pub fn at_ids(self, starts: &'a [usize]) -> Self {
+        self.ants(move |_, _| starts.iter().enumerate().map(|(i, &at)| Ant::new(i as u32, NodeId::from_index(at))).collect())
+    }
+
+    /// Ants of any kind, placed with the run's RNG at hand
+    pub fn ants(mut self, ants: impl FnOnce(&World, &mut fastrand::Rng) -> Vec<Ant> + 'a) -> Self {
+        self.ants = Box::new(ants);
+        self
+    }
+
+    pub fn flags(mut self, flags: &[&'a str]) -> Self {
+        self.flags.extend_from_slice(flags);
+        self
+    }
+
+    pub fn scheduling(self, scheduling: &'a str) -> Self {
+        self.flags(&["--scheduling", scheduling])
+    }
+
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    /// Set what has no command-line flag, once the flags are parsed
+    pub fn args(mut self, configure: impl FnOnce(&mut Args) + 'a) -> Self {
+        self.configure = Box::new(configure);
+        self
+    }
+
+    /// Add hooks, a strategy or rules to the engine
+    pub fn engine(mut self, engine: impl FnOnce(SimulationEngine) -> SimulationEngine + 'a) -> Self {
+        self.engine = Box::new(engine);
+        self
+    }
+
+    pub fn run(self) -> (World, Vec<Ant>, SimulationEngine) {
+        let (world, ants, engine, _) = self.run_timed();
+        (world, ants, engine)
+    }
+
+    /// As [`Run::run`], with the latency `run_simulation` measured
+    pub fn run_timed(self) -> (World, Vec<Ant>, SimulationEngine, Duration) {
+        let Run { mut world, ants, flags, seed, configure, engine } = self;
+        let mut rng = fastrand::Rng::with_seed(seed);
+        let mut ants = ants(&world, &mut rng);
+        let n = ants.len().to_string();
+        let mut argv = vec!["ants", "-n", &n, "-m", "unused", "--suppress-events"];
+        argv.extend_from_slice(&flags);
+        let mut args = Args::parse_from(argv);
+        configure(&mut args);
+        let mut engine = engine(SimulationEngine::new(&world, ants.len()));
+        let latency = engine.run_simulation(&mut world, &mut ants, &args, &mut rng);
+        (world, ants, engine, latency)
+    }
+}
@@ -0,0 +1,28 @@
+use ants_code_challenge::prelude::*;
+use ants_code_challenge::world::load_world;
+
+#[test]
+fn run_compact_and_run_again() {
+    let mut world = load_world("files/hiveum_map_medium.txt", &Default::default()).unwrap();
+    let mut args = Args::with_defaults(2000, "unused");
+    args.suppress_events = true;
+    let mut rng = fastrand::Rng::with_seed(8);
+
+    let mut ants = world.create_ants(args.ants, &mut rng);
+    SimulationEngine::new(&world, ants.len()).run_simulation(&mut world, &mut ants, &args, &mut rng);
+    let survivors = world.count_survivors();
+    let names: Vec<String> = world.alive_nodes().map(|c| c.name().to_string()).collect();
+
+    let map = world.compact();
+    assert!(map.removed() > 0);
+    assert_eq!(world.nodes().len(), survivors);
+    assert_eq!(world.count_survivors(), survivors);
+    let kept: Vec<&str> = world.colonies().map(|c| c.name()).collect();
+    assert_eq!(kept, names);
+    // Surviving ants stand on kept colonies
+    assert!(ants.iter().filter(|a| a.is_alive()).all(|a| map.get(a.pos).is_some()));
+
+    let mut ants = world.create_ants(1000, &mut rng);
+    SimulationEngine::new(&world, ants.len()).run_simulation(&mut world, &mut ants, &args, &mut rng);
+    assert!(world.count_survivors() < survivors);
+}
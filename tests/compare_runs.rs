@@ -0,0 +1,49 @@
+use assert_cmd::prelude::*;
+use predicates::prelude::*;
+use std::process::Command;
+use tempfile::TempDir;
+
+const BIN: &str = "ants_code_challenge";
+const MAP: &str = "files/hiveum_map_small.txt";
+
+fn run_report(dir: &TempDir, name: &str, seed: &str) -> Result<String, Box<dyn std::error::Error>> {
+    let path = dir.path().join(name).to_str().unwrap().to_string();
+    Command::cargo_bin(BIN)?
+        .args(["-n", "20", "-m", MAP, "--seed", seed, "--suppress-events", "--report", &path])
+        .assert()
+        .success();
+    Ok(path)
+}
+
+#[test]
+fn same_seed_runs_compare_identical() -> Result<(), Box<dyn std::error::Error>> {
+    let dir = TempDir::new()?;
+    let a = run_report(&dir, "a.json", "7")?;
+    let b = run_report(&dir, "b.json", "7")?;
+    let out = dir.path().join("cmp.json");
+
+    Command::cargo_bin(BIN)?
+        .args(["compare-runs", &a, &b, "--check", "-o", out.to_str().unwrap()])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("outcome:    identical"));
+
+    let cmp: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(out)?)?;
+    assert_eq!(cmp["only_a"], serde_json::json!([]));
+    assert_eq!(cmp["survivors"]["a"], cmp["survivors"]["b"]);
+    Ok(())
+}
+
+#[test]
+fn check_fails_when_outcomes_differ() -> Result<(), Box<dyn std::error::Error>> {
+    let dir = TempDir::new()?;
+    let a = run_report(&dir, "a.json", "1")?;
+    let b = run_report(&dir, "b.json", "2")?;
+
+    Command::cargo_bin(BIN)?
+        .args(["compare-runs", &a, &b, "--check"])
+        .assert()
+        .code(1)
+        .stdout(predicate::str::contains("outcome:    different"));
+    Ok(())
+}
@@ -0,0 +1,48 @@
+use ants_code_challenge::world::parse_world;
+use std::io::Write;
+
+const MAP: &str = "A north=B\nB south=A east=C\nC\n";
+
+fn temp_with_suffix(suffix: &str, bytes: &[u8]) -> tempfile::NamedTempFile {
+    let mut f = tempfile::Builder::new().suffix(suffix).tempfile().unwrap();
+    f.write_all(bytes).unwrap();
+    f.flush().unwrap();
+    f
+}
+
+#[cfg(feature = "gzip")]
+#[test]
+fn gzip_map_parses_like_plain_text() {
+    let mut enc = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+    enc.write_all(MAP.as_bytes()).unwrap();
+    let f = temp_with_suffix(".gz", &enc.finish().unwrap());
+
+    let (names, nodes) = parse_world(f.path().to_str().unwrap()).unwrap();
+    assert_eq!(names, ["A", "B", "C"]);
+    assert_eq!(nodes.len(), 3);
+}
+
+#[cfg(feature = "zstd")]
+#[test]
+fn zstd_map_parses_like_plain_text() {
+    let f = temp_with_suffix(".zst", &zstd::encode_all(MAP.as_bytes(), 0).unwrap());
+
+    let (names, _) = parse_world(f.path().to_str().unwrap()).unwrap();
+    assert_eq!(names, ["A", "B", "C"]);
+}
+
+#[cfg(not(feature = "gzip"))]
+#[test]
+fn gzip_without_feature_is_a_clear_error() {
+    let f = temp_with_suffix(".gz", b"\x1f\x8b");
+    let err = parse_world(f.path().to_str().unwrap()).unwrap_err();
+    assert!(err.to_string().contains("--features gzip"), "{err}");
+}
+
+#[test]
+fn plain_map_is_unaffected() {
+    let mut f = tempfile::NamedTempFile::new().unwrap();
+    f.write_all(MAP.as_bytes()).unwrap();
+    let (names, _) = parse_world(f.path().to_str().unwrap()).unwrap();
+    assert_eq!(names.len(), 3);
+}
@@ -0,0 +1,161 @@
+use ants_code_challenge::prelude::{Ant, Args, NodeId, SimulationEngine, World};
+use ants_code_challenge::world::parser::parse_world_from_str;
+use clap::Parser;
+use std::sync::atomic::AtomicBool;
+use std::sync::{mpsc, Arc};
+use tempfile::NamedTempFile;
+
+const BIN: &str = "ants_code_challenge";
+
+/// Two loops so the ants never meet; each ant has 6 moves
+fn run(commands: &[&str]) -> (SimulationEngine, String) {
+    let (names, nodes) = parse_world_from_str("A east=B\nB west=A\nC east=D\nD west=C\n");
+    let mut world = World::new(names, nodes);
+    let mut ants = vec![Ant::new(0, NodeId::new(0)), Ant::new(1, NodeId::new(2))];
+    let args = Args::parse_from(["ants", "-n", "2", "-m", "unused", "--max-moves", "6", "--suppress-events"]);
+    let (tx, rx) = mpsc::channel();
+    for c in commands {
+        tx.send(c.to_string()).unwrap();
+    }
+    drop(tx);
+
+    let mut engine = SimulationEngine::new(&world, ants.len());
+    let mut status = Vec::new();
+    engine.run_interactive(&mut world, &mut ants, &args, &mut fastrand::Rng::with_seed(1), &rx, &mut status);
+    (engine, String::from_utf8(status).unwrap())
+}
+
+#[test]
+fn step_runs_a_fixed_number_of_ticks_then_pauses() {
+    let (engine, status) = run(&["step 2", "step", "quit"]);
+
+    assert_eq!(engine.ticks(), 3);
+    assert!(engine.interrupted());
+    assert!(status.contains("paused at tick 2\n"));
+    assert!(status.contains("paused at tick 3\n"));
+}
+
+#[test]
+fn resume_runs_to_the_end() {
+    let (engine, status) = run(&["resume"]);
+
+    assert_eq!(engine.ticks(), 6);
+    assert!(!engine.interrupted());
+    assert!(status.contains("run finished after 6 ticks"));
+}
+
+#[test]
+fn closed_input_while_paused_quits() {
+    let (engine, _) = run(&["step 1"]);
+
+    assert_eq!(engine.ticks(), 1);
+    assert!(engine.interrupted());
+}
+
+#[test]
+fn bad_commands_are_reported_and_ignored() {
+    let (engine, status) = run(&["fly", "step x", "step 1", "quit"]);
+
+    assert_eq!(engine.ticks(), 1);
+    assert!(status.contains("error: unknown command 'fly'"));
+    assert!(status.contains("error: invalid tick count 'x'"));
+}
+
+#[test]
+fn interactive_cli_dumps_the_world() -> Result<(), Box<dyn std::error::Error>> {
+    let dump = NamedTempFile::new()?;
+    let script = format!("step 1\ndump {}\nresume\n", dump.path().display());
+
+    assert_cmd::Command::cargo_bin(BIN)?
+        .args(["-n", "1", "-m", "files/hiveum_map_small.txt", "--interactive", "--suppress-events"])
+        .write_stdin(script)
+        .assert()
+        .success();
+
+    let dumped = std::fs::read_to_string(dump.path())?;
+    assert!(!dumped.is_empty());
+    Ok(())
+}
+
+/// Interactive run on the two-loop map, with `map` on disk as the file to reload
+fn run_with_map_file(map: &str, commands: &[&str], watch: bool) -> (World, Vec<Ant>, String) {
+    let file = NamedTempFile::new().unwrap();
+    std::fs::write(file.path(), map).unwrap();
+    let (names, nodes) = parse_world_from_str("A east=B\nB west=A\nC east=D\nD west=C\n");
+    let mut world = World::new(names, nodes);
+    let mut ants = vec![Ant::new(0, NodeId::new(0)), Ant::new(1, NodeId::new(2))];
+    let path = file.path().to_str().unwrap();
+    let args = Args::parse_from(["ants", "-n", "2", "-m", path, "--max-moves", "6", "--suppress-events"]);
+    let (tx, rx) = mpsc::channel();
+    for c in commands {
+        tx.send(c.to_string()).unwrap();
+    }
+    drop(tx);
+
+    let mut engine = SimulationEngine::new(&world, ants.len());
+    if watch {
+        engine = engine.with_map_watch(Arc::new(AtomicBool::new(true)));
+    }
+    let mut status = Vec::new();
+    engine.run_interactive(&mut world, &mut ants, &args, &mut fastrand::Rng::with_seed(1), &rx, &mut status);
+    (world, ants, String::from_utf8(status).unwrap())
+}
+
+#[test]
+fn reload_keeps_ants_on_colonies_by_name() {
+    // B is gone and C/D swapped ids in the edited map
+    let (world, ants, status) = run_with_map_file("D west=C east=E\nC east=D\nE west=D\n", &["step 1", "reload", "quit"], false);
+
+    assert!(status.contains("map reloaded at tick 1: 3 colonies, 1 ants kept, 1 lost\n"), "{status}");
+    assert_eq!(world.names().len(), 3);
+    assert!(!ants[0].is_alive());
+    assert_eq!(world.get_colony_name(ants[1].pos), "D");
+    assert_eq!(world.get_colony_name(ants[1].home), "C");
+}
+
+#[test]
+fn failed_reload_keeps_the_old_map() {
+    let (world, ants, status) = run_with_map_file("A =B\n", &["reload", "step 1", "quit"], false);
+
+    assert!(status.contains("error: cannot reload"), "{status}");
+    assert_eq!(world.count_survivors(), 4);
+    assert!(ants.iter().all(|a| a.is_alive()));
+}
+
+#[test]
+fn map_watch_reloads_while_paused() {
+    let (world, _, status) = run_with_map_file("A east=B\nB west=A\nC\n", &["quit"], true);
+
+    assert!(status.contains("map reloaded at tick 0: 3 colonies, 2 ants kept, 0 lost\n"), "{status}");
+    assert_eq!(world.names().len(), 3);
+}
+
+#[cfg(feature = "watch")]
+#[test]
+fn watch_map_cli_reloads_on_save() -> Result<(), Box<dyn std::error::Error>> {
+    use std::io::{BufRead, BufReader, Write};
+    use std::process::{Command, Stdio};
+
+    let dir = tempfile::tempdir()?;
+    let map = dir.path().join("map.txt");
+    std::fs::write(&map, "A east=B\nB west=A\n")?;
+    let mut child = Command::new(env!("CARGO_BIN_EXE_ants_code_challenge"))
+        .args(["-n", "1", "--interactive", "--watch-map", "--suppress-events", "-m"])
+        .arg(&map)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .spawn()?;
+    let mut stderr = BufReader::new(child.stderr.take().unwrap()).lines();
+    assert!(stderr.next().unwrap()?.contains("paused at tick 0"));
+
+    // Saved the way editors do: write a new file, then move it over the map
+    let saved = dir.path().join("map.txt.new");
+    std::fs::write(&saved, "A east=B\nB west=A east=C\nC west=B\n")?;
+    std::fs::rename(&saved, &map)?;
+    let reloaded = stderr.next().unwrap()?;
+    writeln!(child.stdin.take().unwrap(), "quit")?;
+    child.wait()?;
+    assert!(reloaded.contains("map reloaded at tick 0: 3 colonies, 1 ants kept, 0 lost"), "{reloaded}");
+    Ok(())
+}
@@ -0,0 +1,64 @@
+use ants_code_challenge::prelude::*;
+use ants_code_challenge::report::StartCasualties;
+
+mod common;
+use common::Run;
+
+/// Ants placed on the named colonies, resolved at t=0 only
+fn start(map: &str, starts: &[&str], configure: impl FnOnce(&mut Args)) -> (World, SimulationEngine) {
+    let (world, _, engine) = Run::on(map)
+        .at(starts)
+        .seed(2)
+        .args(|args| {
+            args.max_ticks = Some(0);
+            configure(args);
+        })
+        .run();
+    (world, engine)
+}
+
+#[test]
+fn crowded_and_sparse_starts_fall_alike() {
+    let crowded = "A east=B\nB west=A east=C\nC west=B\n".to_string();
+    // The same three colonies among a hundred empty ones
+    let sparse = crowded.clone() + &(0..100).map(|i| format!("X{i}\n")).collect::<String>();
+    let starts = ["C", "C", "A", "A", "B"];
+
+    let (_, dense) = start(&crowded, &starts, |_| {});
+    let (_, spread) = start(&sparse, &starts, |_| {});
+    assert_eq!(dense.events(), spread.events());
+    // Colony id order, whatever order the ants were placed in
+    assert_eq!(dense.events().iter().map(|e| e.node_id.index()).collect::<Vec<_>>(), [0, 2]);
+}
+
+#[test]
+fn crowded_and_sparse_ticks_fall_alike() {
+    // Ants 0/1 head for B, ants 2/3 for E, all in tick 1
+    let crowded = "D east=E\nE\nF west=E\nA east=B\nB\nC west=B\n".to_string();
+    let sparse = crowded.clone() + &(0..100).map(|i| format!("X{i}\n")).collect::<String>();
+    let starts = ["A", "C", "D", "F"];
+
+    let (_, dense) = start(&crowded, &starts, |args| args.max_ticks = None);
+    let (_, spread) = start(&sparse, &starts, |args| args.max_ticks = None);
+    assert_eq!(dense.events(), spread.events());
+    // Arrival order, as a sparse tick resolves them
+    assert_eq!(dense.events().iter().map(|e| (e.tick, e.node_id.index())).collect::<Vec<_>>(), [(1, 4), (1, 1)]);
+}
+
+#[test]
+fn start_casualties_count_fights_and_blasts() {
+    let (world, engine) = start("A east=B\nB west=A east=C\nC west=B\nD\n", &["A", "A", "B", "C", "D"], |args| {
+        args.blast_radius = 1;
+    });
+
+    // A's fight takes B down with it; C and D stand
+    assert_eq!(engine.start_casualties(), StartCasualties { colonies: 2, ants: 3, survivors: 2 });
+    assert_eq!(world.alive_count(), 2);
+}
+
+#[test]
+fn a_peaceful_start_costs_nothing() {
+    let (_, engine) = start("A east=B\nB west=A\n", &["A", "B"], |_| {});
+
+    assert_eq!(engine.start_casualties(), StartCasualties { colonies: 0, ants: 0, survivors: 2 });
+}
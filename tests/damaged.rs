@@ -0,0 +1,64 @@
+use ants_code_challenge::prelude::*;
+use ants_code_challenge::world::{load_world_from_str, ParseOptions};
+use assert_cmd::Command;
+use predicates::prelude::*;
+use std::io::Write;
+use tempfile::NamedTempFile;
+
+const BIN: &str = "ants_code_challenge";
+
+#[test]
+fn ants_never_enter_colonies_missing_from_a_damaged_map() {
+    // B was destroyed in the previous run; only its name survives in A's tunnel
+    let options = ParseOptions { damaged: true, ..Default::default() };
+    let mut world = load_world_from_str("A east=B south=C\nC north=A\n", &options).unwrap();
+    let mut rng = fastrand::Rng::with_seed(3);
+    let mut ants = world.create_ants(2, &mut rng);
+    let mut args = Args::with_defaults(2, "unused");
+    args.suppress_events = true;
+    args.collision_prob = Some(0.0);
+    args.max_moves = 50;
+    let mut engine = SimulationEngine::new(&world, ants.len());
+    let ticks: Vec<_> = engine.iter_ticks(&mut world, &mut ants, &args, &mut rng).collect();
+
+    assert_eq!(ticks.len(), 50);
+    let b = NodeId::new(2);
+    assert!(ticks.iter().flat_map(|t| &t.positions).all(|&p| p != Some(b)));
+}
+
+#[test]
+fn chained_run_reports_what_was_lost() {
+    let mut map = NamedTempFile::new().unwrap();
+    map.write_all(b"A east=B\nB west=A east=C\nC west=B east=D\nD west=C\n").unwrap();
+    let final_world = NamedTempFile::new().unwrap();
+    // Four ants on four colonies fight before the moves run out
+    Command::cargo_bin(BIN)
+        .unwrap()
+        .args(["-n", "4", "--seed", "2", "--suppress-events", "-m"])
+        .arg(map.path())
+        .arg(format!("--print-world={}", final_world.path().display()))
+        .assert()
+        .success();
+    let survivors = std::fs::read_to_string(final_world.path()).unwrap().lines().count();
+    assert!(survivors < 4);
+
+    Command::cargo_bin(BIN)
+        .unwrap()
+        .args(["-n", "0", "--suppress-events", "--damaged", "-m"])
+        .arg(final_world.path())
+        .arg("--original")
+        .arg(map.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(format!("Lost before this run: {} colonies", 4 - survivors)))
+        .stderr(predicate::str::is_empty());
+}
+
+#[test]
+fn original_requires_damaged() {
+    Command::cargo_bin(BIN)
+        .unwrap()
+        .args(["-n", "1", "-m", "a.txt", "--original", "b.txt"])
+        .assert()
+        .failure();
+}
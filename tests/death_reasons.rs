@@ -0,0 +1,76 @@
+use ants_code_challenge::prelude::*;
+use ants_code_challenge::{DeathCounts, DeathReason};
+use assert_cmd::Command;
+use std::io::Write;
+
+mod common;
+use common::run;
+
+fn deaths(ants: &[Ant]) -> Vec<Option<DeathReason>> {
+    ants.iter().map(|a| a.death).collect()
+}
+
+/// A and C lead only into B, E only into D and F is a dead end; B's
+/// tunnels reach D and F, so a blast from B takes them down
+const HUB: &str = "A east=B\nB west=A east=C north=D south=F\nC west=B\nD south=B west=E\nE east=D\nF\n";
+
+#[test]
+fn ants_sharing_a_landing_colony_die_at_start() {
+    for scheduling in ["simultaneous", "sequential"] {
+        let (_, ants, _) = run("A east=B\nB west=A\n", &["A", "A"], scheduling, &[]);
+        assert_eq!(deaths(&ants), [Some(DeathReason::CollisionAtStart); 2], "{scheduling}");
+    }
+}
+
+#[test]
+fn fighters_die_of_collision_and_bystanders_with_their_colony() {
+    for scheduling in ["simultaneous", "sequential"] {
+        let (_, ants, _) = run(HUB, &["A", "C", "F"], scheduling, &["--blast-radius", "1", "--max-moves", "1"]);
+        assert_eq!(
+            deaths(&ants),
+            [Some(DeathReason::Collision), Some(DeathReason::Collision), Some(DeathReason::VanishedWithColony)],
+            "{scheduling}"
+        );
+    }
+}
+
+#[test]
+fn an_ant_heading_into_a_blasted_colony_steps_into_ruins() {
+    let (_, ants, _) = run(HUB, &["A", "C", "E"], "simultaneous", &["--blast-radius", "1", "--max-moves", "1"]);
+    assert_eq!(ants[2].death, Some(DeathReason::SteppedIntoRuins));
+}
+
+#[test]
+fn trapped_and_repelled_ants_say_so() {
+    for scheduling in ["simultaneous", "sequential"] {
+        let (_, ants, _) = run("A\nB east=C\nC west=B\n", &["A", "B"], scheduling, &["--trapped-policy", "die", "--max-moves", "4"]);
+        assert_eq!(deaths(&ants), [Some(DeathReason::Trapped), None], "{scheduling}");
+
+        let (_, ants, _) = run("A east=F\nF fortified\nC east=D\nD west=C\n", &["A", "C"], scheduling, &["--max-moves", "4"]);
+        assert_eq!(deaths(&ants), [Some(DeathReason::Repelled), None], "{scheduling}");
+    }
+}
+
+#[test]
+fn report_and_summary_tally_the_reasons() {
+    let mut f = tempfile::NamedTempFile::new().unwrap();
+    write!(f, "A east=B\nB west=A\n").unwrap();
+    let report = tempfile::NamedTempFile::new().unwrap();
+
+    let output = Command::cargo_bin("ants_code_challenge")
+        .unwrap()
+        .args(["-n", "4", "--seed", "1", "--suppress-events", "-m"])
+        .arg(f.path())
+        .arg("--report")
+        .arg(report.path())
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.contains("collision_at_start="), "{stdout}");
+
+    let json: serde_json::Value = serde_json::from_reader(std::fs::File::open(report.path()).unwrap()).unwrap();
+    let counts: DeathCounts = serde_json::from_value(json["deaths"].clone()).unwrap();
+    assert!(counts.collision_at_start >= 2, "{json}");
+    assert_eq!(counts.total(), json["outcomes"]["died"].as_u64().unwrap() as usize);
+}
@@ -0,0 +1,36 @@
+use ants_code_challenge::destruction_freq::DestructionFrequency;
+use assert_cmd::prelude::*;
+use std::process::Command;
+use tempfile::TempDir;
+
+const BIN: &str = "ants_code_challenge";
+const MAP: &str = "files/hiveum_map_small.txt";
+
+#[test]
+fn sweep_writes_destruction_frequency_per_colony() -> Result<(), Box<dyn std::error::Error>> {
+    let dir = TempDir::new()?;
+    let csv = dir.path().join("freq.csv");
+    let json = dir.path().join("freq.json");
+
+    Command::cargo_bin(BIN)?
+        .args(["sweep", "-m", MAP, "-n", "10,20", "--seeds", "1..5", "--max-moves", "200"])
+        .args(["--destruction-csv", csv.to_str().unwrap(), "--destruction-json", json.to_str().unwrap()])
+        .assert()
+        .success();
+
+    let entries: Vec<DestructionFrequency> = serde_json::from_str(&std::fs::read_to_string(&json)?)?;
+    assert_eq!(entries.len(), 28);
+    assert!(entries.iter().all(|e| e.runs == 10 && e.destroyed <= 10));
+    assert!(entries.iter().any(|e| e.destroyed > 0));
+    for e in &entries {
+        assert_eq!(e.fraction, e.destroyed as f64 / 10.0);
+    }
+
+    let csv = std::fs::read_to_string(&csv)?;
+    let mut lines = csv.lines();
+    assert_eq!(lines.next(), Some(DestructionFrequency::CSV_HEADER));
+    let first = &entries[0];
+    assert_eq!(lines.next().unwrap(), format!("{},{},10,{:.6}", first.colony, first.destroyed, first.fraction));
+    assert_eq!(lines.count(), 27);
+    Ok(())
+}
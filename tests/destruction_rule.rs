@@ -0,0 +1,81 @@
+use ants_code_challenge::prelude::*;
+use ants_code_challenge::simulation::{MinAnts, Occupancy};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+mod common;
+use common::Run;
+
+/// A and C lead only into B, where their ants meet on tick 1
+const PINCER: &str = "A east=B\nB west=A east=C\nC west=B\n";
+
+fn run(scheduling: &str, starts: &[usize], engine: impl FnOnce(SimulationEngine) -> SimulationEngine) -> (World, Vec<Ant>) {
+    run_on(PINCER, "1", scheduling, starts, engine)
+}
+
+/// Ants on the colonies of id `starts`, with `max_moves` each, run to the end
+fn run_on(
+    map: &str,
+    max_moves: &str,
+    scheduling: &str,
+    starts: &[usize],
+    engine: impl FnOnce(SimulationEngine) -> SimulationEngine,
+) -> (World, Vec<Ant>) {
+    let (world, ants, _) =
+        Run::on(map).at_ids(starts).scheduling(scheduling).flags(&["--max-moves", max_moves]).engine(engine).run();
+    (world, ants)
+}
+
+#[test]
+fn min_ants_two_is_the_default() {
+    for scheduling in ["simultaneous", "sequential"] {
+        let (world, ants) = run(scheduling, &[0, 2], |e| e.with_destruction_rule(MinAnts::default()));
+        assert!(!world.is_alive(NodeId::from_index(1)), "{scheduling}");
+        assert!(ants.iter().all(|a| !a.is_alive()), "{scheduling}");
+    }
+}
+
+#[test]
+fn a_higher_threshold_spares_pairs() {
+    for scheduling in ["simultaneous", "sequential"] {
+        let (world, ants) = run(scheduling, &[0, 2], |e| e.with_destruction_rule(MinAnts(3)));
+        assert_eq!(world.alive_count(), 3, "{scheduling}");
+        assert!(ants.iter().all(|a| a.is_alive() && a.pos == NodeId::from_index(1)), "{scheduling}");
+    }
+
+    // Three ants arriving at once are enough
+    let (world, _) = run("simultaneous", &[0, 2, 0], |e| e.with_destruction_rule(MinAnts(3)));
+    assert!(!world.is_alive(NodeId::from_index(1)));
+}
+
+#[test]
+fn teams_only_fight_each_other() {
+    // Even ids are one team, odd ids the other
+    let rivals = |o: &Occupancy| o.ant1 % 2 != o.ant2 % 2;
+    let (world, _) = run("simultaneous", &[0, 2], |e| e.with_destruction_rule(rivals));
+    assert!(!world.is_alive(NodeId::from_index(1)));
+
+    // Ants 0 and 2 share a team: they meet at t=0 on A and leave it standing
+    let (world, _) = run("simultaneous", &[0, 2, 0], |e| e.with_destruction_rule(rivals));
+    assert!(world.is_alive(NodeId::from_index(0)));
+}
+
+#[test]
+fn stateful_rules_see_every_meeting() {
+    // Two ants walk a two-colony loop together; colonies have 2 hit points
+    let meetings = Arc::new(Mutex::new(Vec::new()));
+    let log = meetings.clone();
+    let mut hits: HashMap<NodeId, u32> = HashMap::new();
+    let rule = move |o: &Occupancy| {
+        log.lock().unwrap().push((o.tick, o.colony.index(), o.ants));
+        let hp = hits.entry(o.colony).or_insert(2);
+        *hp -= 1;
+        *hp == 0
+    };
+    let (world, ants) = run_on("A east=B\nB west=A\n", "2", "simultaneous", &[0, 0], |e| e.with_destruction_rule(rule));
+
+    // Wounded on A at t=0 and on B at t=1, A falls on the way back
+    assert_eq!(*meetings.lock().unwrap(), [(0, 0, 2), (1, 1, 2), (2, 0, 2)]);
+    assert!(!world.is_alive(NodeId::from_index(0)) && world.is_alive(NodeId::from_index(1)));
+    assert!(ants.iter().all(|a| !a.is_alive()));
+}
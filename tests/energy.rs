@@ -0,0 +1,63 @@
+use ants_code_challenge::AntOutcomes;
+use assert_cmd::Command;
+use std::io::Write;
+
+mod common;
+use common::run;
+
+/// Separate two-colony loops; only A-B and E-F have food
+const LOOPS: &str = "A east=B\nB west=A food\nC east=D\nD west=C\nE east=F\nF west=E food\nG east=H\nH west=G\n";
+
+#[test]
+fn ants_starve_once_their_energy_is_spent() {
+    for scheduling in ["simultaneous", "sequential"] {
+        let (_, ants, _) = run(LOOPS, &["C", "G"], scheduling, &["--energy", "3", "--max-moves", "10"]);
+        for ant in &ants {
+            assert!(ant.is_starved() && !ant.is_alive(), "{scheduling}");
+            assert_eq!((ant.moves, ant.energy), (3, 0), "{scheduling}");
+        }
+        assert_eq!(AntOutcomes::of(&ants), AntOutcomes { starved: 2, ..Default::default() }, "{scheduling}");
+    }
+}
+
+#[test]
+fn food_refills_energy() {
+    for scheduling in ["simultaneous", "sequential"] {
+        let (_, ants, _) = run(LOOPS, &["A", "C", "E"], scheduling, &["--energy", "2", "--max-moves", "10"]);
+        assert!(ants[1].is_starved(), "{scheduling}");
+        for fed in [&ants[0], &ants[2]] {
+            assert!(fed.is_alive(), "{scheduling}");
+            assert_eq!(fed.moves, 10, "{scheduling}");
+        }
+        let outcomes = AntOutcomes::of(&ants);
+        assert_eq!((outcomes.max_moves, outcomes.starved, outcomes.died), (2, 1, 0), "{scheduling}");
+    }
+}
+
+#[test]
+fn energy_is_unlimited_by_default() {
+    let (_, ants, _) = run(LOOPS, &["C", "G"], "simultaneous", &["--max-moves", "10"]);
+    assert!(ants.iter().all(|a| a.is_alive() && a.moves == 10 && !a.is_starved()));
+}
+
+#[test]
+fn summary_and_report_count_starved_ants() {
+    let mut f = tempfile::NamedTempFile::new().unwrap();
+    write!(f, "{LOOPS}").unwrap();
+    let report = tempfile::NamedTempFile::new().unwrap();
+
+    let output = Command::cargo_bin("ants_code_challenge")
+        .unwrap()
+        .args(["-n", "3", "--seed", "1", "--energy", "1", "--collision-prob", "0", "--suppress-events", "-m"])
+        .arg(f.path())
+        .arg("--report")
+        .arg(report.path())
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.contains("starved="), "{stdout}");
+
+    let json: serde_json::Value = serde_json::from_reader(std::fs::File::open(report.path()).unwrap()).unwrap();
+    assert!(json["outcomes"]["starved"].as_u64().unwrap() >= 1, "{json}");
+}
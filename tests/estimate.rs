@@ -0,0 +1,60 @@
+use assert_cmd::prelude::*;
+use predicates::prelude::*;
+use std::io::Write;
+use std::process::Command;
+use tempfile::NamedTempFile;
+
+const BIN: &str = "ants_code_challenge";
+
+fn map() -> NamedTempFile {
+    let mut f = NamedTempFile::new().unwrap();
+    write!(f, "A east=B\nB west=A east=C\nC west=B\n").unwrap();
+    f
+}
+
+#[test]
+fn prints_one_estimate_line() {
+    let f = map();
+    Command::cargo_bin(BIN)
+        .unwrap()
+        .args(["estimate", "-n", "2", "--max-moves", "50", "--seed", "1", "-m", f.path().to_str().unwrap()])
+        .assert()
+        .success()
+        .stdout(predicate::str::starts_with("estimate ants=2 colonies=3 memory_bytes="))
+        .stdout(predicate::str::contains("projected_ticks="));
+}
+
+#[test]
+fn json_has_the_memory_breakdown() {
+    let f = map();
+    let output = Command::cargo_bin(BIN)
+        .unwrap()
+        .args(["estimate", "-n", "2", "--seed", "1", "--json", "-m", f.path().to_str().unwrap()])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json["colonies"], 3);
+    assert!(json["memory"]["ants"].as_u64().unwrap() > 0);
+    assert!(json["projected_ticks"].as_u64().unwrap() >= json["sampled_ticks"].as_u64().unwrap());
+}
+
+#[test]
+fn over_a_limit_refuses_to_run_without_force() {
+    let f = map();
+    let path = f.path().to_str().unwrap();
+    Command::cargo_bin(BIN)
+        .unwrap()
+        .args(["estimate", "-n", "2", "--seed", "1", "--memory-limit", "1", "--run", "-m", path])
+        .assert()
+        .code(4)
+        .stderr(predicate::str::contains("over the --memory-limit of 1 bytes"))
+        .stderr(predicate::str::contains("pass --force to run anyway"));
+
+    Command::cargo_bin(BIN)
+        .unwrap()
+        .args(["estimate", "-n", "2", "--seed", "1", "--memory-limit", "1", "--run", "--force", "-m", path])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("refusing").not());
+}
@@ -0,0 +1,61 @@
+use ants_code_challenge::prelude::*;
+use assert_cmd::Command;
+use std::io::Write;
+
+mod common;
+use common::Run;
+
+/// Two fights in tick 1: ants 0 and 1 meet on E (id 4), then 2 and 3 on B (id 1)
+const TWO_FIGHTS: &str = "D east=E\nE\nF west=E\nA east=B\nB\nC west=B\n";
+
+fn run(stable: bool, blast_radius: &str, src: &str, starts: &[usize]) -> Vec<(u64, NodeId)> {
+    let order: &[&str] = if stable { &["--stable-event-order"] } else { &[] };
+    let (_, _, engine) = Run::on(src).at_ids(starts).flags(&["--blast-radius", blast_radius]).flags(order).run();
+    engine.events().iter().map(|e| (e.tick, e.node_id)).collect()
+}
+
+#[test]
+fn tick_events_are_sorted_by_colony_id() {
+    // D=0, E=1, F=2, A=3, B=4, C=5
+    let starts = [0, 2, 3, 5];
+    assert_eq!(run(false, "0", TWO_FIGHTS, &starts), [(1, NodeId::new(1)), (1, NodeId::new(4))]);
+
+    // Ants 0/1 head for B, ants 2/3 for E: resolution order is B first
+    let starts = [3, 5, 0, 2];
+    assert_eq!(run(false, "0", TWO_FIGHTS, &starts), [(1, NodeId::new(4)), (1, NodeId::new(1))]);
+    assert_eq!(run(true, "0", TWO_FIGHTS, &starts), [(1, NodeId::new(1)), (1, NodeId::new(4))]);
+}
+
+#[test]
+fn blast_collateral_is_interleaved_by_id() {
+    let line = "A east=B\nB west=A east=C\nC west=B east=D\nD west=C\n";
+    // The blast spreads in direction order (east before west)
+    let ids = |events: Vec<(u64, NodeId)>| events.into_iter().map(|(_, id)| id.get()).collect::<Vec<_>>();
+
+    assert_eq!(ids(run(false, "1", line, &[2, 2])), [2, 3, 1]);
+    assert_eq!(ids(run(true, "1", line, &[2, 2])), [1, 2, 3]);
+}
+
+#[test]
+fn cli_prints_events_in_stable_order() {
+    let mut map = tempfile::NamedTempFile::new().unwrap();
+    map.write_all(b"A east=B\nB west=A east=C\nC west=B east=D\nD west=C\n").unwrap();
+    // Every ant lands on one of four colonies, so t=0 sees fights and blasts
+    let output = Command::cargo_bin("ants_code_challenge")
+        .unwrap()
+        .args(["-n", "8", "-m", map.path().to_str().unwrap(), "--seed", "3", "--blast-radius", "1"])
+        .arg("--stable-event-order")
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let colonies: Vec<&str> = stdout
+        .lines()
+        .filter(|l| l.starts_with("[t=0]"))
+        .map(|l| l.split_whitespace().nth(2).unwrap())
+        .collect();
+
+    assert!(!colonies.is_empty());
+    let mut sorted = colonies.clone();
+    sorted.sort();
+    assert_eq!(colonies, sorted);
+}
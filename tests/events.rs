@@ -0,0 +1,35 @@
+use ants_code_challenge::prelude::*;
+use ants_code_challenge::simulation::DestructionCause;
+
+mod common;
+use common::Run;
+
+fn run(engine: impl FnOnce(SimulationEngine) -> SimulationEngine) -> (World, SimulationEngine) {
+    // Two pairs start stacked on A and C; C's blast also takes out D
+    let (world, _, engine) =
+        Run::on("A east=B\nB\nC east=D\nD\n").at(&["A", "A", "C", "C"]).flags(&["--blast-radius", "1"]).engine(engine).run();
+    (world, engine)
+}
+
+#[test]
+fn events_are_kept_with_logging_suppressed() {
+    let (world, engine) = run(|engine| engine);
+    let events = engine.events();
+
+    assert_eq!(events.len(), 4);
+    assert!(events.iter().all(|e| e.tick == 0));
+    assert_eq!(events[0].node_id, world.find("A").unwrap());
+    assert_eq!(events[0].cause, DestructionCause::Fight { ant1: 0, ant2: 1 });
+    assert_eq!(events[1].node_id, world.find("B").unwrap());
+    assert_eq!(events[1].cause, DestructionCause::Blast { origin: world.find("A").unwrap() });
+    assert_eq!(engine.events_dropped(), 0);
+}
+
+#[test]
+fn event_limit_keeps_the_first_events() {
+    let (world, engine) = run(|engine| engine.with_event_limit(1));
+
+    assert_eq!(engine.events().len(), 1);
+    assert_eq!(engine.events()[0].node_id, world.find("A").unwrap());
+    assert_eq!(engine.events_dropped(), 3);
+}
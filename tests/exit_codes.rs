@@ -0,0 +1,62 @@
+use assert_cmd::prelude::*;
+use predicates::prelude::*;
+use std::io::Write;
+use std::process::Command;
+use tempfile::NamedTempFile;
+
+const BIN: &str = "ants_code_challenge";
+
+#[test]
+fn successful_run_exits_zero() -> Result<(), Box<dyn std::error::Error>> {
+    Command::cargo_bin(BIN)?
+        .args(["-n", "10", "-m", "files/hiveum_map_small.txt", "--seed", "1", "--suppress-events"])
+        .assert()
+        .code(0);
+    Ok(())
+}
+
+#[test]
+fn parse_errors_exit_two_with_a_message() -> Result<(), Box<dyn std::error::Error>> {
+    let mut f = NamedTempFile::new()?;
+    writeln!(f, "A north=B\nB =A")?;
+
+    Command::cargo_bin(BIN)?
+        .args(["-n", "2", "-m", f.path().to_str().unwrap()])
+        .assert()
+        .code(2)
+        .stderr(predicate::str::contains("error: Invalid line: missing key in '=A'"))
+        .stderr(predicate::str::contains(" --> line 2, column 3\n  |\n2 | B =A\n  |   ^^\n"));
+
+    Command::cargo_bin(BIN)?
+        .args(["-n", "2", "-m", "does/not/exist.txt"])
+        .assert()
+        .code(2)
+        .stderr(predicate::str::starts_with("error: IO error"));
+    Ok(())
+}
+
+#[test]
+fn invalid_arguments_exit_three() -> Result<(), Box<dyn std::error::Error>> {
+    Command::cargo_bin(BIN)?.args(["-n", "lots", "-m", "x"]).assert().code(3);
+    Command::cargo_bin(BIN)?.args(["-m", "x"]).assert().code(3);
+    Command::cargo_bin(BIN)?.arg("--help").assert().code(0);
+    Ok(())
+}
+
+#[test]
+fn wipeout_exit_is_opt_in() -> Result<(), Box<dyn std::error::Error>> {
+    // Two ants start together on the only colony: it falls at t=0
+    let mut f = NamedTempFile::new()?;
+    writeln!(f, "A")?;
+    let map = f.path().to_str().unwrap();
+
+    Command::cargo_bin(BIN)?
+        .args(["-n", "2", "-m", map, "--suppress-events"])
+        .assert()
+        .code(0);
+    Command::cargo_bin(BIN)?
+        .args(["-n", "2", "-m", map, "--suppress-events", "--wipeout-exit"])
+        .assert()
+        .code(10);
+    Ok(())
+}
@@ -0,0 +1,91 @@
+use ants_code_challenge::report::{Report, TimelineCause};
+use ants_code_challenge::simulation::DestructionCause;
+use assert_cmd::Command;
+use std::io::Write;
+
+mod common;
+use common::run;
+
+/// Ant 0 walks into the fortified dead end F while ant 1 paces C <-> D
+const LONE: &str = "A east=F\nF fortified\nC east=D\nD west=C\n";
+
+#[test]
+fn lone_arrival_is_killed_and_the_colony_stands() {
+    for scheduling in ["simultaneous", "sequential"] {
+        let (world, ants, engine) = run(LONE, &["A", "C"], scheduling, &["--max-moves", "3"]);
+        let f = world.find("F").unwrap();
+        assert!(world.is_alive(f), "{scheduling}");
+        assert!(!ants[0].is_alive(), "{scheduling}");
+        assert_eq!(engine.repelled(), 1, "{scheduling}");
+        let first = engine.events()[0];
+        assert_eq!((first.tick, first.node_id, first.cause), (1, f, DestructionCause::Repelled { ant: 0 }));
+    }
+}
+
+#[test]
+fn lone_arrival_is_killed_even_with_two_ants_settled() {
+    // Near misses leave ants 0 and 1 trapped on F together by tick 1; ant 2 arrives alone at tick 2
+    let map = "B east=A\nA east=F\nF fortified\n";
+    let (world, ants, engine) = run(map, &["F", "F", "B"], "simultaneous", &["--collision-prob", "0", "--max-moves", "3"]);
+    let f = world.find("F").unwrap();
+    assert!(world.is_alive(f));
+    assert!(ants[0].is_alive() && ants[1].is_alive());
+    assert!(!ants[2].is_alive());
+    assert_eq!(engine.repelled(), 1);
+    assert!(engine.events().iter().any(|e| e.tick == 2 && e.cause == DestructionCause::Repelled { ant: 2 }));
+}
+
+#[test]
+fn zero_defense_lets_lone_ants_in() {
+    for scheduling in ["simultaneous", "sequential"] {
+        let (world, ants, engine) =
+            run(LONE, &["A", "C"], scheduling, &["--max-moves", "3", "--fortress-defense", "0"]);
+        assert!(ants[0].is_alive(), "{scheduling}");
+        assert_eq!(ants[0].pos, world.find("F").unwrap(), "{scheduling}");
+        assert_eq!(engine.repelled(), 0, "{scheduling}");
+        assert!(engine.events().is_empty(), "{scheduling}");
+    }
+}
+
+#[test]
+fn only_a_simultaneous_pair_brings_a_fortified_colony_down() {
+    let map = "A east=F\nB west=F\nF fortified\n";
+
+    let (world, _, engine) = run(map, &["A", "B"], "simultaneous", &[]);
+    assert!(!world.is_alive(world.find("F").unwrap()));
+    assert!(matches!(engine.events(), [e] if matches!(e.cause, DestructionCause::Fight { .. })));
+
+    // One ant at a time: each is turned away on its own
+    let (world, ants, engine) = run(map, &["A", "B"], "sequential", &[]);
+    assert!(world.is_alive(world.find("F").unwrap()));
+    assert!(ants.iter().all(|a| !a.is_alive()));
+    assert_eq!(engine.repelled(), 2);
+}
+
+#[test]
+fn unfortified_maps_report_no_defense() {
+    let (_, _, engine) = run("A east=F\nF\nC east=D\nD west=C\n", &["A", "C"], "simultaneous", &["--max-moves", "3"]);
+    assert!(!engine.has_fortified());
+    assert_eq!(engine.repelled(), 0);
+}
+
+#[test]
+fn cli_logs_and_reports_repelled_ants() {
+    let mut map = tempfile::NamedTempFile::new().unwrap();
+    map.write_all(b"A fortified\nB east=A\nC east=A\n").unwrap();
+    let report = tempfile::NamedTempFile::new().unwrap();
+    Command::cargo_bin("ants_code_challenge")
+        .unwrap()
+        .args(["-n", "2", "--seed", "1", "--max-moves", "5"])
+        .arg("-m")
+        .arg(map.path())
+        .arg("--report")
+        .arg(report.path())
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("Repelled:"));
+
+    let report = Report::read_json(report.path().to_str().unwrap()).unwrap();
+    let logged = report.timeline.iter().filter(|e| matches!(e.cause, TimelineCause::Repelled { .. })).count();
+    assert_eq!(report.repelled, Some(logged));
+}
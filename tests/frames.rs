@@ -0,0 +1,72 @@
+use ants_code_challenge::frames::{Frame, FrameHeader, BINARY_MAGIC, BINARY_VERSION};
+use assert_cmd::Command;
+use std::io::Write;
+use tempfile::NamedTempFile;
+
+const BIN: &str = "ants_code_challenge";
+
+fn ring() -> NamedTempFile {
+    let mut f = NamedTempFile::new().unwrap();
+    writeln!(f, "A east=B\nB east=C\nC east=D\nD east=A").unwrap();
+    f
+}
+
+/// Three ants that never fight, so the run lasts all 7 moves
+fn record(map: &NamedTempFile, frames: &std::path::Path, extra: &[&str]) {
+    Command::cargo_bin(BIN)
+        .unwrap()
+        .args(["-n", "3", "--seed", "7", "--max-moves", "7", "--collision-prob", "0", "--suppress-events", "-m"])
+        .arg(map.path())
+        .arg("--frames")
+        .arg(frames)
+        .args(extra)
+        .assert()
+        .success();
+}
+
+#[test]
+fn json_frames_follow_the_header() {
+    let map = ring();
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("frames.jsonl");
+    record(&map, &path, &["--frame-every", "3"]);
+
+    let text = std::fs::read_to_string(&path).unwrap();
+    let mut lines = text.lines();
+    let header: FrameHeader = serde_json::from_str(lines.next().unwrap()).unwrap();
+    assert_eq!(header.colonies, ["A", "B", "C", "D"]);
+    let frames: Vec<Frame> = lines.map(|l| serde_json::from_str(l).unwrap()).collect();
+
+    let ticks: Vec<u64> = frames.iter().map(|f| f.tick).collect();
+    assert_eq!(ticks, [0, 3, 6, 7], "the last tick is always written");
+    for frame in &frames {
+        assert_eq!(frame.occupancy.iter().map(|&(_, n)| n).sum::<u32>(), 3);
+        assert!(frame.destroyed.is_empty());
+    }
+}
+
+#[test]
+fn bin_extension_selects_the_binary_layout() {
+    let map = ring();
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("frames.bin");
+    record(&map, &path, &[]);
+
+    let bytes = std::fs::read(&path).unwrap();
+    assert_eq!(&bytes[..4], BINARY_MAGIC);
+    assert_eq!(bytes[4], BINARY_VERSION);
+}
+
+#[test]
+fn frames_reject_interactive_and_zero_interval() {
+    let map = ring();
+    for extra in [&["--interactive"][..], &["--frame-every", "0"]] {
+        Command::cargo_bin(BIN)
+            .unwrap()
+            .args(["-n", "1", "--frames", "out.jsonl", "-m"])
+            .arg(map.path())
+            .args(extra)
+            .assert()
+            .failure();
+    }
+}
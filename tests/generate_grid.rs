@@ -0,0 +1,34 @@
+use assert_cmd::prelude::*;
+use predicates::prelude::*;
+use std::process::Command;
+use tempfile::NamedTempFile;
+
+const BIN: &str = "ants_code_challenge";
+
+#[test]
+fn generate_grid_writes_coordinates() -> Result<(), Box<dyn std::error::Error>> {
+    let output = Command::cargo_bin(BIN)?.args(["generate-grid", "--width", "2", "--height", "1"]).output()?;
+    assert!(output.status.success());
+    assert_eq!(String::from_utf8(output.stdout)?, "C0_0 east=C1_0 x=0 y=0\nC1_0 west=C0_0 x=1 y=0\n");
+
+    Ok(())
+}
+
+#[test]
+fn a_generated_torus_renders_as_a_grid() -> Result<(), Box<dyn std::error::Error>> {
+    let torus = NamedTempFile::new()?;
+    let path = torus.path().to_str().unwrap();
+    Command::cargo_bin(BIN)?
+        .args(["generate-grid", "--width", "5", "--height", "3", "--torus", "-o", path])
+        .assert()
+        .success();
+
+    // Wrapping tunnels defeat lattice detection; the coordinates lay it out
+    Command::cargo_bin(BIN)?
+        .args(["-n", "0", "-m", path, "--render-grid", "--suppress-events"])
+        .assert()
+        .success()
+        .stdout(predicate::str::starts_with(".....\n.....\n.....\n"));
+
+    Ok(())
+}
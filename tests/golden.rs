@@ -0,0 +1,83 @@
+//! Golden tests: bundled maps run with fixed seeds through the binary, and
+//! the full `--report` JSON compared with `tests/golden/<case>.json`.
+//!
+//! An intended behavior change shows up here as a diff; after checking it,
+//! rewrite the files with `UPDATE_GOLDEN=1 cargo test --test golden` and
+//! commit them with the change.
+
+use assert_cmd::Command;
+use serde_json::Value;
+use std::path::PathBuf;
+
+const SMALL: &str = "files/hiveum_map_small.txt";
+
+/// `(case, map, extra flags)`; every case adds `--seed`, `--quiet` and `--report -`
+const CASES: &[(&str, &str, &[&str])] = &[
+    ("small_default", SMALL, &["-n", "10", "--seed", "42"]),
+    ("small_crowded", SMALL, &["-n", "60", "--seed", "7"]),
+    ("small_sequential", SMALL, &["-n", "20", "--seed", "42", "--scheduling", "sequential"]),
+    ("small_blast", SMALL, &["-n", "12", "--seed", "3", "--blast-radius", "1", "--stable-event-order"]),
+    ("small_near_misses", SMALL, &["-n", "20", "--seed", "5", "--collision-prob", "0.5"]),
+    ("small_queens", SMALL, &["-n", "16", "--seed", "11", "--queen-ratio", "0.25", "--max-moves", "60"]),
+    ("small_homing", SMALL, &["-n", "16", "--seed", "8", "--strategy", "homing:5", "--heatmap"]),
+    ("small_killers", SMALL, &["-n", "30", "--seed", "9", "--top-killers", "3", "--max-moves-dist", "uniform:5,50"]),
+];
+
+fn golden_path(case: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/golden").join(format!("{case}.json"))
+}
+
+/// The report of one case, with the wall-clock latency zeroed
+fn run(map: &str, extra: &[&str]) -> String {
+    let output = Command::cargo_bin("ants_code_challenge")
+        .unwrap()
+        .current_dir(env!("CARGO_MANIFEST_DIR"))
+        .args(["-m", map, "--quiet", "--report", "-"])
+        .args(extra)
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    let mut report: Value = serde_json::from_slice(&output.stdout).unwrap();
+    report["latency_ms"] = Value::from(0.0);
+    serde_json::to_string_pretty(&report).unwrap() + "\n"
+}
+
+/// First differing line, so a failure points at the change
+fn first_difference(expected: &str, actual: &str) -> String {
+    let line = expected.lines().zip(actual.lines()).position(|(e, a)| e != a);
+    match line {
+        Some(i) => format!(
+            "line {}:\n  expected: {}\n  actual:   {}",
+            i + 1,
+            expected.lines().nth(i).unwrap(),
+            actual.lines().nth(i).unwrap()
+        ),
+        None => format!("{} lines expected, {} found", expected.lines().count(), actual.lines().count()),
+    }
+}
+
+#[test]
+fn reports_match_golden_files() {
+    let update = std::env::var_os("UPDATE_GOLDEN").is_some();
+    let mut failures = Vec::new();
+    for (case, map, extra) in CASES {
+        let actual = run(map, extra);
+        let path = golden_path(case);
+        if update {
+            std::fs::write(&path, &actual).unwrap();
+            continue;
+        }
+        let expected = std::fs::read_to_string(&path)
+            .unwrap_or_else(|err| panic!("{}: {err} (run with UPDATE_GOLDEN=1 to create it)", path.display()));
+        if expected != actual {
+            failures.push(format!("{case}: {}", first_difference(&expected, &actual)));
+        }
+    }
+    assert!(failures.is_empty(), "reports differ from tests/golden:\n{}", failures.join("\n"));
+}
+
+#[test]
+fn golden_runs_are_reproducible() {
+    let (_, map, extra) = CASES[1];
+    assert_eq!(run(map, extra), run(map, extra));
+}
@@ -0,0 +1,25 @@
+mod common;
+use common::Run;
+
+fn run(scheduling: &str, extra: &[&str]) -> Vec<u32> {
+    let (_, _, engine) = Run::on("A east=B\nB west=A\nC east=D\nD west=C\n")
+        .at(&["A", "C"])
+        .scheduling(scheduling)
+        .flags(&["--max-moves", "4"])
+        .flags(extra)
+        .run();
+    engine.visits().to_vec()
+}
+
+#[test]
+fn visits_count_placement_and_every_arrival() {
+    for scheduling in ["simultaneous", "sequential"] {
+        // Each ant shuttles back and forth: start + ticks 2, 4 at home, ticks 1, 3 away
+        assert_eq!(run(scheduling, &["--heatmap"]), [3, 2, 3, 2], "{scheduling}");
+    }
+}
+
+#[test]
+fn visits_are_not_tracked_by_default() {
+    assert!(run("simultaneous", &[]).is_empty());
+}
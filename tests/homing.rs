@@ -0,0 +1,57 @@
+use assert_cmd::Command;
+
+mod common;
+use common::Run;
+
+/// A row of 12 colonies plus the island Z; one ant starts on C5 and a
+/// trapped ant on Z keeps the run going
+fn visits(extra: &[&str]) -> Vec<u32> {
+    let mut map = String::new();
+    for i in 0..12 {
+        map.push_str(&format!("C{i}"));
+        if i > 0 {
+            map.push_str(&format!(" west=C{}", i - 1));
+        }
+        if i < 11 {
+            map.push_str(&format!(" east=C{}", i + 1));
+        }
+        map.push('\n');
+    }
+    map.push_str("Z\n");
+    let (_, ants, engine) =
+        Run::on(&map).at(&["C5", "Z"]).flags(&["--max-moves", "200", "--heatmap"]).flags(extra).seed(5).run();
+    assert_eq!(ants[0].moves, 200);
+    engine.visits()[..12].to_vec()
+}
+
+#[test]
+fn homing_ants_stay_within_reach_of_home() {
+    for scheduling in ["simultaneous", "sequential"] {
+        let visits = visits(&["--strategy", "homing:3", "--scheduling", scheduling]);
+        // Back home after 3 moves, then one step out and back again
+        assert!(visits[5] >= 100, "{visits:?}");
+        assert!(visits[..2].iter().chain(&visits[9..]).all(|&v| v == 0), "{visits:?}");
+    }
+}
+
+#[test]
+fn random_walkers_wander_off() {
+    let visits = visits(&[]);
+    assert!(visits[..2].iter().chain(&visits[9..]).any(|&v| v > 0), "{visits:?}");
+}
+
+#[test]
+fn cli_rejects_malformed_strategies() {
+    Command::cargo_bin("ants_code_challenge")
+        .unwrap()
+        .args(["-n", "10", "-m", "files/hiveum_map_small.txt", "--suppress-events", "--strategy", "homing:x"])
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains("invalid number 'x'"));
+
+    Command::cargo_bin("ants_code_challenge")
+        .unwrap()
+        .args(["-n", "10", "-m", "files/hiveum_map_small.txt", "--suppress-events", "--strategy", "homing:5"])
+        .assert()
+        .success();
+}
@@ -0,0 +1,81 @@
+use ants_code_challenge::prelude::*;
+use ants_code_challenge::simulation::{DestructionEvent, SimulationHooks, TickView};
+use ants_code_challenge::world::WorldView;
+use std::sync::{Arc, Mutex};
+
+mod common;
+use common::Run;
+
+#[derive(Default)]
+struct Tally {
+    starts: Vec<u64>,
+    ends: Vec<u64>,
+    events: Vec<DestructionEvent>,
+    deaths: Vec<(u32, u64)>,
+}
+
+#[derive(Clone, Default)]
+struct Recorder(Arc<Mutex<Tally>>);
+
+impl SimulationHooks for Recorder {
+    fn on_tick_start(&mut self, view: &TickView<'_>) {
+        self.0.lock().unwrap().starts.push(view.tick);
+    }
+
+    fn on_tick_end(&mut self, view: &TickView<'_>) {
+        self.0.lock().unwrap().ends.push(view.tick);
+    }
+
+    fn on_destruction(&mut self, event: &DestructionEvent, _world: WorldView<'_>) {
+        self.0.lock().unwrap().events.push(*event);
+    }
+
+    fn on_ant_dead(&mut self, ant: &Ant, tick: u64) {
+        self.0.lock().unwrap().deaths.push((ant.id, tick));
+    }
+}
+
+/// A row of colonies; ants from both ends walk into each other
+const ROW: &str = "A east=B\nB west=A east=C\nC west=B east=D\nD west=C east=E\nE west=D\n";
+
+fn run(scheduling: &str) -> (SimulationEngine, Vec<Ant>, Tally) {
+    let recorder = Recorder::default();
+    let (_, ants, engine) = Run::on(ROW)
+        .at(&["A", "B", "C", "D", "E"])
+        .scheduling(scheduling)
+        .flags(&["--max-moves", "50"])
+        .seed(3)
+        .engine(|engine| engine.with_hooks(recorder.clone()))
+        .run();
+    let tally = std::mem::take(&mut *recorder.0.lock().unwrap());
+    (engine, ants, tally)
+}
+
+#[test]
+fn hooks_see_every_tick_event_and_death() {
+    for scheduling in ["simultaneous", "sequential"] {
+        let (engine, ants, tally) = run(scheduling);
+
+        let ticks: Vec<u64> = (1..=engine.ticks()).collect();
+        assert_eq!(tally.starts, ticks, "{scheduling}");
+        assert_eq!(tally.ends, ticks, "{scheduling}");
+        assert_eq!(tally.events, engine.events(), "{scheduling}");
+        assert!(!tally.events.is_empty(), "{scheduling}");
+
+        let dead = ants.iter().filter(|a| !a.is_alive()).count();
+        assert_eq!(tally.deaths.len(), dead, "{scheduling}");
+        assert!(tally.deaths.iter().all(|&(id, tick)| !ants[id as usize].is_alive() && tick <= engine.ticks()));
+    }
+}
+
+#[test]
+fn hooks_do_not_change_the_run() {
+    let (_, ants, engine) =
+        Run::on(ROW).at(&["A", "B", "C", "D", "E"]).flags(&["--max-moves", "50"]).seed(3).run();
+
+    let (hooked, hooked_ants, _) = run("simultaneous");
+    assert_eq!(hooked.events(), engine.events());
+    assert_eq!(hooked.ticks(), engine.ticks());
+    let alive = |ants: &[Ant]| ants.iter().map(Ant::is_alive).collect::<Vec<_>>();
+    assert_eq!(alive(&hooked_ants), alive(&ants));
+}
@@ -0,0 +1,41 @@
+use ants_code_challenge::prelude::*;
+use ants_code_challenge::world::parser::parse_world_from_str;
+use clap::Parser;
+use std::sync::atomic::AtomicBool;
+use std::sync::Arc;
+
+#[test]
+fn raised_interrupt_stops_before_first_tick() {
+    let (names, nodes) = parse_world_from_str("A east=B\nB west=A\n");
+    let mut world = World::new(names, nodes);
+    let mut ants = vec![Ant::new(0, NodeId::new(0))];
+    let args = Args::parse_from(["ants", "-n", "1", "-m", "unused", "--suppress-events"]);
+    let mut rng = fastrand::Rng::with_seed(1);
+
+    let flag = Arc::new(AtomicBool::new(true));
+    let mut engine = SimulationEngine::new(&world, ants.len()).with_interrupt(flag);
+    engine.run_simulation(&mut world, &mut ants, &args, &mut rng);
+
+    assert!(engine.interrupted());
+    assert_eq!(engine.ticks(), 0);
+    assert_eq!(ants[0].moves, 0);
+}
+
+#[test]
+fn run_without_interrupt_completes() {
+    // Two separate loops so the ants never meet
+    let (names, nodes) = parse_world_from_str("A east=B\nB west=A\nC east=D\nD west=C\n");
+    let mut world = World::new(names, nodes);
+    let mut ants = vec![Ant::new(0, NodeId::new(0)), Ant::new(1, NodeId::new(2))];
+    let args = Args::parse_from([
+        "ants", "-n", "2", "-m", "unused", "--max-moves", "4", "--suppress-events",
+    ]);
+    let mut rng = fastrand::Rng::with_seed(1);
+
+    let flag = Arc::new(AtomicBool::new(false));
+    let mut engine = SimulationEngine::new(&world, ants.len()).with_interrupt(flag);
+    engine.run_simulation(&mut world, &mut ants, &args, &mut rng);
+
+    assert!(!engine.interrupted());
+    assert!(ants.iter().all(|a| a.moves == 4));
+}
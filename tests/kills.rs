@@ -0,0 +1,70 @@
+use ants_code_challenge::prelude::*;
+use ants_code_challenge::report::Report;
+use ants_code_challenge::simulation::DestructionCause;
+use assert_cmd::Command;
+
+mod common;
+use common::Run;
+
+const MAP: &str = "files/hiveum_map_small.txt";
+
+/// 40 ants spread over the small map by the seed, run to the end
+fn run(flags: &[&str]) -> SimulationEngine {
+    let map = std::fs::read_to_string(MAP).unwrap();
+    let (_, _, engine) = Run::on(&map).ants(|world, rng| world.create_ants(40, rng)).flags(flags).seed(21).run();
+    engine
+}
+
+#[test]
+fn every_fighter_is_credited() {
+    for scheduling in ["simultaneous", "sequential"] {
+        let engine = run(&["--top-killers", "5", "--scheduling", scheduling]);
+
+        let kills = engine.kills();
+        let mut fights = 0;
+        for ev in engine.events() {
+            if let DestructionCause::Fight { ant1, ant2 } = ev.cause {
+                fights += 1;
+                assert!(kills[ant1 as usize] > 0 && kills[ant2 as usize] > 0, "{scheduling}: {ev:?}");
+            }
+        }
+        assert!(fights > 0, "{scheduling}");
+        assert!(kills.iter().sum::<u32>() >= 2 * fights, "{scheduling}");
+        // An ant dies in the first fight it joins
+        assert!(kills.iter().all(|&k| k <= 1), "{scheduling}");
+    }
+}
+
+#[test]
+fn crowded_fight_credits_every_ant_on_the_colony() {
+    let (_, _, engine) = Run::on("A east=B\nB west=A\n").at(&["A", "A", "A"]).flags(&["--top-killers", "5"]).run();
+
+    // The event names two ants, but all three fought
+    assert_eq!(engine.events().len(), 1);
+    assert_eq!(engine.kills(), [1, 1, 1]);
+}
+
+#[test]
+fn kills_are_not_tracked_by_default() {
+    let engine = run(&[]);
+    assert!(engine.kills().is_empty());
+}
+
+#[test]
+fn cli_lists_top_killers_in_summary_and_report() {
+    let report = tempfile::NamedTempFile::new().unwrap();
+    Command::cargo_bin("ants_code_challenge")
+        .unwrap()
+        .args(["-n", "40", "-m", MAP, "--seed", "4", "--suppress-events", "--top-killers", "3"])
+        .arg("--report")
+        .arg(report.path())
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("Most destructive ants:"))
+        .stdout(predicates::str::contains(" kills"));
+
+    let report = Report::read_json(report.path().to_str().unwrap()).unwrap();
+    let top = report.top_killers.unwrap();
+    assert_eq!(top.len(), 3);
+    assert!(top.windows(2).all(|w| w[0].kills >= w[1].kills));
+}
@@ -0,0 +1,51 @@
+use assert_cmd::prelude::*;
+use predicates::prelude::*;
+use std::process::Command;
+use tempfile::NamedTempFile;
+
+const BIN: &str = "ants_code_challenge";
+
+/// A map with a stray `=C` token and a line that is not UTF-8
+fn messy_map() -> NamedTempFile {
+    let file = NamedTempFile::new().unwrap();
+    std::fs::write(file.path(), b"A east=B =C\nB west=A\n\xff\xfe\n").unwrap();
+    file
+}
+
+#[test]
+fn lenient_runs_warn_about_what_they_skip() {
+    let map = messy_map();
+    Command::cargo_bin(BIN)
+        .unwrap()
+        .args(["-n", "0", "-m", map.path().to_str().unwrap(), "--lenient", "--suppress-events"])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("warning: Invalid line: missing key in '=C'\n --> line 1, column 10"))
+        .stderr(predicate::str::contains("warning: line 3, column 1: Invalid line: not valid UTF-8"))
+        .stderr(predicate::str::contains("warning: skipped 2 malformed map entries"));
+}
+
+#[test]
+fn strict_runs_stop_at_the_first_issue() {
+    let map = messy_map();
+    Command::cargo_bin(BIN)
+        .unwrap()
+        .args(["-n", "0", "-m", map.path().to_str().unwrap()])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("error: Invalid line: missing key in '=C'"))
+        .stderr(predicate::str::contains("warning").not());
+}
+
+#[test]
+fn bad_colony_names_point_at_the_name() {
+    let map = NamedTempFile::new().unwrap();
+    std::fs::write(map.path(), "A east=B=C\nB west=A\n").unwrap();
+    Command::cargo_bin(BIN)
+        .unwrap()
+        .args(["-n", "0", "-m", map.path().to_str().unwrap(), "--validate-names"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("error: Invalid colony name 'B=C': contains '='\n --> line 1, column 8"))
+        .stderr(predicate::str::contains("1 | A east=B=C\n  |        ^^^"));
+}
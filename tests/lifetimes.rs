@@ -0,0 +1,42 @@
+use ants_code_challenge::prelude::*;
+use ants_code_challenge::report::Report;
+use ants_code_challenge::world::load_world;
+use std::time::Duration;
+
+/// A seeded run on the small map, keeping at most `event_limit` events
+fn run(event_limit: usize) -> Report {
+    let mut world = load_world("files/hiveum_map_small.txt", &Default::default()).unwrap();
+    let mut args = Args::with_defaults(40, "files/hiveum_map_small.txt");
+    args.suppress_events = true;
+    args.seed = Some(11);
+    let mut rng = fastrand::Rng::with_seed(11);
+    let mut ants = world.create_ants(args.ants, &mut rng);
+    let mut engine = SimulationEngine::new(&world, ants.len()).with_event_limit(event_limit);
+    engine.run_simulation(&mut world, &mut ants, &args, &mut rng);
+    Report::new(&world, &ants, &args, &engine, Duration::ZERO)
+}
+
+#[test]
+fn histogram_covers_every_colony() {
+    let report = run(usize::MAX);
+    let lifetimes = &report.lifetimes;
+
+    assert!(lifetimes.fallen() > 0);
+    assert_eq!(lifetimes.fallen() as usize, report.timeline.iter().filter(|e| e.cause.destroyed()).count());
+    assert_eq!(lifetimes.survived, report.survivors);
+    assert!(lifetimes.destroyed.windows(2).all(|w| w[0].ticks < w[1].ticks));
+    assert!(lifetimes.destroyed.iter().all(|bin| bin.colonies > 0 && bin.ticks <= report.ticks));
+    for bin in &lifetimes.destroyed {
+        let at_tick = report.timeline.iter().filter(|e| e.tick == bin.ticks && e.cause.destroyed()).count();
+        assert_eq!(bin.colonies as usize, at_tick, "tick {}", bin.ticks);
+    }
+}
+
+#[test]
+fn histogram_is_complete_past_the_event_limit() {
+    let full = run(usize::MAX);
+    let capped = run(1);
+
+    assert_eq!(capped.timeline.len(), 1);
+    assert_eq!(capped.lifetimes, full.lifetimes);
+}
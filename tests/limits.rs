@@ -0,0 +1,80 @@
+use ants_code_challenge::limits::{self, Limit};
+use ants_code_challenge::prelude::*;
+use assert_cmd::Command;
+use std::io::Write;
+use std::time::Duration;
+
+mod common;
+use common::Run;
+
+/// Two separate two-colony loops: ants in different loops never meet
+const LOOPS: &str = "A east=B\nB west=A\nC east=D\nD west=C\n";
+
+fn run(flags: &[&str], configure: impl FnOnce(&mut Args)) -> (SimulationEngine, World) {
+    let (world, _, engine) = Run::on(LOOPS).at(&["A", "C"]).flags(flags).args(configure).run();
+    (engine, world)
+}
+
+#[test]
+fn wall_time_limit_stops_run_with_partial_results() {
+    let (engine, world) = run(&["--max-wall-time", "0ms"], |args| args.max_moves = u32::MAX);
+
+    assert!(engine.interrupted());
+    assert_eq!(engine.limit_exceeded(), Some(Limit::WallTime));
+    assert_eq!(engine.ticks(), 0);
+    assert_eq!(world.count_survivors(), 4);
+}
+
+#[test]
+fn memory_limit_stops_run_that_outgrows_it() {
+    let (engine, _) = run(&[], |args| args.memory_limit = Some(limits::estimate_bytes(4, 2) - 1));
+
+    assert!(engine.interrupted());
+    assert_eq!(engine.limit_exceeded(), Some(Limit::Memory));
+}
+
+#[test]
+fn generous_limits_do_not_interfere() {
+    let (engine, _) = run(&[], |args| {
+        args.memory_limit = Some(1 << 30);
+        args.max_wall_time = Some(Duration::from_secs(3600));
+    });
+
+    assert!(!engine.interrupted());
+    assert_eq!(engine.limit_exceeded(), None);
+    assert_eq!(engine.ticks(), 10_000);
+}
+
+fn map_file() -> tempfile::NamedTempFile {
+    let mut file = tempfile::NamedTempFile::new().unwrap();
+    file.write_all(LOOPS.as_bytes()).unwrap();
+    file
+}
+
+#[test]
+fn cli_refuses_runs_over_memory_limit() {
+    let map = map_file();
+    Command::cargo_bin("ants_code_challenge")
+        .unwrap()
+        .args(["-n", "1000000000", "-m", map.path().to_str().unwrap(), "--memory-limit", "1M"])
+        .assert()
+        .code(4)
+        .stderr(predicates::str::contains("--memory-limit"));
+}
+
+#[test]
+fn cli_reports_wall_time_limit() {
+    let map = map_file();
+    let report = tempfile::NamedTempFile::new().unwrap();
+    Command::cargo_bin("ants_code_challenge")
+        .unwrap()
+        .args(["-n", "2", "-m", map.path().to_str().unwrap(), "--seed", "1", "--suppress-events", "--max-wall-time", "0s"])
+        .args(["--report", report.path().to_str().unwrap()])
+        .assert()
+        .code(4)
+        .stdout(predicates::str::contains("wall-time limit reached"));
+
+    let json: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(report.path()).unwrap()).unwrap();
+    assert_eq!(json["interrupted"], true);
+    assert_eq!(json["limit_exceeded"], "wall_time");
+}
@@ -0,0 +1,48 @@
+use assert_cmd::prelude::*;
+use std::io::Write;
+use std::process::Command;
+use tempfile::NamedTempFile;
+
+const BIN: &str = "ants_code_challenge";
+
+/// Every run has exactly one fight followed by one blast: sequential ants
+/// on a two-colony loop always meet, and the blast takes the other colony.
+fn log_with_filter(filter: &str) -> Result<String, Box<dyn std::error::Error>> {
+    let mut f = NamedTempFile::new()?;
+    writeln!(f, "X east=Y\nY west=X")?;
+
+    let output = Command::cargo_bin(BIN)?
+        .args([
+            "-n", "2",
+            "-m", f.path().to_str().unwrap(),
+            "--seed", "3",
+            "--scheduling", "sequential",
+            "--blast-radius", "1",
+            "--log-filter", filter,
+        ])
+        .output()?;
+    assert!(output.status.success());
+    Ok(String::from_utf8(output.stdout)?)
+}
+
+#[test]
+fn filter_selects_event_kinds() -> Result<(), Box<dyn std::error::Error>> {
+    let blasts = log_with_filter("blast")?;
+    assert!(blasts.contains("destroyed by the blast from") && !blasts.contains("destroyed by ant"));
+
+    let fights = log_with_filter("fight")?;
+    assert!(fights.contains("destroyed by ant") && !fights.contains("blast from"));
+
+    let both = log_with_filter("destroyed")?;
+    assert!(both.contains("destroyed by ant") && both.contains("blast from"));
+    Ok(())
+}
+
+#[test]
+fn unknown_kind_is_rejected() -> Result<(), Box<dyn std::error::Error>> {
+    Command::cargo_bin(BIN)?
+        .args(["-n", "1", "-m", "files/hiveum_map_small.txt", "--log-filter", "trapped"])
+        .assert()
+        .failure();
+    Ok(())
+}
@@ -0,0 +1,85 @@
+use ants_code_challenge::direction::Direction;
+use ants_code_challenge::error::ParseError;
+use ants_code_challenge::world::{load_world, load_world_from_paths, map_files};
+use assert_cmd::prelude::*;
+use predicates::prelude::*;
+use std::process::Command;
+use tempfile::TempDir;
+
+const BIN: &str = "ants_code_challenge";
+
+/// Two regions whose border tunnels point into each other
+fn regions() -> TempDir {
+    let dir = TempDir::new().unwrap();
+    std::fs::write(dir.path().join("1_west.txt"), "A east=B\nB west=A east=C\n").unwrap();
+    std::fs::write(dir.path().join("2_east.txt"), "C west=B east=D\nD west=C\n").unwrap();
+    std::fs::write(dir.path().join(".1_west.txt.swp"), "not a map =x\n").unwrap();
+    dir
+}
+
+fn path(dir: &TempDir, name: &str) -> String {
+    dir.path().join(name).to_str().unwrap().to_string()
+}
+
+#[test]
+fn fragments_resolve_names_across_files() {
+    let dir = regions();
+    let world = load_world_from_paths(&[path(&dir, "1_west.txt"), path(&dir, "2_east.txt")], &Default::default()).unwrap();
+
+    assert_eq!(world.names(), ["A", "B", "C", "D"]);
+    let id = |name: &str| world.find(name).unwrap();
+    let east = |name: &str| world.node(id(name)).unwrap().get_neighbor(Direction::East.index());
+    assert_eq!(east("B"), Some(id("C")));
+    assert_eq!(east("C"), Some(id("D")));
+}
+
+#[test]
+fn directory_reads_visible_files_in_name_order() {
+    let dir = regions();
+    let files = map_files(&[dir.path().to_str().unwrap()]).unwrap();
+
+    assert_eq!(files, [path(&dir, "1_west.txt"), path(&dir, "2_east.txt")]);
+    let world = load_world(dir.path().to_str().unwrap(), &Default::default()).unwrap();
+    assert_eq!(world.names(), ["A", "B", "C", "D"]);
+}
+
+#[test]
+fn errors_name_the_fragment() {
+    let dir = regions();
+    std::fs::write(dir.path().join("3_broken.txt"), "E west=D\nF =G\n").unwrap();
+
+    let err = load_world(dir.path().to_str().unwrap(), &Default::default()).unwrap_err();
+    assert_eq!(err.file(), Some(path(&dir, "3_broken.txt").as_str()));
+    assert_eq!(err.location().map(|at| (at.line, at.column)), Some((2, 3)));
+
+    let empty = TempDir::new().unwrap();
+    let err = load_world(empty.path().to_str().unwrap(), &Default::default()).unwrap_err();
+    assert!(matches!(err, ParseError::NoMapFiles(_)), "{err}");
+}
+
+#[test]
+fn repeated_map_flags_join_one_world() {
+    let dir = regions();
+    Command::cargo_bin(BIN)
+        .unwrap()
+        .args(["-n", "0", "--print-world", "-m", &path(&dir, "1_west.txt"), "-m", &path(&dir, "2_east.txt")])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("B east=C west=A"))
+        .stdout(predicate::str::contains("C east=D west=B"));
+}
+
+#[test]
+fn cli_quotes_the_line_of_the_broken_fragment() {
+    let dir = regions();
+    std::fs::write(dir.path().join("3_broken.txt"), "E west=D\nF =G\n").unwrap();
+    let broken = path(&dir, "3_broken.txt");
+
+    Command::cargo_bin(BIN)
+        .unwrap()
+        .args(["-n", "2", "-m", dir.path().to_str().unwrap()])
+        .assert()
+        .code(2)
+        .stderr(predicate::str::contains(format!("--> {broken}, line 2, column 3")))
+        .stderr(predicate::str::contains("2 | F =G"));
+}
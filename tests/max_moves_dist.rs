@@ -0,0 +1,33 @@
+use ants_code_challenge::prelude::*;
+
+mod common;
+use common::Run;
+
+/// Ants shuttle on private two-colony loops, so each stops exactly at its own limit
+fn run(extra: &[&str]) -> Vec<Ant> {
+    let map: String = (0..20).map(|i| format!("A{i} east=B{i}\nB{i} west=A{i}\n")).collect();
+    let (_, ants, _) = Run::on(&map)
+        .ants(|_, _| (0..20).map(|i| Ant::new(i, NodeId::from_index(2 * i as usize))).collect())
+        .flags(&["--max-moves", "50"])
+        .flags(extra)
+        .seed(9)
+        .run();
+    ants
+}
+
+#[test]
+fn constant_limit_applies_to_every_ant() {
+    for ant in run(&[]) {
+        assert_eq!((ant.max_moves, ant.moves), (50, 50));
+    }
+}
+
+#[test]
+fn uniform_limits_are_heterogeneous_and_respected() {
+    let ants = run(&["--max-moves-dist", "uniform:10,30"]);
+    for ant in &ants {
+        assert!((10..=30).contains(&ant.max_moves), "{}", ant.max_moves);
+        assert_eq!(ant.moves, ant.max_moves);
+    }
+    assert!(ants.iter().any(|a| a.max_moves != ants[0].max_moves));
+}
@@ -0,0 +1,36 @@
+use ants_code_challenge::prelude::*;
+
+mod common;
+use common::Run;
+
+/// Two ants on opposite sides of a four-colony ring, walking east without meeting
+fn run(max_ticks: Option<u64>, scheduling: &str) -> (SimulationEngine, Vec<Ant>) {
+    let (_, ants, engine) = Run::on("A east=B\nB east=C\nC east=D\nD east=A\n")
+        .at(&["A", "C"])
+        .scheduling(scheduling)
+        .flags(&["--max-moves", "50"])
+        .seed(3)
+        .args(|args| args.max_ticks = max_ticks)
+        .run();
+    (engine, ants)
+}
+
+#[test]
+fn max_ticks_ends_the_run_before_the_move_limit() {
+    for scheduling in ["simultaneous", "sequential"] {
+        let (engine, ants) = run(Some(7), scheduling);
+
+        assert_eq!(engine.ticks(), 7, "{scheduling}");
+        assert!(!engine.interrupted(), "{scheduling}");
+        assert!(ants.iter().all(|a| a.is_alive() && a.moves == 7 && !a.has_max_moves()), "{scheduling}");
+    }
+}
+
+#[test]
+fn move_limit_still_applies_under_a_larger_max_ticks() {
+    let (engine, ants) = run(Some(u64::MAX), "simultaneous");
+
+    assert_eq!(engine.ticks(), 50);
+    assert!(ants.iter().all(|a| a.has_max_moves()));
+    assert_eq!(run(Some(0), "simultaneous").0.ticks(), 0);
+}
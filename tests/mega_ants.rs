@@ -0,0 +1,89 @@
+use ants_code_challenge::cli::Scheduling;
+use ants_code_challenge::{AntKind, DeathReason};
+use ants_code_challenge::prelude::*;
+use ants_code_challenge::simulation::DestructionCause;
+use assert_cmd::prelude::*;
+use predicates::prelude::*;
+use std::process::Command;
+use tempfile::NamedTempFile;
+
+mod common;
+use common::Run;
+
+/// A -> B -> C, two-way: an ant on A can only go to B first
+const LINE: &str = "A east=B\nB west=A east=C\nC west=B\n";
+
+/// One ant per `starts` colony, in order, run to the end
+fn run(starts: &[&str], scheduling: Scheduling, configure: impl FnOnce(&mut Args)) -> (World, Vec<Ant>, SimulationEngine) {
+    Run::on(LINE)
+        .at(starts)
+        .flags(&["--max-moves", "5"])
+        .seed(3)
+        .args(|args| {
+            args.scheduling = scheduling;
+            configure(args);
+        })
+        .run()
+}
+
+#[test]
+fn a_lone_mega_ant_tramples_the_first_colony_it_enters() {
+    for scheduling in [Scheduling::Simultaneous, Scheduling::Sequential] {
+        let (world, ants, engine) = run(&["A"], scheduling, |args| args.mega_fraction = Some(1.0));
+
+        let b = world.find("B").unwrap();
+        assert_eq!(engine.events().len(), 1, "{scheduling:?}");
+        let event = engine.events()[0];
+        assert_eq!((event.tick, event.node_id, event.cause), (1, b, DestructionCause::Trampled { ant: 0 }));
+        assert_eq!(engine.trampled(), 1, "{scheduling:?}");
+        assert!(ants[0].is_mega() && !ants[0].is_alive(), "{scheduling:?}");
+        assert_eq!(ants[0].death, Some(DeathReason::VanishedWithColony), "{scheduling:?}");
+        assert_eq!(world.alive_count(), 2, "{scheduling:?}");
+    }
+}
+
+#[test]
+fn trampling_spreads_a_blast() {
+    for scheduling in [Scheduling::Simultaneous, Scheduling::Sequential] {
+        let (world, _, engine) = run(&["A"], scheduling, |args| {
+            args.mega_fraction = Some(1.0);
+            args.blast_radius = 1;
+        });
+
+        assert_eq!(world.alive_count(), 0, "{scheduling:?}");
+        assert_eq!(engine.trampled(), 1, "{scheduling:?}");
+    }
+}
+
+#[test]
+fn the_highest_worker_ids_turn_mega() {
+    let (_, ants, _) = run(&["A", "A", "C", "C"], Scheduling::Simultaneous, |args| {
+        args.mega_fraction = Some(0.5);
+        args.max_ticks = Some(0);
+    });
+
+    let kinds: Vec<AntKind> = ants.iter().map(|a| a.kind).collect();
+    assert_eq!(kinds, [AntKind::Worker, AntKind::Worker, AntKind::Mega, AntKind::Mega]);
+}
+
+#[test]
+fn no_mega_fraction_keeps_the_rules() {
+    let (world, ants, engine) = run(&["A"], Scheduling::Simultaneous, |_| {});
+
+    assert!(ants[0].is_alive() && !ants[0].is_mega());
+    assert_eq!(world.alive_count(), 3);
+    assert!(engine.events().is_empty());
+}
+
+#[test]
+fn trampling_is_logged_and_summarized() {
+    let map = NamedTempFile::new().unwrap();
+    std::fs::write(map.path(), "A east=B\nB\n").unwrap();
+    Command::cargo_bin("ants_code_challenge")
+        .unwrap()
+        .args(["-n", "1", "-m", map.path().to_str().unwrap(), "--mega-fraction", "1", "--seed", "1"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("[t=1] 🦣 B has been trampled by mega ant 0"))
+        .stdout(predicate::str::contains("1 colonies trampled by mega ants"));
+}
@@ -0,0 +1,48 @@
+use assert_cmd::Command;
+use predicates::prelude::*;
+use std::io::Write;
+
+const MAP: &str = "files/hiveum_map_small.txt";
+
+fn run(extra: &[&str]) -> assert_cmd::assert::Assert {
+    Command::cargo_bin("ants_code_challenge")
+        .unwrap()
+        .args(["-n", "40", "-m", MAP, "--seed", "3"])
+        .args(extra)
+        .assert()
+}
+
+#[test]
+fn spanish_templates_replace_the_english_lines() {
+    run(&["--message-template", "files/messages_es.txt"])
+        .success()
+        .stdout(predicate::str::contains("ha sido destruida por ant "))
+        .stdout(predicate::str::contains("has been destroyed by").not());
+}
+
+#[test]
+fn kinds_without_a_template_keep_the_default_wording() {
+    let mut messages = tempfile::NamedTempFile::new().unwrap();
+    messages.write_all(b"blast = {colony} burned\n").unwrap();
+    let default = run(&[]).success().get_output().stdout.clone();
+    let templated = run(&["--message-template", messages.path().to_str().unwrap()])
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    // Only the latency differs between the two runs
+    let events = |out: &[u8]| -> Vec<String> {
+        String::from_utf8_lossy(out).lines().filter(|l| l.starts_with("[t=")).map(String::from).collect()
+    };
+    assert!(!events(&default).is_empty());
+    assert_eq!(events(&default), events(&templated));
+}
+
+#[test]
+fn bad_template_is_rejected_with_its_line() {
+    let mut messages = tempfile::NamedTempFile::new().unwrap();
+    messages.write_all(b"# ok\nfight = {colony} cae ante {ant}\n").unwrap();
+    run(&["--message-template", messages.path().to_str().unwrap()])
+        .failure()
+        .stderr(predicate::str::contains("line 2: unknown placeholder '{ant}'"));
+}
@@ -0,0 +1,120 @@
+use ants_code_challenge::prelude::*;
+use ants_code_challenge::NodeIndex;
+use ants_code_challenge::cli::Scheduling;
+use ants_code_challenge::simulation::{DestructionCause, Multiverse, MultiverseReport, PortalEnd};
+use ants_code_challenge::world::load_world;
+use ants_code_challenge::world::parser::parse_world_from_str;
+
+/// `S <-> P`: an ant on S can only step onto the portal colony P
+const DOOR: &str = "S east=P\nP west=S\n";
+/// `Q <-> R`: the far side of the portal is Q
+const ROOM: &str = "Q east=R\nR west=Q\n";
+
+fn world(src: &str) -> World {
+    let (names, nodes) = parse_world_from_str(src);
+    World::new(names, nodes)
+}
+
+fn args() -> Args {
+    let mut args = Args::with_defaults(0, "unused");
+    args.suppress_events = true;
+    args
+}
+
+fn end(world: usize, colony: NodeIndex) -> PortalEnd {
+    PortalEnd { world, colony: NodeId::new(colony) }
+}
+
+#[test]
+fn arrivals_fight_ants_already_on_the_colony() {
+    let mut multiverse = Multiverse::new();
+    let door = multiverse.add_world(world(DOOR), vec![Ant::new(0, NodeId::new(0))], 1);
+    let room = multiverse.add_world(world(ROOM), vec![Ant::new(0, NodeId::new(1))], 2);
+    multiverse.add_portal(end(door, 1), end(room, 0));
+
+    // Tick 1: S -> P and R -> Q; the first ant then crosses onto Q
+    let report = multiverse.run(&args());
+
+    assert_eq!(report.migrations, 1);
+    assert_eq!((report.worlds[door].departures, report.worlds[room].arrivals), (1, 1));
+    assert_eq!(report.ants_alive(), 0);
+    assert_eq!((report.worlds[door].survivors, report.worlds[room].survivors), (2, 1));
+    assert_eq!(report.survivors(), 3);
+    let events = multiverse.engine(room).events();
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].tick, 1);
+    assert_eq!(events[0].node_id, NodeId::new(0));
+    assert!(matches!(events[0].cause, DestructionCause::Fight { ant1: 0, ant2: 1 }));
+}
+
+#[test]
+fn migrants_keep_moving_in_an_empty_world() {
+    let mut multiverse = Multiverse::new();
+    // A trapped ant on the island T keeps the run from ending with a lone migrant
+    let ants = vec![Ant::new(0, NodeId::new(0)), Ant::new(1, NodeId::new(2))];
+    let door = multiverse.add_world(world("S east=P\nP west=S\nT\n"), ants, 1);
+    let room = multiverse.add_world(world(ROOM), Vec::new(), 2);
+    multiverse.add_portal(end(door, 1), end(room, 0));
+    let mut args = args();
+    args.max_moves = 4;
+
+    let report = multiverse.run(&args);
+
+    assert_eq!(report.migrations, 1);
+    assert!(!multiverse.ants(door)[0].is_alive());
+    let migrant = &multiverse.ants(room)[0];
+    assert!(migrant.is_alive());
+    assert_eq!(migrant.moves, 4);
+    assert_eq!(report.ticks, 4);
+    assert_eq!(report.worlds[room].ticks, 4);
+    assert_eq!(report.worlds[door].ticks, 1);
+}
+
+#[test]
+fn destroyed_portal_colony_closes_the_portal() {
+    let mut multiverse = Multiverse::new();
+    // Two ants start on P and destroy it at t=0; the ant on S is stuck
+    let ants = vec![Ant::new(0, NodeId::new(1)), Ant::new(1, NodeId::new(1)), Ant::new(2, NodeId::new(0))];
+    let door = multiverse.add_world(world(DOOR), ants, 1);
+    let room = multiverse.add_world(world(ROOM), vec![Ant::new(0, NodeId::new(0))], 2);
+    multiverse.add_portal(end(door, 1), end(room, 0));
+
+    let report = multiverse.run(&args());
+
+    assert_eq!(report.migrations, 0);
+    assert_eq!(report.worlds[room].arrivals, 0);
+    assert_eq!(report.ants_alive(), 2);
+}
+
+#[test]
+fn runs_are_reproducible() {
+    for scheduling in [Scheduling::Simultaneous, Scheduling::Sequential] {
+        let mut args = args();
+        args.scheduling = scheduling;
+        args.max_moves = 50;
+        let report = ring(&args);
+        assert_eq!(report, ring(&args));
+        assert_eq!(report.worlds.len(), 3);
+        assert!(report.migrations > 0, "{report:?}");
+        let (left, came) = report.worlds.iter().fold((0, 0), |(l, c), w| (l + w.departures, c + w.arrivals));
+        assert_eq!((left, came), (report.migrations, report.migrations));
+    }
+}
+
+/// Three copies of the small map linked in a ring through their first colony
+fn ring(args: &Args) -> MultiverseReport {
+    let mut multiverse = Multiverse::new();
+    for seed in 0..3u64 {
+        let world = load_world("files/hiveum_map_small.txt", &Default::default()).unwrap();
+        let ants = world.create_ants(6, &mut fastrand::Rng::with_seed(seed));
+        multiverse.add_world(world, ants, seed);
+    }
+    for (from, to) in [(0, 1), (1, 2), (2, 0)] {
+        for colony in 0..28 {
+            multiverse.add_portal(end(from, colony), end(to, colony));
+        }
+    }
+    let mut report = multiverse.run(args);
+    report.latency_ms = 0.0;
+    report
+}
@@ -0,0 +1,55 @@
+use assert_cmd::prelude::*;
+use std::io::Write;
+use std::process::Command;
+use tempfile::NamedTempFile;
+
+const BIN: &str = "ants_code_challenge";
+
+fn map(src: &str) -> NamedTempFile {
+    let mut f = NamedTempFile::new().unwrap();
+    f.write_all(src.as_bytes()).unwrap();
+    f
+}
+
+fn normalize(path: &str, extra: &[&str]) -> String {
+    let output = Command::cargo_bin(BIN).unwrap().args(["normalize-map", "-m", path]).args(extra).output().unwrap();
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    String::from_utf8(output.stdout).unwrap()
+}
+
+#[test]
+fn normalize_map_sorts_and_cleans_up() {
+    let f = map("Zed west=Ann hp=3 # comment\nAnn east=Zed east=Bee \\\n  region=rim alias=A\nZed spawn\nBee north=A\n");
+    assert_eq!(
+        normalize(f.path().to_str().unwrap(), &[]),
+        "Ann east=Bee region=rim\nBee north=Ann\nZed west=Ann hp=3 spawn=1\n"
+    );
+}
+
+#[test]
+fn normalize_map_mirrors_one_way_tunnels() {
+    let f = map("A east=B\nB\n");
+    assert_eq!(normalize(f.path().to_str().unwrap(), &["--mirror"]), "A east=B\nB west=A\n");
+}
+
+#[test]
+fn normalized_output_is_a_fixed_point() {
+    let once = normalize("files/hiveum_map_medium.txt", &[]);
+    let f = map(&once);
+    assert_eq!(normalize(f.path().to_str().unwrap(), &[]), once);
+
+    Command::cargo_bin(BIN).unwrap().args(["normalize-map", "--check", "-m"]).arg(f.path()).assert().success();
+}
+
+#[test]
+fn check_fails_on_unnormalized_maps() {
+    let f = map("B west=A\nA east=B\n");
+    Command::cargo_bin(BIN)
+        .unwrap()
+        .args(["normalize-map", "--check", "-m"])
+        .arg(f.path())
+        .assert()
+        .code(1)
+        .stdout("")
+        .stderr(predicates::str::contains("is not normalized"));
+}
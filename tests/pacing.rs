@@ -0,0 +1,27 @@
+use std::time::{Duration, Instant};
+
+mod common;
+use common::Run;
+
+fn run(scheduling: &str) -> (Duration, Duration, u64) {
+    // Two ants on separate two-colony loops never meet, so the run lasts max_moves ticks
+    let wall = Instant::now();
+    let (_, _, engine, latency) = Run::on("A east=B\nB west=A\nC east=D\nD west=C\n")
+        .at(&["A", "C"])
+        .scheduling(scheduling)
+        .flags(&["--max-moves", "5", "--tick-delay-ms", "30"])
+        .run_timed();
+    (wall.elapsed(), latency, engine.ticks())
+}
+
+#[test]
+fn tick_delay_paces_the_run_but_not_the_latency() {
+    for scheduling in ["simultaneous", "sequential"] {
+        let (wall, latency, ticks) = run(scheduling);
+
+        assert_eq!(ticks, 5, "{scheduling}");
+        // Every tick after the first waits once
+        assert!(wall >= Duration::from_millis(4 * 30), "{scheduling}: {wall:?}");
+        assert!(latency < Duration::from_millis(30), "{scheduling}: {latency:?}");
+    }
+}
@@ -0,0 +1,45 @@
+use assert_cmd::prelude::*;
+use predicates::prelude::*;
+use std::io::Write;
+use std::process::Command;
+use tempfile::NamedTempFile;
+
+const BIN: &str = "ants_code_challenge";
+
+/// A -> B -> C -> D with a shortcut B -> D; nothing leads back to A
+fn map() -> NamedTempFile {
+    let mut f = NamedTempFile::new().unwrap();
+    writeln!(f, "A east=B\nB east=C south=D\nC east=D\nD").unwrap();
+    f
+}
+
+#[test]
+fn path_prints_the_shortest_route() {
+    let f = map();
+    Command::cargo_bin(BIN)
+        .unwrap()
+        .args(["path", "-m", f.path().to_str().unwrap(), "--from", "A", "--to", "D"])
+        .assert()
+        .success()
+        .stdout("A -> B -> D (2 hops)\n");
+}
+
+#[test]
+fn path_json_and_unreachable_status() {
+    let f = map();
+    let output = Command::cargo_bin(BIN)
+        .unwrap()
+        .args(["path", "-m", f.path().to_str().unwrap(), "--from", "D", "--to", "A", "--json"])
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(1));
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json, serde_json::json!({ "from": "D", "to": "A", "path": null }));
+
+    Command::cargo_bin(BIN)
+        .unwrap()
+        .args(["path", "-m", f.path().to_str().unwrap(), "--from", "A", "--to", "Z"])
+        .assert()
+        .code(1)
+        .stderr(predicate::str::contains("no colony named 'Z'"));
+}
@@ -0,0 +1,50 @@
+use ants_code_challenge::prelude::*;
+use ants_code_challenge::world::{load_world, Placement};
+use assert_cmd::Command;
+use std::collections::HashSet;
+
+const MAP: &str = "files/hiveum_map_small.txt";
+
+fn spots(placement: Placement, count: usize) -> Vec<NodeId> {
+    let world = load_world(MAP, &Default::default()).unwrap();
+    world.place_colony(count, 0, placement, &mut fastrand::Rng::with_seed(11)).iter().map(|a| a.pos).collect()
+}
+
+#[test]
+fn uniform_placement_matches_create_colony() {
+    let world = load_world(MAP, &Default::default()).unwrap();
+    let plain: Vec<NodeId> = world.create_colony(50, 5, &mut fastrand::Rng::with_seed(11)).iter().map(|a| a.pos).collect();
+    assert_eq!(spots(Placement::Uniform, 50), plain);
+}
+
+#[test]
+fn poisson_fills_every_colony_before_doubling_up() {
+    let distinct: HashSet<NodeId> = spots(Placement::Poisson, 28).into_iter().collect();
+    assert_eq!(distinct.len(), 28);
+}
+
+#[test]
+fn clusters_cover_few_colonies() {
+    // Two clusters of 10 ants land on at most 5 colonies each
+    let distinct: HashSet<NodeId> = spots(Placement::Cluster { k: 2 }, 10).into_iter().collect();
+    assert!(distinct.len() <= 10, "{distinct:?}");
+    let spread: HashSet<NodeId> = spots(Placement::Cluster { k: 2 }, 2).into_iter().collect();
+    assert_eq!(spread.len(), 2);
+}
+
+#[test]
+fn cli_accepts_placement_modes() {
+    for placement in ["uniform", "weighted-by-degree", "cluster:3", "poisson"] {
+        Command::cargo_bin("ants_code_challenge")
+            .unwrap()
+            .args(["-n", "20", "-m", MAP, "--seed", "3", "--suppress-events", "--placement", placement])
+            .assert()
+            .success();
+    }
+    Command::cargo_bin("ants_code_challenge")
+        .unwrap()
+        .args(["-n", "20", "-m", MAP, "--placement", "cluster:0"])
+        .assert()
+        .code(3)
+        .stderr(predicates::str::contains("cluster count must be positive"));
+}
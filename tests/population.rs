@@ -0,0 +1,69 @@
+use ants_code_challenge::population::Population;
+use assert_cmd::prelude::*;
+use predicates::prelude::*;
+use std::process::Command;
+use tempfile::TempDir;
+
+const BIN: &str = "ants_code_challenge";
+
+/// A ring of eight colonies, so ants keep walking
+const RING: &str = "A east=B\nB west=A east=C\nC west=B east=D\nD west=C east=E\nE west=D east=F\nF west=E east=G\nG west=F east=H\nH west=G east=A\n";
+
+fn run(dir: &TempDir, extra: &[&str]) -> assert_cmd::assert::Assert {
+    let map = dir.path().join("ring.txt");
+    if !map.exists() {
+        std::fs::write(&map, RING).unwrap();
+    }
+    Command::cargo_bin(BIN)
+        .unwrap()
+        .args(["--seed", "4", "--collision-prob", "0", "--suppress-events", "-m", map.to_str().unwrap()])
+        .args(extra)
+        .assert()
+}
+
+#[test]
+fn population_continues_in_a_later_run() {
+    let dir = TempDir::new().unwrap();
+    let first = dir.path().join("first.csv");
+    let second = dir.path().join("second.bin");
+    run(&dir, &["-n", "2", "--max-moves", "3", "--export-ants", first.to_str().unwrap()]).success();
+    run(&dir, &["-n", "0", "--max-moves", "5", "--import-ants", first.to_str().unwrap(), "--export-ants", second.to_str().unwrap()])
+        .success();
+
+    let before = Population::read_file(first.to_str().unwrap()).unwrap();
+    let after = Population::read_file(second.to_str().unwrap()).unwrap();
+    assert_eq!(before.len(), 2);
+    assert!(before.ants.iter().all(|a| a.alive && a.moves == 3));
+    // Same ids, two more moves each under the raised limit
+    assert_eq!(after.ants.iter().map(|a| a.id).collect::<Vec<_>>(), [0, 1]);
+    assert!(after.ants.iter().all(|a| a.alive && a.moves == 5));
+}
+
+#[test]
+fn fresh_ants_join_after_the_imported_ids() {
+    let dir = TempDir::new().unwrap();
+    let first = dir.path().join("first.csv");
+    let second = dir.path().join("second.csv");
+    run(&dir, &["-n", "2", "--max-moves", "3", "--export-ants", first.to_str().unwrap()]).success();
+    run(&dir, &["-n", "3", "--max-moves", "3", "--import-ants", first.to_str().unwrap(), "--export-ants", second.to_str().unwrap()])
+        .success();
+
+    let after = Population::read_file(second.to_str().unwrap()).unwrap();
+    assert_eq!(after.ants.iter().map(|a| a.id).collect::<Vec<_>>(), [0, 1, 2, 3, 4]);
+}
+
+#[test]
+fn ants_on_missing_colonies_die_on_import() {
+    let dir = TempDir::new().unwrap();
+    let ants = dir.path().join("ants.csv");
+    let small = dir.path().join("small.txt");
+    std::fs::write(&small, "Z\n").unwrap();
+    std::fs::write(&ants, "id,kind,colony,home,moves,alive,trapped,death\n0,worker,A,A,0,true,false,\n").unwrap();
+
+    Command::cargo_bin(BIN)
+        .unwrap()
+        .args(["-n", "0", "--suppress-events", "-m", small.to_str().unwrap(), "--import-ants", ants.to_str().unwrap()])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("warning: 1 imported ants stood on colonies"));
+}
@@ -0,0 +1,72 @@
+use assert_cmd::Command;
+use predicates::prelude::*;
+use std::io::Write;
+use tempfile::NamedTempFile;
+
+const BIN: &str = "ants_code_challenge";
+
+/// Both ants land on `Solo` and fight at t=0; `A`/`B` survive
+fn map() -> NamedTempFile {
+    let mut f = NamedTempFile::new().unwrap();
+    writeln!(f, "Solo spawn\nA east=B\nB west=A").unwrap();
+    f
+}
+
+fn run(map: &NamedTempFile, extra: &[&str]) -> (String, String) {
+    let output = Command::cargo_bin(BIN)
+        .unwrap()
+        .args(["-n", "2", "--seed", "1", "--print-world", "-m"])
+        .arg(map.path())
+        .args(extra)
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    (String::from_utf8(output.stdout).unwrap(), String::from_utf8(output.stderr).unwrap())
+}
+
+#[test]
+fn default_mixes_chatter_with_results_on_stdout() {
+    let map = map();
+    let (stdout, _) = run(&map, &[]);
+    assert!(stdout.contains("Solo has been destroyed"));
+    assert!(stdout.contains("A east=B\nB west=A\n"));
+    assert!(stdout.contains("Simulation Latency:"));
+}
+
+#[test]
+fn porcelain_moves_chatter_to_stderr() {
+    let map = map();
+    let (stdout, stderr) = run(&map, &["--porcelain"]);
+    assert_eq!(stdout, "A east=B\nB west=A\n");
+    assert!(stderr.contains("Solo has been destroyed"));
+    assert!(stderr.contains("Simulation Latency:"));
+}
+
+#[test]
+fn porcelain_report_to_stdout_is_plain_json() {
+    let map = map();
+    let output = Command::cargo_bin(BIN)
+        .unwrap()
+        .args(["-n", "2", "--seed", "1", "--porcelain", "--report", "-", "-m"])
+        .arg(map.path())
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(report["survivors"], 2);
+}
+
+#[test]
+fn quiet_drops_chatter() {
+    let map = map();
+    let (stdout, stderr) = run(&map, &["--quiet"]);
+    assert_eq!(stdout, "A east=B\nB west=A\n");
+    assert!(stderr.is_empty());
+    Command::cargo_bin(BIN)
+        .unwrap()
+        .args(["-n", "2", "--quiet", "-m"])
+        .arg(map.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::is_empty());
+}
@@ -0,0 +1,66 @@
+use assert_cmd::Command;
+use predicates::prelude::*;
+use std::io::Write;
+use tempfile::NamedTempFile;
+
+const BIN: &str = "ants_code_challenge";
+
+/// Four colonies, no ants: everything survives
+fn loops() -> NamedTempFile {
+    let mut f = NamedTempFile::new().unwrap();
+    writeln!(f, "A east=B\nB west=A\nC east=D\nD west=C").unwrap();
+    f
+}
+
+#[test]
+fn print_world_writes_survivors_to_stdout() {
+    let map = loops();
+    let output = Command::cargo_bin(BIN)
+        .unwrap()
+        .args(["-n", "0", "-m", map.path().to_str().unwrap(), "--print-world", "--suppress-events"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.starts_with("A east=B\nB west=A\nC east=D\nD west=C\n"));
+}
+
+#[test]
+fn print_world_leaves_out_destroyed_colonies() {
+    // Both ants land on the only colony of the first line and fight at t=0
+    let mut map = NamedTempFile::new().unwrap();
+    writeln!(map, "Solo").unwrap();
+    Command::cargo_bin(BIN)
+        .unwrap()
+        .args(["-n", "2", "-m", map.path().to_str().unwrap(), "--print-world", "--suppress-events"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Solo").not());
+}
+
+#[test]
+fn print_world_to_file_keeps_stdout_clean() {
+    let map = loops();
+    let out = NamedTempFile::new().unwrap();
+    let flag = format!("--print-world={}", out.path().display());
+    Command::cargo_bin(BIN)
+        .unwrap()
+        .args(["-n", "0", "-m", map.path().to_str().unwrap(), &flag, "--sort-output", "name", "--suppress-events"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("A east=B").not());
+
+    assert_eq!(std::fs::read_to_string(out.path()).unwrap(), "A east=B\nB west=A\nC east=D\nD west=C\n");
+}
+
+#[test]
+fn world_is_not_printed_by_default() {
+    let map = loops();
+    Command::cargo_bin(BIN)
+        .unwrap()
+        .args(["-n", "0", "-m", map.path().to_str().unwrap(), "--suppress-events"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("A east=B").not())
+        .stdout(predicate::str::contains("survivors=4"));
+}
@@ -0,0 +1,62 @@
+use ants_code_challenge::prelude::*;
+
+mod common;
+use common::Run;
+
+/// A 4x4 torus, small enough for ants to fight and blasts to spread
+fn torus() -> String {
+    let name = |r: usize, c: usize| format!("R{}C{}", r % 4, c % 4);
+    (0..16)
+        .map(|i| {
+            let (r, c) = (i / 4, i % 4);
+            format!(
+                "{} north={} south={} east={} west={}\n",
+                name(r, c),
+                name(r + 3, c),
+                name(r + 1, c),
+                name(r, c + 1),
+                name(r, c + 3)
+            )
+        })
+        .collect()
+}
+
+fn run(prune: bool, seed: u64) -> (World, SimulationEngine) {
+    let prune: &[&str] = if prune { &["--prune-dead-edges"] } else { &[] };
+    let (world, _, engine) = Run::on(&torus())
+        .ants(|world, rng| world.create_ants(12, rng))
+        .flags(&["--blast-radius", "1"])
+        .flags(prune)
+        .seed(seed)
+        .run();
+    (world, engine)
+}
+
+#[test]
+fn pruning_leaves_no_edges_into_ruins() {
+    for seed in 0..8 {
+        let (world, _) = run(true, seed);
+        assert!(world.prunes_dead_edges());
+        for node in world.nodes().iter().filter(|n| n.is_alive()) {
+            assert!(node.neighbors.into_iter().flatten().all(|n| world.is_alive(n)), "seed {seed}");
+        }
+    }
+}
+
+#[test]
+fn pruning_does_not_change_the_run() {
+    let mut fell = 0;
+    for seed in 0..8 {
+        let (pruned, pruned_engine) = run(true, seed);
+        let (plain, plain_engine) = run(false, seed);
+        assert_eq!(pruned_engine.events(), plain_engine.events(), "seed {seed}");
+        assert_eq!(pruned_engine.ticks(), plain_engine.ticks(), "seed {seed}");
+
+        let (mut a, mut b) = (Vec::new(), Vec::new());
+        pruned.write_map(&mut a).unwrap();
+        plain.write_map(&mut b).unwrap();
+        assert_eq!(a, b, "seed {seed}");
+        fell += plain.nodes().len() - plain.count_survivors();
+    }
+    assert!(fell > 0);
+}
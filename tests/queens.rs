@@ -0,0 +1,82 @@
+use ants_code_challenge::prelude::*;
+use ants_code_challenge::{AntKind, KindStats};
+use clap::Parser;
+
+mod common;
+use common::Run;
+
+/// A long one-way corridor: the queen marches east and lays a worker behind her
+fn corridor(len: usize) -> String {
+    (0..len)
+        .map(|i| if i + 1 < len { format!("C{i} east=C{}\n", i + 1) } else { format!("C{i}\n") })
+        .collect()
+}
+
+/// A queen at the west end of a 20-colony corridor, a worker at the east end
+fn run(scheduling: &str, flags: &[&str]) -> (World, Vec<Ant>, SimulationEngine) {
+    Run::on(&corridor(20))
+        .ants(|_, _| vec![Ant::queen(0, NodeId::new(0)), Ant::new(1, NodeId::new(19))])
+        .scheduling(scheduling)
+        .flags(flags)
+        .seed(4)
+        .run()
+}
+
+#[test]
+fn moving_queen_spawns_workers_periodically() {
+    let (world, ants, _) = run("simultaneous", &["--max-moves", "3", "--spawn-every", "2"]);
+
+    // Queen spawns at C2 on tick 2; on tick 3 the newborn can only follow her
+    // east, so both land on C3 and fight
+    assert_eq!(KindStats::of(&ants, AntKind::Queen).total, 1);
+    assert_eq!(KindStats::of(&ants, AntKind::Worker).total, 2);
+    assert_eq!(ants[2].id, 2);
+    assert!(!ants[2].is_queen());
+    assert!(!ants[0].is_alive() && !ants[2].is_alive());
+    assert!(!world.is_alive(NodeId::new(3)));
+}
+
+#[test]
+fn sequential_queen_spawns_workers_periodically() {
+    let (world, ants, _) = run("sequential", &["--max-moves", "3", "--spawn-every", "2"]);
+
+    // The queen moves first, then the newborn walks into her
+    assert_eq!(KindStats::of(&ants, AntKind::Worker).total, 2);
+    assert!(!ants[0].is_alive() && !ants[2].is_alive());
+    assert!(!world.is_alive(NodeId::new(3)));
+}
+
+#[test]
+fn queen_done_moving_stops_spawning() {
+    // The queen makes her last move on tick 2, the first spawn tick
+    let (_, ants, _) = run("simultaneous", &["--max-moves", "2", "--spawn-every", "2"]);
+    assert_eq!(ants.len(), 2);
+}
+
+#[test]
+fn no_queens_means_no_spawns() {
+    let (_, ants, _) =
+        Run::on(&corridor(5)).ants(|_, _| vec![Ant::new(0, NodeId::new(0)), Ant::new(1, NodeId::new(4))]).seed(4).run();
+
+    assert_eq!(ants.len(), 2);
+}
+
+#[test]
+fn engine_keeps_count_of_alive_ants_through_births_and_fights() {
+    for scheduling in ["simultaneous", "sequential"] {
+        let (_, ants, engine) = run(scheduling, &["--max-moves", "8", "--spawn-every", "3"]);
+
+        assert!(ants.len() > 2, "{scheduling}");
+        assert_eq!(engine.ants_alive(), ants.iter().filter(|a| a.is_alive()).count(), "{scheduling}");
+    }
+}
+
+#[test]
+fn queen_ratio_is_a_probability() {
+    for ratio in ["--queen-ratio=5", "--queen-ratio=-1"] {
+        let err = Args::try_parse_from(["ants", "-n", "4", "-m", "unused", ratio]).unwrap_err();
+        assert!(err.to_string().contains("outside 0.0..=1.0"), "{err}");
+    }
+    let args = Args::try_parse_from(["ants", "-n", "4", "-m", "unused", "--queen-ratio", "0.5"]).unwrap();
+    assert_eq!(args.queen_count(), 2);
+}
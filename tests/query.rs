@@ -0,0 +1,53 @@
+use ants_code_challenge::world::ColonyInfo;
+use assert_cmd::prelude::*;
+use predicates::prelude::*;
+use std::io::Write;
+use std::process::Command;
+use tempfile::NamedTempFile;
+
+const BIN: &str = "ants_code_challenge";
+
+fn map() -> NamedTempFile {
+    let mut f = NamedTempFile::new().unwrap();
+    writeln!(f, "Bar east=Fizz\nFizz west=Bar south=Buzz\nBuzz\nQux north=Fizz").unwrap();
+    f
+}
+
+#[test]
+fn query_prints_tunnels_degree_and_reachability() -> Result<(), Box<dyn std::error::Error>> {
+    let f = map();
+    let output = Command::cargo_bin(BIN)?
+        .args(["query", "-m", f.path().to_str().unwrap(), "--colony", "Fizz", "--from", "Qux"])
+        .output()?;
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout)?;
+    assert!(stdout.contains("neighbors:    south=Buzz west=Bar"), "{stdout}");
+    assert!(stdout.contains("degree:       out 2, in 2"), "{stdout}");
+    assert!(stdout.contains("reachable:    from Qux in 1 hops"), "{stdout}");
+    Ok(())
+}
+
+#[test]
+fn query_json_round_trips() -> Result<(), Box<dyn std::error::Error>> {
+    let f = map();
+    let output = Command::cargo_bin(BIN)?
+        .args(["query", "-m", f.path().to_str().unwrap(), "-c", "Qux", "--from", "Buzz", "--json"])
+        .output()?;
+    assert!(output.status.success());
+    let info: ColonyInfo = serde_json::from_slice(&output.stdout)?;
+    assert_eq!(info.colony, "Qux");
+    assert!(info.in_neighbors.is_empty());
+    assert!(!info.reachable.unwrap().reachable());
+    Ok(())
+}
+
+#[test]
+fn query_rejects_unknown_colonies() -> Result<(), Box<dyn std::error::Error>> {
+    let f = map();
+    Command::cargo_bin(BIN)?
+        .args(["query", "-m", f.path().to_str().unwrap(), "-c", "Nope"])
+        .assert()
+        .code(1)
+        .stderr(predicate::str::contains("no colony named 'Nope'"));
+    Ok(())
+}
@@ -0,0 +1,63 @@
+use ants_code_challenge::prelude::*;
+use ants_code_challenge::simulation::{DestructionCause, Reinforcement};
+use assert_cmd::prelude::*;
+use predicates::prelude::*;
+use std::process::Command;
+
+mod common;
+use common::Run;
+
+const RING: &str = "A east=B\nB east=C\nC east=D\nD east=A\n";
+
+fn run(map: &str, ants: Vec<Ant>, configure: impl FnOnce(&mut Args)) -> (World, Vec<Ant>, SimulationEngine) {
+    Run::on(map).ants(|_, _| ants).seed(9).args(configure).run()
+}
+
+#[test]
+fn a_run_without_moving_ants_waits_for_its_wave() {
+    let (_, ants, engine) = run(RING, Vec::new(), |args| {
+        args.waves = vec![Reinforcement { tick: 5, ants: 1 }];
+    });
+
+    // Lands after tick 5; alone, it ends the run after its first step
+    assert_eq!(engine.ticks(), 6);
+    assert_eq!(ants.len(), 1);
+    assert!(ants[0].is_alive() && ants[0].moves == 1);
+}
+
+#[test]
+fn landing_ants_fight_whoever_is_there() {
+    let (world, ants, engine) = run("A\n", vec![Ant::new(0, NodeId::from_index(0))], |args| {
+        args.waves = vec![Reinforcement { tick: 0, ants: 1 }];
+    });
+
+    assert_eq!(ants.len(), 2);
+    assert!(ants.iter().all(|a| !a.is_alive()));
+    assert_eq!(world.count_survivors(), 0);
+    let events = engine.events();
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].tick, 0);
+    assert_eq!(events[0].cause, DestructionCause::Fight { ant1: 0, ant2: 1 });
+}
+
+#[test]
+fn waves_past_max_ticks_never_land() {
+    let (_, ants, engine) = run(RING, Vec::new(), |args| {
+        args.max_ticks = Some(4);
+        args.waves = vec![Reinforcement { tick: 2, ants: 1 }, Reinforcement { tick: 9, ants: 5 }];
+    });
+
+    assert_eq!(engine.ticks(), 4);
+    assert_eq!(ants.len(), 1);
+    assert_eq!(ants[0].moves, 2);
+}
+
+#[test]
+fn wave_flag_is_validated() {
+    Command::cargo_bin("ants_code_challenge")
+        .unwrap()
+        .args(["-n", "1", "-m", "files/hiveum_map_small.txt", "--wave", "tick=5"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("needs both tick=T and ants=N"));
+}
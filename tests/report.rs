@@ -0,0 +1,127 @@
+use assert_cmd::prelude::*;
+use std::io::Write;
+use std::process::Command;
+use tempfile::NamedTempFile;
+
+const BIN: &str = "ants_code_challenge";
+
+#[test]
+fn report_contains_timeline_with_ticks() -> Result<(), Box<dyn std::error::Error>> {
+    let mut f = NamedTempFile::new()?;
+    writeln!(f, "X")?;
+    let report = NamedTempFile::new()?;
+
+    let mut cmd = Command::cargo_bin(BIN)?;
+    cmd.args([
+        "-n", "2",
+        "-m", f.path().to_str().unwrap(),
+        "--seed", "5",
+        "--suppress-events",
+        "--report", report.path().to_str().unwrap(),
+    ]);
+    cmd.assert().success();
+
+    let json: serde_json::Value = serde_json::from_reader(std::fs::File::open(report.path())?)?;
+    assert_eq!(json["survivors"], 0);
+    let timeline = json["timeline"].as_array().expect("timeline array");
+    assert_eq!(timeline.len(), 1);
+    assert_eq!(timeline[0]["tick"], 0);
+    assert_eq!(timeline[0]["colony"], "X");
+    assert_eq!(timeline[0]["kind"], "fight");
+
+    Ok(())
+}
+
+#[test]
+fn heatmap_is_embedded_in_report_and_written_as_csv() -> Result<(), Box<dyn std::error::Error>> {
+    let mut f = NamedTempFile::new()?;
+    writeln!(f, "A east=B\nB west=A\nC")?;
+    let report = NamedTempFile::new()?;
+    let csv = NamedTempFile::new()?;
+
+    Command::cargo_bin(BIN)?
+        .args([
+            "-n", "2",
+            "-m", f.path().to_str().unwrap(),
+            "--seed", "3",
+            "--suppress-events",
+            "--report", report.path().to_str().unwrap(),
+            "--heatmap-csv", csv.path().to_str().unwrap(),
+        ])
+        .assert()
+        .success();
+
+    let json: serde_json::Value = serde_json::from_reader(std::fs::File::open(report.path())?)?;
+    let heatmap = json["heatmap"].as_array().expect("heatmap array");
+    assert_eq!(heatmap.len(), 3);
+    let total: u64 = heatmap.iter().map(|e| e["visits"].as_u64().unwrap()).sum();
+    assert!(total >= 2);
+
+    let csv = std::fs::read_to_string(csv.path())?;
+    assert!(csv.starts_with("colony,visits,destroyed\nA,"));
+    assert_eq!(csv.lines().count(), 4);
+
+    Ok(())
+}
+
+fn report_json(extra: &[&str]) -> Result<serde_json::Value, Box<dyn std::error::Error>> {
+    let report = NamedTempFile::new()?;
+    let mut args = vec![
+        "-n", "500",
+        "-m", "files/hiveum_map_small.txt",
+        "--seed", "11",
+        "--suppress-events",
+        "--report", report.path().to_str().unwrap(),
+    ];
+    args.extend_from_slice(extra);
+    Command::cargo_bin(BIN)?.args(args).assert().success();
+    Ok(serde_json::from_reader(std::fs::File::open(report.path())?)?)
+}
+
+#[test]
+fn report_exposes_derived_sub_seeds() -> Result<(), Box<dyn std::error::Error>> {
+    let json = report_json(&[])?;
+    let expected = ants_code_challenge::seeds::SubSeeds::derive(11);
+
+    assert_eq!(json["seeds"]["master"], 11);
+    assert_eq!(json["seeds"]["placement"], expected.placement);
+    assert_eq!(json["seeds"]["movement"], expected.movement);
+    Ok(())
+}
+
+#[test]
+fn drawing_move_limits_does_not_perturb_movement() -> Result<(), Box<dyn std::error::Error>> {
+    // Same limit for everyone, but drawn from the spawning stream
+    let plain = report_json(&[])?;
+    let drawn = report_json(&["--max-moves-dist", "uniform:10000,10000"])?;
+
+    assert_eq!(plain["survivors"], drawn["survivors"]);
+    assert_eq!(plain["ticks"], drawn["ticks"]);
+    assert_eq!(plain["timeline"], drawn["timeline"]);
+    Ok(())
+}
+
+#[test]
+fn report_tallies_how_ants_stopped() -> Result<(), Box<dyn std::error::Error>> {
+    let mut f = NamedTempFile::new()?;
+    writeln!(f, "A east=B\nB west=A")?;
+
+    // Near misses only: both ants walk the loop until their moves run out
+    let output = Command::cargo_bin(BIN)?
+        .args(["-n", "2", "--seed", "1", "--max-moves", "3", "--collision-prob", "0", "--quiet", "--report", "-", "-m"])
+        .arg(f.path())
+        .output()?;
+    assert!(output.status.success());
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+    assert_eq!(json["ticks"], 3);
+    assert_eq!(json["last_destruction"], serde_json::Value::Null);
+    assert_eq!(json["outcomes"], serde_json::json!({"max_moves": 2, "died": 0, "starved": 0, "trapped": 0, "moving": 0}));
+
+    let mut x = NamedTempFile::new()?;
+    writeln!(x, "X")?;
+    let output = Command::cargo_bin(BIN)?.args(["-n", "2", "--seed", "5", "-m"]).arg(x.path()).output()?;
+    let stdout = String::from_utf8(output.stdout)?;
+    assert!(stdout.contains("max_moves=0 died=2 trapped=0 moving=0 | ticks=0 last_destruction=t0"), "{stdout}");
+
+    Ok(())
+}
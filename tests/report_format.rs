@@ -0,0 +1,42 @@
+use assert_cmd::Command;
+use predicates::prelude::*;
+
+const SMALL: &str = "files/hiveum_map_small.txt";
+
+fn report(format: &str) -> String {
+    let output = Command::cargo_bin("ants_code_challenge")
+        .unwrap()
+        .current_dir(env!("CARGO_MANIFEST_DIR"))
+        .args(["-m", SMALL, "-n", "20", "--seed", "3", "--quiet", "--report", "-", "--report-format", format])
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    String::from_utf8(output.stdout).unwrap()
+}
+
+#[test]
+fn every_format_renders_the_same_run() {
+    let json: serde_json::Value = serde_json::from_str(&report("json")).unwrap();
+    let survivors = json["survivors"].as_u64().unwrap();
+
+    assert!(report("text").contains(&format!("ants=20 max_moves=10000 survivors={survivors}")));
+
+    let csv = report("csv");
+    let row: Vec<&str> = csv.lines().nth(1).unwrap().split(',').collect();
+    assert_eq!(row[..2], ["20", "10000"]);
+    assert_eq!(row[7], survivors.to_string());
+
+    let md = report("markdown");
+    assert!(md.starts_with("## Run summary"));
+    assert!(md.contains(&format!(" | {survivors} | ")));
+}
+
+#[test]
+fn report_format_needs_a_report() {
+    Command::cargo_bin("ants_code_challenge")
+        .unwrap()
+        .args(["-m", SMALL, "-n", "2", "--report-format", "csv"])
+        .assert()
+        .code(3)
+        .stderr(predicate::str::contains("--report <REPORT>"));
+}
@@ -0,0 +1,50 @@
+#![cfg(feature = "sqlite")]
+
+use ants_code_challenge::db::ResultsDb;
+use assert_cmd::prelude::*;
+use std::process::Command;
+use tempfile::TempDir;
+
+const BIN: &str = "ants_code_challenge";
+const MAP: &str = "files/hiveum_map_small.txt";
+
+fn count(db: &ResultsDb, sql: &str) -> i64 {
+    db.connection().query_row(sql, [], |row| row.get(0)).unwrap()
+}
+
+#[test]
+fn runs_and_sweeps_share_one_database() {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("results.sqlite");
+    let db_path = path.to_str().unwrap();
+    for seed in ["1", "2"] {
+        Command::cargo_bin(BIN)
+            .unwrap()
+            .args(["-n", "40", "--seed", seed, "--suppress-events", "-m", MAP, "--db", db_path])
+            .assert()
+            .success();
+    }
+    Command::cargo_bin(BIN)
+        .unwrap()
+        .args(["sweep", "-m", MAP, "-n", "10,20", "--seeds", "1..3", "--db", db_path])
+        .assert()
+        .success();
+
+    let db = ResultsDb::open(db_path).unwrap();
+    assert_eq!(count(&db, "SELECT COUNT(*) FROM runs"), 8);
+    assert_eq!(count(&db, "SELECT COUNT(DISTINCT run_id) FROM parameters WHERE name = 'seed'"), 8);
+    // Every run lists every colony; only the two full runs have events
+    let colonies = count(&db, "SELECT COUNT(*) FROM colonies WHERE run_id = 1");
+    assert!(colonies > 0);
+    assert_eq!(count(&db, "SELECT COUNT(*) FROM colonies"), 8 * colonies);
+    assert_eq!(count(&db, "SELECT COUNT(DISTINCT run_id) FROM events"), 2);
+    // A fallen colony of a full run carries the tick it fell on
+    assert_eq!(
+        count(&db, "SELECT COUNT(*) FROM colonies WHERE run_id <= 2 AND destroyed AND destroyed_tick IS NULL"),
+        0
+    );
+    assert_eq!(
+        count(&db, "SELECT survivors FROM runs WHERE id = 1"),
+        count(&db, "SELECT COUNT(*) FROM colonies WHERE run_id = 1 AND NOT destroyed")
+    );
+}
@@ -0,0 +1,67 @@
+use ants_code_challenge::prelude::*;
+use assert_cmd::prelude::*;
+use predicates::prelude::*;
+use std::process::Command;
+
+mod common;
+use common::Run;
+
+/// A two-way ring of four colonies
+const RING: &str = "A east=B south=D\nB west=A south=C\nC north=B west=D\nD east=C north=A\n";
+
+/// Two ants walking a ring whose colony C already fell; they never fight
+fn run(configure: impl FnOnce(&mut Args)) -> (World, SimulationEngine) {
+    let (world, _, engine) = Run::on(RING)
+        .prepare(|world| {
+            world.destroy(world.find("C").unwrap());
+        })
+        .at(&["A", "B"])
+        .flags(&["--max-moves", "4", "--collision-prob", "0"])
+        .seed(3)
+        .args(configure)
+        .run();
+    (world, engine)
+}
+
+#[test]
+fn a_sure_revival_rebuilds_the_ruin_once() {
+    let (world, engine) = run(|args| args.revival_prob = Some(1.0));
+
+    // B and D both border C; B comes first and D finds nothing left to rebuild
+    assert_eq!(engine.revived(), 1);
+    assert_eq!(world.alive_count(), 4);
+}
+
+#[test]
+fn no_revival_leaves_the_ruin() {
+    let (world, engine) = run(|args| args.revival_prob = Some(0.0));
+
+    assert_eq!(engine.revived(), 0);
+    assert!(!world.is_alive(world.find("C").unwrap()));
+}
+
+#[test]
+fn rebuilt_colonies_get_tunnels_back_from_their_rebuilder_only() {
+    let (world, engine) = run(|args| {
+        args.revival_prob = Some(1.0);
+        args.prune_dead_edges = true;
+    });
+    let id = |name| world.find(name).unwrap();
+    let tunnels = |name| world.nodes().neighbors(id(name).index());
+
+    assert_eq!(engine.revived(), 1);
+    assert!(tunnels("B").contains(&Some(id("C"))));
+    assert!(!tunnels("D").contains(&Some(id("C"))));
+    // C's own tunnels all lead somewhere standing
+    assert!(tunnels("C").into_iter().flatten().all(|n| world.is_alive(n)));
+}
+
+#[test]
+fn revival_prob_is_a_probability() {
+    Command::cargo_bin("ants_code_challenge")
+        .unwrap()
+        .args(["-n", "1", "-m", "unused.txt", "--revival-prob", "1.5"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("outside 0.0..=1.0"));
+}
@@ -0,0 +1,61 @@
+use assert_cmd::prelude::*;
+use std::io::Write;
+use std::process::Command;
+use tempfile::NamedTempFile;
+
+const BIN: &str = "ants_code_challenge";
+
+#[test]
+fn scale_map_writes_suffixed_copies() -> Result<(), Box<dyn std::error::Error>> {
+    let mut f = NamedTempFile::new()?;
+    writeln!(f, "A east=B\nB west=A")?;
+
+    let output = Command::cargo_bin(BIN)?
+        .args(["scale-map", "-m", f.path().to_str().unwrap(), "-c", "2", "--bridge"])
+        .output()?;
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8(output.stdout)?,
+        "A_0 east=B_0\nB_0 east=A_1 west=A_0\nA_1 east=B_1 west=B_0\nB_1 west=A_1\n"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn scaled_map_is_a_valid_simulation_input() -> Result<(), Box<dyn std::error::Error>> {
+    let scaled = NamedTempFile::new()?;
+    Command::cargo_bin(BIN)?
+        .args([
+            "scale-map",
+            "-m", "files/hiveum_map_small.txt",
+            "-c", "3",
+            "-o", scaled.path().to_str().unwrap(),
+        ])
+        .assert()
+        .success();
+
+    Command::cargo_bin(BIN)?
+        .args(["-n", "10", "-m", scaled.path().to_str().unwrap(), "--seed", "1", "--suppress-events"])
+        .assert()
+        .success();
+
+    Ok(())
+}
+
+#[test]
+fn scale_map_keeps_colony_attributes() -> Result<(), Box<dyn std::error::Error>> {
+    let mut f = NamedTempFile::new()?;
+    writeln!(f, "A east=B hp=3\nB west=A region=rim")?;
+
+    let output = Command::cargo_bin(BIN)?
+        .args(["scale-map", "-m", f.path().to_str().unwrap(), "-c", "2"])
+        .output()?;
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8(output.stdout)?,
+        "A_0 east=B_0 hp=3\nB_0 west=A_0 region=rim\nA_1 east=B_1 hp=3\nB_1 west=A_1 region=rim\n"
+    );
+
+    Ok(())
+}
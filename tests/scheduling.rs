@@ -0,0 +1,36 @@
+use ants_code_challenge::prelude::*;
+
+mod common;
+use common::Run;
+
+/// Two ants facing each other across a single two-way tunnel
+fn run_swap(scheduling: &str) -> (World, Vec<Ant>) {
+    let (world, ants, _) =
+        Run::on("A east=B\nB west=A\n").at(&["A", "B"]).scheduling(scheduling).flags(&["--max-moves", "3"]).seed(9).run();
+    (world, ants)
+}
+
+#[test]
+fn simultaneous_ants_pass_through_each_other() {
+    let (world, ants) = run_swap("simultaneous");
+    assert_eq!(world.count_survivors(), 2);
+    assert!(ants.iter().all(|a| a.is_alive() && a.moves == 3));
+}
+
+#[test]
+fn sequential_ants_collide_on_first_move() {
+    let (world, ants) = run_swap("sequential");
+    // Ant 0 moves first and lands on ant 1 at B
+    assert!(!world.is_alive(world.find("B").unwrap()));
+    assert!(world.is_alive(world.find("A").unwrap()));
+    assert!(ants.iter().all(|a| !a.is_alive()));
+}
+
+#[test]
+fn sequential_arrival_on_stationary_ant_collides() {
+    let (world, ants, _) = Run::on("A east=B\nB east=C\nC\n").at(&["B", "A"]).scheduling("sequential").seed(3).run();
+
+    // Ant 0 reaches C and gets trapped there; ant 1 follows and collides with it
+    assert!(!world.is_alive(world.find("C").unwrap()));
+    assert!(ants.iter().all(|a| !a.is_alive()));
+}
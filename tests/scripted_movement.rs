@@ -0,0 +1,54 @@
+use ants_code_challenge::prelude::*;
+use ants_code_challenge::simulation::{DestructionCause, DestructionEvent, ScriptedStrategy};
+use Direction::{East, West};
+
+mod common;
+use common::Run;
+
+/// C0 .. C9 in a row, and six colonies without tunnels (X0 .. X5) to park
+/// the ants that sit the run out
+fn map() -> String {
+    let mut map = "C0 east=C1\n".to_string();
+    map.extend((1..9).map(|i| format!("C{i} west=C{} east=C{}\n", i - 1, i + 1)));
+    map.push_str("C9 west=C8\n");
+    map.extend((0..6).map(|i| format!("X{i}\n")));
+    map
+}
+
+/// Eight ants: 3 on C0, 7 on C8, the others parked
+fn run(scheduling: &str, script: ScriptedStrategy) -> (World, Vec<Ant>, Vec<DestructionEvent>) {
+    let (world, ants, engine) = Run::on(&map())
+        .at(&["X0", "X1", "X2", "C0", "X3", "X4", "X5", "C8"])
+        .scheduling(scheduling)
+        .engine(|engine| engine.with_move_strategy(script))
+        .run();
+    let events = engine.events().to_vec();
+    (world, ants, events)
+}
+
+#[test]
+fn scripted_ants_meet_where_and_when_written() {
+    let script = ScriptedStrategy::new().ant(3, [East; 5]).ant(7, [West, East, West, West, West]);
+    for scheduling in ["simultaneous", "sequential"] {
+        let (world, ants, events) = run(scheduling, script.clone());
+        let c5 = world.find("C5").unwrap();
+
+        assert_eq!(events.len(), 1, "{scheduling}");
+        assert_eq!((events[0].tick, events[0].node_id), (5, c5), "{scheduling}");
+        let DestructionCause::Fight { ant1, ant2 } = events[0].cause else { panic!("{scheduling}: {:?}", events[0]) };
+        assert_eq!((ant1.min(ant2), ant1.max(ant2)), (3, 7), "{scheduling}");
+        assert!(!ants[3].is_alive() && !ants[7].is_alive(), "{scheduling}");
+        assert!(ants.iter().filter(|a| a.id != 3 && a.id != 7).all(|a| a.is_alive() && a.moves == 0), "{scheduling}");
+    }
+}
+
+#[test]
+fn ants_stop_where_their_script_ends() {
+    let script = ScriptedStrategy::new().ant(3, [East, East]).ant(7, [West]);
+    let (world, ants, events) = run("simultaneous", script);
+
+    assert!(events.is_empty());
+    assert_eq!(ants[3].pos, world.find("C2").unwrap());
+    assert_eq!(ants[7].pos, world.find("C7").unwrap());
+    assert!(ants.iter().all(|a| a.is_alive() && a.is_trapped()));
+}
@@ -0,0 +1,48 @@
+use ants_code_challenge::prelude::*;
+use ants_code_challenge::simulation::{DestructionCause, DestructionEvent};
+use ants_code_challenge::world::{load_world_from_str, ParseOptions};
+use ants_code_challenge::{AntKind, NodeIndex};
+
+#[test]
+fn world_round_trips_with_attributes_and_reverse_edges() {
+    let world = load_world_from_str("A east=B hp=3\nB west=A south=C\nC region=x\n", &ParseOptions::default()).unwrap();
+    let json = serde_json::to_string(&world).unwrap();
+    let back: World = serde_json::from_str(&json).unwrap();
+
+    assert_eq!(serde_json::to_string(&back).unwrap(), json);
+    assert_eq!(back.attr::<u32>("hp").unwrap()[0], Some(3));
+    assert_eq!(back.in_neighbors(NodeId::new(1)), world.in_neighbors(NodeId::new(1)));
+    assert!(!json.contains("in_edges"));
+}
+
+#[test]
+fn world_rejects_tunnels_past_the_node_table() {
+    let json = r#"{"names":["A"],"nodes":[{"name_idx":0,"neighbors":[5,null,null,null],"alive":true}]}"#;
+    let err = serde_json::from_str::<World>(json).unwrap_err().to_string();
+    assert!(err.contains("tunnel to 5"), "{err}");
+
+    let json = r#"{"names":[],"nodes":[{"name_idx":0,"neighbors":[null,null,null,null],"alive":true}]}"#;
+    assert!(serde_json::from_str::<World>(json).is_err());
+}
+
+#[test]
+fn ants_and_events_round_trip() {
+    let mut ant = Ant::queen(7, NodeId::new(3));
+    ant.set_trapped(true);
+    ant.moves = 12;
+    let back: Ant = serde_json::from_str(&serde_json::to_string(&ant).unwrap()).unwrap();
+    assert_eq!((back.id, back.pos, back.moves, back.kind), (7, NodeId::new(3), 12, AntKind::Queen));
+    assert!(back.is_alive() && back.is_trapped());
+
+    let event = DestructionEvent { tick: 4, node_id: NodeId::new(1), cause: DestructionCause::Blast { origin: NodeId::new(0) } };
+    let json = serde_json::to_string(&event).unwrap();
+    assert_eq!(json, r#"{"tick":4,"node_id":1,"kind":"blast","origin":0}"#);
+    assert_eq!(serde_json::from_str::<DestructionEvent>(&json).unwrap(), event);
+}
+
+#[test]
+fn directions_use_map_spelling() {
+    assert_eq!(serde_json::to_string(&Direction::West).unwrap(), r#""west""#);
+    assert_eq!(serde_json::from_str::<Direction>(r#""north""#).unwrap(), Direction::North);
+    assert!(serde_json::from_str::<NodeId>(&NodeIndex::MAX.to_string()).is_err());
+}
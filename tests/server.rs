@@ -0,0 +1,113 @@
+#![cfg(feature = "server")]
+
+use serde_json::{json, Value};
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::TcpStream;
+use std::process::{Child, Command, Stdio};
+use std::time::Duration;
+
+/// `serve` on a free port, killed on drop
+struct Server {
+    child: Child,
+    addr: String,
+}
+
+impl Server {
+    fn start(extra: &[&str]) -> Self {
+        let mut child = Command::new(env!("CARGO_BIN_EXE_ants_code_challenge"))
+            .args(["serve", "--addr", "127.0.0.1:0"])
+            .args(extra)
+            .stderr(Stdio::piped())
+            .spawn()
+            .unwrap();
+        let mut line = String::new();
+        BufReader::new(child.stderr.take().unwrap()).read_line(&mut line).unwrap();
+        let addr = line.trim().strip_prefix("listening on http://").expect(&line).to_string();
+        Self { child, addr }
+    }
+
+    /// `(status, JSON body)` of one request
+    fn request(&self, method: &str, path: &str, body: &str) -> (u16, Value) {
+        let mut stream = TcpStream::connect(&self.addr).unwrap();
+        let content_type = if body.starts_with('{') { "application/json" } else { "text/plain" };
+        write!(
+            stream,
+            "{method} {path} HTTP/1.1\r\nHost: test\r\nConnection: close\r\nContent-Type: {content_type}\r\nContent-Length: {}\r\n\r\n{body}",
+            body.len()
+        )
+        .unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        let status = response[9..12].parse().unwrap();
+        let (_, body) = response.split_once("\r\n\r\n").unwrap();
+        (status, serde_json::from_str(body).unwrap_or(Value::Null))
+    }
+}
+
+impl Drop for Server {
+    fn drop(&mut self) {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
+
+#[test]
+fn upload_run_poll_and_fetch() {
+    let server = Server::start(&[]);
+
+    let (status, map) = server.request("POST", "/maps", "A east=B\nB west=A east=C\nC west=B\n");
+    assert_eq!(status, 201);
+    assert_eq!(map, json!({ "id": 0, "colonies": 3 }));
+
+    let request = json!({ "map": 0, "ants": 4, "seed": 7, "max_moves": 50 }).to_string();
+    let (status, started) = server.request("POST", "/simulations", &request);
+    assert_eq!(status, 202);
+    assert_eq!(started["id"], 0);
+
+    let mut polled = Value::Null;
+    for _ in 0..100 {
+        polled = server.request("GET", "/simulations/0", "").1;
+        if polled["state"] == "done" {
+            break;
+        }
+        std::thread::sleep(Duration::from_millis(20));
+    }
+    assert_eq!(polled["state"], "done", "{polled}");
+
+    let (status, report) = server.request("GET", "/simulations/0/result", "");
+    assert_eq!(status, 200);
+    assert_eq!(report["seed"], 7);
+    assert_eq!(report["ants"], 4);
+    assert_eq!(report["survivors"], polled["survivors"]);
+    assert_eq!(report["ticks"], polled["tick"]);
+}
+
+#[test]
+fn bad_requests_get_error_replies() {
+    let server = Server::start(&["--max-ants", "10"]);
+
+    let (status, body) = server.request("POST", "/maps", "A =B\n");
+    assert_eq!(status, 400);
+    assert!(body["error"].as_str().unwrap().contains("missing key"), "{body}");
+
+    let (status, _) = server.request("POST", "/simulations", &json!({ "map": 3, "ants": 2, "seed": 1 }).to_string());
+    assert_eq!(status, 404);
+
+    server.request("POST", "/maps", "A\n");
+    let (status, body) = server.request("POST", "/simulations", &json!({ "map": 0, "ants": 11, "seed": 1 }).to_string());
+    assert_eq!(status, 400);
+    assert_eq!(body["error"], "11 ants requested, this server allows at most 10");
+
+    assert_eq!(server.request("GET", "/simulations/5", "").0, 404);
+}
+
+#[test]
+fn runs_past_the_limit_are_refused() {
+    let server = Server::start(&["--max-running", "0"]);
+
+    server.request("POST", "/maps", "A\n");
+    let (status, body) = server.request("POST", "/simulations", &json!({ "map": 0, "ants": 1, "seed": 1 }).to_string());
+    assert_eq!(status, 503);
+    assert_eq!(body["error"], "0 simulations are already running, try again later");
+    assert_eq!(server.request("GET", "/simulations/0", "").0, 404);
+}
@@ -0,0 +1,82 @@
+use ants_code_challenge::limits::Limit;
+use ants_code_challenge::prelude::*;
+use ants_code_challenge::world::load_world_from_str;
+use std::sync::atomic::AtomicBool;
+use std::sync::Arc;
+
+/// Run `ants` (by colony index) on `A <-> B` through `try_run_simulation`
+fn try_run(
+    positions: &[usize],
+    prepare: impl FnOnce(&mut World),
+    engine: impl FnOnce(SimulationEngine) -> SimulationEngine,
+) -> (std::result::Result<std::time::Duration, SimulationError>, World) {
+    let mut world = load_world_from_str("A east=B\nB west=A\n", &Default::default()).unwrap();
+    prepare(&mut world);
+    let mut ants: Vec<Ant> = positions.iter().enumerate().map(|(i, &p)| Ant::new(i as u32, NodeId::from_index(p))).collect();
+    let mut args = Args::with_defaults(ants.len(), "unused");
+    args.suppress_events = true;
+    args.max_moves = 4;
+    let mut engine = engine(SimulationEngine::new(&world, ants.len()));
+    let result = engine.try_run_simulation(&mut world, &mut ants, &args, &mut fastrand::Rng::with_seed(1));
+    (result, world)
+}
+
+#[test]
+fn an_ant_past_the_end_of_the_world_is_refused() {
+    let (result, _) = try_run(&[0, 5], |_| {}, |engine| engine);
+
+    assert_eq!(result.unwrap_err(), SimulationError::NodeIndexOutOfRange { ant: 1, index: 5, len: 2 });
+}
+
+#[test]
+fn a_living_ant_on_a_ruin_is_refused() {
+    let (result, world) = try_run(&[1], |world| {
+        world.destroy(NodeId::new(1));
+    }, |engine| engine);
+
+    let err = result.unwrap_err();
+    assert_eq!(err, SimulationError::InvalidAntPlacement { ant: 0, colony: "B".to_string() });
+    assert_eq!(err.to_string(), "Invalid ant placement: ant 0 starts on destroyed colony B");
+    // Refused before anything ran
+    assert_eq!(world.alive_count(), 1);
+}
+
+#[test]
+fn ants_out_of_id_order_are_refused() {
+    let mut world = load_world_from_str("A east=B\nB west=A\n", &Default::default()).unwrap();
+    let mut ants = vec![Ant::new(1, NodeId::new(0)), Ant::new(0, NodeId::new(1))];
+    let args = Args::with_defaults(2, "unused");
+    let result = SimulationEngine::new(&world, 2).try_run_simulation(&mut world, &mut ants, &args, &mut fastrand::Rng::with_seed(1));
+
+    let err = result.unwrap_err();
+    assert_eq!(err, SimulationError::InconsistentState("ant 1 is at index 0 of the ants".to_string()));
+    assert_eq!(err.to_string(), "Inconsistent state: ant 1 is at index 0 of the ants");
+}
+
+#[test]
+fn an_interrupted_run_is_aborted() {
+    let flag = Arc::new(AtomicBool::new(true));
+    let (result, _) = try_run(&[0], |_| {}, |engine| engine.with_interrupt(flag));
+
+    assert_eq!(result.unwrap_err(), SimulationError::Aborted { tick: 0, limit: None });
+    assert_eq!(
+        SimulationError::Aborted { tick: 3, limit: Some(Limit::Memory) }.to_string(),
+        "Aborted at tick 3: memory limit exceeded"
+    );
+}
+
+#[test]
+fn a_sound_run_succeeds() {
+    let (result, _) = try_run(&[0], |_| {}, |engine| engine);
+
+    assert!(result.is_ok());
+}
+
+#[test]
+#[should_panic(expected = "Node index out of range")]
+fn run_simulation_panics_instead_of_reading_out_of_bounds() {
+    let mut world = load_world_from_str("A east=B\nB west=A\n", &Default::default()).unwrap();
+    let mut ants = vec![Ant::new(0, NodeId::new(9))];
+    let args = Args::with_defaults(1, "unused");
+    SimulationEngine::new(&world, 1).run_simulation(&mut world, &mut ants, &args, &mut fastrand::Rng::with_seed(1));
+}
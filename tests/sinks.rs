@@ -0,0 +1,51 @@
+use assert_cmd::prelude::*;
+use predicates::prelude::*;
+use std::io::Write;
+use std::process::Command;
+use tempfile::NamedTempFile;
+
+const BIN: &str = "ants_code_challenge";
+
+#[test]
+fn warn_sinks_reports_traps_and_ants_inside() -> Result<(), Box<dyn std::error::Error>> {
+    // A <-> B lead one way into C <-> D, which has no way back
+    let mut f = NamedTempFile::new()?;
+    writeln!(f, "A east=B\nB west=A south=C\nC east=D\nD west=C")?;
+
+    Command::cargo_bin(BIN)?
+        .args(["-n", "0", "-m", f.path().to_str().unwrap(), "--warn-sinks", "--suppress-events"])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("1 sink component(s) covering 2 colonies; 0 of 0 ants start inside one"))
+        .stderr(predicate::str::contains("sink: C, D"));
+
+    Ok(())
+}
+
+#[test]
+fn warn_sinks_is_silent_without_traps() -> Result<(), Box<dyn std::error::Error>> {
+    let mut f = NamedTempFile::new()?;
+    writeln!(f, "A east=B\nB west=A")?;
+
+    Command::cargo_bin(BIN)?
+        .args(["-n", "2", "-m", f.path().to_str().unwrap(), "--seed", "1", "--warn-sinks", "--suppress-events"])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("sink").not());
+
+    Ok(())
+}
+
+#[test]
+fn ants_in_sinks_counts_trapped_starts() {
+    use ants_code_challenge::prelude::{Ant, NodeId, World};
+    use ants_code_challenge::world::parser::parse_world_from_str;
+
+    let (names, nodes) = parse_world_from_str("A east=B\nB west=A south=C\nC east=D\nD west=C\n");
+    let world = World::new(names, nodes);
+    let ants = [Ant::new(0, NodeId::new(0)), Ant::new(1, NodeId::new(2)), Ant::new(2, NodeId::new(3))];
+
+    let sinks = world.sink_components();
+    assert_eq!(world.ants_in_sinks(&sinks, &ants), 2);
+    assert_eq!(world.ants_in_sinks(&[], &ants), 0);
+}
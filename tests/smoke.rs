@@ -56,3 +56,17 @@ fn t0_collision_with_single_colony_leaves_zero_survivors() -> Result<(), Box<dyn
 
     Ok(())
 }
+
+#[test]
+fn sort_output_prints_final_world_by_name() -> Result<(), Box<dyn std::error::Error>> {
+    let mut f = NamedTempFile::new()?;
+    writeln!(f, "Zed\nMid\nAnt")?;
+
+    let output = Command::cargo_bin(BIN)?
+        .args(["-n", "0", "-m", f.path().to_str().unwrap(), "--sort-output", "name", "--suppress-events"])
+        .output()?;
+    assert!(output.status.success());
+    assert!(String::from_utf8(output.stdout)?.starts_with("Ant\nMid\nZed\n"));
+
+    Ok(())
+}
@@ -0,0 +1,62 @@
+use ants_code_challenge::prelude::*;
+use ants_code_challenge::world::{load_world_from_str, ParseOptions};
+use assert_cmd::Command;
+use std::io::Write;
+
+/// Six colonies in a line; only C and E take landings
+const MAP: &str = "A east=B\nB east=C\nC spawn east=D\nD east=E\nE spawn=1 east=F\nF spawn=0\n";
+
+fn positions(world: &World, ants: &[Ant]) -> Vec<String> {
+    let mut names: Vec<String> = ants.iter().map(|a| world.get_colony_name(a.pos).to_string()).collect();
+    names.sort();
+    names.dedup();
+    names
+}
+
+#[test]
+fn ants_land_only_on_tagged_colonies() {
+    let world = load_world_from_str(MAP, &ParseOptions::default()).unwrap();
+    let ants = world.create_ants(200, &mut fastrand::Rng::with_seed(3));
+
+    assert_eq!(positions(&world, &ants), ["C", "E"]);
+}
+
+#[test]
+fn ignoring_tags_uses_every_colony() {
+    let options = ParseOptions { ignore_spawn_tags: true, ..Default::default() };
+    let world = load_world_from_str(MAP, &options).unwrap();
+    let ants = world.create_ants(200, &mut fastrand::Rng::with_seed(3));
+
+    assert_eq!(positions(&world, &ants), ["A", "B", "C", "D", "E", "F"]);
+}
+
+#[test]
+fn destroyed_zones_fall_back_to_any_alive_colony() {
+    let mut world = load_world_from_str(MAP, &ParseOptions::default()).unwrap();
+    world.destroy(NodeId::new(2));
+    assert_eq!(world.spawn_zones(), [NodeId::new(4)]);
+
+    world.destroy(NodeId::new(4));
+    assert_eq!(world.spawn_zones().len(), 4);
+}
+
+#[test]
+fn written_maps_keep_their_tags() {
+    let world = load_world_from_str(MAP, &ParseOptions::default()).unwrap();
+    let mut out = Vec::new();
+    world.write_map(&mut out).unwrap();
+    let reread = load_world_from_str(std::str::from_utf8(&out).unwrap(), &ParseOptions::default()).unwrap();
+
+    assert_eq!(reread.spawn_zones(), world.spawn_zones());
+}
+
+#[test]
+fn cli_accepts_ignore_spawn_tags() {
+    let mut map = tempfile::NamedTempFile::new().unwrap();
+    map.write_all(MAP.as_bytes()).unwrap();
+    Command::cargo_bin("ants_code_challenge")
+        .unwrap()
+        .args(["-n", "2", "-m", map.path().to_str().unwrap(), "--seed", "1", "--ignore-spawn-tags"])
+        .assert()
+        .success();
+}
@@ -0,0 +1,28 @@
+use assert_cmd::prelude::*;
+use std::io::Write;
+use std::process::Command;
+use tempfile::NamedTempFile;
+
+const BIN: &str = "ants_code_challenge";
+
+#[test]
+fn stats_describes_the_map_without_simulating() -> Result<(), Box<dyn std::error::Error>> {
+    let mut f = NamedTempFile::new()?;
+    writeln!(f, "A east=B\nB west=A south=C\nC\nD")?;
+    let json = NamedTempFile::new()?;
+
+    let output = Command::cargo_bin(BIN)?
+        .args(["stats", "-m", f.path().to_str().unwrap(), "-o", json.path().to_str().unwrap()])
+        .output()?;
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout)?;
+    assert!(stdout.contains("colonies:          4"), "{stdout}");
+    assert!(stdout.contains("largest component: 3 of 4 (2 components)"), "{stdout}");
+
+    let stats: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(json.path())?)?;
+    assert_eq!(stats["edges"], 3);
+    assert_eq!(stats["dead_ends"], 2);
+    assert_eq!(stats["degree_histogram"], serde_json::json!([2, 1, 1, 0, 0]));
+
+    Ok(())
+}
@@ -0,0 +1,151 @@
+use ants_code_challenge::prelude::*;
+use ants_code_challenge::simulation::{DestructionCause, TickDelta, TickSnapshot};
+use ants_code_challenge::world::parser::parse_world_from_str;
+use clap::Parser;
+
+fn setup(map: &str, starts: &[usize], extra: &[&str]) -> (World, Vec<Ant>, Args) {
+    let (names, nodes) = parse_world_from_str(map);
+    let world = World::new(names, nodes);
+    let ants = starts
+        .iter()
+        .enumerate()
+        .map(|(i, &n)| Ant::new(i as u32, NodeId::from_index(n)))
+        .collect();
+    let n = starts.len().to_string();
+    let mut argv = vec!["ants", "-n", n.as_str(), "-m", "unused", "--suppress-events"];
+    argv.extend_from_slice(extra);
+    (world, ants, Args::parse_from(argv))
+}
+
+#[test]
+fn take_limits_the_number_of_ticks() {
+    let (mut world, mut ants, args) = setup("A east=B\nB west=A\nC east=D\nD west=C\n", &[0, 2], &[]);
+    let mut rng = fastrand::Rng::with_seed(1);
+    let mut engine = SimulationEngine::new(&world, ants.len());
+
+    let ticks: Vec<TickSnapshot> = engine.iter_ticks(&mut world, &mut ants, &args, &mut rng).take(3).collect();
+
+    assert_eq!(ticks.iter().map(|t| t.tick).collect::<Vec<_>>(), [1, 2, 3]);
+    assert_eq!(ticks[0].positions, [Some(NodeId::new(1)), Some(NodeId::new(3))]);
+    assert_eq!(ticks[1].positions, [Some(NodeId::new(0)), Some(NodeId::new(2))]);
+    assert_eq!(engine.ticks(), 3);
+}
+
+#[test]
+fn snapshots_carry_destructions_of_their_tick() {
+    // Both ants walk into C on tick 1
+    let (mut world, mut ants, args) = setup("A east=C\nB west=C\nC\n", &[0, 1], &[]);
+    let mut rng = fastrand::Rng::with_seed(1);
+    let mut engine = SimulationEngine::new(&world, ants.len());
+
+    let ticks: Vec<TickSnapshot> = engine.iter_ticks(&mut world, &mut ants, &args, &mut rng).collect();
+
+    assert_eq!(ticks.len(), 1);
+    assert_eq!(ticks[0].positions, [None, None]);
+    assert_eq!(ticks[0].destroyed.len(), 1);
+    assert_eq!(ticks[0].destroyed[0].node_id, NodeId::new(2));
+    assert_eq!(ticks[0].destroyed[0].cause, DestructionCause::Fight { ant1: 0, ant2: 1 });
+    assert!(!world.is_alive(NodeId::new(2)));
+}
+
+#[test]
+fn iterating_to_the_end_matches_run_simulation() {
+    let map = "A north=B east=C\nB south=A east=D\nC west=A north=D\nD west=B south=C\n";
+    for scheduling in ["simultaneous", "sequential"] {
+        let extra = ["--scheduling", scheduling, "--max-moves", "50"];
+        let (mut world_a, mut ants_a, args) = setup(map, &[0, 1, 2, 3, 0], &extra);
+        let (mut world_b, mut ants_b, _) = setup(map, &[0, 1, 2, 3, 0], &extra);
+
+        let mut engine = SimulationEngine::new(&world_a, 5);
+        engine.run_simulation(&mut world_a, &mut ants_a, &args, &mut fastrand::Rng::with_seed(7));
+        let expected_ticks = engine.ticks();
+
+        let mut rng = fastrand::Rng::with_seed(7);
+        let last = engine.iter_ticks(&mut world_b, &mut ants_b, &args, &mut rng).last();
+
+        assert_eq!(last.map(|t| t.tick).unwrap_or(0), expected_ticks, "{scheduling}");
+        assert_eq!(world_a.count_survivors(), world_b.count_survivors(), "{scheduling}");
+        let pos = |ants: &[Ant]| ants.iter().map(|a| (a.pos, a.is_alive())).collect::<Vec<_>>();
+        assert_eq!(pos(&ants_a), pos(&ants_b), "{scheduling}");
+    }
+}
+
+#[test]
+fn deltas_start_at_placement_and_list_each_tick() {
+    // Ants 0 and 1 fight on A at t=0; ant 2 walks into B once and stops
+    for scheduling in ["simultaneous", "sequential"] {
+        let extra = ["--scheduling", scheduling, "--max-moves", "1"];
+        let (mut world, mut ants, args) = setup("A\nB west=C\nC east=B\n", &[0, 0, 2], &extra);
+        let mut rng = fastrand::Rng::with_seed(1);
+        let mut engine = SimulationEngine::new(&world, ants.len());
+
+        let deltas: Vec<TickDelta> = engine.iter_deltas(&mut world, &mut ants, &args, &mut rng).collect();
+
+        let placement = TickDelta { tick: 0, destroyed: vec![NodeId::new(0)], ants_died: vec![0, 1], ..Default::default() };
+        let first = TickDelta { tick: 1, ants_stopped: vec![2], ..Default::default() };
+        assert_eq!(deltas, [placement, first], "{scheduling}");
+    }
+}
+
+#[test]
+fn replaying_deltas_matches_run_simulation() {
+    let map = "A north=B east=C\nB south=A east=D\nC west=A north=D\nD west=B south=C\n";
+    for scheduling in ["simultaneous", "sequential"] {
+        let extra = ["--scheduling", scheduling, "--max-moves", "50"];
+        let (mut world_a, mut ants_a, args) = setup(map, &[0, 1, 2, 3, 0], &extra);
+        let (mut world_b, mut ants_b, _) = setup(map, &[0, 1, 2, 3, 0], &extra);
+        let (mut fallen, mut dead) = (Vec::new(), Vec::new());
+
+        let mut engine = SimulationEngine::new(&world_a, 5);
+        engine.run_simulation(&mut world_a, &mut ants_a, &args, &mut fastrand::Rng::with_seed(7));
+        let mut rng = fastrand::Rng::with_seed(7);
+        for delta in engine.iter_deltas(&mut world_b, &mut ants_b, &args, &mut rng) {
+            fallen.extend(delta.destroyed);
+            dead.extend(delta.ants_died);
+        }
+
+        fallen.sort_unstable();
+        let expected: Vec<NodeId> = (0..4).map(NodeId::from_index).filter(|&id| !world_a.is_alive(id)).collect();
+        assert_eq!(fallen, expected, "{scheduling}");
+        dead.sort_unstable();
+        let expected: Vec<u32> = ants_a.iter().filter(|a| !a.is_alive()).map(|a| a.id).collect();
+        assert_eq!(dead, expected, "{scheduling}");
+    }
+}
+
+#[test]
+fn deltas_list_starved_ants() {
+    // Ants 0 and 1 pace two separate pairs with energy for two moves each
+    for scheduling in ["simultaneous", "sequential"] {
+        let extra = ["--scheduling", scheduling, "--energy", "2", "--max-moves", "10"];
+        let (mut world, mut ants, args) = setup("A east=B\nB west=A\nC east=D\nD west=C\n", &[0, 2], &extra);
+        let mut rng = fastrand::Rng::with_seed(1);
+        let mut engine = SimulationEngine::new(&world, ants.len());
+
+        let deltas: Vec<TickDelta> = engine.iter_deltas(&mut world, &mut ants, &args, &mut rng).collect();
+
+        let died: Vec<(u64, u32)> = deltas.iter().flat_map(|d| d.ants_died.iter().map(move |&id| (d.tick, id))).collect();
+        assert_eq!(died, [(3, 0), (3, 1)], "{scheduling}");
+        assert!(ants.iter().all(|a| a.is_starved()), "{scheduling}");
+    }
+}
+
+#[test]
+fn deltas_list_births_and_rebuilds() {
+    // The queen paces A <-> B laying a worker every tick, which the next
+    // tick's fight on the shared colony may take down; C rebuilds the ruins
+    let extra = ["--queen-ratio", "1", "--spawn-every", "1", "--max-moves", "6", "--revival-prob", "1", "--max-ticks", "6"];
+    let (mut world, mut ants, args) = setup("A east=B\nB west=A east=C\nC west=B\n", &[0], &extra);
+    ants[0] = Ant::queen(0, NodeId::new(0));
+    let mut rng = fastrand::Rng::with_seed(1);
+    let mut engine = SimulationEngine::new(&world, ants.len());
+
+    let deltas: Vec<TickDelta> = engine.iter_deltas(&mut world, &mut ants, &args, &mut rng).collect();
+
+    let born: Vec<u32> = deltas.iter().flat_map(|d| d.ants_born.iter().copied()).collect();
+    assert_eq!(born, (1..ants.len() as u32).collect::<Vec<_>>());
+    let destroyed = deltas.iter().map(|d| d.destroyed.len()).sum::<usize>();
+    let revived = deltas.iter().map(|d| d.revived.len()).sum::<usize>();
+    assert_eq!(revived, engine.revived());
+    assert_eq!(world.alive_count(), 3 - destroyed + revived);
+}
@@ -0,0 +1,44 @@
+use ants_code_challenge::prelude::*;
+
+mod common;
+
+/// Ant 0 reaches dead end B on tick 1 and is trapped on tick 2; ant 1 arrives at B on tick 3
+fn run(policy: &str, scheduling: &str) -> (World, Vec<Ant>) {
+    let (world, ants, _) = common::run(
+        "A east=B\nB\nD east=C\nC east=E\nE east=B\n",
+        &["A", "D"],
+        scheduling,
+        &["--trapped-policy", policy],
+    );
+    (world, ants)
+}
+
+#[test]
+fn stay_trapped_ant_fights_later_arrival() {
+    for scheduling in ["simultaneous", "sequential"] {
+        let (world, ants) = run("stay", scheduling);
+        assert!(!world.is_alive(world.find("B").unwrap()), "{scheduling}");
+        assert!(!ants[1].is_alive(), "{scheduling}");
+    }
+}
+
+#[test]
+fn die_removes_trapped_ant_without_destroying_colony() {
+    for scheduling in ["simultaneous", "sequential"] {
+        let (world, ants) = run("die", scheduling);
+        assert!(!ants[0].is_alive(), "{scheduling}");
+        assert_eq!(world.count_survivors(), 5, "{scheduling}");
+    }
+}
+
+#[test]
+fn ghost_is_ignored_by_collisions() {
+    for scheduling in ["simultaneous", "sequential"] {
+        let (world, ants) = run("ghost", scheduling);
+        assert_eq!(world.count_survivors(), 5, "{scheduling}");
+        for ant in &ants {
+            assert!(ant.is_alive() && ant.is_trapped(), "{scheduling}");
+            assert_eq!(ant.pos, world.find("B").unwrap(), "{scheduling}");
+        }
+    }
+}
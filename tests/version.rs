@@ -0,0 +1,32 @@
+use assert_cmd::Command;
+use predicates::prelude::*;
+
+const BIN: &str = "ants_code_challenge";
+
+#[test]
+fn version_lists_build_and_defaults() {
+    Command::cargo_bin(BIN)
+        .unwrap()
+        .arg("--version")
+        .assert()
+        .success()
+        .stdout(predicate::str::starts_with(format!("ant_mania {} (", env!("CARGO_PKG_VERSION"))))
+        .stdout(predicate::str::contains("\nfeatures: cli"))
+        .stdout(predicate::str::contains("\ndefaults: max_moves=10000 "));
+}
+
+#[test]
+fn version_json() {
+    let output = Command::cargo_bin(BIN).unwrap().args(["--version", "--json"]).output().unwrap();
+    assert!(output.status.success());
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json["version"], env!("CARGO_PKG_VERSION"));
+    assert!(json["git_hash"].as_str().is_some_and(|hash| !hash.is_empty()));
+    assert!(json["features"].as_array().unwrap().contains(&"cli".into()));
+    assert_eq!(json["defaults"]["scheduling"], "simultaneous");
+}
+
+#[test]
+fn json_alone_is_not_a_version_request() {
+    Command::cargo_bin(BIN).unwrap().arg("--json").assert().code(3);
+}
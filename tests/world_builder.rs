@@ -0,0 +1,42 @@
+use ants_code_challenge::prelude::*;
+use ants_code_challenge::world::load_world_from_str;
+use Direction::{East, North, South, West};
+
+/// A 2x2 square built in code, next to the same square as map text
+fn square() -> (World, World) {
+    let built = World::builder()
+        .two_way("NW", East, "NE")
+        .two_way("NE", South, "SE")
+        .two_way("SE", West, "SW")
+        .two_way("SW", North, "NW")
+        .build()
+        .unwrap();
+    let text = "NW east=NE south=SW\nNE west=NW south=SE\nSE north=NE west=SW\nSW east=SE north=NW\n";
+    (built, load_world_from_str(text, &Default::default()).unwrap())
+}
+
+#[test]
+fn built_world_runs_like_the_loaded_one() {
+    let (mut built, mut loaded) = square();
+    let mut args = Args::with_defaults(6, "unused");
+    args.suppress_events = true;
+
+    let mut survivors = Vec::new();
+    for world in [&mut built, &mut loaded] {
+        let mut rng = fastrand::Rng::with_seed(5);
+        let mut ants = world.create_ants(args.ants, &mut rng);
+        let mut engine = SimulationEngine::new(world, ants.len());
+        engine.run_simulation(world, &mut ants, &args, &mut rng);
+        survivors.push((world.count_survivors(), engine.events().to_vec()));
+    }
+    assert_eq!(survivors[0], survivors[1]);
+}
+
+#[test]
+fn colonies_only_entered_get_ids_last() {
+    let world = World::builder().tunnel("A", North, "Z").colony("B").build().unwrap();
+
+    assert_eq!(world.find("A").map(|id| id.index()), Some(0));
+    assert_eq!(world.find("B").map(|id| id.index()), Some(1));
+    assert_eq!(world.find("Z").map(|id| id.index()), Some(2));
+}
@@ -0,0 +1,38 @@
+use ants_code_challenge::prelude::*;
+use ants_code_challenge::simulation::TickView;
+use ants_code_challenge::world::WorldView;
+
+mod common;
+use common::Run;
+
+/// A row of colonies; ants from both ends walk into each other
+const ROW: &str = "A east=B\nB west=A east=C\nC west=B east=D\nD west=C east=E\nE west=D\n";
+
+/// Ants 0 and 1 on either end of the row, 20 moves each
+fn two_ants(engine: impl FnOnce(SimulationEngine) -> SimulationEngine) -> (World, Vec<Ant>, SimulationEngine) {
+    Run::on(ROW).at(&["A", "E"]).flags(&["--max-moves", "20"]).engine(engine).run()
+}
+
+#[test]
+fn a_strategy_walks_the_view() {
+    // Always take the alive exit with the highest id, else stay trapped
+    let strategy = |view: WorldView<'_>, ant: &Ant, _: &mut fastrand::Rng| match view.alive_neighbors(ant.pos).max() {
+        Some(to) => (to, false),
+        None => (ant.pos, true),
+    };
+    let (world, _, engine) = two_ants(|engine| engine.with_move_strategy(strategy));
+
+    // Ant 0 heads east, ant 1 bounces between D and E: they meet on D
+    assert_eq!(engine.events().len(), 1);
+    assert_eq!(world.get_colony_name(engine.events()[0].node_id), "D");
+}
+
+#[test]
+fn a_stop_condition_ends_the_run_before_the_tick() {
+    let stop = |view: &TickView<'_>| view.tick == 1 && view.world.is_alive(view.world.find("C").unwrap());
+    let (_, ants, engine) = two_ants(|engine| engine.with_stop_condition(stop));
+
+    assert_eq!(engine.ticks(), 1);
+    assert!(!engine.interrupted());
+    assert!(ants.iter().all(|a| a.is_alive()));
+}